@@ -0,0 +1,50 @@
+//! 把事件交给 callback 时 clone 与 move 的开销对比
+//!
+//! `cargo bench --bench event_clone`
+//!
+//! 解析器按值把事件交给 callback，不再为每个事件 clone 一次；
+//! 带大量账户或原始账户数据的事件 clone 时需要分配并复制这些 Vec。
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::protocols::meteora_dlmm::MeteoraDlmmLbPairAccountEvent;
+use solana_streamer_sdk::streaming::event_parser::protocols::raydium_clmm::RaydiumClmmSwapEvent;
+use solana_streamer_sdk::streaming::event_parser::DexEvent;
+
+/// LbPair 账户数据长度
+const LB_PAIR_ACCOUNT_LEN: usize = 904;
+
+fn account_heavy_events() -> Vec<(&'static str, DexEvent)> {
+    let swap = DexEvent::RaydiumClmmSwapEvent(RaydiumClmmSwapEvent {
+        remaining_accounts: (0..32).map(|_| Pubkey::new_unique()).collect(),
+        ..Default::default()
+    });
+    let account = DexEvent::MeteoraDlmmLbPairAccountEvent(MeteoraDlmmLbPairAccountEvent {
+        pubkey: Pubkey::new_unique(),
+        raw_account_data: vec![7u8; LB_PAIR_ACCOUNT_LEN],
+        ..Default::default()
+    });
+    vec![("clmm_swap_32_remaining_accounts", swap), ("dlmm_lb_pair_account", account)]
+}
+
+fn bench_event_clone(c: &mut Criterion) {
+    let mut group = c.benchmark_group("event_clone");
+    for (name, event) in account_heavy_events() {
+        let callback = |event: DexEvent| {
+            black_box(event);
+        };
+        group.bench_function(format!("{name}/clone"), |b| {
+            b.iter(|| callback(black_box(&event).clone()))
+        });
+        group.bench_function(format!("{name}/move"), |b| {
+            b.iter_batched(
+                || event.clone(),
+                |event| callback(black_box(event)),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_event_clone);
+criterion_main!(benches);
@@ -0,0 +1,75 @@
+//! 热路径上的指标开销：一笔交易经 `process_shred_transaction` 解析并发出事件的耗时
+//!
+//! 对比开启与关闭 `metrics` feature 的构建：
+//!
+//! `cargo bench --bench metrics_overhead`
+//! `cargo bench --bench metrics_overhead --no-default-features --features grpc,all-protocols`
+//!
+//! 关闭 feature 时不创建指标 callback 包装器，记录调用编译为空操作；两次结果之差即为指标开销。
+use std::sync::Arc;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use futures::executor::block_on;
+use solana_sdk::message::compiled_instruction::CompiledInstruction;
+use solana_sdk::message::{Message, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_streamer_sdk::streaming::common::process_shred_transaction;
+use solana_streamer_sdk::streaming::event_parser::protocols::meteora_damm_v2::{
+    discriminators, parser::METEORA_DAMM_V2_PROGRAM_ID,
+};
+use solana_streamer_sdk::streaming::event_parser::{DexEvent, Protocol};
+use solana_streamer_sdk::streaming::shred::TransactionWithSlot;
+
+/// 一笔 DAMM v2 claim_position_fee 交易（只有外层指令，ShredStream 不带 inner instructions）
+fn claim_position_fee_transaction() -> VersionedTransaction {
+    let mut accounts: Vec<Pubkey> = (0..15).map(|_| Pubkey::new_unique()).collect();
+    accounts.push(METEORA_DAMM_V2_PROGRAM_ID);
+    VersionedTransaction {
+        signatures: vec![Signature::new_unique()],
+        message: VersionedMessage::Legacy(Message {
+            account_keys: accounts,
+            instructions: vec![CompiledInstruction {
+                program_id_index: 15,
+                accounts: (0..15).collect(),
+                data: discriminators::CLAIM_POSITION_FEE_IX.to_vec(),
+            }],
+            ..Default::default()
+        }),
+    }
+}
+
+fn bench_metrics_overhead(c: &mut Criterion) {
+    let protocols = [Protocol::MeteoraDammV2];
+    let transaction = claim_position_fee_transaction();
+    let callback: Arc<dyn Fn(DexEvent) + Send + Sync> = Arc::new(|event: DexEvent| {
+        black_box(event);
+    });
+
+    let mut group = c.benchmark_group("metrics_overhead");
+    group.bench_function(
+        if cfg!(feature = "metrics") {
+            "process_shred_transaction"
+        } else {
+            "process_shred_transaction_no_metrics"
+        },
+        |b| {
+            b.iter(|| {
+                block_on(process_shred_transaction(
+                    TransactionWithSlot::new(transaction.clone(), 1, 0),
+                    &protocols,
+                    None,
+                    None,
+                    callback.clone(),
+                    None,
+                ))
+                .unwrap()
+            })
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, bench_metrics_overhead);
+criterion_main!(benches);
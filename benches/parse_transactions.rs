@@ -0,0 +1,139 @@
+//! 顺序解析与 `EventParser::parse_transactions_parallel` 的对比
+//!
+//! `cargo bench --bench parse_transactions`
+use std::sync::{Arc, Mutex};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use futures::executor::block_on;
+use solana_sdk::message::compiled_instruction::CompiledInstruction;
+use solana_sdk::message::{Message, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_streamer_sdk::streaming::event_parser::core::event_parser::{
+    EventParser, VersionedTransactionInput,
+};
+use solana_streamer_sdk::streaming::event_parser::core::ParseOptions;
+use solana_streamer_sdk::streaming::event_parser::protocols::meteora_damm_v2::{
+    discriminators, parser::METEORA_DAMM_V2_PROGRAM_ID,
+};
+use solana_streamer_sdk::streaming::event_parser::{DexEvent, Protocol};
+use solana_transaction_status::{InnerInstruction, InnerInstructions};
+
+struct Fixture {
+    transaction: VersionedTransaction,
+    signature: Signature,
+    accounts: Vec<Pubkey>,
+    inner_instructions: Vec<InnerInstructions>,
+}
+
+/// 一笔 DAMM v2 claim_position_fee 交易（外层指令 + CPI 事件）
+fn claim_position_fee_transaction() -> Fixture {
+    let mut accounts: Vec<Pubkey> = (0..15).map(|_| Pubkey::new_unique()).collect();
+    accounts.push(METEORA_DAMM_V2_PROGRAM_ID);
+
+    let mut cpi_event = discriminators::CLAIM_POSITION_FEE_EVENT.to_vec();
+    for account in [accounts[1], accounts[2], accounts[10]] {
+        cpi_event.extend_from_slice(account.as_ref());
+    }
+    cpi_event.extend_from_slice(&1_500u64.to_le_bytes());
+    cpi_event.extend_from_slice(&42u64.to_le_bytes());
+
+    let signature = Signature::new_unique();
+    let transaction = VersionedTransaction {
+        signatures: vec![signature],
+        message: VersionedMessage::Legacy(Message {
+            account_keys: accounts.clone(),
+            instructions: vec![CompiledInstruction {
+                program_id_index: 15,
+                accounts: (0..15).collect(),
+                data: discriminators::CLAIM_POSITION_FEE_IX.to_vec(),
+            }],
+            ..Default::default()
+        }),
+    };
+    let inner_instructions = vec![InnerInstructions {
+        index: 0,
+        instructions: vec![InnerInstruction {
+            instruction: CompiledInstruction {
+                program_id_index: 15,
+                accounts: vec![14],
+                data: cpi_event,
+            },
+            stack_height: Some(2),
+        }],
+    }];
+    Fixture { transaction, signature, accounts, inner_instructions }
+}
+
+fn bench_parse_transactions(c: &mut Criterion) {
+    let protocols = [Protocol::MeteoraDammV2];
+    let mut group = c.benchmark_group("parse_transactions");
+    for count in [100usize, 2_000] {
+        let fixtures: Vec<Fixture> = (0..count).map(|_| claim_position_fee_transaction()).collect();
+        let inputs: Vec<VersionedTransactionInput> = fixtures
+            .iter()
+            .enumerate()
+            .map(|(i, fixture)| VersionedTransactionInput {
+                transaction: &fixture.transaction,
+                signature: fixture.signature,
+                slot: Some(1),
+                block_time: None,
+                accounts: &fixture.accounts,
+                inner_instructions: &fixture.inner_instructions,
+                transaction_index: Some(i as u64),
+            })
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("sequential", count), &inputs, |b, inputs| {
+            b.iter(|| {
+                let events: Vec<Vec<DexEvent>> = inputs
+                    .iter()
+                    .map(|input| {
+                        let events = Arc::new(Mutex::new(Vec::new()));
+                        let collected = events.clone();
+                        let callback = Arc::new(move |event: DexEvent| {
+                            collected.lock().unwrap().push(event);
+                        });
+                        block_on(EventParser::parse_instruction_events_from_versioned_transaction(
+                            &protocols,
+                            None,
+                            input.transaction,
+                            input.signature,
+                            input.slot,
+                            input.block_time,
+                            0,
+                            input.accounts,
+                            input.inner_instructions,
+                            None,
+                            input.transaction_index,
+                            None,
+                            callback,
+                        ))
+                        .unwrap();
+                        let events = std::mem::take(&mut *events.lock().unwrap());
+                        events
+                    })
+                    .collect();
+                black_box(events)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", count), &inputs, |b, inputs| {
+            b.iter(|| {
+                black_box(EventParser::parse_transactions_parallel(
+                    &protocols,
+                    None,
+                    inputs,
+                    0,
+                    None,
+                    None,
+                    ParseOptions::disabled(),
+                ))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse_transactions);
+criterion_main!(benches);
@@ -85,8 +85,14 @@ async fn subscribe_arb_events() -> Result<(), Box<dyn std::error::Error>> {
         account_include: account_include.clone(),
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
+    };
+    let account_filter = AccountFilter {
+        account: vec![],
+        owner: account_include,
+        filters: vec![],
+        event_types: vec![],
     };
-    let account_filter = AccountFilter { account: vec![], owner: account_include, filters: vec![] };
 
     let callback = create_arb_callback();
 
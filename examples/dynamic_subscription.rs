@@ -1,7 +1,9 @@
 use anyhow::Result;
 use solana_sdk::signature::{Keypair, Signer};
 use solana_streamer_sdk::streaming::event_parser::common::filter::EventTypeFilter;
-use solana_streamer_sdk::streaming::event_parser::common::types::EventType;
+use solana_streamer_sdk::streaming::event_parser::protocols::{
+    PUMPFUN_PROGRAM_ID, RAYDIUM_CPMM_PROGRAM_ID,
+};
 use solana_streamer_sdk::streaming::event_parser::Protocol;
 use solana_streamer_sdk::streaming::yellowstone_grpc::{
     AccountFilter, TransactionFilter, YellowstoneGrpc,
@@ -11,9 +13,6 @@ use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
-const PUMPFUN_PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
-const RAYDIUM_CPMM_PROGRAM_ID: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
-
 const GRPC_ENDPOINT: &str = "https://solana-yellowstone-grpc.publicnode.com:443";
 const API_KEY: Option<&str> = None;
 const MONITORING_DURATION_SECS: u64 = 10;
@@ -32,16 +31,7 @@ async fn main() -> Result<()> {
 
     let callback = move |event: solana_streamer_sdk::streaming::event_parser::DexEvent| {
         let count = counter.fetch_add(1, Ordering::Relaxed);
-
-        let protocol = match event.metadata().event_type {
-            EventType::PumpFunBuy | EventType::PumpFunSell => "PumpFun",
-            EventType::RaydiumCpmmSwapBaseInput | EventType::RaydiumCpmmSwapBaseOutput => {
-                "RaydiumCpmm"
-            }
-            _ => "Unknown",
-        };
-
-        println!("Event #{}: {:11} - {:.8}...", count + 1, protocol, event.metadata().signature);
+        println!("Event #{}: {}", count + 1, event);
     };
 
     println!("\n=== Phase 1: PumpFun only ===");
@@ -49,17 +39,15 @@ async fn main() -> Result<()> {
         account_include: vec![PUMPFUN_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
-    let account_filter = AccountFilter { account: vec![], owner: vec![], filters: vec![] };
-    let trade_event_filter = EventTypeFilter {
-        include: vec![
-            EventType::PumpFunBuy,
-            EventType::PumpFunSell,
-            EventType::RaydiumCpmmSwapBaseInput,
-            EventType::RaydiumCpmmSwapBaseOutput,
-        ],
-    };
+    let account_filter =
+        AccountFilter { account: vec![], owner: vec![], filters: vec![], event_types: vec![] };
+    let trade_event_filter = EventTypeFilter::builder()
+        .protocol_swaps(Protocol::PumpFun)
+        .protocol_swaps(Protocol::RaydiumCpmm)
+        .build();
 
     if let Err(e) = client
         .subscribe_events_immediate(
@@ -90,18 +78,27 @@ async fn main() -> Result<()> {
         account_include: vec![PUMPFUN_PROGRAM_ID.to_string(), RAYDIUM_CPMM_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     if let Err(e) = client
         .update_subscription(
             vec![multi_protocol_filter],
-            vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
+            vec![AccountFilter {
+                account: vec![],
+                owner: vec![],
+                filters: vec![],
+                event_types: vec![],
+            }],
         )
         .await
     {
         println!("Failed to update subscription: {}", e);
         return Ok(());
     }
+    if let Some(summary) = client.current_filter_summary().await {
+        println!("Active account_include: {:?}", summary.transaction_account_include);
+    }
 
     println!(
         "Updated to PumpFun + RaydiumCpmm transactions, monitoring for {}s...",
@@ -116,12 +113,18 @@ async fn main() -> Result<()> {
         account_include: vec![RAYDIUM_CPMM_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     if let Err(e) = client
         .update_subscription(
             vec![raydium_cpmm_filter],
-            vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
+            vec![AccountFilter {
+                account: vec![],
+                owner: vec![],
+                filters: vec![],
+                event_types: vec![],
+            }],
         )
         .await
     {
@@ -142,12 +145,18 @@ async fn main() -> Result<()> {
         account_include: vec![PUMPFUN_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     if let Err(e) = client
         .update_subscription(
             vec![pumpfun_only_filter],
-            vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
+            vec![AccountFilter {
+                account: vec![],
+                owner: vec![],
+                filters: vec![],
+                event_types: vec![],
+            }],
         )
         .await
     {
@@ -168,12 +177,18 @@ async fn main() -> Result<()> {
         account_include: vec![],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     if let Err(e) = client
         .update_subscription(
             vec![empty_filter],
-            vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
+            vec![AccountFilter {
+                account: vec![],
+                owner: vec![],
+                filters: vec![],
+                event_types: vec![],
+            }],
         )
         .await
     {
@@ -200,12 +215,18 @@ async fn main() -> Result<()> {
         account_include: vec![],
         account_exclude: vec![],
         account_required: vec![random_pubkey_1.to_string(), random_pubkey_2.to_string()],
+        failed: Some(false),
     };
 
     if let Err(e) = client
         .update_subscription(
             vec![silence_filter],
-            vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
+            vec![AccountFilter {
+                account: vec![],
+                owner: vec![],
+                filters: vec![],
+                event_types: vec![],
+            }],
         )
         .await
     {
@@ -265,8 +286,14 @@ async fn main() -> Result<()> {
                 account_include: vec![],
                 account_exclude: vec![],
                 account_required: vec![],
+                failed: Some(false),
+            }],
+            vec![AccountFilter {
+                account: vec![],
+                owner: vec![],
+                filters: vec![],
+                event_types: vec![],
             }],
-            vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
             None,
             None,
             shutdown_callback,
@@ -335,8 +362,14 @@ async fn main() -> Result<()> {
                 account_include: vec![RAYDIUM_CPMM_PROGRAM_ID.to_string()],
                 account_exclude: vec![],
                 account_required: vec![],
+                failed: Some(false),
+            }],
+            vec![AccountFilter {
+                account: vec![],
+                owner: vec![],
+                filters: vec![],
+                event_types: vec![],
             }],
-            vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
             None,
             None,
             test_callback,
@@ -367,8 +400,14 @@ async fn main() -> Result<()> {
                 account_include: vec![RAYDIUM_CPMM_PROGRAM_ID.to_string()],
                 account_exclude: vec![],
                 account_required: vec![],
+                failed: Some(false),
+            }],
+            vec![AccountFilter {
+                account: vec![],
+                owner: vec![],
+                filters: vec![],
+                event_types: vec![],
             }],
-            vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
             None,
             None,
             client2_callback,
@@ -401,8 +440,14 @@ async fn main() -> Result<()> {
                 account_include: vec![RAYDIUM_CPMM_PROGRAM_ID.to_string()],
                 account_exclude: vec![],
                 account_required: vec![],
+                failed: Some(false),
+            }],
+            vec![AccountFilter {
+                account: vec![],
+                owner: vec![],
+                filters: vec![],
+                event_types: vec![],
             }],
-            vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
             None,
             None,
             test_callback_advanced,
@@ -419,8 +464,14 @@ async fn main() -> Result<()> {
                         account_include: vec![RAYDIUM_CPMM_PROGRAM_ID.to_string()],
                         account_exclude: vec![],
                         account_required: vec![],
+                        failed: Some(false),
+                    }],
+                    vec![AccountFilter {
+                        account: vec![],
+                        owner: vec![],
+                        filters: vec![],
+                        event_types: vec![],
                     }],
-                    vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
                     None,
                     None,
                     |_| {},
@@ -455,8 +506,14 @@ async fn main() -> Result<()> {
                 account_include: vec![RAYDIUM_CPMM_PROGRAM_ID.to_string()],
                 account_exclude: vec![],
                 account_required: vec![],
+                failed: Some(false),
+            }],
+            vec![AccountFilter {
+                account: vec![],
+                owner: vec![],
+                filters: vec![],
+                event_types: vec![],
             }],
-            vec![AccountFilter { account: vec![], owner: vec![], filters: vec![] }],
             None,
             None,
             client4_callback,
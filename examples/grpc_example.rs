@@ -69,11 +69,16 @@ async fn test_grpc() -> Result<(), Box<dyn std::error::Error>> {
         account_include: account_include.clone(),
         account_exclude,
         account_required,
+        failed: Some(false),
     };
 
     // Listen to account data belonging to owner programs -> account event monitoring
-    let account_filter =
-        AccountFilter { account: vec![], owner: account_include.clone(), filters: vec![] };
+    let account_filter = AccountFilter {
+        account: vec![],
+        owner: account_include.clone(),
+        filters: vec![],
+        event_types: vec![],
+    };
 
     // Event filtering
     // No event filtering, includes all events
@@ -57,6 +57,7 @@ async fn subscribe_meteora_dlmm_accounts() -> Result<(), Box<dyn std::error::Err
                 data: Some(Data::Bytes(discriminators::LB_PAIR.to_vec())),
             })),
         }],
+        event_types: vec![],
     };
 
     // 创建 BinArrayBitmapExtension 账户过滤器
@@ -70,6 +71,7 @@ async fn subscribe_meteora_dlmm_accounts() -> Result<(), Box<dyn std::error::Err
                 data: Some(Data::Bytes(discriminators::BIN_ARRAY_BITMAP_EXTENSION.to_vec())),
             })),
         }],
+        event_types: vec![],
     };
 
     // 交易过滤器（可选，如果只想订阅账户数据，可以留空）
@@ -77,6 +79,7 @@ async fn subscribe_meteora_dlmm_accounts() -> Result<(), Box<dyn std::error::Err
         account_include: vec![METEORA_DLMM_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     // 事件类型过滤器 - 只订阅账户事件
@@ -56,6 +56,7 @@ async fn subscribe_meteora_dlmm_bin_array_accounts() -> Result<(), Box<dyn std::
                 data: Some(Data::Bytes(discriminators::BIN_ARRAY.to_vec())),
             })),
         }],
+        event_types: vec![],
     };
 
     // 交易过滤器（可选，如果只想订阅账户数据，可以留空）
@@ -63,6 +64,7 @@ async fn subscribe_meteora_dlmm_bin_array_accounts() -> Result<(), Box<dyn std::
         account_include: vec![METEORA_DLMM_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     // 事件类型过滤器 - 只订阅 BinArray 账户事件
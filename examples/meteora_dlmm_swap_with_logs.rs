@@ -37,6 +37,7 @@ async fn subscribe_meteora_dlmm_swaps() -> Result<(), Box<dyn std::error::Error>
         account_include: vec![METEORA_DLMM_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     // 事件类型过滤器 - 只订阅 Swap/Swap2 事件
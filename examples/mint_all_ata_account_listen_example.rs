@@ -45,8 +45,12 @@ async fn test_grpc() -> Result<(), Box<dyn std::error::Error>> {
     let account_required = vec![];
 
     // Listen to transaction data
-    let transaction_filter =
-        TransactionFilter { account_include, account_exclude, account_required };
+    let transaction_filter = TransactionFilter {
+        account_include,
+        account_exclude,
+        account_required,
+        failed: Some(false),
+    };
 
     let pump = Pubkey::from_str("pumpCmXqMfrsAkQ5r49WcJnRayYRqmXz6ae8H7H9Dfn").unwrap();
     let usdc = Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap();
@@ -60,6 +64,7 @@ async fn test_grpc() -> Result<(), Box<dyn std::error::Error>> {
                 data: Some(Data::Bytes(pump.to_bytes().to_vec())),
             })),
         }],
+        event_types: vec![],
     };
     let all_usdc_ata = AccountFilter {
         account: vec![],
@@ -70,6 +75,7 @@ async fn test_grpc() -> Result<(), Box<dyn std::error::Error>> {
                 data: Some(Data::Bytes(usdc.to_bytes().to_vec())),
             })),
         }],
+        event_types: vec![],
     };
 
     // Event filtering
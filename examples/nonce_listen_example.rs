@@ -37,13 +37,21 @@ async fn test_grpc() -> Result<(), Box<dyn std::error::Error>> {
     let account_required = vec![];
 
     // Listen to transaction data
-    let transaction_filter =
-        TransactionFilter { account_include, account_exclude, account_required };
+    let transaction_filter = TransactionFilter {
+        account_include,
+        account_exclude,
+        account_required,
+        failed: Some(false),
+    };
 
     let nonce_account = "use_your_nonce_account_here".to_string();
     // Listen to account data belonging to owner programs -> account event monitoring
-    let account_filter =
-        AccountFilter { account: vec![nonce_account], owner: vec![], filters: vec![] };
+    let account_filter = AccountFilter {
+        account: vec![nonce_account],
+        owner: vec![],
+        filters: vec![],
+        event_types: vec![],
+    };
 
     // Event filtering
     let event_type_filter = Some(EventTypeFilter { include: vec![EventType::NonceAccount] });
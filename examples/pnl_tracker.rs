@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::{
+    event_parser::{
+        common::{filter::EventTypeFilter, EventType},
+        core::QuoteMints,
+        DexEvent, Protocol,
+    },
+    grpc::ClientConfig,
+    pnl::PnlTracker,
+    yellowstone_grpc::TransactionFilter,
+    YellowstoneGrpc,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let wallet: Pubkey = "use_your_wallet_here".parse()?;
+
+    // 让没有专用数量字段的 swap 事件也能从其后的转账中得到成交数量
+    let config = ClientConfig { extract_swap_data: true, ..Default::default() };
+    let grpc = YellowstoneGrpc::new_with_config(
+        "https://solana-yellowstone-grpc.publicnode.com:443".to_string(),
+        None,
+        config,
+    )?;
+
+    let tracker = Arc::new(PnlTracker::new([wallet], QuoteMints::global()));
+    let callback = {
+        let tracker = tracker.clone();
+        move |event: DexEvent| {
+            if !tracker.record_event(&event) {
+                return;
+            }
+            let Some(trade) =
+                QuoteMints::with_global(|quote_mints| event.to_normalized_trade(quote_mints))
+            else {
+                return;
+            };
+            if let Some(position) = tracker.get_pnl(&trade.trader, &trade.base_mint) {
+                println!(
+                    "{} {} {}: holding {} (avg cost {:?}), realized pnl {} {}",
+                    trade.signature,
+                    if trade.is_buy { "buy" } else { "sell" },
+                    trade.base_mint,
+                    position.base_amount,
+                    position.average_cost(),
+                    position.realized_pnl,
+                    position.quote_mint,
+                );
+            }
+        }
+    };
+
+    // 只订阅该钱包参与的交易
+    let transaction_filter = TransactionFilter {
+        account_include: vec![wallet.to_string()],
+        account_exclude: vec![],
+        account_required: vec![],
+        failed: Some(false),
+    };
+    let event_type_filter = Some(EventTypeFilter {
+        include: vec![
+            EventType::PumpFunBuy,
+            EventType::PumpFunSell,
+            EventType::PumpSwapBuy,
+            EventType::PumpSwapBuyExactQuoteIn,
+            EventType::PumpSwapSell,
+            EventType::BonkBuyExactIn,
+            EventType::BonkBuyExactOut,
+            EventType::BonkSellExactIn,
+            EventType::BonkSellExactOut,
+            EventType::RaydiumCpmmSwapBaseInput,
+            EventType::RaydiumCpmmSwapBaseOutput,
+            EventType::RaydiumAmmV4SwapBaseIn,
+            EventType::RaydiumAmmV4SwapBaseOut,
+        ],
+    });
+
+    grpc.subscribe_events_immediate(
+        vec![
+            Protocol::PumpFun,
+            Protocol::PumpSwap,
+            Protocol::Bonk,
+            Protocol::RaydiumCpmm,
+            Protocol::RaydiumAmmV4,
+        ],
+        None,
+        vec![transaction_filter],
+        vec![],
+        event_type_filter,
+        None,
+        callback,
+    )
+    .await?;
+
+    println!("Tracking realized PnL for {wallet}, press Ctrl+C to stop...");
+    tokio::signal::ctrl_c().await?;
+
+    for (mint, position) in tracker.positions(&wallet) {
+        println!("{mint}: {position:?}");
+    }
+    grpc.stop().await;
+    Ok(())
+}
@@ -45,8 +45,12 @@ async fn test_grpc() -> Result<(), Box<dyn std::error::Error>> {
     let account_required = vec![];
 
     // Listen to transaction data
-    let transaction_filter =
-        TransactionFilter { account_include, account_exclude, account_required };
+    let transaction_filter = TransactionFilter {
+        account_include,
+        account_exclude,
+        account_required,
+        failed: Some(false),
+    };
 
     // Pump.fun AMM (PUMP-USDC) Market
     let pump_usdc = Pubkey::from_str("2uF4Xh61rDwxnG9woyxsVQP7zuA6kLFpb3NvnRQeoiSd").unwrap();
@@ -62,6 +66,7 @@ async fn test_grpc() -> Result<(), Box<dyn std::error::Error>> {
                 data: Some(Data::Bytes(pump_usdc.to_bytes().to_vec())),
             })),
         }],
+        event_types: vec![],
     };
     let wsol_deepseekai_account_filter = AccountFilter {
         account: vec![],
@@ -72,6 +77,7 @@ async fn test_grpc() -> Result<(), Box<dyn std::error::Error>> {
                 data: Some(Data::Bytes(wsol_deepseekai.to_bytes().to_vec())),
             })),
         }],
+        event_types: vec![],
     };
 
     // Event filtering
@@ -42,6 +42,7 @@ async fn subscribe_raydium_clmm_bitmap_extension() -> Result<(), Box<dyn std::er
         account: vec![],
         owner: vec![RAYDIUM_CLMM_PROGRAM_ID.to_string()],
         filters: vec![],
+        event_types: vec![],
     };
 
     // 交易过滤器（可选，如果只想订阅账户数据，可以留空）
@@ -49,6 +50,7 @@ async fn subscribe_raydium_clmm_bitmap_extension() -> Result<(), Box<dyn std::er
         account_include: vec![],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     // 事件类型过滤器 - 只订阅 TickArrayBitmapExtension 账户事件
@@ -31,6 +31,7 @@ async fn subscribe_raydium_clmm_swaps() -> Result<(), Box<dyn std::error::Error>
         account_include: vec![RAYDIUM_CLMM_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     use solana_streamer_sdk::streaming::event_parser::common::filter::EventTypeFilter;
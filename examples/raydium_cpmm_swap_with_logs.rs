@@ -37,6 +37,7 @@ async fn subscribe_raydium_cpmm_swaps() -> Result<(), Box<dyn std::error::Error>
         account_include: vec![RAYDIUM_CPMM_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     // 事件类型过滤器 - 只订阅 Swap 事件
@@ -37,14 +37,22 @@ async fn test_grpc() -> Result<(), Box<dyn std::error::Error>> {
     let account_required = vec![];
 
     // Listen to transaction data
-    let transaction_filter =
-        TransactionFilter { account_include, account_exclude, account_required };
+    let transaction_filter = TransactionFilter {
+        account_include,
+        account_exclude,
+        account_required,
+        failed: Some(false),
+    };
 
     let account_to_listen = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string();
 
     // Listen to account data belonging to owner programs -> account event monitoring
-    let account_filter =
-        AccountFilter { account: vec![account_to_listen], owner: vec![], filters: vec![] };
+    let account_filter = AccountFilter {
+        account: vec![account_to_listen],
+        owner: vec![],
+        filters: vec![],
+        event_types: vec![],
+    };
 
     // Event filtering
     let event_type_filter = Some(EventTypeFilter { include: vec![EventType::TokenAccount] });
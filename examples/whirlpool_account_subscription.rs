@@ -42,6 +42,7 @@ async fn subscribe_whirlpool_accounts() -> Result<(), Box<dyn std::error::Error>
         account: vec![],
         owner: vec![WHIRLPOOL_PROGRAM_ID.to_string()],
         filters: vec![],
+        event_types: vec![],
     };
 
     // 交易过滤器（可选，如果只想订阅账户数据，可以留空）
@@ -49,6 +50,7 @@ async fn subscribe_whirlpool_accounts() -> Result<(), Box<dyn std::error::Error>
         account_include: vec![WHIRLPOOL_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     // 事件类型过滤器 - 只订阅账户事件
@@ -35,12 +35,14 @@ async fn subscribe_whirlpool_tick_array_accounts() -> Result<(), Box<dyn std::er
         account: vec![],
         owner: vec![WHIRLPOOL_PROGRAM_ID.to_string()],
         filters: vec![],
+        event_types: vec![],
     };
 
     let transaction_filter = TransactionFilter {
         account_include: vec![WHIRLPOOL_PROGRAM_ID.to_string()],
         account_exclude: vec![],
         account_required: vec![],
+        failed: Some(false),
     };
 
     use solana_streamer_sdk::streaming::event_parser::common::filter::EventTypeFilter;
@@ -1,3 +1,4 @@
 pub mod common;
+#[cfg(feature = "grpc")]
 pub mod protos;
 pub mod streaming;
@@ -0,0 +1,216 @@
+use crate::streaming::common::MetricsManager;
+use crate::streaming::event_parser::DexEvent;
+use parking_lot::Mutex;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Default)]
+struct CoalescerState {
+    /// 当前缓存的 slot
+    slot: u64,
+    /// 当前 slot 内每个账户最后一次的事件，按账户首次出现的顺序
+    pending: Vec<DexEvent>,
+    index: HashMap<Pubkey, usize>,
+}
+
+/// 按 (pubkey, slot) 合并账户事件
+///
+/// 同一 slot 内同一账户的多次更新只保留最后一次，收到更高 slot 的任意事件时按账户首次出现的
+/// 顺序发出上一个 slot 缓存的账户事件。被覆盖的更新计入 `coalesced_account_updates_count`。
+///
+/// - 非账户事件不会缓存，直接发出（在同一 slot 的账户事件之前）
+/// - 低于当前 slot 的迟到账户事件无法再合并，直接发出
+/// - 最后一个 slot 的账户事件在更高 slot 的事件到达、订阅循环收到该 slot 的 BlockMeta /
+///   更高 slot 的 slot 更新（`flush_until`），或流结束（`flush`）时发出
+pub struct AccountCoalescer {
+    state: Mutex<CoalescerState>,
+}
+
+impl Default for AccountCoalescer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccountCoalescer {
+    pub fn new() -> Self {
+        Self { state: Mutex::new(CoalescerState::default()) }
+    }
+
+    /// 缓存中尚未发出的账户事件数量
+    pub fn pending_events(&self) -> usize {
+        self.state.lock().pending.len()
+    }
+
+    /// 包装 callback：账户事件经过合并后转发
+    ///
+    /// 返回的 handle 由订阅循环持有，用于在 slot 结束和流结束时把缓存发给 `callback`
+    pub fn wrap(
+        callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+    ) -> (Arc<dyn Fn(DexEvent) + Send + Sync>, AccountCoalescerHandle) {
+        let handle =
+            AccountCoalescerHandle { coalescer: Arc::new(AccountCoalescer::new()), callback };
+        let inner = handle.clone();
+        let wrapped = Arc::new(move |event: DexEvent| {
+            for event in inner.coalescer.push(event) {
+                (inner.callback)(event);
+            }
+        });
+        (wrapped, handle)
+    }
+
+    /// 放入一个事件，返回现在可以发出的事件
+    pub fn push(&self, event: DexEvent) -> Vec<DexEvent> {
+        let mut state = self.state.lock();
+        let slot = event.metadata().slot;
+        let mut ready = Vec::new();
+
+        if slot > state.slot {
+            ready.append(&mut state.pending);
+            state.index.clear();
+            state.slot = slot;
+        }
+
+        match event.account_pubkey() {
+            Some(pubkey) if slot == state.slot => match state.index.get(&pubkey) {
+                Some(&i) => {
+                    state.pending[i] = event;
+                    MetricsManager::global().add_coalesced_account_updates(1);
+                }
+                None => {
+                    let i = state.pending.len();
+                    state.index.insert(pubkey, i);
+                    state.pending.push(event);
+                }
+            },
+            _ => ready.push(event),
+        }
+        ready
+    }
+
+    /// 发出所有缓存的账户事件
+    pub fn flush(&self) -> Vec<DexEvent> {
+        let mut state = self.state.lock();
+        state.index.clear();
+        std::mem::take(&mut state.pending)
+    }
+
+    /// 缓存的 slot 小于 `slot` 时发出所有缓存的账户事件
+    ///
+    /// 收到 slot S 的 BlockMeta 时调用 `flush_until(S + 1)`，收到 slot S 的 slot 更新时调用
+    /// `flush_until(S)`
+    pub fn flush_until(&self, slot: u64) -> Vec<DexEvent> {
+        let mut state = self.state.lock();
+        if state.slot >= slot {
+            return Vec::new();
+        }
+        state.index.clear();
+        std::mem::take(&mut state.pending)
+    }
+}
+
+/// `AccountCoalescer::wrap` 返回的 handle，把缓存的账户事件发给被包装的 callback
+#[derive(Clone)]
+pub struct AccountCoalescerHandle {
+    coalescer: Arc<AccountCoalescer>,
+    callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+}
+
+impl AccountCoalescerHandle {
+    /// 发出 slot 小于 `slot` 的缓存账户事件，见 `AccountCoalescer::flush_until`
+    pub fn flush_until(&self, slot: u64) {
+        for event in self.coalescer.flush_until(slot) {
+            (self.callback)(event);
+        }
+    }
+
+    /// 发出所有缓存的账户事件，订阅循环在流结束时调用
+    pub fn flush(&self) {
+        for event in self.coalescer.flush() {
+            (self.callback)(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::common::EventMetadata;
+    use crate::streaming::event_parser::core::account_event_parser::TokenAccountEvent;
+    use crate::streaming::event_parser::core::common_event_parser::SetComputeUnitLimitEvent;
+
+    fn account_event(pubkey: Pubkey, slot: u64, amount: u64) -> DexEvent {
+        DexEvent::TokenAccountEvent(TokenAccountEvent {
+            metadata: EventMetadata { slot, ..Default::default() },
+            pubkey,
+            amount: Some(amount),
+            ..Default::default()
+        })
+    }
+
+    fn amounts(events: &[DexEvent]) -> Vec<(u64, Option<u64>)> {
+        events
+            .iter()
+            .map(|e| match e {
+                DexEvent::TokenAccountEvent(e) => (e.metadata.slot, e.amount),
+                _ => (e.metadata().slot, None),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_keeps_last_update_per_pubkey_and_slot() {
+        let coalescer = AccountCoalescer::new();
+        let pool = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+
+        assert!(coalescer.push(account_event(pool, 10, 1)).is_empty());
+        assert!(coalescer.push(account_event(vault, 10, 2)).is_empty());
+        assert!(coalescer.push(account_event(pool, 10, 3)).is_empty());
+        assert_eq!(coalescer.pending_events(), 2);
+
+        // 非账户事件直接发出
+        let tx_event = DexEvent::SetComputeUnitLimitEvent(SetComputeUnitLimitEvent {
+            metadata: EventMetadata { slot: 10, ..Default::default() },
+            units: 0,
+        });
+        assert_eq!(amounts(&coalescer.push(tx_event)), vec![(10, None)]);
+
+        // slot 前进时发出上一个 slot 每个账户的最后状态
+        let ready = coalescer.push(account_event(pool, 11, 4));
+        assert_eq!(amounts(&ready), vec![(10, Some(3)), (10, Some(2))]);
+
+        // 迟到的账户事件直接发出
+        assert_eq!(amounts(&coalescer.push(account_event(vault, 10, 5))), vec![(10, Some(5))]);
+        assert_eq!(amounts(&coalescer.flush()), vec![(11, Some(4))]);
+        assert_eq!(coalescer.pending_events(), 0);
+    }
+
+    #[test]
+    fn test_handle_flushes_on_slot_end_and_stream_end() {
+        let emitted = Arc::new(Mutex::new(Vec::new()));
+        let sink = emitted.clone();
+        let (callback, handle) = AccountCoalescer::wrap(Arc::new(move |event: DexEvent| {
+            sink.lock().push(event);
+        }));
+        let pool = Pubkey::new_unique();
+
+        callback(account_event(pool, 20, 1));
+        callback(account_event(pool, 20, 2));
+        assert!(emitted.lock().is_empty());
+
+        // slot 20 的 slot 更新不表示 slot 20 已结束
+        handle.flush_until(20);
+        assert!(emitted.lock().is_empty());
+
+        // 收到 slot 20 的 BlockMeta 后发出，不再等待更高 slot 的事件
+        handle.flush_until(21);
+        assert_eq!(amounts(&emitted.lock()), vec![(20, Some(2))]);
+
+        // 流结束时发出剩余的缓存
+        callback(account_event(pool, 21, 3));
+        handle.flush();
+        assert_eq!(amounts(&emitted.lock()), vec![(20, Some(2)), (21, Some(3))]);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::streaming::common::MetricsManager;
+use crate::streaming::event_parser::common::EventType;
+use crate::streaming::event_parser::{DexEvent, TxDexEvents};
+use solana_sdk::signature::Signature;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+/// Callback 执行耗时监控配置
+#[derive(Debug, Clone)]
+pub struct CallbackWatchdogConfig {
+    /// 单次 callback 超过该耗时时记录警告并计入 `slow_callbacks_count`（默认 50ms）
+    pub slow_threshold: Duration,
+    /// 在独立线程上按顺序执行 callback（默认 false）
+    ///
+    /// 流处理线程最多等待 `slow_threshold`，超时后不再等待，后续事件排队交给 callback 线程，
+    /// 直到其追上后恢复同步等待。callback 阻塞时流不会停滞，但排队的事件会占用内存。
+    pub offload_after_threshold: bool,
+}
+
+impl Default for CallbackWatchdogConfig {
+    fn default() -> Self {
+        Self { slow_threshold: Duration::from_millis(50), offload_after_threshold: false }
+    }
+}
+
+/// 用于慢 callback 日志定位的事件信息
+pub trait CallbackEventInfo {
+    fn callback_info(&self) -> (Signature, Option<EventType>);
+}
+
+impl CallbackEventInfo for DexEvent {
+    fn callback_info(&self) -> (Signature, Option<EventType>) {
+        let metadata = self.metadata();
+        (metadata.signature, Some(metadata.event_type.clone()))
+    }
+}
+
+impl CallbackEventInfo for TxDexEvents {
+    fn callback_info(&self) -> (Signature, Option<EventType>) {
+        (self.signature, None)
+    }
+}
+
+/// 测量 callback 执行耗时的包装器
+pub struct CallbackWatchdog;
+
+impl CallbackWatchdog {
+    /// 配置为 None 时原样返回 callback
+    pub fn wrap_if_enabled<T>(
+        config: Option<&CallbackWatchdogConfig>,
+        callback: Arc<dyn Fn(T) + Send + Sync>,
+    ) -> Arc<dyn Fn(T) + Send + Sync>
+    where
+        T: CallbackEventInfo + Send + 'static,
+    {
+        match config {
+            Some(config) => Self::wrap(config, callback),
+            None => callback,
+        }
+    }
+
+    /// 包装 callback：超过阈值时记录日志和指标，按配置在独立线程上执行
+    pub fn wrap<T>(
+        config: &CallbackWatchdogConfig,
+        callback: Arc<dyn Fn(T) + Send + Sync>,
+    ) -> Arc<dyn Fn(T) + Send + Sync>
+    where
+        T: CallbackEventInfo + Send + 'static,
+    {
+        let threshold = config.slow_threshold;
+        if !config.offload_after_threshold {
+            return Arc::new(move |event: T| {
+                let info = event.callback_info();
+                let start = Instant::now();
+                callback(event);
+                report_if_slow(start.elapsed(), threshold, &info);
+            });
+        }
+
+        let (event_tx, event_rx) = mpsc::channel::<T>();
+        let (done_tx, done_rx) = crossbeam::channel::unbounded::<()>();
+        // 已交给 callback 线程但尚未执行完的事件数
+        let pending = Arc::new(AtomicUsize::new(0));
+
+        let worker_pending = pending.clone();
+        let spawned =
+            std::thread::Builder::new().name("callback-watchdog".to_string()).spawn(move || {
+                for event in event_rx {
+                    let info = event.callback_info();
+                    let start = Instant::now();
+                    callback(event);
+                    report_if_slow(start.elapsed(), threshold, &info);
+                    // 先发完成信号再减计数，等待方看到 0 时不会残留旧信号
+                    let _ = done_tx.send(());
+                    worker_pending.fetch_sub(1, Ordering::AcqRel);
+                }
+            });
+        if let Err(e) = spawned {
+            log::error!("Failed to spawn callback watchdog thread: {e}");
+        }
+
+        Arc::new(move |event: T| {
+            let caught_up = pending.load(Ordering::Acquire) == 0;
+            if caught_up {
+                while done_rx.try_recv().is_ok() {}
+            }
+            pending.fetch_add(1, Ordering::AcqRel);
+            if event_tx.send(event).is_err() {
+                // callback 线程已退出（例如 callback panic）
+                pending.fetch_sub(1, Ordering::AcqRel);
+                MetricsManager::global().increment_dropped_events();
+                return;
+            }
+            if caught_up && done_rx.recv_timeout(threshold).is_err() {
+                log::warn!(
+                    "Callback still running after {:?}, queueing further events to the callback thread",
+                    threshold
+                );
+            }
+        })
+    }
+}
+
+fn report_if_slow(elapsed: Duration, threshold: Duration, info: &(Signature, Option<EventType>)) {
+    if elapsed <= threshold {
+        return;
+    }
+    MetricsManager::global().increment_slow_callbacks();
+    match &info.1 {
+        Some(event_type) => {
+            log::warn!("Slow callback: {:?} for {} {}", elapsed, event_type, info.0)
+        }
+        None => log::warn!("Slow callback: {:?} for transaction {}", elapsed, info.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offloaded_callback_does_not_block_stream() {
+        let config = CallbackWatchdogConfig {
+            slow_threshold: Duration::from_millis(1),
+            offload_after_threshold: true,
+        };
+        // slot 1 的 callback 阻塞到测试放行，不依赖耗时判断
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+        let gate_rx = parking_lot::Mutex::new(gate_rx);
+        let (handled_tx, handled_rx) = mpsc::channel::<u64>();
+        let handled_tx = parking_lot::Mutex::new(handled_tx);
+        let callback = CallbackWatchdog::wrap(
+            &config,
+            Arc::new(move |event: DexEvent| {
+                let slot = event.metadata().slot;
+                if slot == 1 {
+                    gate_rx.lock().recv().unwrap();
+                }
+                handled_tx.lock().send(slot).unwrap();
+            }),
+        );
+
+        let event = |slot: u64| {
+            let mut event = DexEvent::BlockMetaEvent(Default::default());
+            event.metadata_mut().slot = slot;
+            event
+        };
+        // 同步执行时 slot 1 会一直阻塞，全部返回说明后续事件已交给 callback 线程排队
+        for slot in 1..=5 {
+            callback(event(slot));
+        }
+        assert!(handled_rx.try_recv().is_err());
+
+        gate_tx.send(()).unwrap();
+        let handled: Vec<u64> = handled_rx.iter().take(5).collect();
+        assert_eq!(handled, vec![1, 2, 3, 4, 5]);
+    }
+}
@@ -1,5 +1,12 @@
+use super::callback_watchdog::CallbackWatchdogConfig;
 use super::constants::*;
+use super::metrics::LatencyMonitor;
+use crate::streaming::event_parser::common::filter::AccountDiscriminatorFilter;
 use crate::streaming::event_parser::common::SwapCuParseConfig;
+use crate::streaming::event_parser::core::{ParseOptions, QuoteMints};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use yellowstone_grpc_proto::geyser::CommitmentLevel;
 
 /// Connection configuration
 #[derive(Debug, Clone)]
@@ -10,6 +17,11 @@ pub struct ConnectionConfig {
     pub request_timeout: u64,
     /// Maximum decoding message size in bytes (default: 10MB)
     pub max_decoding_message_size: usize,
+    /// Client ping interval in seconds, 0 disables client pings (default: 10)
+    pub ping_interval: u64,
+    /// End the stream when no message (including ping/pong) arrives for this many seconds and
+    /// report it through `YellowstoneGrpc::on_disconnect`, 0 disables the watchdog (default: 60)
+    pub idle_timeout: u64,
 }
 
 impl Default for ConnectionConfig {
@@ -18,6 +30,42 @@ impl Default for ConnectionConfig {
             connect_timeout: DEFAULT_CONNECT_TIMEOUT,
             request_timeout: DEFAULT_REQUEST_TIMEOUT,
             max_decoding_message_size: DEFAULT_MAX_DECODING_MESSAGE_SIZE,
+            ping_interval: DEFAULT_PING_INTERVAL,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+        }
+    }
+}
+
+/// Object pool configuration
+///
+/// Each pool pre-allocates `*_initial_size` boxed objects and keeps at most `*_max_size`
+/// returned objects. Approximate footprint per entry (excluding heap buffers, which are
+/// moved out rather than retained):
+/// - `AccountPretty`: ~200 bytes
+/// - `BlockMetaPretty`: ~150 bytes
+/// - `TransactionPretty`: ~1 KiB, dominated by the inline `SubscribeUpdateTransactionInfo`
+///
+/// With the defaults this is roughly 2 MB (account) + 75 KB (block meta) + 10 MB (transaction)
+/// pre-allocated at startup. Use `std::mem::size_of` on the types for exact numbers on your target.
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    pub account_initial_size: usize,
+    pub account_max_size: usize,
+    pub block_meta_initial_size: usize,
+    pub block_meta_max_size: usize,
+    pub transaction_initial_size: usize,
+    pub transaction_max_size: usize,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            account_initial_size: DEFAULT_ACCOUNT_POOL_INITIAL_SIZE,
+            account_max_size: DEFAULT_ACCOUNT_POOL_MAX_SIZE,
+            block_meta_initial_size: DEFAULT_BLOCK_META_POOL_INITIAL_SIZE,
+            block_meta_max_size: DEFAULT_BLOCK_META_POOL_MAX_SIZE,
+            transaction_initial_size: DEFAULT_TRANSACTION_POOL_INITIAL_SIZE,
+            transaction_max_size: DEFAULT_TRANSACTION_POOL_MAX_SIZE,
         }
     }
 }
@@ -31,6 +79,102 @@ pub struct StreamClientConfig {
     pub enable_metrics: bool,
     /// Optional swap compute-unit parsing. None means no CU log parsing overhead.
     pub swap_cu_parse_config: Option<SwapCuParseConfig>,
+    /// Drop vote transactions (`is_vote`) without parsing them (default: true). The default
+    /// transaction filter already requests `vote: false`; this only applies to custom filters
+    /// that let votes through
+    pub skip_votes: bool,
+    /// Emit a TransactionSummaryEvent after each transaction that produced events (default: false)
+    pub emit_transaction_summary: bool,
+    /// Fill `metadata.swap_data` of every swap event from the token / system transfers that
+    /// follow the swap instruction (default: false). Costs one extra scan of the following inner
+    /// instructions and one `SwapData` allocation per swap event; when off, swap_data is only set
+    /// (mints, no amounts) for events whose instruction accounts carry the mints.
+    pub extract_swap_data: bool,
+    /// Cross-check the mint carried by `TransferChecked` transfers against the mint inferred
+    /// from the pool vaults while extracting swap_data, and set `SwapData::mint_mismatch` when
+    /// they disagree (default: false). Only applies with `extract_swap_data`.
+    pub verify_transfer_mints: bool,
+    /// Attach mint decimals from MintRegistry to SwapData for UI amounts (default: false)
+    pub attach_ui_amounts: bool,
+    /// Keep the raw bytes in account events' `raw_account_data` after decoding (default: true)
+    pub retain_raw_account_data: bool,
+    /// Hold events in a `SlotReorderBuffer` of this many slots and emit `SlotRolledBackEvent`
+    /// for slots dropped by a fork (default: None). Requires BlockMeta updates; slots on both
+    /// sides of a fork are held until a slot update marks them rooted or dead. Events are
+    /// delayed until their block's BlockMeta plus `depth` slots (~400ms each) and the buffer
+    /// keeps all events of those slots in memory. Only applies to `subscribe_events_immediate`.
+    pub reorder_buffer_depth: Option<u64>,
+    /// With `reorder_buffer_depth`, also request inter-slot status updates (first shred,
+    /// completed, bank created, dead) on its slot subscription (default: false). Dead slots are
+    /// then discarded as soon as they are reported instead of when a later slot is rooted, at
+    /// the cost of several extra slot messages per slot.
+    pub slot_interslot_updates: bool,
+    /// Quote currencies used by direction / notional helpers such as `DexEvent::quote_leg`
+    /// (default: WSOL, USDC, USDT). Written to `QuoteMints::global()` when the client is created;
+    /// `sol_volume_lamports` / `total_fee_lamports` only count trades whose quote side is WSOL.
+    pub quote_mints: QuoteMints,
+    /// Measure callback execution time and log callbacks slower than the threshold
+    /// (default: None). See `CallbackWatchdogConfig` for running callbacks off the stream task.
+    pub callback_watchdog: Option<CallbackWatchdogConfig>,
+    /// Only emit the last account event per (pubkey, slot), once an event from a later slot
+    /// arrives (default: false). See `AccountCoalescer`. Applies to `subscribe_events_immediate`
+    /// and `subscribe_accounts_immediate`.
+    pub coalesce_account_updates: bool,
+    /// Commitment for account updates in `subscribe_events_immediate`; the `commitment`
+    /// argument then only applies to transactions and block meta (default: None, same as
+    /// transactions). Yellowstone applies commitment to a whole subscribe request, so a
+    /// different level opens a second gRPC stream for accounts. Events from the two streams
+    /// are interleaved by arrival, and account events at a higher commitment arrive later
+    /// than transactions of the same slot.
+    pub account_commitment: Option<CommitmentLevel>,
+    /// Only decode protocol accounts whose data starts with one of these discriminators
+    /// (default: None, decode all). Skipped accounts cost no decode, e.g. keep Whirlpool pool
+    /// state but drop tick arrays. Token / nonce accounts are not affected. Build one from
+    /// account event types with `AccountDiscriminatorFilter::from_event_types`.
+    pub account_discriminator_filter: Option<AccountDiscriminatorFilter>,
+    /// Log the account list the parser resolved for each transaction that touches a subscribed
+    /// protocol, with padded indices marked (default: false). One info line per transaction,
+    /// meant for debugging wrong account positions in events.
+    pub log_account_keys: bool,
+    /// Emit `SolTransferEvent` for top-level System Program transfers from or to these wallets
+    /// (default: None, off). Transactions touching a watched wallet are parsed even without a
+    /// subscribed protocol; add the wallets to a `TransactionFilter` to receive them. Wallets
+    /// can be changed at runtime through the client's `sol_transfer_watchlist()`.
+    pub sol_transfer_wallets: Option<Vec<Pubkey>>,
+    /// Deliver at most this many transactions per second (default: None, unthrottled). Meant for
+    /// load testing consumers against live subscriptions: the stream task stops reading between
+    /// transactions, so the upstream stream backs up like a slow consumer would, while keepalive
+    /// pings and subscription updates are still served. Account and block meta updates are not
+    /// paced. There is no replay source in this crate, so only live streams are throttled.
+    pub throttle_tps: Option<u32>,
+    /// Emit `UnknownInstructionEvent` for instructions of subscribed protocols whose
+    /// discriminator the parser does not recognize, and count them per program in metrics
+    /// (default: false). An early warning that a protocol upgrade added instructions.
+    pub emit_unknown: bool,
+    /// Emit only the first event of each `EventType` per transaction (default: false), e.g. the
+    /// entry swap of a routed trade without the routing hops. Applies to the transaction
+    /// subscriptions; account and block events are not affected.
+    pub first_event_per_transaction: bool,
+    /// Emit `RawProgramDataEvent` for `Program data:` logs of subscribed protocols that the
+    /// parser cannot decode (default: false). Needs transaction logs, so ShredStream never
+    /// produces these events.
+    pub emit_raw_program_data: bool,
+    /// Build Raydium CLMM / Whirlpool swap events directly from their event logs at invocations
+    /// whose instruction produced no event (default: false), e.g. DEX calls made by an
+    /// aggregator in a form the instruction parser does not match. Needs transaction logs.
+    pub parse_logs_only: bool,
+    /// Log a warning when a transaction event's calibrated latency exceeds
+    /// `high_latency_threshold_ms` (default: false). Rate-limited to one warning per second per
+    /// client, with the number of suppressed warnings. Works without the `metrics` feature.
+    pub warn_on_high_latency: bool,
+    /// Latency threshold in milliseconds for `warn_on_high_latency` (default: 1000)
+    pub high_latency_threshold_ms: i64,
+    /// Milliseconds added to the second-precision block time to approximate when the block was
+    /// produced, used for this client's latency warnings (default: 500). Pass it to
+    /// `EventMetadata::latency_ms_with_adjustment` to compute event latency the same way.
+    pub block_time_adjustment_ms: i64,
+    /// Object pool sizes. Only the first client created in a process decides the global pools.
+    pub pool: PoolConfig,
 }
 
 impl Default for StreamClientConfig {
@@ -39,6 +183,55 @@ impl Default for StreamClientConfig {
             connection: ConnectionConfig::default(),
             enable_metrics: false,
             swap_cu_parse_config: None,
+            skip_votes: true,
+            emit_transaction_summary: false,
+            extract_swap_data: false,
+            verify_transfer_mints: false,
+            attach_ui_amounts: false,
+            retain_raw_account_data: true,
+            reorder_buffer_depth: None,
+            slot_interslot_updates: false,
+            quote_mints: QuoteMints::default(),
+            callback_watchdog: None,
+            coalesce_account_updates: false,
+            account_commitment: None,
+            account_discriminator_filter: None,
+            log_account_keys: false,
+            sol_transfer_wallets: None,
+            throttle_tps: None,
+            emit_unknown: false,
+            first_event_per_transaction: false,
+            emit_raw_program_data: false,
+            parse_logs_only: false,
+            warn_on_high_latency: false,
+            high_latency_threshold_ms: MAX_LATENCY_THRESHOLD_MS,
+            block_time_adjustment_ms: SOLANA_BLOCK_TIME_ADJUSTMENT_MS,
+            pool: PoolConfig::default(),
         }
     }
 }
+
+impl StreamClientConfig {
+    /// 按本配置构建解析选项，客户端在每次订阅时调用；SOL 转账关注列表由客户端持有，不在其中
+    pub fn parse_options(&self) -> ParseOptions {
+        ParseOptions {
+            extract_swap_data: self.extract_swap_data,
+            verify_transfer_mints: self.verify_transfer_mints,
+            log_account_keys: self.log_account_keys,
+            emit_unknown: self.emit_unknown,
+            emit_raw_program_data: self.emit_raw_program_data,
+            parse_logs_only: self.parse_logs_only,
+            sol_transfer_watchlist: None,
+        }
+    }
+
+    /// 按本配置创建高延迟警告，未开启 `warn_on_high_latency` 时为 None
+    pub fn latency_monitor(&self) -> Option<Arc<LatencyMonitor>> {
+        self.warn_on_high_latency.then(|| {
+            Arc::new(LatencyMonitor::new(
+                self.high_latency_threshold_ms,
+                self.block_time_adjustment_ms,
+            ))
+        })
+    }
+}
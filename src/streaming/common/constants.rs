@@ -5,6 +5,16 @@ pub const DEFAULT_CONNECT_TIMEOUT: u64 = 10;
 pub const DEFAULT_REQUEST_TIMEOUT: u64 = 60;
 pub const DEFAULT_CHANNEL_SIZE: usize = 1000;
 pub const DEFAULT_MAX_DECODING_MESSAGE_SIZE: usize = 1024 * 1024 * 10;
+pub const DEFAULT_PING_INTERVAL: u64 = 10;
+pub const DEFAULT_IDLE_TIMEOUT: u64 = 60;
+
+// 对象池默认大小 (预分配数量 / 最大保留数量)
+pub const DEFAULT_ACCOUNT_POOL_INITIAL_SIZE: usize = 10000;
+pub const DEFAULT_ACCOUNT_POOL_MAX_SIZE: usize = 20000;
+pub const DEFAULT_BLOCK_META_POOL_INITIAL_SIZE: usize = 500;
+pub const DEFAULT_BLOCK_META_POOL_MAX_SIZE: usize = 1000;
+pub const DEFAULT_TRANSACTION_POOL_INITIAL_SIZE: usize = 10000;
+pub const DEFAULT_TRANSACTION_POOL_MAX_SIZE: usize = 20000;
 
 // 性能监控相关常量
 pub const DEFAULT_METRICS_WINDOW_SECONDS: u64 = 5;
@@ -13,6 +23,7 @@ pub const SLOW_PROCESSING_THRESHOLD_US: f64 = 3000.0;
 
 // gRPC 延迟监控
 // Solana 不存储毫秒，所以我们用500ms来校准以获得更好的近似值
+// （默认值，可通过 StreamClientConfig::block_time_adjustment_ms 按客户端修改）
 pub const SOLANA_BLOCK_TIME_ADJUSTMENT_MS: i64 = 500;
-// 默认最大延迟阈值（毫秒）
+// 默认最大延迟阈值（毫秒），可通过 StreamClientConfig::high_latency_threshold_ms 按客户端修改
 pub const MAX_LATENCY_THRESHOLD_MS: i64 = 1000;
@@ -1,45 +1,88 @@
 use crate::common::AnyResult;
-use crate::streaming::common::MetricsEventType;
+use crate::streaming::common::{LatencyMonitor, MetricsEventType};
 use crate::streaming::event_parser::common::filter::EventTypeFilter;
+use crate::streaming::event_parser::common::high_performance_clock::elapsed_micros_since;
 use crate::streaming::event_parser::common::SwapCuParseConfig;
-use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
-use crate::streaming::event_parser::core::common_event_parser::CommonEventParser;
+use crate::streaming::event_parser::core::account_event_parser::{
+    AccountEventParser, AccountParseOptions,
+};
+use crate::streaming::event_parser::core::common_event_parser::{
+    CommonEventParser, TransactionSummaryEvent,
+};
 use crate::streaming::event_parser::core::event_parser::EventParser;
+use crate::streaming::event_parser::core::ParseOptions;
 use crate::streaming::event_parser::{core::traits::DexEvent, Protocol, TxDexEvents};
-use crate::streaming::grpc::{EventPretty, MetricsManager};
+use crate::streaming::grpc::{AccountPretty, EventPretty, MetricsManager};
 use crate::streaming::shred::TransactionWithSlot;
+use parking_lot::Mutex;
 use solana_sdk::pubkey::Pubkey;
 use std::sync::Arc;
 
 /// 创建带 metrics 统计的 callback 包装器
 ///
-/// 用于 Transaction 事件处理，在调用原始 callback 的同时更新 metrics
+/// 用于 Transaction 事件处理，在调用原始 callback 的同时更新 metrics；
+/// 传入客户端的 `LatencyMonitor` 时检查延迟（不依赖 `metrics` feature）。
+/// 未启用 `metrics` feature 且不检查延迟时直接返回原始 callback
 #[inline]
 fn create_metrics_callback(
     callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+    latency_monitor: Option<Arc<LatencyMonitor>>,
 ) -> Arc<dyn Fn(DexEvent) + Send + Sync> {
+    if !cfg!(feature = "metrics") && latency_monitor.is_none() {
+        return callback;
+    }
     Arc::new(move |event: DexEvent| {
         let metadata = event.metadata();
+        #[cfg(feature = "metrics")]
         let processing_time_us = metadata.handle_us as f64;
         let recv_us = metadata.recv_us;
         let block_time_ms = metadata.block_time_ms;
 
         callback(event);
 
-        update_metrics_with_latency(
-            MetricsEventType::Transaction,
-            1,
-            processing_time_us,
-            recv_us,
-            block_time_ms,
-        );
+        if let Some(monitor) = latency_monitor.as_deref() {
+            monitor.check(recv_us, block_time_ms);
+        }
+        #[cfg(feature = "metrics")]
+        update_metrics(MetricsEventType::Transaction, 1, processing_time_us);
+    })
+}
+
+/// 创建交易汇总 callback 包装器
+///
+/// 转发事件的同时将其累加到 TransactionSummaryEvent
+#[inline]
+fn create_summary_callback(
+    callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+    summary: Arc<Mutex<TransactionSummaryEvent>>,
+) -> Arc<dyn Fn(DexEvent) + Send + Sync> {
+    Arc::new(move |event: DexEvent| {
+        summary.lock().record(&event);
+        callback(event);
+    })
+}
+
+/// 创建填充 gRPC 服务端推送时间的 callback 包装器
+#[inline]
+fn create_grpc_created_at_callback(
+    callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+    grpc_created_at_us: Option<i64>,
+) -> Arc<dyn Fn(DexEvent) + Send + Sync> {
+    Arc::new(move |mut event: DexEvent| {
+        event.metadata_mut().grpc_created_at_us = grpc_created_at_us;
+        callback(event);
     })
 }
 
+/// 交易级事件的 metrics / 延迟检查包装器，规则同 `create_metrics_callback`
 #[inline]
 fn create_tx_metrics_callback(
     callback: Arc<dyn Fn(TxDexEvents) + Send + Sync>,
+    latency_monitor: Option<Arc<LatencyMonitor>>,
 ) -> Arc<dyn Fn(TxDexEvents) + Send + Sync> {
+    if !cfg!(feature = "metrics") && latency_monitor.is_none() {
+        return callback;
+    }
     Arc::new(move |tx_events: TxDexEvents| {
         let metrics = tx_events.events.first().map(|event| {
             let metadata = event.metadata();
@@ -54,17 +97,72 @@ fn create_tx_metrics_callback(
         callback(tx_events);
 
         if let Some((count, processing_time_us, recv_us, block_time_ms)) = metrics {
-            update_metrics_with_latency(
-                MetricsEventType::Transaction,
-                count,
-                processing_time_us,
-                recv_us,
-                block_time_ms,
-            );
+            if let Some(monitor) = latency_monitor.as_deref() {
+                monitor.check(recv_us, block_time_ms);
+            }
+            #[cfg(feature = "metrics")]
+            update_metrics(MetricsEventType::Transaction, count, processing_time_us);
+            #[cfg(not(feature = "metrics"))]
+            let _ = (count, processing_time_us);
         }
     })
 }
 
+/// `process_grpc_*` 的按订阅选项
+///
+/// 新增选项只增加字段，不改变各函数的签名
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessOptions<'a> {
+    /// 账户更新在解码之前的过滤
+    pub account: AccountParseOptions<'a>,
+    /// 交易解析选项
+    pub parse: &'a ParseOptions,
+    /// 不解析投票交易
+    pub skip_votes: bool,
+    /// 在每笔产出事件的交易之后发出 TransactionSummaryEvent
+    pub emit_transaction_summary: bool,
+    /// 客户端的高延迟警告，None 时不检查延迟
+    pub latency_monitor: Option<&'a Arc<LatencyMonitor>>,
+}
+
+impl Default for ProcessOptions<'_> {
+    fn default() -> Self {
+        Self {
+            account: AccountParseOptions::default(),
+            parse: ParseOptions::disabled(),
+            skip_votes: false,
+            emit_transaction_summary: false,
+            latency_monitor: None,
+        }
+    }
+}
+
+/// Process one GRPC account update through the account-event dispatch path
+pub fn process_grpc_account<F>(
+    account_pretty: AccountPretty,
+    protocols: &[Protocol],
+    event_type_filter: Option<&EventTypeFilter>,
+    options: AccountParseOptions<'_>,
+    callback: &F,
+) where
+    F: Fn(DexEvent) + ?Sized,
+{
+    MetricsManager::global().add_account_process_count();
+
+    let account_event = AccountEventParser::parse_account_event_with_options(
+        protocols,
+        account_pretty,
+        event_type_filter,
+        options,
+    );
+
+    if let Some(event) = account_event {
+        let processing_time_us = event.metadata().handle_us as f64;
+        callback(event);
+        update_metrics(MetricsEventType::Account, 1, processing_time_us);
+    }
+}
+
 /// Process GRPC transaction events
 pub async fn process_grpc_transaction(
     event_pretty: EventPretty,
@@ -73,24 +171,45 @@ pub async fn process_grpc_transaction(
     swap_cu_parse_config: Option<&SwapCuParseConfig>,
     callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
     bot_wallet: Option<Pubkey>,
+) -> AnyResult<()> {
+    process_grpc_transaction_with_options(
+        event_pretty,
+        protocols,
+        event_type_filter,
+        swap_cu_parse_config,
+        callback,
+        bot_wallet,
+        ProcessOptions::default(),
+    )
+    .await
+}
+
+/// Process GRPC transaction events with per-subscription options
+pub async fn process_grpc_transaction_with_options(
+    event_pretty: EventPretty,
+    protocols: &[Protocol],
+    event_type_filter: Option<&EventTypeFilter>,
+    swap_cu_parse_config: Option<&SwapCuParseConfig>,
+    callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+    bot_wallet: Option<Pubkey>,
+    options: ProcessOptions<'_>,
 ) -> AnyResult<()> {
     match event_pretty {
         EventPretty::Account(account_pretty) => {
-            MetricsManager::global().add_account_process_count();
-
-            let account_event = AccountEventParser::parse_account_event(
-                protocols,
+            process_grpc_account(
                 account_pretty,
+                protocols,
                 event_type_filter,
+                options.account,
+                &*callback,
             );
-
-            if let Some(event) = account_event {
-                let processing_time_us = event.metadata().handle_us as f64;
-                callback(event);
-                update_metrics(MetricsEventType::Account, 1, processing_time_us);
-            }
         }
         EventPretty::Transaction(transaction_pretty) => {
+            // 投票交易不会包含 DEX 指令
+            if options.skip_votes && transaction_pretty.is_vote {
+                return Ok(());
+            }
+
             MetricsManager::global().add_tx_process_count();
 
             let slot = transaction_pretty.slot;
@@ -98,11 +217,42 @@ pub async fn process_grpc_transaction(
             let block_time = transaction_pretty.block_time;
             let recv_us = transaction_pretty.recv_us;
             let transaction_index = transaction_pretty.transaction_index;
+            let grpc_created_at_us = transaction_pretty.grpc_created_at_us;
             let grpc_tx = transaction_pretty.grpc_tx;
 
-            let adapter_callback = create_metrics_callback(callback.clone());
+            let mut adapter_callback =
+                create_metrics_callback(callback.clone(), options.latency_monitor.cloned());
+
+            let summary = options.emit_transaction_summary.then(|| {
+                let (fee, compute_units) = grpc_tx
+                    .meta
+                    .as_ref()
+                    .map(|meta| (meta.fee, meta.compute_units_consumed))
+                    .unwrap_or_default();
+                let block_time_ms = block_time
+                    .as_ref()
+                    .map(|ts| ts.seconds * 1000 + ts.nanos as i64 / 1_000_000)
+                    .unwrap_or(0);
+                Arc::new(Mutex::new(CommonEventParser::new_transaction_summary_event(
+                    signature,
+                    slot,
+                    block_time_ms,
+                    recv_us,
+                    transaction_index,
+                    fee,
+                    compute_units,
+                    EventParser::grpc_compute_unit_price(&grpc_tx),
+                )))
+            });
+            if let Some(summary) = &summary {
+                adapter_callback = create_summary_callback(adapter_callback, summary.clone());
+            }
+            if grpc_created_at_us.is_some() {
+                adapter_callback =
+                    create_grpc_created_at_callback(adapter_callback, grpc_created_at_us);
+            }
 
-            EventParser::parse_grpc_transaction(
+            EventParser::parse_grpc_transaction_with_options(
                 protocols,
                 event_type_filter,
                 grpc_tx,
@@ -114,8 +264,18 @@ pub async fn process_grpc_transaction(
                 transaction_index,
                 swap_cu_parse_config,
                 adapter_callback,
+                options.parse,
             )
             .await?;
+
+            if let Some(summary) = summary {
+                let mut summary = std::mem::take(&mut *summary.lock());
+                if summary.num_events > 0 {
+                    summary.metadata.handle_us = elapsed_micros_since(recv_us);
+                    summary.metadata.grpc_created_at_us = grpc_created_at_us;
+                    callback(DexEvent::TransactionSummaryEvent(summary));
+                }
+            }
         }
         EventPretty::BlockMeta(block_meta_pretty) => {
             MetricsManager::global().add_block_meta_process_count();
@@ -125,12 +285,8 @@ pub async fn process_grpc_transaction(
                 .map(|ts| ts.seconds * 1000 + ts.nanos as i64 / 1_000_000)
                 .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
 
-            let block_meta_event = CommonEventParser::generate_block_meta_event(
-                block_meta_pretty.slot,
-                block_meta_pretty.block_hash,
-                block_time_ms,
-                block_meta_pretty.recv_us,
-            );
+            let block_meta_event =
+                CommonEventParser::generate_block_meta_event(block_meta_pretty, block_time_ms);
 
             let processing_time_us = block_meta_event.metadata().handle_us as f64;
             callback(block_meta_event);
@@ -149,10 +305,36 @@ pub async fn process_grpc_tx_events(
     swap_cu_parse_config: Option<&SwapCuParseConfig>,
     callback: Arc<dyn Fn(TxDexEvents) + Send + Sync>,
     bot_wallet: Option<Pubkey>,
+) -> AnyResult<()> {
+    process_grpc_tx_events_with_options(
+        event_pretty,
+        protocols,
+        event_type_filter,
+        swap_cu_parse_config,
+        callback,
+        bot_wallet,
+        ProcessOptions::default(),
+    )
+    .await
+}
+
+/// Process one GRPC transaction as a transaction-level DEX event batch with per-subscription
+/// options. Account options and `emit_transaction_summary` do not apply here.
+pub async fn process_grpc_tx_events_with_options(
+    event_pretty: EventPretty,
+    protocols: &[Protocol],
+    event_type_filter: Option<&EventTypeFilter>,
+    swap_cu_parse_config: Option<&SwapCuParseConfig>,
+    callback: Arc<dyn Fn(TxDexEvents) + Send + Sync>,
+    bot_wallet: Option<Pubkey>,
+    options: ProcessOptions<'_>,
 ) -> AnyResult<()> {
     let EventPretty::Transaction(transaction_pretty) = event_pretty else {
         return Ok(());
     };
+    if options.skip_votes && transaction_pretty.is_vote {
+        return Ok(());
+    }
 
     MetricsManager::global().add_tx_process_count();
 
@@ -161,9 +343,10 @@ pub async fn process_grpc_tx_events(
     let block_time = transaction_pretty.block_time;
     let recv_us = transaction_pretty.recv_us;
     let transaction_index = transaction_pretty.transaction_index;
+    let grpc_created_at_us = transaction_pretty.grpc_created_at_us;
     let grpc_tx = transaction_pretty.grpc_tx;
 
-    if let Some(tx_events) = EventParser::parse_grpc_transaction_to_events(
+    if let Some(mut tx_events) = EventParser::parse_grpc_transaction_to_events_with_options(
         protocols,
         event_type_filter,
         grpc_tx,
@@ -174,10 +357,16 @@ pub async fn process_grpc_tx_events(
         bot_wallet,
         transaction_index,
         swap_cu_parse_config,
+        options.parse,
     )
     .await?
     {
-        create_tx_metrics_callback(callback)(tx_events);
+        if grpc_created_at_us.is_some() {
+            for event in tx_events.events.iter_mut() {
+                event.metadata_mut().grpc_created_at_us = grpc_created_at_us;
+            }
+        }
+        create_tx_metrics_callback(callback, options.latency_monitor.cloned())(tx_events);
     }
 
     Ok(())
@@ -191,6 +380,28 @@ pub async fn process_shred_transaction(
     swap_cu_parse_config: Option<&SwapCuParseConfig>,
     callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
     bot_wallet: Option<Pubkey>,
+) -> AnyResult<()> {
+    process_shred_transaction_with_options(
+        transaction_with_slot,
+        protocols,
+        event_type_filter,
+        swap_cu_parse_config,
+        callback,
+        bot_wallet,
+        ParseOptions::disabled(),
+    )
+    .await
+}
+
+/// Process Shred transaction events with per-client parse options
+pub async fn process_shred_transaction_with_options(
+    transaction_with_slot: TransactionWithSlot,
+    protocols: &[Protocol],
+    event_type_filter: Option<&EventTypeFilter>,
+    swap_cu_parse_config: Option<&SwapCuParseConfig>,
+    callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+    bot_wallet: Option<Pubkey>,
+    options: &ParseOptions,
 ) -> AnyResult<()> {
     MetricsManager::global().add_tx_process_count();
 
@@ -204,10 +415,10 @@ pub async fn process_shred_transaction(
     let signature = tx.signatures[0];
     let recv_us = transaction_with_slot.recv_us;
 
-    let adapter_callback = create_metrics_callback(callback);
+    let adapter_callback = create_metrics_callback(callback, None);
     let accounts = tx.message.static_account_keys();
 
-    EventParser::parse_instruction_events_from_versioned_transaction(
+    EventParser::parse_instruction_events_from_versioned_transaction_with_options(
         protocols,
         event_type_filter,
         &tx,
@@ -221,6 +432,7 @@ pub async fn process_shred_transaction(
         None,
         swap_cu_parse_config,
         adapter_callback,
+        options,
     )
     .await?;
 
@@ -237,6 +449,34 @@ pub async fn process_shred_tx_events(
     bot_wallet: Option<Pubkey>,
     entry_index: Option<u64>,
     tx_index_in_entry: Option<u64>,
+) -> AnyResult<()> {
+    process_shred_tx_events_with_options(
+        transaction_with_slot,
+        protocols,
+        event_type_filter,
+        swap_cu_parse_config,
+        callback,
+        bot_wallet,
+        entry_index,
+        tx_index_in_entry,
+        ParseOptions::disabled(),
+    )
+    .await
+}
+
+/// Process one Shred transaction as a transaction-level DEX event batch with per-client parse
+/// options.
+#[allow(clippy::too_many_arguments)]
+pub async fn process_shred_tx_events_with_options(
+    transaction_with_slot: TransactionWithSlot,
+    protocols: &[Protocol],
+    event_type_filter: Option<&EventTypeFilter>,
+    swap_cu_parse_config: Option<&SwapCuParseConfig>,
+    callback: Arc<dyn Fn(TxDexEvents) + Send + Sync>,
+    bot_wallet: Option<Pubkey>,
+    entry_index: Option<u64>,
+    tx_index_in_entry: Option<u64>,
+    options: &ParseOptions,
 ) -> AnyResult<()> {
     MetricsManager::global().add_tx_process_count();
 
@@ -251,7 +491,7 @@ pub async fn process_shred_tx_events(
     let recv_us = transaction_with_slot.recv_us;
     let accounts = tx.message.static_account_keys();
 
-    if let Some(tx_events) = EventParser::parse_versioned_transaction_to_events(
+    if let Some(tx_events) = EventParser::parse_versioned_transaction_to_events_with_options(
         protocols,
         event_type_filter,
         &tx,
@@ -266,10 +506,11 @@ pub async fn process_shred_tx_events(
         entry_index,
         tx_index_in_entry,
         swap_cu_parse_config,
+        options,
     )
     .await?
     {
-        create_tx_metrics_callback(callback)(tx_events);
+        create_tx_metrics_callback(callback, None)(tx_events);
     }
 
     Ok(())
@@ -280,21 +521,3 @@ pub async fn process_shred_tx_events(
 fn update_metrics(ty: MetricsEventType, count: u64, time_us: f64) {
     MetricsManager::global().update_metrics(ty, count, time_us);
 }
-
-/// Update metrics with latency check
-#[inline]
-fn update_metrics_with_latency(
-    ty: MetricsEventType,
-    count: u64,
-    time_us: f64,
-    recv_us: i64,
-    block_time_ms: i64,
-) {
-    MetricsManager::global().update_metrics_with_latency(
-        ty,
-        count,
-        time_us,
-        recv_us,
-        block_time_ms,
-    );
-}
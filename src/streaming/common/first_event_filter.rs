@@ -0,0 +1,137 @@
+use crate::streaming::event_parser::common::{EventType, ACCOUNT_EVENT_TYPES, BLOCK_EVENT_TYPES};
+use crate::streaming::event_parser::{DexEvent, TxDexEvents};
+use parking_lot::Mutex;
+use smallvec::SmallVec;
+use solana_sdk::signature::Signature;
+use std::sync::Arc;
+
+#[derive(Default)]
+struct FirstEventState {
+    /// 当前交易的签名
+    signature: Signature,
+    /// 当前交易中已发出的事件类型
+    seen: SmallVec<[EventType; 4]>,
+}
+
+/// 每笔交易的每种 `EventType` 只保留第一个事件
+///
+/// 用于只关心交易入口的场景，例如只要聚合器路由中的第一笔 swap，忽略后续的路由跳转。
+/// “第一个”按解析器发出的顺序，即外层指令在前、其 CPI 事件在后。
+///
+/// - 同一笔交易的事件由流处理任务连续发出，签名变化即视为新交易
+/// - 账户事件和区块事件不参与过滤，同一笔交易更新的多个账户都会发出
+/// - `TransactionSummaryEvent` 仍统计交易中解析出的全部事件
+pub struct FirstEventPerTransaction {
+    state: Mutex<FirstEventState>,
+}
+
+impl Default for FirstEventPerTransaction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FirstEventPerTransaction {
+    pub fn new() -> Self {
+        Self { state: Mutex::new(FirstEventState::default()) }
+    }
+
+    /// 包装 callback：每笔交易中重复类型的事件被丢弃
+    pub fn wrap(
+        callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+    ) -> Arc<dyn Fn(DexEvent) + Send + Sync> {
+        let filter = FirstEventPerTransaction::new();
+        Arc::new(move |event: DexEvent| {
+            if filter.admit(&event) {
+                callback(event);
+            }
+        })
+    }
+
+    /// 包装按交易批量发出的 callback：只保留每种类型的第一个事件
+    pub fn wrap_tx_events(
+        callback: Arc<dyn Fn(TxDexEvents) + Send + Sync>,
+    ) -> Arc<dyn Fn(TxDexEvents) + Send + Sync> {
+        Arc::new(move |mut tx_events: TxDexEvents| {
+            let mut seen = SmallVec::<[EventType; 4]>::new();
+            tx_events.events.retain(|event| {
+                let event_type = event.metadata().event_type;
+                let first = !seen.contains(&event_type);
+                if first {
+                    seen.push(event_type);
+                }
+                first
+            });
+            callback(tx_events);
+        })
+    }
+
+    /// 事件是否为所在交易中该类型的第一个
+    pub fn admit(&self, event: &DexEvent) -> bool {
+        let metadata = event.metadata();
+        if ACCOUNT_EVENT_TYPES.contains(&metadata.event_type)
+            || BLOCK_EVENT_TYPES.contains(&metadata.event_type)
+        {
+            return true;
+        }
+        let mut state = self.state.lock();
+        if state.signature != metadata.signature {
+            state.signature = metadata.signature;
+            state.seen.clear();
+        }
+        if state.seen.contains(&metadata.event_type) {
+            return false;
+        }
+        state.seen.push(metadata.event_type);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::common::EventMetadata;
+    use crate::streaming::event_parser::core::account_event_parser::TokenAccountEvent;
+    use crate::streaming::event_parser::protocols::pumpfun::events::PumpFunTradeEvent;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn trade(signature: u8, event_type: EventType) -> DexEvent {
+        let metadata = EventMetadata {
+            event_type,
+            signature: Signature::from([signature; 64]),
+            ..Default::default()
+        };
+        DexEvent::PumpFunTradeEvent(PumpFunTradeEvent { metadata, ..Default::default() })
+    }
+
+    #[test]
+    fn test_admits_first_event_of_each_type_per_transaction() {
+        let filter = FirstEventPerTransaction::new();
+        assert!(filter.admit(&trade(1, EventType::PumpFunBuy)));
+        assert!(!filter.admit(&trade(1, EventType::PumpFunBuy)));
+        assert!(filter.admit(&trade(1, EventType::PumpFunSell)));
+        // 新交易重新计数
+        assert!(filter.admit(&trade(2, EventType::PumpFunBuy)));
+    }
+
+    #[test]
+    fn test_admits_every_account_update_of_a_transaction() {
+        let account = |pubkey: Pubkey| {
+            let metadata = EventMetadata {
+                event_type: EventType::TokenAccount,
+                signature: Signature::from([1; 64]),
+                ..Default::default()
+            };
+            DexEvent::TokenAccountEvent(TokenAccountEvent {
+                metadata,
+                pubkey,
+                ..Default::default()
+            })
+        };
+        let filter = FirstEventPerTransaction::new();
+        assert!(filter.admit(&trade(1, EventType::PumpFunBuy)));
+        assert!(filter.admit(&account(Pubkey::new_unique())));
+        assert!(filter.admit(&account(Pubkey::new_unique())));
+        assert!(!filter.admit(&trade(1, EventType::PumpFunBuy)));
+    }
+}
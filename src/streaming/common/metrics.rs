@@ -1,6 +1,93 @@
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
 
 use super::constants::*;
+use crate::streaming::event_parser::common::high_performance_clock::get_high_perf_clock;
+use crate::streaming::event_parser::common::ProtocolType;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+#[cfg(feature = "grpc")]
+use parking_lot::Mutex;
+use solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "grpc")]
+use tokio::task::{AbortHandle, JoinHandle};
+
+/// 校准后的延迟（毫秒）: time - (block_time + adjustment_ms)
+///
+/// Solana 的 block_time 只精确到秒，加校准值（默认 500ms）近似出块时刻。
+#[inline]
+pub fn calibrated_latency_ms(time_us: i64, block_time_ms: i64, adjustment_ms: i64) -> i64 {
+    time_us / 1000 - (block_time_ms + adjustment_ms)
+}
+
+/// 同一客户端两条高延迟警告之间的最小间隔（微秒）
+const LATENCY_WARNING_INTERVAL_US: i64 = 1_000_000;
+
+/// 按客户端的高延迟警告
+///
+/// 由 `StreamClientConfig::latency_monitor` 按客户端配置创建，同一进程中的多个客户端
+/// 互不影响。警告每秒最多输出一条，期间被抑制的次数随下一条警告输出。
+#[derive(Debug)]
+pub struct LatencyMonitor {
+    threshold_ms: i64,
+    adjustment_ms: i64,
+    last_warn_us: AtomicI64,
+    suppressed: AtomicU64,
+}
+
+impl LatencyMonitor {
+    pub fn new(threshold_ms: i64, adjustment_ms: i64) -> Self {
+        Self {
+            threshold_ms,
+            adjustment_ms,
+            last_warn_us: AtomicI64::new(i64::MIN),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// 检查校准后的延迟，超过阈值时（按限速）输出警告，返回是否超过阈值
+    ///
+    /// 没有 block_time 的事件（例如 ShredStream）不检查
+    #[inline]
+    pub fn check(&self, recv_us: i64, block_time_ms: i64) -> bool {
+        if block_time_ms <= 0 {
+            return false;
+        }
+        let latency_ms = calibrated_latency_ms(recv_us, block_time_ms, self.adjustment_ms);
+        if latency_ms <= self.threshold_ms {
+            return false;
+        }
+        if self.try_acquire_warning(get_high_perf_clock()) {
+            let suppressed = self.suppressed.swap(0, Ordering::Relaxed);
+            log::warn!(
+                "⚠️  High gRPC latency: {}ms (threshold: {}ms, raw: recv={}ms, block={}ms, suppressed since last warning: {})",
+                latency_ms,
+                self.threshold_ms,
+                recv_us / 1000,
+                block_time_ms,
+                suppressed
+            );
+        } else {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+        }
+        true
+    }
+
+    /// 距上一条警告超过间隔时占用本次输出
+    fn try_acquire_warning(&self, now_us: i64) -> bool {
+        let last = self.last_warn_us.load(Ordering::Relaxed);
+        if last != i64::MIN && now_us.saturating_sub(last) < LATENCY_WARNING_INTERVAL_US {
+            return false;
+        }
+        self.last_warn_us
+            .compare_exchange(last, now_us, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    /// 自上一条警告以来被抑制的警告数
+    pub fn suppressed_warnings(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+}
 
 /// Event type enumeration
 #[derive(Debug, Clone, Copy)]
@@ -31,13 +118,92 @@ impl EventType {
     pub const TX: EventType = EventType::Transaction;
 }
 
+/// 滑动窗口桶数：窗口秒数 + 当前尚未结束的一秒
+const RATE_BUCKETS: usize = DEFAULT_METRICS_WINDOW_SECONDS as usize + 1;
+
+/// 按秒计数的环形缓冲区，用于计算最近 `DEFAULT_METRICS_WINDOW_SECONDS` 秒的平均事件速率
+///
+/// 每个桶把秒数（高 32 位）和该秒的事件数（低 32 位）打包在一个 AtomicU64 中，
+/// 写入方在进入新的一秒时用 CAS 覆盖过期的桶，无需后台任务重置，也不会在窗口边界归零。
+#[derive(Debug)]
+struct SlidingRate {
+    buckets: [AtomicU64; RATE_BUCKETS],
+    /// 第一次记录的秒数 + 1，0 表示尚未记录
+    first_second: AtomicU64,
+}
+
+impl SlidingRate {
+    const fn new_const() -> Self {
+        Self {
+            buckets: [const { AtomicU64::new(0) }; RATE_BUCKETS],
+            first_second: AtomicU64::new(0),
+        }
+    }
+
+    #[inline]
+    fn now_second() -> u64 {
+        (get_high_perf_clock() / 1_000_000) as u64
+    }
+
+    #[inline]
+    fn record(&self, count: u64) {
+        self.record_at(Self::now_second(), count);
+    }
+
+    #[inline]
+    fn record_at(&self, second: u64, count: u64) {
+        if self.first_second.load(Ordering::Relaxed) == 0 {
+            let _ = self.first_second.compare_exchange(
+                0,
+                second + 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+        }
+        let tag = second & 0xFFFF_FFFF;
+        let count = count.min(u32::MAX as u64);
+        let bucket = &self.buckets[second as usize % RATE_BUCKETS];
+        let _ = bucket.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |packed| {
+            let total = if packed >> 32 == tag { (packed & 0xFFFF_FFFF) + count } else { count };
+            Some((tag << 32) | total.min(u32::MAX as u64))
+        });
+    }
+
+    /// 最近 `DEFAULT_METRICS_WINDOW_SECONDS` 个完整秒的平均每秒事件数，不含当前这一秒；
+    /// 开始记录不足一个窗口时按已经过的完整秒数平均
+    fn per_second(&self) -> f64 {
+        self.per_second_at(Self::now_second())
+    }
+
+    fn per_second_at(&self, now: u64) -> f64 {
+        let first = self.first_second.load(Ordering::Relaxed);
+        if first == 0 {
+            return 0.0;
+        }
+        let window = DEFAULT_METRICS_WINDOW_SECONDS.min(now.saturating_sub(first - 1));
+        if window == 0 {
+            return 0.0;
+        }
+        let total: u64 = (now - window..now)
+            .map(|second| {
+                let packed = self.buckets[second as usize % RATE_BUCKETS].load(Ordering::Relaxed);
+                if packed >> 32 == second & 0xFFFF_FFFF {
+                    packed & 0xFFFF_FFFF
+                } else {
+                    0
+                }
+            })
+            .sum();
+        total as f64 / window as f64
+    }
+}
+
 /// High-performance atomic event metrics
 #[derive(Debug)]
 struct AtomicEventMetrics {
     process_count: AtomicU64,
     events_processed: AtomicU64,
-    events_in_window: AtomicU64,
-    window_start_nanos: AtomicU64,
+    rate: SlidingRate,
     // Processing time statistics per event type
     processing_stats: AtomicProcessingTimeStats,
 }
@@ -47,8 +213,7 @@ impl AtomicEventMetrics {
         Self {
             process_count: AtomicU64::new(0),
             events_processed: AtomicU64::new(0),
-            events_in_window: AtomicU64::new(0),
-            window_start_nanos: AtomicU64::new(0),
+            rate: SlidingRate::new_const(),
             processing_stats: AtomicProcessingTimeStats::new_const(),
         }
     }
@@ -63,29 +228,13 @@ impl AtomicEventMetrics {
     #[inline]
     fn add_events_processed(&self, count: u64) {
         self.events_processed.fetch_add(count, Ordering::Relaxed);
-        self.events_in_window.fetch_add(count, Ordering::Relaxed);
+        self.rate.record(count);
     }
 
     /// Get current count (non-blocking)
     #[inline]
-    fn get_counts(&self) -> (u64, u64, u64) {
-        (
-            self.process_count.load(Ordering::Relaxed),
-            self.events_processed.load(Ordering::Relaxed),
-            self.events_in_window.load(Ordering::Relaxed),
-        )
-    }
-
-    /// Reset window count
-    #[inline]
-    fn reset_window(&self, new_start_nanos: u64) {
-        self.events_in_window.store(0, Ordering::Relaxed);
-        self.window_start_nanos.store(new_start_nanos, Ordering::Relaxed);
-    }
-
-    #[inline]
-    fn get_window_start(&self) -> u64 {
-        self.window_start_nanos.load(Ordering::Relaxed)
+    fn get_counts(&self) -> (u64, u64) {
+        (self.process_count.load(Ordering::Relaxed), self.events_processed.load(Ordering::Relaxed))
     }
 
     /// Get processing time statistics for this event type
@@ -147,6 +296,69 @@ impl AtomicProcessingTimeStats {
     }
 }
 
+/// 参与解码耗时统计的协议，顺序与 `protocol_index` 一致
+const DECODE_PROTOCOLS: [ProtocolType; 11] = [
+    ProtocolType::PancakeSwap,
+    ProtocolType::PumpSwap,
+    ProtocolType::PumpFun,
+    ProtocolType::Bonk,
+    ProtocolType::RaydiumCpmm,
+    ProtocolType::RaydiumClmm,
+    ProtocolType::RaydiumAmmV4,
+    ProtocolType::MeteoraDammV2,
+    ProtocolType::MeteoraDlmm,
+    ProtocolType::Whirlpool,
+    ProtocolType::Common,
+];
+
+#[inline]
+const fn protocol_index(protocol: &ProtocolType) -> usize {
+    match protocol {
+        ProtocolType::PancakeSwap => 0,
+        ProtocolType::PumpSwap => 1,
+        ProtocolType::PumpFun => 2,
+        ProtocolType::Bonk => 3,
+        ProtocolType::RaydiumCpmm => 4,
+        ProtocolType::RaydiumClmm => 5,
+        ProtocolType::RaydiumAmmV4 => 6,
+        ProtocolType::MeteoraDammV2 => 7,
+        ProtocolType::MeteoraDlmm => 8,
+        ProtocolType::Whirlpool => 9,
+        ProtocolType::Common => 10,
+    }
+}
+
+/// 单个协议的累计指令解码次数和耗时
+#[derive(Debug)]
+struct AtomicDecodeStats {
+    calls: AtomicU64,
+    total_ns: AtomicU64,
+}
+
+impl AtomicDecodeStats {
+    const fn new_const() -> Self {
+        Self { calls: AtomicU64::new(0), total_ns: AtomicU64::new(0) }
+    }
+
+    #[inline]
+    fn record(&self, elapsed_ns: u64) {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.total_ns.fetch_add(elapsed_ns, Ordering::Relaxed);
+    }
+}
+
+/// 协议指令解码耗时统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtocolDecodeStats {
+    pub protocol: ProtocolType,
+    /// 解码调用次数（含未解析出事件的调用）
+    pub calls: u64,
+    /// 累计解码耗时（微秒）
+    pub total_us: f64,
+    /// 平均每次解码耗时（微秒）
+    pub avg_us: f64,
+}
+
 /// Processing time statistics result
 #[derive(Debug, Clone)]
 pub struct ProcessingTimeStats {
@@ -171,6 +383,9 @@ pub struct PerformanceMetrics {
     pub block_meta_metrics: EventMetricsSnapshot,
     pub processing_stats: ProcessingTimeStats,
     pub dropped_events_count: u64,
+    pub short_inner_instructions_count: u64,
+    pub slow_callbacks_count: u64,
+    pub coalesced_account_updates_count: u64,
 }
 
 impl PerformanceMetrics {
@@ -190,6 +405,9 @@ impl PerformanceMetrics {
             block_meta_metrics: default_metrics,
             processing_stats: default_stats,
             dropped_events_count: 0,
+            short_inner_instructions_count: 0,
+            slow_callbacks_count: 0,
+            coalesced_account_updates_count: 0,
         }
     }
 }
@@ -202,6 +420,14 @@ pub struct HighPerformanceMetrics {
     processing_stats: AtomicProcessingTimeStats,
     // 丢弃事件指标
     dropped_events_count: AtomicU64,
+    // 因长度不足 inner discriminator 而跳过的 inner instruction 数
+    short_inner_instructions_count: AtomicU64,
+    // 执行耗时超过 CallbackWatchdogConfig::slow_threshold 的 callback 数
+    slow_callbacks_count: AtomicU64,
+    // 被同一 slot 内更新的账户事件覆盖而未发出的账户事件数
+    coalesced_account_updates_count: AtomicU64,
+    // 按协议累计的指令解码耗时
+    protocol_decode: [AtomicDecodeStats; DECODE_PROTOCOLS.len()],
 }
 
 impl HighPerformanceMetrics {
@@ -216,6 +442,10 @@ impl HighPerformanceMetrics {
             ],
             processing_stats: AtomicProcessingTimeStats::new_const(),
             dropped_events_count: AtomicU64::new(0),
+            short_inner_instructions_count: AtomicU64::new(0),
+            slow_callbacks_count: AtomicU64::new(0),
+            coalesced_account_updates_count: AtomicU64::new(0),
+            protocol_decode: [const { AtomicDecodeStats::new_const() }; DECODE_PROTOCOLS.len()],
         }
     }
 
@@ -248,7 +478,7 @@ impl HighPerformanceMetrics {
     #[inline]
     pub fn get_event_metrics(&self, event_type: EventType) -> EventMetricsSnapshot {
         let index = event_type.as_index();
-        let (process_count, events_processed, _) = self.event_metrics[index].get_counts();
+        let (process_count, events_processed) = self.event_metrics[index].get_counts();
         let processing_stats = self.event_metrics[index].get_processing_stats();
 
         EventMetricsSnapshot { process_count, events_processed, processing_stats }
@@ -260,33 +490,71 @@ impl HighPerformanceMetrics {
         self.processing_stats.get_stats()
     }
 
+    /// 最近 `DEFAULT_METRICS_WINDOW_SECONDS` 秒的滑动平均每秒事件数，尚无事件时为 0
+    pub fn get_events_per_second(&self, event_type: EventType) -> f64 {
+        self.event_metrics[event_type.as_index()].rate.per_second()
+    }
+
     /// 获取丢弃事件计数
     #[inline]
     pub fn get_dropped_events_count(&self) -> u64 {
         self.dropped_events_count.load(Ordering::Relaxed)
     }
 
-    /// 更新窗口指标（后台任务调用）
-    fn update_window_metrics(&self, event_type: EventType, window_duration_nanos: u64) {
-        let now_nanos =
-            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos()
-                as u64;
+    /// 获取因过短被跳过的 inner instruction 计数
+    #[inline]
+    pub fn get_short_inner_instructions_count(&self) -> u64 {
+        self.short_inner_instructions_count.load(Ordering::Relaxed)
+    }
 
-        let index = event_type.as_index();
-        let event_metric = &self.event_metrics[index];
+    /// 获取慢 callback 计数
+    #[inline]
+    pub fn get_slow_callbacks_count(&self) -> u64 {
+        self.slow_callbacks_count.load(Ordering::Relaxed)
+    }
 
-        let window_start = event_metric.get_window_start();
-        if now_nanos.saturating_sub(window_start) >= window_duration_nanos {
-            event_metric.reset_window(now_nanos);
-        }
+    /// 获取被合并的账户更新计数
+    #[inline]
+    pub fn get_coalesced_account_updates_count(&self) -> u64 {
+        self.coalesced_account_updates_count.load(Ordering::Relaxed)
+    }
+
+    /// 记录一次协议指令解码耗时
+    #[inline]
+    fn record_decode(&self, protocol: &ProtocolType, elapsed_ns: u64) {
+        self.protocol_decode[protocol_index(protocol)].record(elapsed_ns);
+    }
+
+    /// 有解码记录的协议的耗时统计，按累计耗时降序
+    pub fn get_protocol_decode_stats(&self) -> Vec<ProtocolDecodeStats> {
+        let mut stats: Vec<_> = DECODE_PROTOCOLS
+            .iter()
+            .zip(&self.protocol_decode)
+            .filter_map(|(protocol, decode)| {
+                let calls = decode.calls.load(Ordering::Relaxed);
+                let total_us = decode.total_ns.load(Ordering::Relaxed) as f64 / 1_000.0;
+                (calls > 0).then(|| ProtocolDecodeStats {
+                    protocol: protocol.clone(),
+                    calls,
+                    total_us,
+                    avg_us: total_us / calls as f64,
+                })
+            })
+            .collect();
+        stats.sort_by(|a, b| b.total_us.total_cmp(&a.total_us));
+        stats
     }
 }
 
 /// Global singleton instance - zero-cost static allocation
 static GLOBAL_METRICS: HighPerformanceMetrics = HighPerformanceMetrics::new_const();
 
-/// Background task initialization flag
-static BACKGROUND_TASK_STARTED: AtomicBool = AtomicBool::new(false);
+/// 按程序统计的未知指令数，程序 ID 不固定，无法放入 const 初始化的 GLOBAL_METRICS
+static UNKNOWN_INSTRUCTION_COUNTS: Lazy<DashMap<Pubkey, u64>> = Lazy::new(DashMap::new);
+
+/// 已启动的自动监控任务，供 `MetricsManager::shutdown` 终止并等待
+#[cfg(feature = "grpc")]
+static MONITORING_TASKS: Lazy<Mutex<Vec<JoinHandle<()>>>> = Lazy::new(|| Mutex::new(Vec::new()));
 
 /// Metrics enabled flag
 static METRICS_ENABLED: AtomicBool = AtomicBool::new(true);
@@ -302,36 +570,21 @@ impl MetricsManager {
         Self
     }
 
-    /// Initialize and start background task (call once at startup)
+    /// Initialize metrics (call once at startup)
+    ///
+    /// 未启用 `metrics` feature 时指标始终关闭
     pub fn init(enable_metrics: bool) {
-        METRICS_ENABLED.store(enable_metrics, Ordering::Relaxed);
-
-        // Start background task only once
-        if enable_metrics
-            && BACKGROUND_TASK_STARTED
-                .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
-                .is_ok()
-        {
-            tokio::spawn(async {
-                let mut interval = tokio::time::interval(std::time::Duration::from_millis(500));
-                loop {
-                    interval.tick().await;
-                    let window_duration_nanos = DEFAULT_METRICS_WINDOW_SECONDS * 1_000_000_000;
-
-                    // Update window metrics for all event types
-                    GLOBAL_METRICS
-                        .update_window_metrics(EventType::Transaction, window_duration_nanos);
-                    GLOBAL_METRICS.update_window_metrics(EventType::Account, window_duration_nanos);
-                    GLOBAL_METRICS
-                        .update_window_metrics(EventType::BlockMeta, window_duration_nanos);
-                }
-            });
+        if enable_metrics && !cfg!(feature = "metrics") {
+            log::warn!("enable_metrics is ignored: built without the `metrics` feature");
         }
+        let enable_metrics = enable_metrics && cfg!(feature = "metrics");
+        METRICS_ENABLED.store(enable_metrics, Ordering::Relaxed);
     }
 
+    /// 未启用 `metrics` feature 时为常量 false，所有记录调用在编译期被消除
     #[inline]
     fn is_enabled(&self) -> bool {
-        METRICS_ENABLED.load(Ordering::Relaxed)
+        cfg!(feature = "metrics") && METRICS_ENABLED.load(Ordering::Relaxed)
     }
 
     /// 记录处理次数（非阻塞）
@@ -369,23 +622,15 @@ impl MetricsManager {
         }
     }
 
-    /// 检查并警告高延迟 (校准后的 gRPC latency)
+    /// 检查高延迟 (校准后的 gRPC latency)，返回是否超过默认阈值
     /// latency = recv_time - (block_time + 500ms)
+    ///
+    /// 使用默认阈值和校准值且不输出警告；按客户端配置的限速警告见 `LatencyMonitor`
     #[inline]
-    pub fn check_and_warn_high_latency(&self, recv_us: i64, block_time_ms: i64) {
-        let recv_ms = recv_us / 1000;
-        // 校准延迟: recv_time - (block_time + 500ms)
-        let adjusted_latency_ms = recv_ms - (block_time_ms + SOLANA_BLOCK_TIME_ADJUSTMENT_MS);
-
-        if adjusted_latency_ms > MAX_LATENCY_THRESHOLD_MS {
-            // log::warn!(
-            //     "⚠️  High gRPC latency: {}ms (threshold: {}ms, raw: recv={}ms, block={}ms)",
-            //     adjusted_latency_ms,
-            //     MAX_LATENCY_THRESHOLD_MS,
-            //     recv_ms,
-            //     block_time_ms
-            // );
-        }
+    pub fn check_and_warn_high_latency(&self, recv_us: i64, block_time_ms: i64) -> bool {
+        block_time_ms > 0
+            && calibrated_latency_ms(recv_us, block_time_ms, SOLANA_BLOCK_TIME_ADJUSTMENT_MS)
+                > MAX_LATENCY_THRESHOLD_MS
     }
 
     /// 获取运行时长
@@ -408,6 +653,58 @@ impl MetricsManager {
         GLOBAL_METRICS.get_dropped_events_count()
     }
 
+    /// 获取因长度不足 inner discriminator 而跳过的 inner instruction 计数（只统计所属协议程序的指令）
+    pub fn get_short_inner_instructions_count(&self) -> u64 {
+        GLOBAL_METRICS.get_short_inner_instructions_count()
+    }
+
+    /// 获取执行耗时超过阈值的 callback 计数
+    pub fn get_slow_callbacks_count(&self) -> u64 {
+        GLOBAL_METRICS.get_slow_callbacks_count()
+    }
+
+    /// 获取被同一 slot 内更新覆盖而未发出的账户事件计数
+    pub fn get_coalesced_account_updates_count(&self) -> u64 {
+        GLOBAL_METRICS.get_coalesced_account_updates_count()
+    }
+
+    /// 计时执行一次协议指令解码，并累计到该协议的解码耗时（需启用 metrics）
+    ///
+    /// 未启用时直接执行 `decode`，不读取时钟
+    #[inline]
+    pub fn time_decode<T>(&self, protocol: &ProtocolType, decode: impl FnOnce() -> T) -> T {
+        if !self.is_enabled() {
+            return decode();
+        }
+        let start = std::time::Instant::now();
+        let result = decode();
+        GLOBAL_METRICS.record_decode(protocol, start.elapsed().as_nanos() as u64);
+        result
+    }
+
+    /// 按协议的累计指令解码耗时，按累计耗时降序，用于定位解码开销大的协议
+    pub fn protocol_decode_stats(&self) -> Vec<ProtocolDecodeStats> {
+        GLOBAL_METRICS.get_protocol_decode_stats()
+    }
+
+    /// 按程序的未知指令数（需开启 `ParseOptions::emit_unknown`），按数量降序
+    pub fn unknown_instruction_counts(&self) -> Vec<(Pubkey, u64)> {
+        let mut counts: Vec<_> =
+            UNKNOWN_INSTRUCTION_COUNTS.iter().map(|entry| (*entry.key(), *entry.value())).collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1));
+        counts
+    }
+
+    /// 最近 `DEFAULT_METRICS_WINDOW_SECONDS` 秒的滑动平均每秒事件数（需启用 metrics）
+    pub fn events_per_second(&self, event_type: EventType) -> f64 {
+        GLOBAL_METRICS.get_events_per_second(event_type)
+    }
+
+    /// 兼容性方法，同 [`MetricsManager::events_per_second`]
+    pub fn get_events_per_second(&self, event_type: EventType) -> f64 {
+        self.events_per_second(event_type)
+    }
+
     /// 打印性能指标（非阻塞）
     pub fn print_metrics(&self) {
         println!("\n📊 Performance Metrics");
@@ -418,6 +715,21 @@ impl MetricsManager {
         if dropped_count > 0 {
             println!("\n⚠️  Dropped Events: {}", dropped_count);
         }
+        let short_inner_count = self.get_short_inner_instructions_count();
+        if short_inner_count > 0 {
+            println!("   Short Inner Instructions Skipped: {}", short_inner_count);
+        }
+        let slow_callbacks = self.get_slow_callbacks_count();
+        if slow_callbacks > 0 {
+            println!("   Slow Callbacks: {}", slow_callbacks);
+        }
+        let coalesced = self.get_coalesced_account_updates_count();
+        if coalesced > 0 {
+            println!("   Coalesced Account Updates: {}", coalesced);
+        }
+        for (program_id, count) in self.unknown_instruction_counts() {
+            println!("   Unknown Instructions of {}: {}", program_id, count);
+        }
 
         // 打印事件指标表格（包含处理时间统计）
         println!("┌─────────────┬──────────────┬──────────────────┬─────────────┬─────────────┐");
@@ -437,11 +749,29 @@ impl MetricsManager {
         }
 
         println!("└─────────────┴──────────────┴──────────────────┴─────────────┴─────────────┘");
+
+        let decode_stats = self.protocol_decode_stats();
+        if !decode_stats.is_empty() {
+            println!("   Instruction Decode Time by Protocol:");
+            for stats in decode_stats {
+                println!(
+                    "     {:14} calls {:10}  total {:12.2}μs  avg {:8.2}μs",
+                    format!("{:?}", stats.protocol),
+                    stats.calls,
+                    stats.total_us,
+                    stats.avg_us
+                );
+            }
+        }
         println!();
     }
 
     /// 启动自动性能监控任务
-    pub async fn start_auto_monitoring(&self) -> Option<tokio::task::JoinHandle<()>> {
+    ///
+    /// 任务永不退出，其 `JoinHandle` 由 `MetricsManager` 持有，返回的 `AbortHandle` 只用于单独终止；
+    /// 丢弃后仍可通过 `shutdown` 终止
+    #[cfg(feature = "grpc")]
+    pub async fn start_auto_monitoring(&self) -> Option<AbortHandle> {
         if !self.is_enabled() {
             return None;
         }
@@ -455,7 +785,31 @@ impl MetricsManager {
                 MetricsManager::global().print_metrics();
             }
         });
-        Some(handle)
+        Some(Self::track_monitoring_task(handle))
+    }
+
+    #[cfg(feature = "grpc")]
+    fn track_monitoring_task(handle: JoinHandle<()>) -> AbortHandle {
+        let abort_handle = handle.abort_handle();
+        let mut tasks = MONITORING_TASKS.lock();
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(handle);
+        abort_handle
+    }
+
+    /// 终止所有自动监控任务并等待其结束
+    ///
+    /// 测试和短生命周期进程退出前调用，避免监控任务让运行时无法结束。
+    /// 之后仍可再次调用 `start_auto_monitoring`
+    #[cfg(feature = "grpc")]
+    pub async fn shutdown(&self) {
+        let tasks = std::mem::take(&mut *MONITORING_TASKS.lock());
+        for task in &tasks {
+            task.abort();
+        }
+        for task in tasks {
+            let _ = task.await;
+        }
     }
 
     /// 获取完整的性能指标（兼容性方法）
@@ -467,6 +821,9 @@ impl MetricsManager {
             block_meta_metrics: self.get_event_metrics(EventType::BlockMeta),
             processing_stats: self.get_processing_stats(),
             dropped_events_count: self.get_dropped_events_count(),
+            short_inner_instructions_count: self.get_short_inner_instructions_count(),
+            slow_callbacks_count: self.get_slow_callbacks_count(),
+            coalesced_account_updates_count: self.get_coalesced_account_updates_count(),
         }
     }
 
@@ -496,21 +853,25 @@ impl MetricsManager {
         events_processed: u64,
         processing_time_us: f64,
     ) {
+        if !cfg!(feature = "metrics") {
+            return;
+        }
         self.record_events(event_type, events_processed, processing_time_us);
         self.log_slow_processing(processing_time_us, events_processed as usize);
     }
 
-    /// 更新指标并检查延迟
+    /// 更新指标
+    ///
+    /// 延迟警告由各客户端的 `LatencyMonitor` 负责，这里不再检查延迟
     #[inline]
     pub fn update_metrics_with_latency(
         &self,
         event_type: MetricsEventType,
         events_processed: u64,
         processing_time_us: f64,
-        recv_us: i64,
-        block_time_ms: i64,
+        _recv_us: i64,
+        _block_time_ms: i64,
     ) {
-        self.check_and_warn_high_latency(recv_us, block_time_ms);
         self.update_metrics(event_type, events_processed, processing_time_us);
     }
 
@@ -551,4 +912,148 @@ impl MetricsManager {
             log::debug!("Dropped events count reached: {}", new_count);
         }
     }
+
+    /// 增加因长度不足 inner discriminator 而跳过的 inner instruction 计数
+    #[inline]
+    pub fn increment_short_inner_instructions(&self) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let new_count =
+            GLOBAL_METRICS.short_inner_instructions_count.fetch_add(1, Ordering::Relaxed) + 1;
+        if new_count % 1000 == 0 {
+            log::debug!("Short inner instructions skipped: {}", new_count);
+        }
+    }
+
+    /// 增加执行耗时超过阈值的 callback 计数
+    #[inline]
+    pub fn increment_slow_callbacks(&self) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        GLOBAL_METRICS.slow_callbacks_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 增加该程序的未知指令计数
+    #[inline]
+    pub fn increment_unknown_instructions(&self, program_id: &Pubkey) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        *UNKNOWN_INSTRUCTION_COUNTS.entry(*program_id).or_insert(0) += 1;
+    }
+
+    /// 增加被合并的账户更新计数
+    #[inline]
+    pub fn add_coalesced_account_updates(&self, count: u64) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        GLOBAL_METRICS.coalesced_account_updates_count.fetch_add(count, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol_decode_stats_sorted_by_total() {
+        for (index, protocol) in DECODE_PROTOCOLS.iter().enumerate() {
+            assert_eq!(protocol_index(protocol), index);
+        }
+
+        let metrics = HighPerformanceMetrics::new_const();
+        assert!(metrics.get_protocol_decode_stats().is_empty());
+        metrics.record_decode(&ProtocolType::PumpFun, 1_000);
+        metrics.record_decode(&ProtocolType::Whirlpool, 4_000);
+        metrics.record_decode(&ProtocolType::Whirlpool, 2_000);
+
+        let stats = metrics.get_protocol_decode_stats();
+        assert_eq!(
+            stats,
+            vec![
+                ProtocolDecodeStats {
+                    protocol: ProtocolType::Whirlpool,
+                    calls: 2,
+                    total_us: 6.0,
+                    avg_us: 3.0,
+                },
+                ProtocolDecodeStats {
+                    protocol: ProtocolType::PumpFun,
+                    calls: 1,
+                    total_us: 1.0,
+                    avg_us: 1.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sliding_rate_has_no_window_reset() {
+        let rate = SlidingRate::new_const();
+        assert_eq!(rate.per_second_at(100), 0.0);
+
+        rate.record_at(100, 10);
+        // 当前这一秒尚未结束，不计入
+        assert_eq!(rate.per_second_at(100), 0.0);
+        assert_eq!(rate.per_second_at(101), 10.0);
+
+        for second in 101..110 {
+            rate.record_at(second, 10);
+            rate.record_at(second, 10);
+        }
+        // 窗口内 5 个完整秒都是 20/s，旧桶已被覆盖
+        assert_eq!(rate.per_second_at(110), 20.0);
+        // 停止记录后逐秒平滑下降，而不是在窗口边界归零
+        assert_eq!(rate.per_second_at(112), 12.0);
+        assert_eq!(rate.per_second_at(114), 4.0);
+        assert_eq!(rate.per_second_at(115), 0.0);
+    }
+
+    #[test]
+    fn test_latency_monitor_per_client_and_rate_limited() {
+        let block_time_ms = 1_700_000_000_000;
+        // 收到时间为出块后 1.8s：默认校准 500ms 时延迟 1300ms，校准 1000ms 时延迟 800ms
+        let recv_us = (block_time_ms + 1_800) * 1000;
+        let default_client = LatencyMonitor::new(1000, SOLANA_BLOCK_TIME_ADJUSTMENT_MS);
+        let tuned_client = LatencyMonitor::new(1000, 1000);
+        assert!(default_client.check(recv_us, block_time_ms));
+        assert!(!tuned_client.check(recv_us, block_time_ms));
+        // 没有 block_time 的事件不检查
+        assert!(!default_client.check(recv_us, 0));
+
+        // 第一条警告已输出，间隔内的警告被抑制并计数
+        assert!(default_client.check(recv_us, block_time_ms));
+        assert!(default_client.check(recv_us, block_time_ms));
+        assert_eq!(default_client.suppressed_warnings(), 2);
+        assert_eq!(tuned_client.suppressed_warnings(), 0);
+
+        let monitor = LatencyMonitor::new(1000, 0);
+        assert!(monitor.try_acquire_warning(5_000_000));
+        assert!(!monitor.try_acquire_warning(5_999_999));
+        assert!(monitor.try_acquire_warning(6_000_000));
+    }
+
+    #[cfg(feature = "grpc")]
+    #[tokio::test]
+    async fn test_shutdown_aborts_monitoring_tasks() {
+        // 任务被终止时 future 被丢弃，持有的 Arc 随之释放
+        let alive = std::sync::Arc::new(());
+        let held = alive.clone();
+        let handle = tokio::spawn(async move {
+            let _held = held;
+            std::future::pending::<()>().await
+        });
+        let abort_handle = MetricsManager::track_monitoring_task(handle);
+        MetricsManager::global().shutdown().await;
+        assert!(abort_handle.is_finished());
+        assert_eq!(std::sync::Arc::strong_count(&alive), 1);
+        assert!(MONITORING_TASKS.lock().is_empty());
+    }
 }
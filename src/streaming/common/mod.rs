@@ -1,15 +1,36 @@
 // 公用模块 - 包含流处理相关的通用功能
+// config / event_processor / subscription / tps_throttle 依赖 gRPC 客户端与 tokio，只在启用 `grpc` feature 时编译
+pub mod account_coalescer;
+pub mod callback_watchdog;
+#[cfg(feature = "grpc")]
 pub mod config;
 pub mod constants;
+#[cfg(feature = "grpc")]
 pub mod event_processor;
+pub mod first_event_filter;
 pub mod metrics;
+pub mod reorder_buffer;
 pub mod simd_utils;
+pub mod slot_tracker;
+#[cfg(feature = "grpc")]
 pub mod subscription;
+#[cfg(feature = "grpc")]
+pub mod tps_throttle;
 
 // 重新导出主要类型
+pub use account_coalescer::*;
+pub use callback_watchdog::*;
+#[cfg(feature = "grpc")]
 pub use config::*;
 pub use constants::*;
+#[cfg(feature = "grpc")]
 pub use event_processor::*;
+pub use first_event_filter::*;
 pub use metrics::*;
+pub use reorder_buffer::*;
 pub use simd_utils::*;
+pub use slot_tracker::*;
+#[cfg(feature = "grpc")]
 pub use subscription::*;
+#[cfg(feature = "grpc")]
+pub use tps_throttle::*;
@@ -0,0 +1,325 @@
+use crate::streaming::event_parser::protocols::SlotRolledBackEvent;
+use crate::streaming::event_parser::DexEvent;
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+/// 已发出的 slot 继续跟踪回滚的窗口，超出后视为不会再被回滚
+pub const ROLLBACK_TRACKING_SLOTS: u64 = 32;
+
+/// 分叉候选 slot 等待 rooted / dead 更新的最大 slot 数，超出后按未回滚处理发出
+pub const FORK_RESOLUTION_SLOTS: u64 = 128;
+
+#[derive(Default)]
+struct ReorderState {
+    /// 已收到的最高 BlockMeta slot
+    head: u64,
+    /// 已收到的最高 rooted slot
+    last_root: u64,
+    /// 尚未发出的事件，按 slot 排序，同一 slot 内保持到达顺序
+    pending: BTreeMap<u64, Vec<DexEvent>>,
+    /// 已发出的 slot 及其事件数量
+    released: BTreeMap<u64, u64>,
+    /// 处于分叉中、等待 rooted / dead 更新的 slot，其事件保持缓冲
+    candidates: BTreeSet<u64>,
+    /// 已回滚的 slot，迟到的事件直接丢弃
+    rolled_back: BTreeSet<u64>,
+}
+
+/// 按 slot 排序的事件缓冲区，用于 Processed 级别下的分叉回滚
+///
+/// 事件先按 slot 缓存，直到收到 slot >= `slot + depth` 的 BlockMeta 后按 slot 顺序发出。
+/// 收到 BlockMeta 时，`parent_slot` 与 `slot` 之间已见过事件的 slot 说明出现了分叉：
+/// 两侧的 slot（以及之后建立在它们之上的 slot）都成为候选，事件继续缓冲，
+/// 直到 `slot_rooted` / `slot_dead` 判定结果。被 rooted 的 slot 照常发出；
+/// dead 或被更高的 rooted slot 越过的候选 slot 才被丢弃，并发出 `SlotRolledBackEvent`。
+///
+/// `depth` 的取舍：
+/// - 延迟：每个事件至少等到所在区块的 BlockMeta，再额外等待 `depth` 个 slot（约 400ms/slot）；
+///   分叉中的 slot 额外等到 rooted（约 32 个 slot）
+/// - 内存：缓存最近 `depth` 个 slot 以及分叉中 slot 的全部事件，另外按 slot 记录最近
+///   `ROLLBACK_TRACKING_SLOTS` 个已发出 slot 的事件数量
+/// - `depth` 越大，在发现分叉之前就已发出的事件越少；`depth = 0` 时事件在所在区块的
+///   BlockMeta 到达后立即发出，回滚只能通过 `released_events` 事后撤销
+///
+/// 依赖 BlockMeta 更新；未订阅 BlockMeta 时，缓冲区最多保留
+/// `depth + ROLLBACK_TRACKING_SLOTS` 个 slot，超出后按 slot 顺序强制发出最早的 slot。
+/// 未收到 slot 状态更新时，候选 slot 在 `FORK_RESOLUTION_SLOTS` 个 slot 后按未回滚处理发出。
+pub struct SlotReorderBuffer {
+    depth: u64,
+    state: Mutex<ReorderState>,
+}
+
+impl SlotReorderBuffer {
+    pub fn new(depth: u64) -> Self {
+        Self { depth, state: Mutex::new(ReorderState::default()) }
+    }
+
+    pub fn depth(&self) -> u64 {
+        self.depth
+    }
+
+    /// 缓冲区中尚未发出的事件数量
+    pub fn pending_events(&self) -> usize {
+        self.state.lock().pending.values().map(Vec::len).sum()
+    }
+
+    /// 包装 callback：事件经过缓冲区后按 slot 顺序转发，回滚时额外转发 `SlotRolledBackEvent`
+    ///
+    /// slot 状态更新需要由调用方通过同一个 `buffer` 的 `slot_rooted` / `slot_dead` 送入，
+    /// 返回的事件同样转发给 `callback`。
+    pub fn wrap(
+        buffer: Arc<SlotReorderBuffer>,
+        callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+    ) -> Arc<dyn Fn(DexEvent) + Send + Sync> {
+        Arc::new(move |event: DexEvent| {
+            for event in buffer.push(event) {
+                callback(event);
+            }
+        })
+    }
+
+    /// 放入一个事件，返回现在可以按顺序发出的事件
+    pub fn push(&self, event: DexEvent) -> Vec<DexEvent> {
+        let mut state = self.state.lock();
+        let mut ready = Vec::new();
+
+        if let DexEvent::BlockMetaEvent(block_meta) = &event {
+            let slot = block_meta.slot;
+            let parent_slot = block_meta.parent_slot;
+            if parent_slot > 0 && slot > parent_slot + 1 {
+                state.mark_fork(parent_slot, slot);
+            } else if state.candidates.contains(&parent_slot) {
+                // 建立在候选 slot 上的区块与其父区块一起等待判定
+                state.candidates.insert(slot);
+            }
+            if state.rolled_back.contains(&slot) {
+                return ready;
+            }
+            state.head = state.head.max(slot);
+            state.pending.entry(slot).or_default().push(event);
+        } else {
+            let slot = event.metadata().slot;
+            if state.rolled_back.contains(&slot) {
+                return ready;
+            }
+            if slot + self.depth <= state.head && !state.candidates.contains(&slot) {
+                // 所在 slot 已经发出，迟到的事件直接发出
+                *state.released.entry(slot).or_default() += 1;
+                ready.push(event);
+            } else {
+                state.pending.entry(slot).or_default().push(event);
+            }
+        }
+
+        state.release(self.depth, &mut ready);
+        ready
+    }
+
+    /// slot 已 rooted：该 slot 不再是候选，低于它且未 rooted 的候选 slot 属于被放弃的分叉
+    pub fn slot_rooted(&self, slot: u64) -> Vec<DexEvent> {
+        let mut state = self.state.lock();
+        let mut ready = Vec::new();
+
+        state.last_root = state.last_root.max(slot);
+        state.candidates.remove(&slot);
+        // rooted 更新按 slot 递增到达，被越过的候选 slot 不会再被 rooted
+        let abandoned: Vec<u64> = state.candidates.range(..slot).copied().collect();
+        for abandoned_slot in abandoned {
+            state.candidates.remove(&abandoned_slot);
+            state.roll_back(abandoned_slot, slot, &mut ready);
+        }
+
+        state.release(self.depth, &mut ready);
+        ready
+    }
+
+    /// slot 已 dead：丢弃该 slot 缓冲的事件，迟到的事件也直接丢弃
+    pub fn slot_dead(&self, slot: u64) -> Vec<DexEvent> {
+        let mut state = self.state.lock();
+        let mut ready = Vec::new();
+
+        state.candidates.remove(&slot);
+        state.roll_back(slot, 0, &mut ready);
+
+        state.release(self.depth, &mut ready);
+        ready
+    }
+}
+
+impl ReorderState {
+    /// BlockMeta 的父区块跳过了 (parent_slot, slot) 中见过事件的 slot：两侧都成为候选
+    fn mark_fork(&mut self, parent_slot: u64, slot: u64) {
+        let from = (parent_slot + 1).max(self.last_root + 1);
+        let mut forked: BTreeSet<u64> =
+            self.pending.range(from..slot).map(|(slot, _)| *slot).collect();
+        forked.extend(self.released.range(from..slot).map(|(slot, _)| *slot));
+        if forked.is_empty() && !self.candidates.contains(&parent_slot) {
+            return;
+        }
+        self.candidates.extend(forked);
+        self.candidates.insert(slot);
+    }
+
+    /// 回滚单个 slot，该 slot 见过事件时发出 `SlotRolledBackEvent`
+    fn roll_back(&mut self, slot: u64, replaced_by_slot: u64, ready: &mut Vec<DexEvent>) {
+        self.rolled_back.insert(slot);
+        let discarded = self.pending.remove(&slot);
+        let released = self.released.remove(&slot);
+        if discarded.is_none() && released.is_none() {
+            return;
+        }
+        let mut event = SlotRolledBackEvent::new(slot, replaced_by_slot);
+        event.discarded_events = discarded.map_or(0, |e| e.len() as u64);
+        event.released_events = released.unwrap_or(0);
+        ready.push(DexEvent::SlotRolledBackEvent(event));
+    }
+
+    /// 发出 head - depth 及之前的非候选 slot，并清理超出跟踪窗口的记录
+    fn release(&mut self, depth: u64, ready: &mut Vec<DexEvent>) {
+        let unresolved: Vec<u64> = self
+            .candidates
+            .iter()
+            .copied()
+            .take_while(|slot| slot + FORK_RESOLUTION_SLOTS <= self.head)
+            .collect();
+        for slot in unresolved {
+            log::warn!(
+                "Slot {slot} not rooted or dead after {FORK_RESOLUTION_SLOTS} slots, releasing its events"
+            );
+            self.candidates.remove(&slot);
+        }
+
+        let max_pending = (depth + ROLLBACK_TRACKING_SLOTS) as usize;
+        let mut releasable = Vec::new();
+        let mut remaining = self.pending.len();
+        for &slot in self.pending.keys() {
+            if self.candidates.contains(&slot) {
+                continue;
+            }
+            if slot + depth > self.head && remaining <= max_pending {
+                break;
+            }
+            releasable.push(slot);
+            remaining -= 1;
+        }
+        for slot in releasable {
+            let events = self.pending.remove(&slot).unwrap_or_default();
+            *self.released.entry(slot).or_default() += events.len() as u64;
+            ready.extend(events);
+        }
+
+        // 候选 slot 的已发出数量保留到判定结果，回滚时需要报告
+        let horizon = self.head.saturating_sub(depth + ROLLBACK_TRACKING_SLOTS);
+        let candidates = &self.candidates;
+        self.released.retain(|slot, _| *slot >= horizon || candidates.contains(slot));
+        self.rolled_back = self.rolled_back.split_off(&horizon);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::common::EventMetadata;
+    use crate::streaming::event_parser::core::common_event_parser::SetComputeUnitLimitEvent;
+    use crate::streaming::event_parser::protocols::BlockMetaEvent;
+
+    fn tx_event(slot: u64) -> DexEvent {
+        DexEvent::SetComputeUnitLimitEvent(SetComputeUnitLimitEvent {
+            metadata: EventMetadata { slot, ..Default::default() },
+            units: 0,
+        })
+    }
+
+    fn block_meta(slot: u64, parent_slot: u64) -> DexEvent {
+        let mut event = BlockMetaEvent::new(slot, String::new(), 0, 0);
+        event.parent_slot = parent_slot;
+        DexEvent::BlockMetaEvent(event)
+    }
+
+    fn slots(events: &[DexEvent]) -> Vec<(u64, bool)> {
+        events
+            .iter()
+            .map(|e| (e.metadata().slot, matches!(e, DexEvent::SlotRolledBackEvent(_))))
+            .collect()
+    }
+
+    #[test]
+    fn test_releases_in_slot_order_after_depth() {
+        let buffer = SlotReorderBuffer::new(1);
+        assert!(buffer.push(tx_event(11)).is_empty());
+        assert!(buffer.push(tx_event(10)).is_empty());
+        assert!(buffer.push(block_meta(10, 9)).is_empty());
+        assert_eq!(buffer.pending_events(), 3);
+
+        // head = 11 -> slot 10 发出，slot 11 继续缓冲
+        let ready = buffer.push(block_meta(11, 10));
+        assert_eq!(slots(&ready), vec![(10, false), (10, false)]);
+        assert_eq!(buffer.pending_events(), 2);
+
+        // 已发出 slot 的迟到事件直接发出
+        assert_eq!(slots(&buffer.push(tx_event(10))), vec![(10, false)]);
+    }
+
+    fn rolled_back(events: &[DexEvent]) -> Vec<(u64, u64, u64, u64)> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                DexEvent::SlotRolledBackEvent(e) => {
+                    Some((e.slot, e.replaced_by_slot, e.released_events, e.discarded_events))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// slot 20 已发出，21、22 仍在缓冲，随后区块 23 的父区块是 19
+    fn forked_buffer() -> SlotReorderBuffer {
+        let buffer = SlotReorderBuffer::new(1);
+        buffer.push(tx_event(20));
+        buffer.push(block_meta(20, 19));
+        buffer.push(tx_event(21));
+        // slot 20 发出
+        assert_eq!(buffer.push(block_meta(21, 20)).len(), 2);
+        buffer.push(tx_event(22));
+
+        // 分叉只标记候选，不立即回滚
+        assert!(buffer.push(block_meta(23, 19)).is_empty());
+        // 建立在候选 slot 上的事件继续缓冲
+        assert!(buffer.push(tx_event(23)).is_empty());
+        assert_eq!(buffer.pending_events(), 5);
+        buffer
+    }
+
+    #[test]
+    fn test_rollback_waits_for_root_and_reports_released() {
+        let buffer = forked_buffer();
+
+        // slot 23 rooted：slot 20、21、22 所在的分叉被放弃
+        let ready = buffer.slot_rooted(23);
+        assert_eq!(rolled_back(&ready), vec![(20, 23, 2, 0), (21, 23, 0, 2), (22, 23, 0, 1)]);
+
+        // 已回滚 slot 的迟到事件被丢弃
+        assert!(buffer.push(tx_event(21)).is_empty());
+
+        // slot 23 照常发出
+        let ready = buffer.push(block_meta(24, 23));
+        assert_eq!(slots(&ready), vec![(23, false), (23, false)]);
+    }
+
+    #[test]
+    fn test_dead_fork_releases_surviving_slots() {
+        let buffer = forked_buffer();
+
+        // slot 23 dead：只丢弃 slot 23，原分叉继续等待 rooted
+        let ready = buffer.slot_dead(23);
+        assert_eq!(rolled_back(&ready), vec![(23, 0, 0, 2)]);
+        assert_eq!(buffer.pending_events(), 3);
+
+        let mut ready = buffer.slot_rooted(20);
+        ready.extend(buffer.slot_rooted(21));
+        ready.extend(buffer.slot_rooted(22));
+        assert_eq!(slots(&ready), vec![(21, false), (21, false), (22, false)]);
+        assert_eq!(buffer.pending_events(), 0);
+    }
+}
@@ -0,0 +1,136 @@
+use parking_lot::{Mutex, RwLock};
+use std::sync::Arc;
+
+/// 漏收区块的回调，参数为缺失区间 `(from, to)`（闭区间）
+pub type SlotGapCallback = Arc<dyn Fn(u64, u64) + Send + Sync>;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct SlotRange {
+    start: u64,
+    last: u64,
+    /// 最近一次 BlockMeta 的 slot
+    last_block: Option<u64>,
+}
+
+/// 跟踪交易 / 区块更新的 slot，根据 BlockMeta 的 `parent_slot` 发现漏收的区块
+///
+/// Solana 的 slot 本身可能被跳过（leader 未出块），slot 不连续并不代表漏数据。
+/// 只有 BlockMeta 的 `parent_slot` 大于上一个收到的区块 slot 时，说明中间的区块没有收到，
+/// 回调 `(上一个区块 + 1, parent_slot)`；`parent_slot` 与 `slot` 之间是被跳过的 slot，不算缺口。
+/// 交易更新只推进最高 slot，不参与缺口判断；低于已见最高值的 slot 直接忽略。
+/// 因此需要缺口检测时必须订阅 BlockMeta。
+#[derive(Default)]
+pub struct SlotGapTracker {
+    range: Mutex<Option<SlotRange>>,
+    callback: RwLock<Option<SlotGapCallback>>,
+}
+
+impl SlotGapTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置缺口回调，覆盖之前的回调
+    pub fn set_callback(&self, callback: SlotGapCallback) {
+        *self.callback.write() = Some(callback);
+    }
+
+    /// 清除缺口回调
+    pub fn clear_callback(&self) {
+        *self.callback.write() = None;
+    }
+
+    /// 记录收到的 slot，返回本次发现的缺口
+    ///
+    /// `parent_slot` 仅 BlockMeta 更新携带，交易等其他更新传 None
+    pub fn observe(&self, slot: u64, parent_slot: Option<u64>) -> Option<(u64, u64)> {
+        let gap = {
+            let mut range = self.range.lock();
+            let current =
+                range.get_or_insert(SlotRange { start: slot, last: slot, last_block: None });
+            current.last = current.last.max(slot);
+            match parent_slot {
+                // 晚到或重复的 BlockMeta 直接忽略
+                Some(_) if current.last_block.is_some_and(|last_block| slot <= last_block) => None,
+                Some(parent_slot) => {
+                    let gap = current
+                        .last_block
+                        .filter(|&last_block| parent_slot > last_block)
+                        .map(|last_block| (last_block + 1, parent_slot));
+                    current.last_block = Some(slot);
+                    if gap.is_some() {
+                        current.start = slot;
+                    }
+                    gap
+                }
+                None => None,
+            }
+        };
+        if let Some((from, to)) = gap {
+            log::warn!("Slot gap detected: {}..={}", from, to);
+            if let Some(callback) = self.callback.read().clone() {
+                callback(from, to);
+            }
+        }
+        gap
+    }
+
+    /// 当前连续收到的 slot 区间 `(start, last)`（闭区间），尚未收到数据时为 None
+    pub fn contiguous_range(&self) -> Option<(u64, u64)> {
+        self.range.lock().map(|range| (range.start, range.last))
+    }
+
+    /// 清空已记录的 slot 区间，保留回调；重新订阅时调用，避免与上一次订阅的 slot 比较出缺口
+    pub fn reset(&self) {
+        *self.range.lock() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot_gap_detection() {
+        let tracker = SlotGapTracker::new();
+        let gaps = Arc::new(Mutex::new(Vec::new()));
+        {
+            let gaps = gaps.clone();
+            tracker.set_callback(Arc::new(move |from, to| gaps.lock().push((from, to))));
+        }
+
+        assert_eq!(tracker.contiguous_range(), None);
+        assert_eq!(tracker.observe(100, None), None);
+        assert_eq!(tracker.observe(100, Some(99)), None);
+        assert_eq!(tracker.observe(101, None), None);
+        assert_eq!(tracker.observe(101, Some(100)), None);
+        // 晚到的旧 slot 不影响区间
+        assert_eq!(tracker.observe(99, None), None);
+        assert_eq!(tracker.observe(99, Some(98)), None);
+        assert_eq!(tracker.contiguous_range(), Some((100, 101)));
+
+        // 102、103 被 leader 跳过：parent_slot 指向已收到的 101，不是缺口
+        assert_eq!(tracker.observe(104, None), None);
+        assert_eq!(tracker.observe(104, Some(101)), None);
+        assert_eq!(tracker.contiguous_range(), Some((100, 104)));
+
+        // 交易 slot 跳号本身不算缺口
+        assert_eq!(tracker.observe(110, None), None);
+        assert!(gaps.lock().is_empty());
+
+        // 区块 105..=109 没有收到：110 的 parent 是 109
+        assert_eq!(tracker.observe(110, Some(109)), Some((105, 109)));
+        assert_eq!(tracker.contiguous_range(), Some((110, 110)));
+        assert_eq!(tracker.observe(111, Some(110)), None);
+        assert_eq!(tracker.contiguous_range(), Some((110, 111)));
+
+        assert_eq!(*gaps.lock(), vec![(105, 109)]);
+
+        // reset 后重新开始，不与之前的区块比较
+        tracker.reset();
+        assert_eq!(tracker.contiguous_range(), None);
+        assert_eq!(tracker.observe(200, Some(150)), None);
+        assert_eq!(tracker.contiguous_range(), Some((200, 200)));
+        assert_eq!(*gaps.lock(), vec![(105, 109)]);
+    }
+}
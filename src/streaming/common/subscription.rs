@@ -1,10 +1,10 @@
-use tokio::task::JoinHandle;
+use tokio::task::{AbortHandle, JoinHandle};
 
 /// Subscription handle for managing and stopping subscriptions
 pub struct SubscriptionHandle {
     stream_handle: JoinHandle<()>,
     event_handle: Option<JoinHandle<()>>,
-    metrics_handle: Option<JoinHandle<()>>,
+    metrics_handle: Option<AbortHandle>,
 }
 
 impl SubscriptionHandle {
@@ -12,7 +12,7 @@ impl SubscriptionHandle {
     pub fn new(
         stream_handle: JoinHandle<()>,
         event_handle: Option<JoinHandle<()>>,
-        metrics_handle: Option<JoinHandle<()>>,
+        metrics_handle: Option<AbortHandle>,
     ) -> Self {
         Self { stream_handle, event_handle, metrics_handle }
     }
@@ -29,14 +29,14 @@ impl SubscriptionHandle {
     }
 
     /// Asynchronously wait for all tasks to complete
+    ///
+    /// The metrics task never exits on its own and is owned by `MetricsManager`, so it is not
+    /// awaited here; it is aborted by `stop` or `MetricsManager::shutdown`.
     pub async fn join(self) -> Result<(), tokio::task::JoinError> {
         let _ = self.stream_handle.await;
         if let Some(handle) = self.event_handle {
             let _ = handle.await;
         }
-        if let Some(handle) = self.metrics_handle {
-            let _ = handle.await;
-        }
         Ok(())
     }
 }
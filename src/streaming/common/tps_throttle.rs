@@ -0,0 +1,76 @@
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// 按固定速率放行交易，用于压测下游消费者
+///
+/// 交易之间至少间隔 `1 / tps` 秒，没有突发额度：流空闲后不会补发积压的额度，
+/// 因此只要上游供给充足，实际投递速率就稳定在目标值。
+/// 只作用于实时订阅，上游会像遇到慢消费者一样积压，可用于复现背压场景：
+/// - Yellowstone 订阅在 `resume_at` 之前暂停读取流，keepalive 和订阅更新照常处理
+/// - ShredStream 订阅没有 keepalive，在处理每笔交易前 `acquire`
+#[derive(Debug)]
+pub struct TpsThrottle {
+    interval: Duration,
+    next: Option<Instant>,
+}
+
+impl TpsThrottle {
+    /// 每秒最多放行 `tps` 笔交易，`tps` 为 0 时返回 None（不限速）
+    pub fn new(tps: u32) -> Option<Self> {
+        (tps > 0).then(|| Self { interval: Duration::from_secs(1) / tps, next: None })
+    }
+
+    /// 按配置创建，未配置时返回 None
+    pub fn from_config(tps: Option<u32>) -> Option<Self> {
+        tps.and_then(Self::new)
+    }
+
+    /// 相邻两笔交易的最小间隔
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// 预约一个放行时刻：`now` 已过上次预约的时刻时立即放行，否则返回上次预约时刻
+    pub fn reserve(&mut self, now: Instant) -> Instant {
+        let at = self.next.map_or(now, |next| next.max(now));
+        self.next = Some(at + self.interval);
+        at
+    }
+
+    /// 下一笔交易还不能放行时返回可以放行的时刻
+    pub fn resume_at(&self, now: Instant) -> Option<Instant> {
+        self.next.filter(|next| *next > now)
+    }
+
+    /// 等待到可以放行下一笔交易
+    pub async fn acquire(&mut self) {
+        let at = self.reserve(Instant::now());
+        tokio::time::sleep_until(at).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_paces_without_burst() {
+        assert!(TpsThrottle::new(0).is_none());
+        assert!(TpsThrottle::from_config(None).is_none());
+        let mut throttle = TpsThrottle::from_config(Some(4)).unwrap();
+        assert_eq!(throttle.interval(), Duration::from_millis(250));
+
+        // 连续到达的交易按间隔排队
+        let start = Instant::now();
+        let slots: Vec<_> = (0..3).map(|_| throttle.reserve(start) - start).collect();
+        assert_eq!(slots, [0, 250, 500].map(Duration::from_millis));
+
+        // 空闲一段时间后不会补发积压的额度
+        let later = start + Duration::from_secs(5);
+        assert_eq!(throttle.resume_at(later), None);
+        assert_eq!(throttle.reserve(later), later);
+        assert_eq!(throttle.resume_at(later), Some(later + Duration::from_millis(250)));
+        assert_eq!(throttle.resume_at(later + Duration::from_millis(250)), None);
+        assert_eq!(throttle.reserve(later), later + Duration::from_millis(250));
+    }
+}
@@ -0,0 +1,83 @@
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+
+use crate::streaming::event_parser::common::EventType;
+
+const MAX_TRACKED_LAYOUTS: usize = 1024;
+
+/// 账户数据长度超过已知布局的记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OversizedAccountLayout {
+    /// 账户事件类型
+    pub event_type: EventType,
+    /// 解析器已知的布局长度（含 discriminator）
+    pub expected: usize,
+    /// 实际账户数据长度
+    pub actual: usize,
+}
+
+/// (事件类型, 实际长度) -> 已知布局长度
+static OVERSIZED_LAYOUTS: Lazy<DashMap<(EventType, usize), usize>> = Lazy::new(DashMap::new);
+
+/// 记录账户数据长度与已知布局的差异
+///
+/// 账户解析器只按已知布局解码前 `expected` 字节，程序升级追加字段或预留空间后仍能解析，
+/// 但新字段会被静默丢弃。每种 (事件类型, 实际长度) 组合第一次出现时打印一条 info 日志，
+/// 并可通过 [`oversized_account_layouts`] 查询，用于发现程序升级。长度不足由各解析器自行处理。
+pub fn note_account_data_len(event_type: &EventType, expected: usize, actual: usize) {
+    if actual <= expected {
+        return;
+    }
+    let key = (event_type.clone(), actual);
+    if OVERSIZED_LAYOUTS.contains_key(&key) || OVERSIZED_LAYOUTS.len() >= MAX_TRACKED_LAYOUTS {
+        return;
+    }
+    if OVERSIZED_LAYOUTS.insert(key, expected).is_none() {
+        log::info!(
+            "{} account data is {} bytes, exceeding the known {}-byte layout; {} bytes unparsed (program upgrade?)",
+            event_type,
+            actual,
+            expected,
+            actual - expected
+        );
+    }
+}
+
+/// 已观察到的超长账户布局，按事件类型和长度排序
+pub fn oversized_account_layouts() -> Vec<OversizedAccountLayout> {
+    let mut layouts: Vec<_> = OVERSIZED_LAYOUTS
+        .iter()
+        .map(|entry| OversizedAccountLayout {
+            event_type: entry.key().0.clone(),
+            expected: *entry.value(),
+            actual: entry.key().1,
+        })
+        .collect();
+    layouts.sort_by(|a, b| {
+        a.event_type.to_string().cmp(&b.event_type.to_string()).then(a.actual.cmp(&b.actual))
+    });
+    layouts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_each_oversized_length_once() {
+        let event_type = EventType::AccountWhirlpoolTickArray;
+        note_account_data_len(&event_type, 100, 100);
+        note_account_data_len(&event_type, 100, 90);
+        note_account_data_len(&event_type, 100, 120);
+        note_account_data_len(&event_type, 100, 120);
+
+        let layouts: Vec<_> = oversized_account_layouts()
+            .into_iter()
+            .filter(|layout| layout.event_type == event_type && layout.expected == 100)
+            .collect();
+        assert_eq!(
+            layouts,
+            vec![OversizedAccountLayout { event_type, expected: 100, actual: 120 }]
+        );
+    }
+}
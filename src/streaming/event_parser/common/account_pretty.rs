@@ -0,0 +1,38 @@
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::fmt;
+
+/// 账户更新，账户解析器的输入
+///
+/// 与数据来源无关：gRPC 订阅在 `streaming::grpc` 中从 `SubscribeUpdateAccount` 构造，
+/// 也可以直接从 RPC 或快照读到的账户数据构造后交给 `AccountEventParser`
+#[derive(Clone, Default)]
+pub struct AccountPretty {
+    pub slot: u64,
+    pub write_version: u64,
+    pub is_startup: bool,
+    pub signature: Signature,
+    pub pubkey: Pubkey,
+    pub executable: bool,
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub rent_epoch: u64,
+    pub data: Vec<u8>,
+    pub recv_us: i64,
+}
+
+impl fmt::Debug for AccountPretty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccountPretty")
+            .field("slot", &self.slot)
+            .field("write_version", &self.write_version)
+            .field("is_startup", &self.is_startup)
+            .field("signature", &self.signature)
+            .field("pubkey", &self.pubkey)
+            .field("executable", &self.executable)
+            .field("lamports", &self.lamports)
+            .field("owner", &self.owner)
+            .field("rent_epoch", &self.rent_epoch)
+            .field("data", &self.data)
+            .finish()
+    }
+}
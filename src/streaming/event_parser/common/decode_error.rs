@@ -0,0 +1,37 @@
+use borsh::BorshDeserialize;
+
+/// 账户数据解码错误，包含出错的字段和偏移，便于排查大账户（TickArray、BinArray 等）解析失败
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DecodeError {
+    /// 数据长度不足
+    #[error("data too short: need {expected} bytes, got {actual}")]
+    TooShort { expected: usize, actual: usize },
+    /// 在 `offset` 处解码字段失败
+    #[error("failed to decode {field} at offset {offset}: {reason}")]
+    InvalidField { field: String, offset: usize, reason: String },
+}
+
+impl DecodeError {
+    /// 检查数据长度至少为 `expected`
+    pub fn ensure_len(data: &[u8], expected: usize) -> Result<(), DecodeError> {
+        if data.len() < expected {
+            return Err(DecodeError::TooShort { expected, actual: data.len() });
+        }
+        Ok(())
+    }
+
+    pub fn invalid_field(field: impl Into<String>, offset: usize, reason: impl ToString) -> Self {
+        DecodeError::InvalidField { field: field.into(), offset, reason: reason.to_string() }
+    }
+}
+
+/// 从 `offset` 处按 borsh 解码一个字段
+pub fn decode_field<T: BorshDeserialize>(
+    data: &[u8],
+    offset: usize,
+    field: &str,
+) -> Result<T, DecodeError> {
+    let mut buf =
+        data.get(offset..).ok_or(DecodeError::TooShort { expected: offset, actual: data.len() })?;
+    T::deserialize(&mut buf).map_err(|e| DecodeError::invalid_field(field, offset, e))
+}
@@ -1,6 +1,11 @@
 use crate::streaming::event_parser::common::{
     types::EventType, ACCOUNT_EVENT_TYPES, BLOCK_EVENT_TYPES,
 };
+use crate::streaming::event_parser::core::capabilities::CapabilityKind;
+use crate::streaming::event_parser::core::dispatcher::EventDispatcher;
+use crate::streaming::event_parser::protocols::types::{Protocol, ALL_PROTOCOLS};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct EventTypeFilter {
@@ -8,6 +13,12 @@ pub struct EventTypeFilter {
 }
 
 impl EventTypeFilter {
+    /// 按协议批量选择事件类型，例如
+    /// `EventTypeFilter::builder().protocol_swaps(Protocol::PumpFun).event(EventType::PumpFunMigrate).build()`
+    pub fn builder() -> EventTypeFilterBuilder {
+        EventTypeFilterBuilder::default()
+    }
+
     pub fn include_transaction_event(&self) -> bool {
         self.include
             .iter()
@@ -22,3 +33,244 @@ impl EventTypeFilter {
         self.include.iter().any(|event| BLOCK_EVENT_TYPES.contains(event))
     }
 }
+
+/// `EventTypeFilter` 构建器，协议级选择按协议解析能力展开为具体的事件类型，重复的类型只保留一次
+#[derive(Debug, Clone, Default)]
+pub struct EventTypeFilterBuilder {
+    include: Vec<EventType>,
+}
+
+impl EventTypeFilterBuilder {
+    /// 加入单个事件类型
+    pub fn event(mut self, event_type: EventType) -> Self {
+        self.push(event_type);
+        self
+    }
+
+    /// 加入多个事件类型
+    pub fn events<I: IntoIterator<Item = EventType>>(mut self, event_types: I) -> Self {
+        for event_type in event_types {
+            self.push(event_type);
+        }
+        self
+    }
+
+    /// 加入该协议的交易（swap）事件类型
+    pub fn protocol_swaps(self, protocol: Protocol) -> Self {
+        self.protocol_subset(protocol, EventType::swap_types())
+    }
+
+    /// 加入该协议的发币与建池事件类型
+    pub fn protocol_launches(self, protocol: Protocol) -> Self {
+        self.protocol_subset(protocol, EventType::launch_types())
+    }
+
+    /// 加入该协议的所有事件类型，包括账户事件
+    pub fn protocol(self, protocol: Protocol) -> Self {
+        let event_types = protocol.capabilities().iter().flat_map(|c| c.event_types.iter());
+        self.events(event_types.copied())
+    }
+
+    /// 加入所有协议的交易（swap）事件类型
+    pub fn swaps(self) -> Self {
+        self.events(EventType::swap_types().iter().copied())
+    }
+
+    /// 加入所有协议的发币与建池事件类型
+    pub fn launches(self) -> Self {
+        self.events(EventType::launch_types().iter().copied())
+    }
+
+    pub fn build(self) -> EventTypeFilter {
+        EventTypeFilter { include: self.include }
+    }
+
+    fn protocol_subset(self, protocol: Protocol, subset: &[EventType]) -> Self {
+        let capabilities = protocol.capabilities();
+        let event_types = subset.iter().copied().filter(|event_type| {
+            capabilities.iter().any(|capability| capability.event_types.contains(event_type))
+        });
+        self.events(event_types)
+    }
+
+    fn push(&mut self, event_type: EventType) {
+        if !self.include.contains(&event_type) {
+            self.include.push(event_type);
+        }
+    }
+}
+
+/// 协议账户的 discriminator 白名单，在解码之前按账户数据前缀过滤
+///
+/// 按 owner 订阅协议账户时会收到该程序的所有账户类型，例如 Whirlpool 的大量 tick array。
+/// 只对属于已订阅协议的账户生效，Token / Nonce 等通用账户不受影响。
+/// Raydium AMM V4 的 AmmInfo 没有 discriminator（空前缀），选中它时该过滤器不再按前缀跳过任何账户，
+/// 需要按 owner 限定时使用 `AccountFilter::event_types`。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AccountDiscriminatorFilter {
+    pub include: Vec<Vec<u8>>,
+}
+
+impl AccountDiscriminatorFilter {
+    /// 保留解码后会产出 `event_types` 中任一账户事件类型的 discriminator
+    pub fn from_event_types(event_types: &[EventType]) -> Self {
+        let include = ALL_PROTOCOLS
+            .iter()
+            .flat_map(|protocol| protocol.capabilities())
+            .filter(|capability| capability.kind == CapabilityKind::Account)
+            .filter(|capability| capability.event_types.iter().any(|t| event_types.contains(t)))
+            .map(|capability| capability.discriminator.to_vec())
+            .collect();
+        Self { include }
+    }
+
+    /// 账户数据是否以白名单中的某个 discriminator 开头
+    #[inline]
+    pub fn allows(&self, data: &[u8]) -> bool {
+        self.include.iter().any(|discriminator| data.starts_with(discriminator))
+    }
+}
+
+/// 按 owner 程序选择要解码的账户事件类型，由 `AccountFilter::event_types` 生成
+///
+/// 反向索引：每个 owner 程序对应其协议中会产出所选事件类型的账户 discriminator，
+/// 该 owner 的其他账户类型在解码之前跳过。不属于已知协议的 owner（Token、Nonce 等）不受影响。
+/// 多个过滤器的选择取并集，某个过滤器未指定事件类型时其 owner 不受限制。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountEventTypeIndex {
+    /// owner 程序 -> 允许解码的 discriminator
+    by_owner: HashMap<Pubkey, Vec<&'static [u8]>>,
+    /// 有过滤器未指定事件类型的 owner
+    unrestricted: HashSet<Pubkey>,
+    /// 有按地址订阅且未指定事件类型的过滤器，此时所有 owner 都不受限制
+    unrestricted_all: bool,
+}
+
+impl AccountEventTypeIndex {
+    /// 加入一个账户过滤器的选择
+    ///
+    /// `owners` 为空（只按地址订阅）时作用于所有协议，`event_types` 为空时不限制
+    pub fn add(&mut self, owners: &[Pubkey], event_types: &[EventType]) {
+        if event_types.is_empty() {
+            if owners.is_empty() {
+                self.unrestricted_all = true;
+            } else {
+                self.unrestricted.extend(owners.iter().copied());
+            }
+            return;
+        }
+        let targets: Vec<(Pubkey, Protocol)> = if owners.is_empty() {
+            ALL_PROTOCOLS.iter().map(|protocol| (protocol.program_id(), protocol.clone())).collect()
+        } else {
+            owners
+                .iter()
+                .filter_map(|owner| {
+                    Some((*owner, EventDispatcher::match_protocol_by_program_id(owner)?))
+                })
+                .collect()
+        };
+        for (owner, protocol) in targets {
+            let discriminators = self.by_owner.entry(owner).or_default();
+            for capability in protocol.capabilities() {
+                if capability.kind == CapabilityKind::Account
+                    && capability.event_types.iter().any(|t| event_types.contains(t))
+                    && !discriminators.contains(&capability.discriminator)
+                {
+                    discriminators.push(capability.discriminator);
+                }
+            }
+        }
+    }
+
+    /// 是否有 owner 受到限制
+    pub fn is_restricted(&self) -> bool {
+        !self.unrestricted_all && !self.by_owner.is_empty()
+    }
+
+    /// 该 owner 的账户数据是否需要解码
+    #[inline]
+    pub fn allows(&self, owner: &Pubkey, data: &[u8]) -> bool {
+        if self.unrestricted_all || self.unrestricted.contains(owner) {
+            return true;
+        }
+        self.by_owner
+            .get(owner)
+            .is_none_or(|discriminators| discriminators.iter().any(|d| data.starts_with(d)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::protocols::whirlpool::discriminators;
+
+    #[test]
+    fn test_event_type_filter_builder_expands_protocols() {
+        let filter = EventTypeFilter::builder()
+            .protocol_swaps(Protocol::PumpFun)
+            .event(EventType::PumpFunMigrate)
+            .event(EventType::PumpFunBuy)
+            .build();
+        assert_eq!(
+            filter.include,
+            vec![EventType::PumpFunBuy, EventType::PumpFunSell, EventType::PumpFunMigrate]
+        );
+
+        let filter = EventTypeFilter::builder().protocol_launches(Protocol::MeteoraDammV2).build();
+        assert!(!filter.include.is_empty());
+        assert!(filter
+            .include
+            .iter()
+            .all(|t| t.is_launch() && t.to_string().starts_with("MeteoraDammV2")));
+
+        let filter = EventTypeFilter::builder().protocol(Protocol::Whirlpool).build();
+        assert!(filter.include.contains(&EventType::WhirlpoolSwapV2));
+        assert!(filter.include.contains(&EventType::AccountWhirlpool));
+        assert!(filter.include_transaction_event() && filter.include_account_event());
+
+        // 每个协议都有 swap 类型，且所有 swap 类型都能被某个协议产出
+        let mut builder = EventTypeFilter::builder();
+        for protocol in ALL_PROTOCOLS {
+            let swaps = EventTypeFilter::builder().protocol_swaps(protocol.clone()).build();
+            assert!(!swaps.include.is_empty(), "{protocol} has no swap types");
+            builder = builder.protocol_swaps(protocol.clone());
+        }
+        assert_eq!(builder.build(), EventTypeFilter::builder().swaps().build());
+    }
+
+    #[test]
+    fn test_account_discriminator_filter_from_event_types() {
+        let filter = AccountDiscriminatorFilter::from_event_types(&[
+            EventType::AccountWhirlpool,
+            EventType::PumpFunBuy,
+        ]);
+        assert_eq!(filter.include, vec![discriminators::WHIRLPOOL.to_vec()]);
+
+        let mut data = discriminators::WHIRLPOOL.to_vec();
+        data.extend_from_slice(&[0; 16]);
+        assert!(filter.allows(&data));
+        assert!(!filter.allows(discriminators::TICK_ARRAY));
+        assert!(!filter.allows(&data[..4]));
+    }
+
+    #[test]
+    fn test_account_event_type_index_by_owner() {
+        use crate::streaming::event_parser::protocols::raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID;
+        use crate::streaming::event_parser::protocols::whirlpool::WHIRLPOOL_PROGRAM_ID;
+
+        let mut index = AccountEventTypeIndex::default();
+        index.add(&[WHIRLPOOL_PROGRAM_ID, RAYDIUM_CLMM_PROGRAM_ID], &[EventType::AccountWhirlpool]);
+        assert!(index.is_restricted());
+        assert!(index.allows(&WHIRLPOOL_PROGRAM_ID, discriminators::WHIRLPOOL));
+        assert!(!index.allows(&WHIRLPOOL_PROGRAM_ID, discriminators::TICK_ARRAY));
+        // 列出但没有选中任何账户类型的 owner 不解码
+        assert!(!index.allows(&RAYDIUM_CLMM_PROGRAM_ID, &[0; 8]));
+        // 其他 owner 不受影响
+        assert!(index.allows(&Pubkey::new_unique(), &[0; 8]));
+
+        // 另一个过滤器未指定事件类型时该 owner 不受限制
+        index.add(&[WHIRLPOOL_PROGRAM_ID], &[]);
+        assert!(index.allows(&WHIRLPOOL_PROGRAM_ID, discriminators::TICK_ARRAY));
+        assert!(!index.allows(&RAYDIUM_CLMM_PROGRAM_ID, &[0; 8]));
+    }
+}
@@ -0,0 +1,124 @@
+//! gRPC 与 VersionedTransaction 指令的统一视图
+//!
+//! 两条解析路径的指令类型不同（yellowstone proto / solana-sdk），解析逻辑只依赖
+//! program_id_index、账户索引和指令数据，通过这里的 trait 共用同一套实现。
+//! yellowstone proto 的实现只在启用 `grpc` feature 时提供。
+use solana_sdk::message::compiled_instruction::CompiledInstruction;
+use solana_transaction_status::{InnerInstruction, InnerInstructions};
+#[cfg(feature = "grpc")]
+use yellowstone_grpc_proto::prelude as grpc;
+
+/// 单条指令（外层或 inner）
+pub trait InstructionView {
+    fn program_id_index(&self) -> usize;
+    fn accounts(&self) -> &[u8];
+    fn data(&self) -> &[u8];
+}
+
+/// 某条外层指令下的 inner instructions
+pub trait InnerInstructionsView {
+    type Instruction: InstructionView;
+
+    /// 所属外层指令的下标
+    fn index(&self) -> usize;
+    fn instructions(&self) -> &[Self::Instruction];
+}
+
+#[cfg(feature = "grpc")]
+impl InstructionView for grpc::CompiledInstruction {
+    #[inline]
+    fn program_id_index(&self) -> usize {
+        self.program_id_index as usize
+    }
+
+    #[inline]
+    fn accounts(&self) -> &[u8] {
+        &self.accounts
+    }
+
+    #[inline]
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl InstructionView for grpc::InnerInstruction {
+    #[inline]
+    fn program_id_index(&self) -> usize {
+        self.program_id_index as usize
+    }
+
+    #[inline]
+    fn accounts(&self) -> &[u8] {
+        &self.accounts
+    }
+
+    #[inline]
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl InnerInstructionsView for grpc::InnerInstructions {
+    type Instruction = grpc::InnerInstruction;
+
+    #[inline]
+    fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    #[inline]
+    fn instructions(&self) -> &[Self::Instruction] {
+        &self.instructions
+    }
+}
+
+impl InstructionView for CompiledInstruction {
+    #[inline]
+    fn program_id_index(&self) -> usize {
+        self.program_id_index as usize
+    }
+
+    #[inline]
+    fn accounts(&self) -> &[u8] {
+        &self.accounts
+    }
+
+    #[inline]
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl InstructionView for InnerInstruction {
+    #[inline]
+    fn program_id_index(&self) -> usize {
+        self.instruction.program_id_index as usize
+    }
+
+    #[inline]
+    fn accounts(&self) -> &[u8] {
+        &self.instruction.accounts
+    }
+
+    #[inline]
+    fn data(&self) -> &[u8] {
+        &self.instruction.data
+    }
+}
+
+impl InnerInstructionsView for InnerInstructions {
+    type Instruction = InnerInstruction;
+
+    #[inline]
+    fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    #[inline]
+    fn instructions(&self) -> &[Self::Instruction] {
+        &self.instructions
+    }
+}
@@ -1,10 +1,20 @@
+pub mod account_layout;
+pub mod account_pretty;
+pub mod decode_error;
 pub mod filter;
 pub mod high_performance_clock;
+pub mod instruction_view;
 pub mod program_data_index;
+pub mod program_failure;
 pub mod swap_cu;
 pub mod types;
 pub mod utils;
+pub use account_layout::*;
+pub use account_pretty::*;
+pub use decode_error::*;
+pub use instruction_view::*;
 pub use program_data_index::*;
+pub use program_failure::*;
 pub use swap_cu::*;
 pub use types::*;
 pub use utils::*;
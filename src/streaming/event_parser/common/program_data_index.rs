@@ -1,4 +1,4 @@
-use crate::streaming::event_parser::common::extract_program_data;
+use crate::streaming::event_parser::common::{extract_program_data, InnerInstructionsView};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
@@ -8,6 +8,22 @@ pub struct ProgramDataItem {
     pub program_id: Pubkey,
     pub depth: usize,
     pub log_index: usize,
+    /// 同一调用直接输出的后续 Program data 在日志中的下标（如 CLMM 多跳路由的每跳 SwapEvent），
+    /// 只记录下标，按需通过 [`ProgramDataItem::all_program_data`] 从日志中读取
+    pub additional: Vec<usize>,
+}
+
+impl ProgramDataItem {
+    /// 按日志顺序返回该调用直接输出的全部 Program data：`base64` 在前，`additional` 指向的日志在后
+    ///
+    /// `logs` 须为构建索引时使用的同一份日志
+    pub fn all_program_data<'a>(&'a self, logs: &'a [String]) -> impl Iterator<Item = &'a str> {
+        std::iter::once(self.base64.as_str()).chain(
+            self.additional
+                .iter()
+                .filter_map(|&idx| logs.get(idx).and_then(|log| extract_program_data(log))),
+        )
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -31,6 +47,19 @@ impl ProgramDataIndex {
         let outer = self.inner.get(outer_index as usize)?;
         outer.get(inner_index as usize)?.as_ref()
     }
+
+    /// 按调用顺序遍历所有 Program data：外层指令在前，其 inner instructions 在后，
+    /// 返回 (outer_index, inner_index, item)
+    pub fn iter(&self) -> impl Iterator<Item = (i64, Option<i64>, &ProgramDataItem)> + '_ {
+        self.outer.iter().enumerate().flat_map(move |(outer_index, outer)| {
+            let inner = self.inner.get(outer_index).map_or(&[][..], Vec::as_slice);
+            std::iter::once((None, outer))
+                .chain(inner.iter().enumerate().map(|(i, item)| (Some(i as i64), item)))
+                .filter_map(move |(inner_index, item)| {
+                    Some((outer_index as i64, inner_index, item.as_ref()?))
+                })
+        })
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -106,34 +135,41 @@ fn find_program_data_in_span(
         return None;
     }
     let end = span.end.min(logs.len() - 1);
+    let mut found: Option<ProgramDataItem> = None;
     for idx in span.start..=end {
         if is_within_child_span(idx, span, spans) {
             continue;
         }
         if let Some(base64) = extract_program_data(&logs[idx]) {
-            return Some(ProgramDataItem {
-                base64: base64.to_string(),
-                program_id: span.program_id,
-                depth: span.depth,
-                log_index: idx,
-            });
+            match found.as_mut() {
+                Some(item) => item.additional.push(idx),
+                None => {
+                    found = Some(ProgramDataItem {
+                        base64: base64.to_string(),
+                        program_id: span.program_id,
+                        depth: span.depth,
+                        log_index: idx,
+                        additional: Vec::new(),
+                    })
+                }
+            }
         }
     }
-    None
+    found
 }
 
-pub fn build_program_data_index(
+pub fn build_program_data_index<G: InnerInstructionsView>(
     logs: &[String],
     outer_len: usize,
-    inner_instructions: &[yellowstone_grpc_proto::prelude::InnerInstructions],
+    inner_instructions: &[G],
 ) -> ProgramDataIndex {
     let mut index =
         ProgramDataIndex { outer: vec![None; outer_len], inner: vec![Vec::new(); outer_len] };
 
     for inner in inner_instructions.iter() {
-        let outer_idx = inner.index as usize;
+        let outer_idx = inner.index();
         if outer_idx < outer_len {
-            index.inner[outer_idx] = vec![None; inner.instructions.len()];
+            index.inner[outer_idx] = vec![None; inner.instructions().len()];
         }
     }
 
@@ -0,0 +1,116 @@
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// 失败交易中从 log_messages 提取的程序失败信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramFailure {
+    /// 最内层失败的程序
+    pub program_id: Pubkey,
+    /// 失败程序所在的外层指令下标
+    pub outer_index: usize,
+    /// `Program <id> failed: <reason>` 中的原因，例如 `custom program error: 0x1771`
+    pub error: String,
+    /// 失败程序在失败前输出的最后一条 `Program log:`，例如 Anchor 的错误信息
+    pub error_log: Option<String>,
+    /// 失败时的调用栈，外层在前，最后一个为 `program_id`
+    pub call_stack: Vec<Pubkey>,
+}
+
+struct Frame {
+    program_id: Pubkey,
+    last_log: Option<String>,
+}
+
+fn parse_invoke_line(log: &str) -> Option<(Pubkey, usize)> {
+    let rest = log.strip_prefix("Program ")?;
+    let (program_id, depth) = rest.split_once(" invoke [")?;
+    let depth = depth.strip_suffix(']')?.parse().ok()?;
+    Some((Pubkey::from_str(program_id).ok()?, depth))
+}
+
+fn parse_failed_line(log: &str) -> Option<(Pubkey, &str)> {
+    let rest = log.strip_prefix("Program ")?;
+    let (program_id, reason) = rest.split_once(" failed: ")?;
+    Some((Pubkey::from_str(program_id).ok()?, reason))
+}
+
+fn is_success_line(log: &str) -> bool {
+    log.starts_with("Program ") && log.ends_with(" success")
+}
+
+/// 找出第一条 `Program <id> failed` 日志对应的失败信息
+///
+/// CPI 失败时外层程序也会输出 failed 日志，这里只取最内层（最先出现）的一条。
+/// 日志被截断或没有 failed 日志时返回 None。
+pub fn extract_program_failure(logs: &[String]) -> Option<ProgramFailure> {
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut outer_count = 0usize;
+    for log in logs {
+        if let Some(message) = log.strip_prefix("Program log: ") {
+            if let Some(frame) = stack.last_mut() {
+                frame.last_log = Some(message.to_string());
+            }
+        } else if let Some((program_id, depth)) = parse_invoke_line(log) {
+            if depth == 1 {
+                outer_count += 1;
+                stack.clear();
+            }
+            stack.push(Frame { program_id, last_log: None });
+        } else if let Some((program_id, reason)) = parse_failed_line(log) {
+            let error_log = match stack.last() {
+                Some(frame) if frame.program_id == program_id => frame.last_log.clone(),
+                _ => None,
+            };
+            let mut call_stack: Vec<Pubkey> = stack.iter().map(|frame| frame.program_id).collect();
+            if call_stack.last() != Some(&program_id) {
+                call_stack.push(program_id);
+            }
+            return Some(ProgramFailure {
+                program_id,
+                outer_index: outer_count.saturating_sub(1),
+                error: reason.to_string(),
+                error_log,
+                call_stack,
+            });
+        } else if is_success_line(log) {
+            stack.pop();
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_innermost_failure() {
+        let router = Pubkey::new_unique();
+        let amm = Pubkey::new_unique();
+        let logs = vec![
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]".to_string(),
+            "Program ComputeBudget111111111111111111111111111111 success".to_string(),
+            format!("Program {} invoke [1]", router),
+            "Program log: Instruction: Route".to_string(),
+            format!("Program {} invoke [2]", amm),
+            "Program log: Instruction: Swap".to_string(),
+            "Program log: AnchorError occurred. Error Code: ExceededSlippage. Error Message: Slippage exceeded.".to_string(),
+            format!("Program {} consumed 21000 of 180000 compute units", amm),
+            format!("Program {} failed: custom program error: 0x1771", amm),
+            format!("Program {} consumed 35000 of 200000 compute units", router),
+            format!("Program {} failed: custom program error: 0x1771", router),
+        ];
+
+        let failure = extract_program_failure(&logs).expect("failure");
+        assert_eq!(failure.program_id, amm);
+        assert_eq!(failure.outer_index, 1);
+        assert_eq!(failure.error, "custom program error: 0x1771");
+        assert_eq!(
+            failure.error_log.as_deref(),
+            Some("AnchorError occurred. Error Code: ExceededSlippage. Error Message: Slippage exceeded.")
+        );
+        assert_eq!(failure.call_stack, vec![router, amm]);
+
+        assert_eq!(extract_program_failure(&logs[..2]), None);
+    }
+}
@@ -1,6 +1,7 @@
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
+use crate::streaming::event_parser::common::{InnerInstructionsView, InstructionView};
 use crate::streaming::event_parser::Protocol;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -167,12 +168,12 @@ fn parse_invocation_spans(logs: &[String]) -> Vec<InvocationSpan> {
     spans
 }
 
-pub fn build_swap_cu_index(
+pub fn build_swap_cu_index<I: InstructionView, G: InnerInstructionsView>(
     config: &SwapCuParseConfig,
     logs: &[String],
-    outer_instructions: &[yellowstone_grpc_proto::prelude::CompiledInstruction],
+    outer_instructions: &[I],
     accounts: &[Pubkey],
-    inner_instructions: &[yellowstone_grpc_proto::prelude::InnerInstructions],
+    inner_instructions: &[G],
 ) -> SwapCuIndex {
     let outer_len = outer_instructions.len();
     let mut index = SwapCuIndex {
@@ -181,9 +182,9 @@ pub fn build_swap_cu_index(
     };
 
     for inner in inner_instructions.iter() {
-        let outer_idx = inner.index as usize;
+        let outer_idx = inner.index();
         if outer_idx < outer_len {
-            index.inner[outer_idx] = vec![None; inner.instructions.len()];
+            index.inner[outer_idx] = vec![None; inner.instructions().len()];
         }
     }
 
@@ -195,11 +196,11 @@ pub fn build_swap_cu_index(
     let outer_spans: Vec<&InvocationSpan> = spans.iter().filter(|span| span.depth == 1).collect();
 
     for (outer_idx, instruction) in outer_instructions.iter().enumerate() {
-        if let Some(program_id) = accounts.get(instruction.program_id_index as usize) {
+        if let Some(program_id) = accounts.get(instruction.program_id_index()) {
             if let Some(protocol) =
                 crate::streaming::event_parser::core::dispatcher::EventDispatcher::match_protocol_by_program_id(program_id)
             {
-                if config.is_target_swap(&protocol, program_id, &instruction.data) {
+                if config.is_target_swap(&protocol, program_id, instruction.data()) {
                     index.outer[outer_idx] = outer_spans
                         .get(outer_idx)
                         .filter(|span| span.program_id == *program_id)
@@ -225,13 +226,13 @@ pub fn build_swap_cu_index(
 
         let Some(inner_group) = inner_instructions
             .iter()
-            .find(|inner| inner.index as usize == outer_idx)
+            .find(|inner| inner.index() == outer_idx)
         else {
             continue;
         };
 
-        for (inner_idx, inner_instruction) in inner_group.instructions.iter().enumerate() {
-            let Some(program_id) = accounts.get(inner_instruction.program_id_index as usize) else {
+        for (inner_idx, inner_instruction) in inner_group.instructions().iter().enumerate() {
+            let Some(program_id) = accounts.get(inner_instruction.program_id_index()) else {
                 continue;
             };
             let Some(protocol) =
@@ -239,7 +240,7 @@ pub fn build_swap_cu_index(
             else {
                 continue;
             };
-            if !config.is_target_swap(&protocol, program_id, &inner_instruction.data) {
+            if !config.is_target_swap(&protocol, program_id, inner_instruction.data()) {
                 continue;
             }
             index.inner[outer_idx][inner_idx] = inner_spans
@@ -261,7 +262,7 @@ pub fn default_swap_cu_targets() -> Vec<SwapCuTarget> {
     vec![
         SwapCuTarget {
             protocol: Protocol::PancakeSwap,
-            program_id: pancakeswap::parser::PANCAKESWAP_PROGRAM_ID,
+            program_id: pancakeswap::PANCAKESWAP_PROGRAM_ID,
             matcher: SwapCuInstructionMatcher::Discriminator8(vec![
                 &pancakeswap::discriminators::SWAP,
                 &pancakeswap::discriminators::SWAP_V2,
@@ -269,7 +270,7 @@ pub fn default_swap_cu_targets() -> Vec<SwapCuTarget> {
         },
         SwapCuTarget {
             protocol: Protocol::RaydiumCpmm,
-            program_id: raydium_cpmm::parser::RAYDIUM_CPMM_PROGRAM_ID,
+            program_id: raydium_cpmm::RAYDIUM_CPMM_PROGRAM_ID,
             matcher: SwapCuInstructionMatcher::Discriminator8(vec![
                 &raydium_cpmm::discriminators::SWAP_BASE_IN,
                 &raydium_cpmm::discriminators::SWAP_BASE_OUT,
@@ -277,7 +278,7 @@ pub fn default_swap_cu_targets() -> Vec<SwapCuTarget> {
         },
         SwapCuTarget {
             protocol: Protocol::RaydiumClmm,
-            program_id: raydium_clmm::parser::RAYDIUM_CLMM_PROGRAM_ID,
+            program_id: raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID,
             matcher: SwapCuInstructionMatcher::Discriminator8(vec![
                 &raydium_clmm::discriminators::SWAP,
                 &raydium_clmm::discriminators::SWAP_V2,
@@ -285,7 +286,7 @@ pub fn default_swap_cu_targets() -> Vec<SwapCuTarget> {
         },
         SwapCuTarget {
             protocol: Protocol::Whirlpool,
-            program_id: whirlpool::parser::WHIRLPOOL_PROGRAM_ID,
+            program_id: whirlpool::WHIRLPOOL_PROGRAM_ID,
             matcher: SwapCuInstructionMatcher::Discriminator8(vec![
                 &whirlpool::discriminators::SWAP,
                 &whirlpool::discriminators::SWAP_V2,
@@ -293,7 +294,7 @@ pub fn default_swap_cu_targets() -> Vec<SwapCuTarget> {
         },
         SwapCuTarget {
             protocol: Protocol::MeteoraDlmm,
-            program_id: meteora_dlmm::parser::METEORA_DLMM_PROGRAM_ID,
+            program_id: meteora_dlmm::METEORA_DLMM_PROGRAM_ID,
             matcher: SwapCuInstructionMatcher::Discriminator8(vec![
                 &meteora_dlmm::discriminators::SWAP_IX,
                 &meteora_dlmm::discriminators::SWAP2_IX,
@@ -302,7 +303,7 @@ pub fn default_swap_cu_targets() -> Vec<SwapCuTarget> {
         },
         SwapCuTarget {
             protocol: Protocol::MeteoraDammV2,
-            program_id: meteora_damm_v2::parser::METEORA_DAMM_V2_PROGRAM_ID,
+            program_id: meteora_damm_v2::METEORA_DAMM_V2_PROGRAM_ID,
             matcher: SwapCuInstructionMatcher::Discriminator8(vec![
                 &meteora_damm_v2::discriminators::SWAP_IX,
                 &meteora_damm_v2::discriminators::SWAP2_IX,
@@ -310,7 +311,7 @@ pub fn default_swap_cu_targets() -> Vec<SwapCuTarget> {
         },
         SwapCuTarget {
             protocol: Protocol::PumpSwap,
-            program_id: pumpswap::parser::PUMPSWAP_PROGRAM_ID,
+            program_id: pumpswap::PUMPSWAP_PROGRAM_ID,
             matcher: SwapCuInstructionMatcher::Discriminator8(vec![
                 &pumpswap::discriminators::BUY_IX,
                 &pumpswap::discriminators::BUY_EXACT_QUOTE_IN_IX,
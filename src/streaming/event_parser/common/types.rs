@@ -4,7 +4,14 @@ use serde::{Deserialize, Serialize};
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
 use std::{borrow::Cow, fmt, str::FromStr, sync::Arc};
 
-use crate::streaming::{common::SimdUtils, event_parser::DexEvent};
+use crate::streaming::{
+    common::{calibrated_latency_ms, SOLANA_BLOCK_TIME_ADJUSTMENT_MS},
+    event_parser::{
+        common::{high_performance_clock::get_high_perf_clock, InstructionView},
+        protocols::raydium_amm_v4::events::RaydiumAmmV4SwapEvent,
+        DexEvent,
+    },
+};
 
 // Object pool size configuration
 const EVENT_METADATA_POOL_SIZE: usize = 1000;
@@ -59,6 +66,13 @@ pub enum ProtocolType {
 }
 
 /// Event type enumeration
+///
+/// 与 `DexEvent` 一样标记为 `#[non_exhaustive]`，新增事件类型不是破坏性变更，
+/// crate 外的 match 需要保留通配分支；按类别判断可使用 `ACCOUNT_EVENT_TYPES` / `BLOCK_EVENT_TYPES` /
+/// `SWAP_EVENT_TYPES` / `LAUNCH_EVENT_TYPES`
+///
+/// 每个变体带有固定编号（见 [`EventType::stable_id`]），变体改名或调整顺序时编号不变；
+/// 新增变体使用未用过的编号，已有编号不得修改或复用。Borsh 编码仍按变体顺序，不受编号影响
 #[derive(
     Debug,
     Clone,
@@ -71,111 +85,129 @@ pub enum ProtocolType {
     BorshSerialize,
     BorshDeserialize,
 )]
+#[borsh(use_discriminant = false)]
+#[non_exhaustive]
 pub enum EventType {
     // PancakeSwap events
-    PancakeSwapSwap,
-    PancakeSwapSwapV2,
+    PancakeSwapSwap = 0,
+    PancakeSwapSwapV2 = 1,
 
     // PumpSwap events
     #[default]
-    PumpSwapBuy,
-    PumpSwapBuyExactQuoteIn,
-    PumpSwapSell,
-    PumpSwapCreatePool,
-    PumpSwapDeposit,
-    PumpSwapWithdraw,
+    PumpSwapBuy = 2,
+    PumpSwapBuyExactQuoteIn = 3,
+    PumpSwapSell = 4,
+    PumpSwapCreatePool = 5,
+    PumpSwapDeposit = 6,
+    PumpSwapWithdraw = 7,
 
     // PumpFun events
-    PumpFunCreateToken,
-    PumpFunCreateV2Token,
-    PumpFunBuy,
-    PumpFunSell,
-    PumpFunMigrate,
+    PumpFunCreateToken = 8,
+    PumpFunCreateV2Token = 9,
+    PumpFunBuy = 10,
+    PumpFunSell = 11,
+    PumpFunMigrate = 12,
+    PumpFunClaimCreatorFee = 13,
 
     // Bonk events
-    BonkBuyExactIn,
-    BonkBuyExactOut,
-    BonkSellExactIn,
-    BonkSellExactOut,
-    BonkInitialize,
-    BonkInitializeV2,
-    BonkInitializeWithToken2022,
-    BonkMigrateToAmm,
-    BonkMigrateToCpswap,
+    BonkBuyExactIn = 14,
+    BonkBuyExactOut = 15,
+    BonkSellExactIn = 16,
+    BonkSellExactOut = 17,
+    BonkInitialize = 18,
+    BonkInitializeV2 = 19,
+    BonkInitializeWithToken2022 = 20,
+    BonkMigrateToAmm = 21,
+    BonkMigrateToCpswap = 22,
+    BonkClaimVestedToken = 23,
+    BonkClaimPlatformFee = 24,
 
     // Raydium CPMM events
-    RaydiumCpmmSwapBaseInput,
-    RaydiumCpmmSwapBaseOutput,
-    RaydiumCpmmDeposit,
-    RaydiumCpmmInitialize,
-    RaydiumCpmmWithdraw,
+    RaydiumCpmmSwapBaseInput = 25,
+    RaydiumCpmmSwapBaseOutput = 26,
+    RaydiumCpmmDeposit = 27,
+    RaydiumCpmmInitialize = 28,
+    RaydiumCpmmWithdraw = 29,
 
     // Raydium CLMM events
-    RaydiumClmmSwap,
-    RaydiumClmmSwapV2,
-    RaydiumClmmClosePosition,
-    RaydiumClmmIncreaseLiquidityV2,
-    RaydiumClmmDecreaseLiquidityV2,
-    RaydiumClmmCreatePool,
-    RaydiumClmmOpenPositionWithToken22Nft,
-    RaydiumClmmOpenPositionV2,
+    RaydiumClmmSwap = 30,
+    RaydiumClmmSwapV2 = 31,
+    RaydiumClmmSwapRouterBaseIn = 32,
+    RaydiumClmmClosePosition = 33,
+    RaydiumClmmIncreaseLiquidityV2 = 34,
+    RaydiumClmmDecreaseLiquidityV2 = 35,
+    RaydiumClmmCreatePool = 36,
+    RaydiumClmmOpenPositionWithToken22Nft = 37,
+    RaydiumClmmOpenPositionV2 = 38,
 
     // Raydium AMM V4 events
-    RaydiumAmmV4SwapBaseIn,
-    RaydiumAmmV4SwapBaseOut,
-    RaydiumAmmV4Deposit,
-    RaydiumAmmV4Initialize2,
-    RaydiumAmmV4Withdraw,
-    RaydiumAmmV4WithdrawPnl,
+    RaydiumAmmV4SwapBaseIn = 39,
+    RaydiumAmmV4SwapBaseOut = 40,
+    RaydiumAmmV4Deposit = 41,
+    RaydiumAmmV4Initialize2 = 42,
+    RaydiumAmmV4Withdraw = 43,
+    RaydiumAmmV4WithdrawPnl = 44,
 
     // Meteora DAMM v2 events
-    MeteoraDammV2Swap,
-    MeteoraDammV2Swap2,
-    MeteoraDammV2InitializePool,
-    MeteoraDammV2InitializeCustomizablePool,
-    MeteoraDammV2InitializePoolWithDynamicConfig,
+    MeteoraDammV2Swap = 45,
+    MeteoraDammV2Swap2 = 46,
+    MeteoraDammV2InitializePool = 47,
+    MeteoraDammV2InitializeCustomizablePool = 48,
+    MeteoraDammV2InitializePoolWithDynamicConfig = 49,
+    MeteoraDammV2ClaimPositionFee = 50,
+    MeteoraDammV2ClaimPartnerFee = 51,
+    MeteoraDammV2ClaimProtocolFee = 52,
 
     // Meteora DLMM events
-    MeteoraDlmmSwap,
-    MeteoraDlmmSwap2,
+    MeteoraDlmmSwap = 53,
+    MeteoraDlmmSwap2 = 54,
+    MeteoraDlmmInitializeLbPair = 55,
 
     // Whirlpool events
-    WhirlpoolSwap,
-    WhirlpoolSwapV2,
+    WhirlpoolSwap = 56,
+    WhirlpoolSwapV2 = 57,
 
     // Account events
-    AccountRaydiumAmmV4AmmInfo,
-    AccountPumpSwapGlobalConfig,
-    AccountPumpSwapPool,
-    AccountBonkPoolState,
-    AccountBonkGlobalConfig,
-    AccountBonkPlatformConfig,
-    AccountBonkVestingRecord,
-    AccountPumpFunBondingCurve,
-    AccountPumpFunGlobal,
-    AccountPancakeSwapPoolState,
-    AccountPancakeSwapTickArrayState,
-    AccountPancakeSwapTickArrayBitmapExtension,
-    AccountRaydiumClmmAmmConfig,
-    AccountRaydiumClmmPoolState,
-    AccountRaydiumClmmTickArrayState,
-    AccountRaydiumClmmTickArrayBitmapExtension,
-    AccountRaydiumCpmmAmmConfig,
-    AccountRaydiumCpmmPoolState,
-    AccountMeteoraDlmmLbPair,
-    AccountMeteoraDlmmBinArray,
-    AccountMeteoraDlmmBinArrayBitmapExtension,
-    AccountWhirlpool,
-    AccountWhirlpoolTickArray,
-
-    NonceAccount,
-    TokenAccount,
+    AccountRaydiumAmmV4AmmInfo = 58,
+    AccountPumpSwapGlobalConfig = 59,
+    AccountPumpSwapPool = 60,
+    AccountBonkPoolState = 61,
+    AccountBonkGlobalConfig = 62,
+    AccountBonkPlatformConfig = 63,
+    AccountBonkVestingRecord = 64,
+    AccountPumpFunBondingCurve = 65,
+    AccountPumpFunGlobal = 66,
+    AccountPancakeSwapPoolState = 67,
+    AccountPancakeSwapTickArrayState = 68,
+    AccountPancakeSwapTickArrayBitmapExtension = 69,
+    AccountRaydiumClmmAmmConfig = 70,
+    AccountRaydiumClmmPoolState = 71,
+    AccountRaydiumClmmTickArrayState = 72,
+    AccountRaydiumClmmTickArrayBitmapExtension = 73,
+    AccountRaydiumCpmmAmmConfig = 74,
+    AccountRaydiumCpmmPoolState = 75,
+    AccountMeteoraDlmmLbPair = 76,
+    AccountMeteoraDlmmBinArray = 77,
+    AccountMeteoraDlmmBinArrayBitmapExtension = 78,
+    AccountWhirlpool = 79,
+    AccountWhirlpoolTickArray = 80,
+
+    NonceAccount = 81,
+    TokenAccount = 82,
 
     // Common events
-    BlockMeta,
-    SetComputeUnitLimit,
-    SetComputeUnitPrice,
-    Unknown,
+    BlockMeta = 83,
+    SetComputeUnitLimit = 84,
+    SetComputeUnitPrice = 85,
+    SetLoadedAccountsDataSizeLimit = 86,
+    AtaCreate = 87,
+    SolTransfer = 88,
+    TransactionSummary = 89,
+    SlotRolledBack = 90,
+    SwapFailed = 91,
+    UnknownInstruction = 92,
+    RawProgramData = 93,
+    Unknown = 94,
 }
 
 pub const ACCOUNT_EVENT_TYPES: &[EventType] = &[
@@ -205,7 +237,179 @@ pub const ACCOUNT_EVENT_TYPES: &[EventType] = &[
     EventType::TokenAccount,
     EventType::NonceAccount,
 ];
-pub const BLOCK_EVENT_TYPES: &[EventType] = &[EventType::BlockMeta];
+pub const BLOCK_EVENT_TYPES: &[EventType] = &[EventType::BlockMeta, EventType::SlotRolledBack];
+
+/// 所有协议的交易（swap）事件类型
+pub const SWAP_EVENT_TYPES: &[EventType] = &[
+    EventType::PancakeSwapSwap,
+    EventType::PancakeSwapSwapV2,
+    EventType::PumpSwapBuy,
+    EventType::PumpSwapBuyExactQuoteIn,
+    EventType::PumpSwapSell,
+    EventType::PumpFunBuy,
+    EventType::PumpFunSell,
+    EventType::BonkBuyExactIn,
+    EventType::BonkBuyExactOut,
+    EventType::BonkSellExactIn,
+    EventType::BonkSellExactOut,
+    EventType::RaydiumCpmmSwapBaseInput,
+    EventType::RaydiumCpmmSwapBaseOutput,
+    EventType::RaydiumClmmSwap,
+    EventType::RaydiumClmmSwapV2,
+    EventType::RaydiumClmmSwapRouterBaseIn,
+    EventType::RaydiumAmmV4SwapBaseIn,
+    EventType::RaydiumAmmV4SwapBaseOut,
+    EventType::MeteoraDammV2Swap,
+    EventType::MeteoraDammV2Swap2,
+    EventType::MeteoraDlmmSwap,
+    EventType::MeteoraDlmmSwap2,
+    EventType::WhirlpoolSwap,
+    EventType::WhirlpoolSwapV2,
+];
+
+/// 所有协议的发币与建池事件类型，不包含迁移事件（如 `PumpFunMigrate`）
+pub const LAUNCH_EVENT_TYPES: &[EventType] = &[
+    EventType::PumpFunCreateToken,
+    EventType::PumpFunCreateV2Token,
+    EventType::PumpSwapCreatePool,
+    EventType::BonkInitialize,
+    EventType::BonkInitializeV2,
+    EventType::BonkInitializeWithToken2022,
+    EventType::RaydiumCpmmInitialize,
+    EventType::RaydiumClmmCreatePool,
+    EventType::RaydiumAmmV4Initialize2,
+    EventType::MeteoraDammV2InitializePool,
+    EventType::MeteoraDammV2InitializeCustomizablePool,
+    EventType::MeteoraDammV2InitializePoolWithDynamicConfig,
+    EventType::MeteoraDlmmInitializeLbPair,
+];
+
+impl EventType {
+    /// 事件类型的固定编号，不随变体名称和顺序变化，用于 `DexEvent::event_id`
+    #[inline]
+    pub fn stable_id(&self) -> u16 {
+        self.clone() as u16
+    }
+
+    /// 交易（swap）事件类型，见 `SWAP_EVENT_TYPES`
+    pub fn swap_types() -> &'static [EventType] {
+        SWAP_EVENT_TYPES
+    }
+
+    /// 发币与建池事件类型，见 `LAUNCH_EVENT_TYPES`
+    pub fn launch_types() -> &'static [EventType] {
+        LAUNCH_EVENT_TYPES
+    }
+
+    /// 是否为交易（swap）事件
+    pub fn is_swap(&self) -> bool {
+        SWAP_EVENT_TYPES.contains(self)
+    }
+
+    /// 是否为发币或建池事件
+    pub fn is_launch(&self) -> bool {
+        LAUNCH_EVENT_TYPES.contains(self)
+    }
+}
+
+/// 所有事件类型
+pub const ALL_EVENT_TYPES: &[EventType] = &[
+    EventType::PancakeSwapSwap,
+    EventType::PancakeSwapSwapV2,
+    EventType::PumpSwapBuy,
+    EventType::PumpSwapBuyExactQuoteIn,
+    EventType::PumpSwapSell,
+    EventType::PumpSwapCreatePool,
+    EventType::PumpSwapDeposit,
+    EventType::PumpSwapWithdraw,
+    EventType::PumpFunCreateToken,
+    EventType::PumpFunCreateV2Token,
+    EventType::PumpFunBuy,
+    EventType::PumpFunSell,
+    EventType::PumpFunMigrate,
+    EventType::PumpFunClaimCreatorFee,
+    EventType::BonkBuyExactIn,
+    EventType::BonkBuyExactOut,
+    EventType::BonkSellExactIn,
+    EventType::BonkSellExactOut,
+    EventType::BonkInitialize,
+    EventType::BonkInitializeV2,
+    EventType::BonkInitializeWithToken2022,
+    EventType::BonkMigrateToAmm,
+    EventType::BonkMigrateToCpswap,
+    EventType::BonkClaimVestedToken,
+    EventType::BonkClaimPlatformFee,
+    EventType::RaydiumCpmmSwapBaseInput,
+    EventType::RaydiumCpmmSwapBaseOutput,
+    EventType::RaydiumCpmmDeposit,
+    EventType::RaydiumCpmmInitialize,
+    EventType::RaydiumCpmmWithdraw,
+    EventType::RaydiumClmmSwap,
+    EventType::RaydiumClmmSwapV2,
+    EventType::RaydiumClmmSwapRouterBaseIn,
+    EventType::RaydiumClmmClosePosition,
+    EventType::RaydiumClmmIncreaseLiquidityV2,
+    EventType::RaydiumClmmDecreaseLiquidityV2,
+    EventType::RaydiumClmmCreatePool,
+    EventType::RaydiumClmmOpenPositionWithToken22Nft,
+    EventType::RaydiumClmmOpenPositionV2,
+    EventType::RaydiumAmmV4SwapBaseIn,
+    EventType::RaydiumAmmV4SwapBaseOut,
+    EventType::RaydiumAmmV4Deposit,
+    EventType::RaydiumAmmV4Initialize2,
+    EventType::RaydiumAmmV4Withdraw,
+    EventType::RaydiumAmmV4WithdrawPnl,
+    EventType::MeteoraDammV2Swap,
+    EventType::MeteoraDammV2Swap2,
+    EventType::MeteoraDammV2InitializePool,
+    EventType::MeteoraDammV2InitializeCustomizablePool,
+    EventType::MeteoraDammV2InitializePoolWithDynamicConfig,
+    EventType::MeteoraDammV2ClaimPositionFee,
+    EventType::MeteoraDammV2ClaimPartnerFee,
+    EventType::MeteoraDammV2ClaimProtocolFee,
+    EventType::MeteoraDlmmSwap,
+    EventType::MeteoraDlmmSwap2,
+    EventType::MeteoraDlmmInitializeLbPair,
+    EventType::WhirlpoolSwap,
+    EventType::WhirlpoolSwapV2,
+    EventType::AccountRaydiumAmmV4AmmInfo,
+    EventType::AccountPumpSwapGlobalConfig,
+    EventType::AccountPumpSwapPool,
+    EventType::AccountBonkPoolState,
+    EventType::AccountBonkGlobalConfig,
+    EventType::AccountBonkPlatformConfig,
+    EventType::AccountBonkVestingRecord,
+    EventType::AccountPumpFunBondingCurve,
+    EventType::AccountPumpFunGlobal,
+    EventType::AccountPancakeSwapPoolState,
+    EventType::AccountPancakeSwapTickArrayState,
+    EventType::AccountPancakeSwapTickArrayBitmapExtension,
+    EventType::AccountRaydiumClmmAmmConfig,
+    EventType::AccountRaydiumClmmPoolState,
+    EventType::AccountRaydiumClmmTickArrayState,
+    EventType::AccountRaydiumClmmTickArrayBitmapExtension,
+    EventType::AccountRaydiumCpmmAmmConfig,
+    EventType::AccountRaydiumCpmmPoolState,
+    EventType::AccountMeteoraDlmmLbPair,
+    EventType::AccountMeteoraDlmmBinArray,
+    EventType::AccountMeteoraDlmmBinArrayBitmapExtension,
+    EventType::AccountWhirlpool,
+    EventType::AccountWhirlpoolTickArray,
+    EventType::NonceAccount,
+    EventType::TokenAccount,
+    EventType::BlockMeta,
+    EventType::SetComputeUnitLimit,
+    EventType::SetComputeUnitPrice,
+    EventType::SetLoadedAccountsDataSizeLimit,
+    EventType::AtaCreate,
+    EventType::SolTransfer,
+    EventType::TransactionSummary,
+    EventType::SlotRolledBack,
+    EventType::SwapFailed,
+    EventType::UnknownInstruction,
+    EventType::RawProgramData,
+    EventType::Unknown,
+];
 
 impl fmt::Display for EventType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -223,6 +427,7 @@ impl fmt::Display for EventType {
             EventType::PumpFunBuy => write!(f, "PumpFunBuy"),
             EventType::PumpFunSell => write!(f, "PumpFunSell"),
             EventType::PumpFunMigrate => write!(f, "PumpFunMigrate"),
+            EventType::PumpFunClaimCreatorFee => write!(f, "PumpFunClaimCreatorFee"),
             EventType::BonkBuyExactIn => write!(f, "BonkBuyExactIn"),
             EventType::BonkBuyExactOut => write!(f, "BonkBuyExactOut"),
             EventType::BonkSellExactIn => write!(f, "BonkSellExactIn"),
@@ -232,6 +437,8 @@ impl fmt::Display for EventType {
             EventType::BonkInitializeWithToken2022 => write!(f, "BonkInitializeWithToken2022"),
             EventType::BonkMigrateToAmm => write!(f, "BonkMigrateToAmm"),
             EventType::BonkMigrateToCpswap => write!(f, "BonkMigrateToCpswap"),
+            EventType::BonkClaimVestedToken => write!(f, "BonkClaimVestedToken"),
+            EventType::BonkClaimPlatformFee => write!(f, "BonkClaimPlatformFee"),
             EventType::RaydiumCpmmSwapBaseInput => write!(f, "RaydiumCpmmSwapBaseInput"),
             EventType::RaydiumCpmmSwapBaseOutput => write!(f, "RaydiumCpmmSwapBaseOutput"),
             EventType::RaydiumCpmmDeposit => write!(f, "RaydiumCpmmDeposit"),
@@ -239,6 +446,7 @@ impl fmt::Display for EventType {
             EventType::RaydiumCpmmWithdraw => write!(f, "RaydiumCpmmWithdraw"),
             EventType::RaydiumClmmSwap => write!(f, "RaydiumClmmSwap"),
             EventType::RaydiumClmmSwapV2 => write!(f, "RaydiumClmmSwapV2"),
+            EventType::RaydiumClmmSwapRouterBaseIn => write!(f, "RaydiumClmmSwapRouterBaseIn"),
             EventType::RaydiumClmmClosePosition => write!(f, "RaydiumClmmClosePosition"),
             EventType::RaydiumClmmDecreaseLiquidityV2 => {
                 write!(f, "RaydiumClmmDecreaseLiquidityV2")
@@ -266,8 +474,12 @@ impl fmt::Display for EventType {
             EventType::MeteoraDammV2InitializePoolWithDynamicConfig => {
                 write!(f, "MeteoraDammV2InitializePoolWithDynamicConfig")
             }
+            EventType::MeteoraDammV2ClaimPositionFee => write!(f, "MeteoraDammV2ClaimPositionFee"),
+            EventType::MeteoraDammV2ClaimPartnerFee => write!(f, "MeteoraDammV2ClaimPartnerFee"),
+            EventType::MeteoraDammV2ClaimProtocolFee => write!(f, "MeteoraDammV2ClaimProtocolFee"),
             EventType::MeteoraDlmmSwap => write!(f, "MeteoraDlmmSwap"),
             EventType::MeteoraDlmmSwap2 => write!(f, "MeteoraDlmmSwap2"),
+            EventType::MeteoraDlmmInitializeLbPair => write!(f, "MeteoraDlmmInitializeLbPair"),
             EventType::WhirlpoolSwap => write!(f, "WhirlpoolSwap"),
             EventType::WhirlpoolSwapV2 => write!(f, "WhirlpoolSwapV2"),
             EventType::AccountRaydiumAmmV4AmmInfo => write!(f, "AccountRaydiumAmmV4AmmInfo"),
@@ -310,11 +522,35 @@ impl fmt::Display for EventType {
             EventType::BlockMeta => write!(f, "BlockMeta"),
             EventType::SetComputeUnitLimit => write!(f, "SetComputeUnitLimit"),
             EventType::SetComputeUnitPrice => write!(f, "SetComputeUnitPrice"),
+            EventType::SetLoadedAccountsDataSizeLimit => {
+                write!(f, "SetLoadedAccountsDataSizeLimit")
+            }
+            EventType::AtaCreate => write!(f, "AtaCreate"),
+            EventType::SolTransfer => write!(f, "SolTransfer"),
+            EventType::TransactionSummary => write!(f, "TransactionSummary"),
+            EventType::SlotRolledBack => write!(f, "SlotRolledBack"),
+            EventType::SwapFailed => write!(f, "SwapFailed"),
+            EventType::UnknownInstruction => write!(f, "UnknownInstruction"),
+            EventType::RawProgramData => write!(f, "RawProgramData"),
             EventType::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
+/// 解析 `Display` 输出的事件类型名，忽略大小写
+impl std::str::FromStr for EventType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        ALL_EVENT_TYPES
+            .iter()
+            .find(|event_type| event_type.to_string().eq_ignore_ascii_case(s))
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Unsupported event type: {}", s))
+    }
+}
+
 /// Parse result
 #[derive(Debug, Clone)]
 pub struct ParseResult<T> {
@@ -361,16 +597,134 @@ impl ProtocolInfo {
 #[derive(
     Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
 )]
+#[serde(default)]
 pub struct SwapData {
     pub from_mint: Pubkey,
     pub to_mint: Pubkey,
     pub from_amount: u64,
     pub to_amount: u64,
     pub description: Option<Cow<'static, str>>,
+    /// from_mint 的精度，仅在开启 attach_ui_amounts 且 MintRegistry 已知时填充
+    #[serde(default)]
+    pub from_decimals: Option<u8>,
+    /// to_mint 的精度，仅在开启 attach_ui_amounts 且 MintRegistry 已知时填充
+    #[serde(default)]
+    pub to_decimals: Option<u8>,
+    /// 开启 verify_transfer_mints 时，某笔 TransferChecked 携带的 mint 与按 vault 推断的
+    /// from_mint / to_mint 不一致，说明解析器对池子 vault 或交易方向的假设有误
+    #[serde(default)]
+    pub mint_mismatch: bool,
 }
 
+impl SwapData {
+    /// from_amount 按 from_decimals 换算后的 UI 数量
+    #[inline]
+    pub fn from_amount_ui(&self) -> Option<f64> {
+        self.from_decimals.map(|decimals| to_ui_amount(self.from_amount, decimals))
+    }
+
+    /// to_amount 按 to_decimals 换算后的 UI 数量
+    #[inline]
+    pub fn to_amount_ui(&self) -> Option<f64> {
+        self.to_decimals.map(|decimals| to_ui_amount(self.to_amount, decimals))
+    }
+
+    /// 成交价格：每 1 个 from_mint 换得的 to_mint 数量（均按精度换算），from_amount 为 0 时返回 None
+    #[inline]
+    pub fn price(&self, from_decimals: u8, to_decimals: u8) -> Option<f64> {
+        if self.from_amount == 0 {
+            return None;
+        }
+        Some(
+            to_ui_amount(self.to_amount, to_decimals)
+                / to_ui_amount(self.from_amount, from_decimals),
+        )
+    }
+
+    /// 使用已填充的 from_decimals / to_decimals 计算价格，任一精度未知时返回 None
+    ///
+    /// 精度未填充时可用 `MintRegistry::swap_price` 直接从注册表查询
+    #[inline]
+    pub fn ui_price(&self) -> Option<f64> {
+        self.price(self.from_decimals?, self.to_decimals?)
+    }
+
+    /// 合并同一笔 swap 的另一部分转账，同一侧的数量累加
+    ///
+    /// 一侧可能拆成多笔转账（例如手续费分账 + 主转账）。`other` 某一侧数量非 0 时：
+    /// 本侧 mint 未知则采用 `other` 的 mint；两侧 mint 相同时数量饱和相加，mint 不同的部分忽略。
+    /// 精度和 description 只在本侧未填充时采用 `other` 的值。
+    pub fn merge(&mut self, other: &SwapData) {
+        fn merge_side(mint: &mut Pubkey, amount: &mut u64, other_mint: Pubkey, other_amount: u64) {
+            if other_amount == 0 {
+                return;
+            }
+            if *mint == Pubkey::default() {
+                *mint = other_mint;
+            }
+            if *mint == other_mint {
+                *amount = amount.saturating_add(other_amount);
+            }
+        }
+        merge_side(&mut self.from_mint, &mut self.from_amount, other.from_mint, other.from_amount);
+        merge_side(&mut self.to_mint, &mut self.to_amount, other.to_mint, other.to_amount);
+        self.from_decimals = self.from_decimals.or(other.from_decimals);
+        self.to_decimals = self.to_decimals.or(other.to_decimals);
+        if self.description.is_none() {
+            self.description = other.description.clone();
+        }
+        self.mint_mismatch |= other.mint_mismatch;
+    }
+}
+
+#[inline]
+fn to_ui_amount(amount: u64, decimals: u8) -> f64 {
+    amount as f64 / 10f64.powi(decimals as i32)
+}
+
+/// 相对池子有序 mint 对 (token0/token1, token_a/token_b) 的交易方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SwapDirection {
+    /// token0 -> token1 (CLMM zero_for_one / Whirlpool a_to_b)
+    AToB,
+    /// token1 -> token0
+    BToA,
+}
+
+impl SwapDirection {
+    #[inline]
+    pub fn from_a_to_b(a_to_b: bool) -> Self {
+        if a_to_b {
+            SwapDirection::AToB
+        } else {
+            SwapDirection::BToA
+        }
+    }
+
+    #[inline]
+    pub fn is_a_to_b(self) -> bool {
+        matches!(self, SwapDirection::AToB)
+    }
+
+    /// 将池子的 (a, b) 按方向排列为 (from, to)
+    #[inline]
+    pub fn order<T>(self, a: T, b: T) -> (T, T) {
+        match self {
+            SwapDirection::AToB => (a, b),
+            SwapDirection::BToA => (b, a),
+        }
+    }
+}
+
+/// 事件 serde 序列化格式版本，持久化事件时可一并保存以检测格式变化
+///
+/// 所有事件结构体都带有 `#[serde(default)]`，新增字段时旧数据缺失的字段取默认值，不提升版本；
+/// 删除或重命名字段、修改字段类型或含义、删除 `DexEvent` 变体时版本 +1
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
 /// Event metadata
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct EventMetadata {
     pub signature: Signature,
     pub slot: u64,
@@ -391,6 +745,21 @@ pub struct EventMetadata {
     pub inner_index: Option<i64>,
     #[serde(default)]
     pub swap_compute_units: Option<u32>,
+    /// gRPC 服务端推送时间（`SubscribeUpdate.created_at`，微秒），仅 Yellowstone 交易事件携带
+    ///
+    /// `recv_us - grpc_created_at_us` 为节点到客户端的网络延迟，不含出块传播延迟
+    #[serde(default)]
+    pub grpc_created_at_us: Option<i64>,
+    /// 外层指令的程序（不是 DEX 协议时），即事件是聚合器 / 路由通过 CPI 调用 DEX 产生的
+    ///
+    /// 用户直接调用 DEX 的事件为 None
+    #[serde(default)]
+    pub via_router: Option<Pubkey>,
+    /// 指令引用了超出交易账户列表的索引，部分账户字段是补齐的 `Pubkey::default()` 而不是真实地址
+    ///
+    /// 通常说明交易数据不完整（例如缺少地址查找表加载的账户），不应依据该事件中的地址交易
+    #[serde(default)]
+    pub has_padded_accounts: bool,
 }
 
 impl EventMetadata {
@@ -425,6 +794,9 @@ impl EventMetadata {
             inner_index,
             transaction_index,
             swap_compute_units: None,
+            grpc_created_at_us: None,
+            via_router: None,
+            has_padded_accounts: false,
         }
     }
 
@@ -432,12 +804,45 @@ impl EventMetadata {
         self.swap_data = Some(swap_data);
     }
 
+    /// 事件到当前时刻的年龄（毫秒），按默认校准值 `SOLANA_BLOCK_TIME_ADJUSTMENT_MS` 校准
+    ///
+    /// `block_time_ms` 为 0（例如 ShredStream 事件）时结果没有意义。
+    #[inline]
+    pub fn age_ms(&self) -> i64 {
+        self.age_ms_with_adjustment(SOLANA_BLOCK_TIME_ADJUSTMENT_MS)
+    }
+
+    /// 按指定校准值计算的年龄（毫秒），例如客户端的 `block_time_adjustment_ms`
+    #[inline]
+    pub fn age_ms_with_adjustment(&self, adjustment_ms: i64) -> i64 {
+        calibrated_latency_ms(get_high_perf_clock(), self.block_time_ms, adjustment_ms)
+    }
+
+    /// 出块到客户端收到事件的延迟（毫秒），按默认校准值 `SOLANA_BLOCK_TIME_ADJUSTMENT_MS` 校准
+    #[inline]
+    pub fn latency_ms(&self) -> i64 {
+        self.latency_ms_with_adjustment(SOLANA_BLOCK_TIME_ADJUSTMENT_MS)
+    }
+
+    /// 按指定校准值计算的延迟（毫秒），例如客户端的 `block_time_adjustment_ms`
+    #[inline]
+    pub fn latency_ms_with_adjustment(&self, adjustment_ms: i64) -> i64 {
+        calibrated_latency_ms(self.recv_us, self.block_time_ms, adjustment_ms)
+    }
+
     /// Recycle EventMetadata to object pool
     pub fn recycle(self) {
         EVENT_METADATA_POOL.release(self);
     }
 }
 
+/// Wrapped SOL mint
+pub const WSOL_MINT: Pubkey = solana_sdk::pubkey!("So11111111111111111111111111111111111111112");
+/// USDC mint
+pub const USDC_MINT: Pubkey = solana_sdk::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+/// USDT mint
+pub const USDT_MINT: Pubkey = solana_sdk::pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+
 lazy_static::lazy_static! {
     static ref SOL_MINT: Pubkey = Pubkey::from_str("So11111111111111111111111111111111111111111").unwrap();
     static ref SYSTEM_PROGRAMS: [Pubkey; 3] = [
@@ -447,19 +852,212 @@ lazy_static::lazy_static! {
     ];
 }
 
-/// Parse token transfer data from next instructions
-pub fn parse_swap_data_from_next_instructions(
+const SYSTEM_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("11111111111111111111111111111111");
+const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("ATokenGPvbdGVxr1b2hQ2Kc4bnfXixjrJaUtfJfWWRo");
+/// SPL Token SyncNative 指令
+const SYNC_NATIVE_DISCRIMINATOR: u8 = 17;
+const TRANSFER_DISCRIMINATOR: u8 = 3;
+const TRANSFER_CHECKED_DISCRIMINATOR: u8 = 12;
+/// Token-2022 TransferFeeExtension 指令，子指令 1 为 TransferCheckedWithFee
+const TRANSFER_FEE_EXTENSION_DISCRIMINATOR: u8 = 26;
+const TRANSFER_CHECKED_WITH_FEE_DISCRIMINATOR: u8 = 1;
+
+/// 解析 SPL Token / Token-2022 转账指令，返回 `(source, destination, amount)`
+///
+/// 调用方需确认指令属于 Token / Token-2022 程序。支持的指令：
+/// - Transfer `[3, amount: u64]`，账户 (source, destination, authority)
+/// - TransferChecked `[12, amount: u64, decimals: u8]`，账户 (source, mint, destination, authority)
+/// - TransferCheckedWithFee `[26, 1, amount: u64, decimals: u8, fee: u64]`，账户同 TransferChecked；
+///   amount 为 source 扣除的数量，destination 实际到账 amount - fee
+pub fn decode_token_transfer(
+    ix_accounts: &[u8],
+    data: &[u8],
+    accounts: &[Pubkey],
+) -> Option<(Pubkey, Pubkey, u64)> {
+    let (amount_offset, destination_index, min_accounts) = match data {
+        [TRANSFER_DISCRIMINATOR, ..] => (1, 1, 3),
+        [TRANSFER_CHECKED_DISCRIMINATOR, ..] => (1, 2, 4),
+        [TRANSFER_FEE_EXTENSION_DISCRIMINATOR, TRANSFER_CHECKED_WITH_FEE_DISCRIMINATOR, ..] => {
+            (2, 2, 4)
+        }
+        _ => return None,
+    };
+    if ix_accounts.len() < min_accounts {
+        return None;
+    }
+    let amount = u64::from_le_bytes(data.get(amount_offset..amount_offset + 8)?.try_into().ok()?);
+    let get_pubkey = |i: usize| accounts.get(*ix_accounts.get(i)? as usize).copied();
+    Some((get_pubkey(0)?, get_pubkey(destination_index)?, amount))
+}
+
+/// TransferChecked / TransferCheckedWithFee 账户中的 mint，普通 Transfer 不带 mint，返回 None
+///
+/// 调用方需确认指令属于 Token / Token-2022 程序
+pub fn decode_token_transfer_mint(
+    ix_accounts: &[u8],
+    data: &[u8],
+    accounts: &[Pubkey],
+) -> Option<Pubkey> {
+    match data {
+        [TRANSFER_CHECKED_DISCRIMINATOR, ..]
+        | [TRANSFER_FEE_EXTENSION_DISCRIMINATOR, TRANSFER_CHECKED_WITH_FEE_DISCRIMINATOR, ..]
+            if ix_accounts.len() >= 4 =>
+        {
+            accounts.get(*ix_accounts.get(1)? as usize).copied()
+        }
+        _ => None,
+    }
+}
+
+/// Raydium AMM V4 swap 的成交数量
+///
+/// AMM V4 没有 swap 日志，且指令中只有池子两侧 vault，没有 mint，无法事先确定哪个 vault 是输入侧。
+/// 这里按用户账户判断方向：`user_source -> 任一 vault` 为输入，`另一 vault -> user_destination` 为输出。
+/// 缺失的一侧按指令类型回退到精确参数：SwapBaseIn 的 `amount_in`、SwapBaseOut 的 `amount_out`。
+fn parse_raydium_amm_v4_swap_data<X: InstructionView>(
+    event: &RaydiumAmmV4SwapEvent,
+    instructions: &[X],
+    accounts: &[Pubkey],
+) -> Option<SwapData> {
+    let vaults = [event.pool_coin_token_account, event.pool_pc_token_account];
+    let mut swap_data = SwapData {
+        description: Some("Unable to get from_mint and to_mint from RaydiumAmmV4SwapEvent".into()),
+        ..Default::default()
+    };
+    let mut input_vault: Option<Pubkey> = None;
+    let mut output_vault: Option<Pubkey> = None;
+
+    for instruction in instructions {
+        let Some(program_id) = accounts.get(instruction.program_id_index()) else {
+            break;
+        };
+        if *program_id == ASSOCIATED_TOKEN_PROGRAM_ID || *program_id == SYSTEM_PROGRAM_ID {
+            continue;
+        }
+        if !SYSTEM_PROGRAMS.contains(program_id) {
+            break;
+        }
+        let Some((source, destination, amount)) =
+            decode_token_transfer(instruction.accounts(), instruction.data(), accounts)
+        else {
+            continue;
+        };
+
+        if input_vault.is_none()
+            && source == event.user_source_token_account
+            && vaults.contains(&destination)
+            && output_vault != Some(destination)
+        {
+            input_vault = Some(destination);
+            swap_data.from_amount = amount;
+        } else if output_vault.is_none()
+            && destination == event.user_destination_token_account
+            && vaults.contains(&source)
+            && input_vault != Some(source)
+        {
+            output_vault = Some(source);
+            swap_data.to_amount = amount;
+        }
+        if input_vault.is_some() && output_vault.is_some() {
+            break;
+        }
+    }
+
+    match event.metadata.event_type {
+        EventType::RaydiumAmmV4SwapBaseIn if input_vault.is_none() => {
+            swap_data.from_amount = event.amount_in;
+        }
+        EventType::RaydiumAmmV4SwapBaseOut if output_vault.is_none() => {
+            swap_data.to_amount = event.amount_out;
+        }
+        _ => {}
+    }
+
+    if swap_data.from_amount != 0 || swap_data.to_amount != 0 {
+        Some(swap_data)
+    } else {
+        None
+    }
+}
+
+/// 紧挨在 swap 之前包装进 `wsol_account` 的原生 SOL（lamports）
+///
+/// 包装由 System Transfer 转入 WSOL 账户、随后对该账户 SyncNative 组成，位于 swap 指令之前。
+/// 从 swap 往前扫描 token / system 指令（跳过 ATA 创建），遇到其他程序的指令即停止；
+/// 只统计离 swap 最近的一次 SyncNative 之前转入该账户的 lamports。
+fn wrapped_lamports_before<P: InstructionView>(
+    preceding: &[P],
+    accounts: &[Pubkey],
+    wsol_account: Pubkey,
+) -> Option<u64> {
+    let mut synced = false;
+    let mut lamports: u64 = 0;
+    for instruction in preceding.iter().rev() {
+        let Some(&program_id) = accounts.get(instruction.program_id_index()) else {
+            break;
+        };
+        if program_id == ASSOCIATED_TOKEN_PROGRAM_ID {
+            continue;
+        }
+        if !SYSTEM_PROGRAMS.contains(&program_id) {
+            break;
+        }
+        let data = instruction.data();
+        let ix_accounts = instruction.accounts();
+        let get_pubkey =
+            |i: usize| ix_accounts.get(i).and_then(|index| accounts.get(*index as usize));
+
+        if program_id != SYSTEM_PROGRAM_ID {
+            if data.first() == Some(&SYNC_NATIVE_DISCRIMINATOR)
+                && get_pubkey(0) == Some(&wsol_account)
+            {
+                // 更早的 SyncNative 属于之前的包装
+                if synced {
+                    break;
+                }
+                synced = true;
+            }
+            continue;
+        }
+        // System Transfer: [2u32, lamports]，账户为 (from, to)
+        if synced
+            && data.first() == Some(&2)
+            && data.len() >= 12
+            && get_pubkey(1) == Some(&wsol_account)
+        {
+            lamports = lamports.saturating_add(u64::from_le_bytes(data[4..12].try_into().unwrap()));
+        }
+    }
+    (lamports > 0).then_some(lamports)
+}
+
+/// 从 swap 指令之后的 token / system 转账中提取 swap_data
+///
+/// `instructions` 为紧跟在 swap 指令之后的 inner instructions，遇到非 token / system 程序的指令时
+/// 停止扫描。`preceding` 为同一层级中位于 swap 之前的指令，输入为 WSOL 且没有 token 转账计入
+/// 输入一侧时，用其中包装进输入账户的 SOL 作为输入数量。不支持的事件返回 None。
+/// `verify_mints` 为 true 时用 TransferChecked 携带的 mint 核对按 vault 推断的 mint，
+/// 见 `SwapData::mint_mismatch`。
+pub fn parse_swap_data_from_following_instructions<P: InstructionView, X: InstructionView>(
     event: &DexEvent,
-    inner_instruction: &solana_transaction_status::InnerInstructions,
-    current_index: i8,
+    preceding: &[P],
+    instructions: &[X],
     accounts: &[Pubkey],
+    verify_mints: bool,
 ) -> Option<SwapData> {
+    if let DexEvent::RaydiumAmmV4SwapEvent(e) = event {
+        return parse_raydium_amm_v4_swap_data(e, instructions, accounts);
+    }
+
     let mut swap_data = SwapData {
         from_mint: Pubkey::default(),
         to_mint: Pubkey::default(),
         from_amount: 0,
         to_amount: 0,
         description: None,
+        from_decimals: None,
+        to_decimals: None,
     };
 
     // 先根据 event 取出关键信息
@@ -540,15 +1138,6 @@ pub fn parse_swap_data_from_next_instructions(
             from_vault = Some(e.input_vault);
             to_vault = Some(e.output_vault);
         }
-        DexEvent::RaydiumAmmV4SwapEvent(e) => {
-            // user = Some(e.user_source_owner);
-            swap_data.description =
-                Some("Unable to get from_mint and to_mint from RaydiumAmmV4SwapEvent".into());
-            user_from_token = Some(e.user_source_token_account);
-            user_to_token = Some(e.user_destination_token_account);
-            from_vault = Some(e.pool_pc_token_account);
-            to_vault = Some(e.pool_coin_token_account);
-        }
         DexEvent::MeteoraDlmmSwapEvent(e) => {
             if e.swap_for_y {
                 from_mint = e.token_x_mint;
@@ -611,7 +1200,7 @@ pub fn parse_swap_data_from_next_instructions(
                 to_vault = Some(e.token_vault_a);
             }
         }
-        _ => {}
+        _ => return None,
     }
 
     let user_to_token = user_to_token.unwrap_or_default();
@@ -621,69 +1210,93 @@ pub fn parse_swap_data_from_next_instructions(
     let to_mint = to_mint.unwrap_or_default();
     let from_mint = from_mint.unwrap_or_default();
 
-    // 单次循环完成提取和判断
-    for instruction in inner_instruction.instructions.iter().skip((current_index + 1) as usize) {
-        let compiled = &instruction.instruction;
-        let program_id = accounts[compiled.program_id_index as usize];
+    // PumpFun / PumpSwap 等事件不带 token 账户，转账无法归到任何一侧
+    let instructions = if user_from_token == Pubkey::default() && user_to_token == Pubkey::default()
+    {
+        &instructions[..0]
+    } else {
+        instructions
+    };
+
+    // 一侧可能有多笔转账（手续费分账 + 主转账），扫描到非 token / system 指令为止并累加
+    for instruction in instructions {
+        let Some(&program_id) = accounts.get(instruction.program_id_index()) else {
+            break;
+        };
+        // ATA CreateIdempotent 不涉及资金转移，跳过而不是终止扫描
+        if program_id == ASSOCIATED_TOKEN_PROGRAM_ID {
+            continue;
+        }
         if !SYSTEM_PROGRAMS.contains(&program_id) {
             break;
         }
-        let data = &compiled.data;
+        let data = instruction.data();
+        let ix_accounts = instruction.accounts();
+        let get_pubkey = |i: usize| {
+            ix_accounts
+                .get(i)
+                .and_then(|index| accounts.get(*index as usize))
+                .copied()
+                .unwrap_or_default()
+        };
 
-        // 使用 SIMD 验证数据格式
-        if !SimdUtils::validate_data_format(data, 8) {
+        let transfer = if program_id == SYSTEM_PROGRAM_ID {
+            // System Transfer: [2u32, lamports]，账户为 (from, to)
+            (data.first() == Some(&2) && data.len() >= 12 && ix_accounts.len() >= 2).then(|| {
+                (get_pubkey(0), get_pubkey(1), u64::from_le_bytes(data[4..12].try_into().unwrap()))
+            })
+        } else {
+            decode_token_transfer(ix_accounts, data, accounts)
+        };
+        let Some((source, destination, amount)) = transfer else {
             continue;
-        }
-
-        let get_pubkey = |i: usize| accounts[compiled.accounts[i] as usize];
-        let (source, destination, amount) = match data[0] {
-            12 if compiled.accounts.len() >= 4 => {
-                let amt = u64::from_le_bytes(data[1..9].try_into().unwrap());
-                (get_pubkey(0), get_pubkey(2), amt)
-            }
-            3 if compiled.accounts.len() >= 3 => {
-                let amt = u64::from_le_bytes(data[1..9].try_into().unwrap());
-                (get_pubkey(0), get_pubkey(1), amt)
-            }
-            2 if compiled.accounts.len() >= 2 => {
-                let amt = u64::from_le_bytes(data[4..12].try_into().unwrap());
-                (get_pubkey(0), get_pubkey(1), amt)
-            }
-            _ => continue,
         };
 
-        match (source, destination) {
-            (s, d) if s == user_to_token && d == to_vault => {
-                swap_data.from_mint = to_mint;
-                swap_data.from_amount = amount;
-            }
-            (s, d) if s == from_vault && d == user_from_token => {
-                swap_data.to_mint = from_mint;
-                swap_data.to_amount = amount;
-            }
-            (s, d) if s == user_from_token && d == from_vault => {
-                swap_data.from_mint = from_mint;
-                swap_data.from_amount = amount;
-            }
-            (s, d) if s == to_vault && d == user_to_token => {
-                swap_data.to_mint = to_mint;
-                swap_data.to_amount = amount;
-            }
-            (s, d) if s == user_from_token && d == to_vault => {
-                swap_data.from_mint = from_mint;
-                swap_data.from_amount = amount;
-            }
-            (s, d) if s == from_vault && d == user_to_token => {
-                swap_data.to_mint = to_mint;
-                swap_data.to_amount = amount;
+        // (是否为输入一侧, mint)
+        let side = match (source, destination) {
+            (s, d) if s == user_to_token && d == to_vault => Some((true, to_mint)),
+            (s, d) if s == from_vault && d == user_from_token => Some((false, from_mint)),
+            (s, d) if s == user_from_token && d == from_vault => Some((true, from_mint)),
+            (s, d) if s == to_vault && d == user_to_token => Some((false, to_mint)),
+            (s, d) if s == user_from_token && d == to_vault => Some((true, from_mint)),
+            (s, d) if s == from_vault && d == user_to_token => Some((false, to_mint)),
+            _ => None,
+        };
+        // TransferChecked 带有 mint，与按 vault 推断的 mint 交叉核对
+        if let Some((_, mint)) = side.filter(|_| verify_mints && program_id != SYSTEM_PROGRAM_ID) {
+            if let Some(transfer_mint) = decode_token_transfer_mint(ix_accounts, data, accounts) {
+                if mint != Pubkey::default() && transfer_mint != mint {
+                    log::debug!(
+                        "{:?} swap_data mint mismatch: expected {}, TransferChecked mint {}",
+                        event.metadata().event_type,
+                        mint,
+                        transfer_mint
+                    );
+                    swap_data.mint_mismatch = true;
+                }
             }
-            _ => {}
         }
-        if swap_data.from_mint != Pubkey::default() && swap_data.to_mint != Pubkey::default() {
-            break;
+        match side {
+            Some((true, mint)) => swap_data.merge(&SwapData {
+                from_mint: mint,
+                from_amount: amount,
+                ..Default::default()
+            }),
+            Some((false, mint)) => swap_data.merge(&SwapData {
+                to_mint: mint,
+                to_amount: amount,
+                ..Default::default()
+            }),
+            None => {}
         }
-        if swap_data.from_amount != 0 && swap_data.to_amount != 0 {
-            break;
+    }
+
+    // swap 之前包装的 SOL 作为 SOL 输入一侧
+    if swap_data.from_amount == 0 && from_mint == WSOL_MINT && user_from_token != Pubkey::default()
+    {
+        if let Some(lamports) = wrapped_lamports_before(preceding, accounts, user_from_token) {
+            swap_data.from_mint = WSOL_MINT;
+            swap_data.from_amount = lamports;
         }
     }
 
@@ -699,253 +1312,430 @@ pub fn parse_swap_data_from_next_instructions(
 }
 
 /// Parse token transfer data from next instructions
-/// TODO: - wait refactor
+pub fn parse_swap_data_from_next_instructions(
+    event: &DexEvent,
+    inner_instruction: &solana_transaction_status::InnerInstructions,
+    current_index: i8,
+    accounts: &[Pubkey],
+) -> Option<SwapData> {
+    let preceding = inner_instruction.instructions.get(..current_index as usize)?;
+    let following = inner_instruction.instructions.get((current_index + 1) as usize..)?;
+    parse_swap_data_from_following_instructions(event, preceding, following, accounts, false)
+}
+
+/// Parse token transfer data from next instructions
+#[cfg(feature = "grpc")]
 pub fn parse_swap_data_from_next_grpc_instructions(
     event: &DexEvent,
     inner_instruction: &yellowstone_grpc_proto::prelude::InnerInstructions,
     current_index: i8,
     accounts: &[Pubkey],
 ) -> Option<SwapData> {
-    let mut swap_data = SwapData {
-        from_mint: Pubkey::default(),
-        to_mint: Pubkey::default(),
-        from_amount: 0,
-        to_amount: 0,
-        description: None,
-    };
+    let preceding = inner_instruction.instructions.get(..current_index as usize)?;
+    let following = inner_instruction.instructions.get((current_index + 1) as usize..)?;
+    parse_swap_data_from_following_instructions(event, preceding, following, accounts, false)
+}
 
-    // 先根据 event 取出关键信息
-    // let mut user: Option<Pubkey> = None;
-    let mut from_mint: Option<Pubkey> = None;
-    let mut to_mint: Option<Pubkey> = None;
-    let mut user_from_token: Option<Pubkey> = None;
-    let mut user_to_token: Option<Pubkey> = None;
-    let mut from_vault: Option<Pubkey> = None;
-    let mut to_vault: Option<Pubkey> = None;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
 
-    match event {
-        DexEvent::BonkTradeEvent(e) => {
-            // user = Some(e.payer);
-            from_mint = Some(e.base_token_mint);
-            to_mint = Some(e.quote_token_mint);
-            user_from_token = Some(e.user_base_token);
-            user_to_token = Some(e.user_quote_token);
-            from_vault = Some(e.base_vault);
-            to_vault = Some(e.quote_vault);
-        }
-        DexEvent::PumpFunTradeEvent(e) => {
-            swap_data.from_mint = if e.is_buy { *SOL_MINT } else { e.mint };
-            swap_data.to_mint = if e.is_buy { e.mint } else { *SOL_MINT };
-        }
-        DexEvent::PumpSwapBuyEvent(e) => {
-            swap_data.from_mint = e.quote_mint;
-            swap_data.to_mint = e.base_mint;
-        }
-        DexEvent::PumpSwapBuyExactQuoteInEvent(e) => {
-            swap_data.from_mint = e.quote_mint;
-            swap_data.to_mint = e.base_mint;
-        }
-        DexEvent::PumpSwapSellEvent(e) => {
-            swap_data.from_mint = e.base_mint;
-            swap_data.to_mint = e.quote_mint;
-        }
-        DexEvent::PancakeSwapSwapEvent(e) => {
-            swap_data.description =
-                Some("Unable to get from_mint and to_mint from PancakeSwapSwapEvent".into());
-            user_from_token = Some(e.input_token_account);
-            user_to_token = Some(e.output_token_account);
-            from_vault = Some(e.input_vault);
-            to_vault = Some(e.output_vault);
+    #[test]
+    fn test_event_type_display_from_str_round_trip() {
+        let unique: std::collections::HashSet<_> = ALL_EVENT_TYPES.iter().collect();
+        assert_eq!(unique.len(), ALL_EVENT_TYPES.len());
+        for event_type in ALL_EVENT_TYPES {
+            assert_eq!(&EventType::from_str(&event_type.to_string()).unwrap(), event_type);
         }
-        DexEvent::PancakeSwapSwapV2Event(e) => {
-            from_mint = Some(e.input_mint);
-            to_mint = Some(e.output_mint);
-            user_from_token = Some(e.input_token_account);
-            user_to_token = Some(e.output_token_account);
-            from_vault = Some(e.input_vault);
-            to_vault = Some(e.output_vault);
-        }
-        DexEvent::RaydiumCpmmSwapEvent(e) => {
-            // user = Some(e.payer);
-            from_mint = Some(e.input_token_mint);
-            to_mint = Some(e.output_token_mint);
-            user_from_token = Some(e.input_token_account);
-            user_to_token = Some(e.output_token_account);
-            from_vault = Some(e.input_vault);
-            to_vault = Some(e.output_vault);
-        }
-        DexEvent::RaydiumClmmSwapEvent(e) => {
-            // user = Some(e.payer);
-            swap_data.description =
-                Some("Unable to get from_mint and to_mint from RaydiumClmmSwapEvent".into());
-            user_from_token = Some(e.input_token_account);
-            user_to_token = Some(e.output_token_account);
-            from_vault = Some(e.input_vault);
-            to_vault = Some(e.output_vault);
-        }
-        DexEvent::RaydiumClmmSwapV2Event(e) => {
-            // user = Some(e.payer);
-            from_mint = Some(e.input_vault_mint);
-            to_mint = Some(e.output_vault_mint);
-            user_from_token = Some(e.input_token_account);
-            user_to_token = Some(e.output_token_account);
-            from_vault = Some(e.input_vault);
-            to_vault = Some(e.output_vault);
-        }
-        DexEvent::RaydiumAmmV4SwapEvent(e) => {
-            // user = Some(e.user_source_owner);
-            swap_data.description =
-                Some("Unable to get from_mint and to_mint from RaydiumAmmV4SwapEvent".into());
-            user_from_token = Some(e.user_source_token_account);
-            user_to_token = Some(e.user_destination_token_account);
-            from_vault = Some(e.pool_pc_token_account);
-            to_vault = Some(e.pool_coin_token_account);
-        }
-        DexEvent::MeteoraDlmmSwapEvent(e) => {
-            if e.swap_for_y {
-                from_mint = e.token_x_mint;
-                to_mint = e.token_y_mint;
-                from_vault = e.reserve_x;
-                to_vault = e.reserve_y;
-            } else {
-                from_mint = e.token_y_mint;
-                to_mint = e.token_x_mint;
-                from_vault = e.reserve_y;
-                to_vault = e.reserve_x;
-            }
-            user_from_token = e.user_token_in;
-            user_to_token = e.user_token_out;
-        }
-        DexEvent::MeteoraDlmmSwap2Event(e) => {
-            if e.swap_for_y {
-                from_mint = e.token_x_mint;
-                to_mint = e.token_y_mint;
-                from_vault = e.reserve_x;
-                to_vault = e.reserve_y;
-            } else {
-                from_mint = e.token_y_mint;
-                to_mint = e.token_x_mint;
-                from_vault = e.reserve_y;
-                to_vault = e.reserve_x;
-            }
-            user_from_token = e.user_token_in;
-            user_to_token = e.user_token_out;
-        }
-        DexEvent::WhirlpoolSwapEvent(e) => {
-            swap_data.description =
-                Some("Unable to get from_mint and to_mint from WhirlpoolSwapEvent".into());
-            if e.a_to_b {
-                user_from_token = Some(e.token_owner_account_a);
-                user_to_token = Some(e.token_owner_account_b);
-                from_vault = Some(e.token_vault_a);
-                to_vault = Some(e.token_vault_b);
-            } else {
-                user_from_token = Some(e.token_owner_account_b);
-                user_to_token = Some(e.token_owner_account_a);
-                from_vault = Some(e.token_vault_b);
-                to_vault = Some(e.token_vault_a);
+        assert_eq!(EventType::from_str("pumpfunbuy").unwrap(), EventType::PumpFunBuy);
+        assert!(EventType::from_str("PumpFunTrade").is_err());
+    }
+
+    #[test]
+    fn test_metadata_latency_and_age() {
+        let metadata = EventMetadata {
+            block_time_ms: 1_700_000_000_000,
+            recv_us: 1_700_000_001_200_000,
+            ..Default::default()
+        };
+        assert_eq!(metadata.latency_ms(), 700);
+        assert_eq!(metadata.latency_ms_with_adjustment(1000), 200);
+        assert!(metadata.age_ms() > metadata.latency_ms());
+    }
+
+    #[test]
+    fn test_raydium_amm_v4_swap_data_direction() {
+        use solana_sdk::message::compiled_instruction::CompiledInstruction;
+        use solana_transaction_status::{InnerInstruction, InnerInstructions};
+
+        // 0: user coin，1: user pc，2: coin vault，3: pc vault，4: 手续费账户，5: owner，6: token program
+        let mut accounts: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        accounts.push(solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"));
+        let transfer = |source: u8, destination: u8, amount: u64| {
+            let mut data = vec![3u8];
+            data.extend_from_slice(&amount.to_le_bytes());
+            InnerInstruction {
+                instruction: CompiledInstruction {
+                    program_id_index: 6,
+                    accounts: vec![source, destination, 5],
+                    data,
+                },
+                stack_height: Some(2),
             }
-        }
-        DexEvent::WhirlpoolSwapV2Event(e) => {
-            if e.a_to_b {
-                from_mint = Some(e.token_mint_a);
-                to_mint = Some(e.token_mint_b);
-                user_from_token = Some(e.token_owner_account_a);
-                user_to_token = Some(e.token_owner_account_b);
-                from_vault = Some(e.token_vault_a);
-                to_vault = Some(e.token_vault_b);
-            } else {
-                from_mint = Some(e.token_mint_b);
-                to_mint = Some(e.token_mint_a);
-                user_from_token = Some(e.token_owner_account_b);
-                user_to_token = Some(e.token_owner_account_a);
-                from_vault = Some(e.token_vault_b);
-                to_vault = Some(e.token_vault_a);
+        };
+        let swap_event = |event_type: EventType, user_source: usize, user_destination: usize| {
+            DexEvent::RaydiumAmmV4SwapEvent(RaydiumAmmV4SwapEvent {
+                metadata: EventMetadata { event_type, ..Default::default() },
+                amount_in: 1_000,
+                amount_out: 1_000,
+                pool_coin_token_account: accounts[2],
+                pool_pc_token_account: accounts[3],
+                user_source_token_account: accounts[user_source],
+                user_destination_token_account: accounts[user_destination],
+                ..Default::default()
+            })
+        };
+        let parse = |event: &DexEvent, instructions: Vec<InnerInstruction>| {
+            let inner = InnerInstructions { index: 0, instructions };
+            let swap_data = parse_swap_data_from_next_instructions(event, &inner, -1, &accounts)
+                .expect("swap data");
+            (swap_data.from_amount, swap_data.to_amount)
+        };
+
+        // SwapBaseIn coin -> pc，前面有一笔路由手续费转账
+        let base_in = swap_event(EventType::RaydiumAmmV4SwapBaseIn, 0, 1);
+        assert_eq!(
+            parse(&base_in, vec![transfer(0, 4, 7), transfer(0, 2, 1_000), transfer(3, 1, 250)]),
+            (1_000, 250)
+        );
+
+        // SwapBaseOut pc -> coin
+        let base_out = swap_event(EventType::RaydiumAmmV4SwapBaseOut, 1, 0);
+        assert_eq!(
+            parse(&base_out, vec![transfer(1, 3, 260), transfer(2, 0, 1_000)]),
+            (260, 1_000)
+        );
+        // 缺少输出转账时回退到指令中的 amount_out
+        assert_eq!(parse(&base_out, vec![transfer(1, 3, 260)]), (260, 1_000));
+    }
+
+    #[test]
+    fn test_swap_data_from_following_instructions() {
+        use crate::streaming::event_parser::protocols::raydium_cpmm::RaydiumCpmmSwapEvent;
+        use solana_sdk::message::compiled_instruction::CompiledInstruction;
+
+        // 0: user in，1: user out，2: vault in，3: vault out，4: owner，5: token program，6: 其他程序
+        let mut accounts: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        accounts.push(solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"));
+        accounts.push(Pubkey::new_unique());
+        let transfer = |source: u8, destination: u8, amount: u64| {
+            let mut data = vec![3u8];
+            data.extend_from_slice(&amount.to_le_bytes());
+            CompiledInstruction {
+                program_id_index: 5,
+                accounts: vec![source, destination, 4],
+                data,
             }
-        }
-        _ => {}
+        };
+        let (input_mint, output_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let event = DexEvent::RaydiumCpmmSwapEvent(RaydiumCpmmSwapEvent {
+            input_token_mint: input_mint,
+            output_token_mint: output_mint,
+            input_token_account: accounts[0],
+            output_token_account: accounts[1],
+            input_vault: accounts[2],
+            output_vault: accounts[3],
+            ..Default::default()
+        });
+
+        let instructions = vec![
+            transfer(0, 2, 1_000),
+            transfer(3, 1, 250),
+            CompiledInstruction { program_id_index: 6, accounts: vec![], data: vec![] },
+        ];
+        let swap_data = parse_swap_data_from_following_instructions(
+            &event,
+            &instructions[..0],
+            &instructions,
+            &accounts,
+            false,
+        )
+        .expect("swap data");
+        assert_eq!(
+            (swap_data.from_mint, swap_data.from_amount, swap_data.to_mint, swap_data.to_amount),
+            (input_mint, 1_000, output_mint, 250)
+        );
+
+        // 同一侧的多笔转账（手续费分账 + 主转账）累加
+        let instructions = vec![
+            transfer(0, 2, 10),
+            transfer(3, 1, 250),
+            transfer(0, 2, 990),
+            CompiledInstruction { program_id_index: 6, accounts: vec![], data: vec![] },
+            transfer(0, 2, 5_000),
+        ];
+        let swap_data = parse_swap_data_from_following_instructions(
+            &event,
+            &instructions[..0],
+            &instructions,
+            &accounts,
+            false,
+        )
+        .expect("swap data");
+        assert_eq!((swap_data.from_amount, swap_data.to_amount), (1_000, 250));
+
+        // 非 swap 事件不提取
+        let other = DexEvent::RaydiumCpmmDepositEvent(Default::default());
+        assert!(parse_swap_data_from_following_instructions(
+            &other,
+            &instructions[..0],
+            &instructions,
+            &accounts,
+            false
+        )
+        .is_none());
     }
 
-    let user_to_token = user_to_token.unwrap_or_default();
-    let user_from_token = user_from_token.unwrap_or_default();
-    let to_vault = to_vault.unwrap_or_default();
-    let from_vault = from_vault.unwrap_or_default();
-    let to_mint = to_mint.unwrap_or_default();
-    let from_mint = from_mint.unwrap_or_default();
+    #[test]
+    fn test_swap_data_sol_wrapped_before_swap() {
+        use crate::streaming::event_parser::protocols::raydium_cpmm::RaydiumCpmmSwapEvent;
+        use solana_sdk::message::compiled_instruction::CompiledInstruction;
 
-    // 单次循环完成提取和判断
-    for instruction in inner_instruction.instructions.iter().skip((current_index + 1) as usize) {
-        let compiled = &instruction;
-        let program_id = accounts[compiled.program_id_index as usize];
-        if !SYSTEM_PROGRAMS.contains(&program_id) {
-            break;
-        }
-        let data = &compiled.data;
+        // 0: user WSOL，1: user out，2: vault in，3: vault out，4: owner，5: token program，
+        // 6: system program，7: 其他程序
+        let mut accounts: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        accounts.push(solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"));
+        accounts.push(SYSTEM_PROGRAM_ID);
+        accounts.push(Pubkey::new_unique());
+        let system_transfer = |lamports: u64| {
+            let mut data = 2u32.to_le_bytes().to_vec();
+            data.extend_from_slice(&lamports.to_le_bytes());
+            CompiledInstruction { program_id_index: 6, accounts: vec![4, 0], data }
+        };
+        let sync_native = CompiledInstruction {
+            program_id_index: 5,
+            accounts: vec![0],
+            data: vec![SYNC_NATIVE_DISCRIMINATOR],
+        };
+        let other = CompiledInstruction { program_id_index: 7, accounts: vec![], data: vec![] };
+        let mut output = vec![3u8];
+        output.extend_from_slice(&250u64.to_le_bytes());
+        let following =
+            [CompiledInstruction { program_id_index: 5, accounts: vec![3, 1, 4], data: output }];
+        let output_mint = Pubkey::new_unique();
+        let event = DexEvent::RaydiumCpmmSwapEvent(RaydiumCpmmSwapEvent {
+            input_token_mint: WSOL_MINT,
+            output_token_mint: output_mint,
+            input_token_account: accounts[0],
+            output_token_account: accounts[1],
+            input_vault: accounts[2],
+            output_vault: accounts[3],
+            ..Default::default()
+        });
+        let parse = |preceding: &[CompiledInstruction]| {
+            parse_swap_data_from_following_instructions(
+                &event, preceding, &following, &accounts, false,
+            )
+            .expect("swap data")
+        };
 
-        // 使用 SIMD 验证数据格式
-        if !SimdUtils::validate_data_format(data, 8) {
-            continue;
-        }
+        // System Transfer + SyncNative 在 swap 之前：包装的 lamports 作为输入
+        let swap_data = parse(&[
+            other.clone(),
+            system_transfer(600),
+            system_transfer(400),
+            sync_native.clone(),
+        ]);
+        assert_eq!(
+            (swap_data.from_mint, swap_data.from_amount, swap_data.to_mint, swap_data.to_amount),
+            (WSOL_MINT, 1_000, output_mint, 250)
+        );
 
-        let get_pubkey = |i: usize| accounts[compiled.accounts[i] as usize];
-        let (source, destination, amount) = match data[0] {
-            12 if compiled.accounts.len() >= 4 => {
-                let amt = u64::from_le_bytes(data[1..9].try_into().unwrap());
-                (get_pubkey(0), get_pubkey(2), amt)
-            }
-            3 if compiled.accounts.len() >= 3 => {
-                let amt = u64::from_le_bytes(data[1..9].try_into().unwrap());
-                (get_pubkey(0), get_pubkey(1), amt)
-            }
-            2 if compiled.accounts.len() >= 2 => {
-                let amt = u64::from_le_bytes(data[4..12].try_into().unwrap());
-                (get_pubkey(0), get_pubkey(1), amt)
+        // 只统计离 swap 最近的一次包装
+        let swap_data = parse(&[
+            system_transfer(5_000),
+            sync_native.clone(),
+            system_transfer(1_000),
+            sync_native.clone(),
+        ]);
+        assert_eq!(swap_data.from_amount, 1_000);
+
+        // 包装与 swap 之间有其他程序的指令，或者没有 SyncNative 时不计入
+        let swap_data = parse(&[system_transfer(1_000), sync_native.clone(), other.clone()]);
+        assert_eq!(swap_data.from_amount, 0);
+        let swap_data = parse(&[system_transfer(1_000)]);
+        assert_eq!(swap_data.from_amount, 0);
+
+        // swap 之后的包装不计入
+        let following = [sync_native.clone(), following[0].clone()];
+        let swap_data = parse_swap_data_from_following_instructions(
+            &event,
+            &[system_transfer(1_000)],
+            &following,
+            &accounts,
+            false,
+        )
+        .expect("swap data");
+        assert_eq!(swap_data.from_amount, 0);
+    }
+
+    #[test]
+    fn test_swap_data_transfer_checked_mint_verification() {
+        use crate::streaming::event_parser::protocols::raydium_cpmm::RaydiumCpmmSwapEvent;
+        use solana_sdk::message::compiled_instruction::CompiledInstruction;
+
+        // 0: user in，1: user out，2: vault in，3: vault out，4: owner，5: token program，
+        // 6: input mint，7: output mint
+        let mut accounts: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        accounts.push(solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"));
+        let (input_mint, output_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        accounts.extend([input_mint, output_mint]);
+        let transfer_checked = |source: u8, mint: u8, destination: u8, amount: u64| {
+            let mut data = vec![12u8];
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.push(6);
+            CompiledInstruction {
+                program_id_index: 5,
+                accounts: vec![source, mint, destination, 4],
+                data,
             }
-            _ => continue,
         };
+        let event = DexEvent::RaydiumCpmmSwapEvent(RaydiumCpmmSwapEvent {
+            input_token_mint: input_mint,
+            output_token_mint: output_mint,
+            input_token_account: accounts[0],
+            output_token_account: accounts[1],
+            input_vault: accounts[2],
+            output_vault: accounts[3],
+            ..Default::default()
+        });
 
-        match (source, destination) {
-            (s, d) if s == user_to_token && d == to_vault => {
-                swap_data.from_mint = to_mint;
-                swap_data.from_amount = amount;
-            }
-            (s, d) if s == from_vault && d == user_from_token => {
-                swap_data.to_mint = from_mint;
-                swap_data.to_amount = amount;
-            }
-            (s, d) if s == user_from_token && d == from_vault => {
-                swap_data.from_mint = from_mint;
-                swap_data.from_amount = amount;
-            }
-            (s, d) if s == to_vault && d == user_to_token => {
-                swap_data.to_mint = to_mint;
-                swap_data.to_amount = amount;
-            }
-            (s, d) if s == user_from_token && d == to_vault => {
-                swap_data.from_mint = from_mint;
-                swap_data.from_amount = amount;
-            }
-            (s, d) if s == from_vault && d == user_to_token => {
-                swap_data.to_mint = to_mint;
-                swap_data.to_amount = amount;
+        let parse = |instructions: &[CompiledInstruction]| {
+            parse_swap_data_from_following_instructions(
+                &event,
+                &instructions[..0],
+                instructions,
+                &accounts,
+                true,
+            )
+            .expect("swap data")
+        };
+        let matching = parse(&[transfer_checked(0, 6, 2, 1_000), transfer_checked(3, 7, 1, 250)]);
+        assert!(!matching.mint_mismatch);
+
+        // 输出 vault 转出的是输入 mint：事件中的 vault 与实际不符
+        let swapped = parse(&[transfer_checked(0, 6, 2, 1_000), transfer_checked(3, 6, 1, 250)]);
+        assert!(swapped.mint_mismatch);
+        assert_eq!((swapped.to_mint, swapped.to_amount), (output_mint, 250));
+
+        // 未开启核对时不比对 mint
+        let unverified = parse_swap_data_from_following_instructions(
+            &event,
+            &[] as &[CompiledInstruction],
+            &[transfer_checked(0, 6, 2, 1_000), transfer_checked(3, 6, 1, 250)],
+            &accounts,
+            false,
+        )
+        .expect("swap data");
+        assert!(!unverified.mint_mismatch);
+    }
+
+    #[test]
+    fn test_swap_data_merge() {
+        let (sol, token) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut swap_data = SwapData {
+            from_mint: sol,
+            from_amount: 100,
+            to_decimals: Some(6),
+            ..Default::default()
+        };
+        swap_data.merge(&SwapData {
+            from_mint: sol,
+            from_amount: 5,
+            to_mint: token,
+            to_amount: 700,
+            from_decimals: Some(9),
+            to_decimals: Some(3),
+            description: Some("fee".into()),
+        });
+        assert_eq!(
+            swap_data,
+            SwapData {
+                from_mint: sol,
+                from_amount: 105,
+                to_mint: token,
+                to_amount: 700,
+                from_decimals: Some(9),
+                to_decimals: Some(6),
+                description: Some("fee".into()),
+                mint_mismatch: false,
             }
-            _ => {}
-        }
-        if swap_data.from_mint != Pubkey::default() && swap_data.to_mint != Pubkey::default() {
-            break;
-        }
-        if swap_data.from_amount != 0 && swap_data.to_amount != 0 {
-            break;
-        }
+        );
+
+        // mint 不同的部分不累加，数量为 0 的一侧不影响 mint
+        swap_data.merge(&SwapData {
+            from_mint: token,
+            from_amount: 1,
+            to_mint: sol,
+            ..Default::default()
+        });
+        assert_eq!((swap_data.from_mint, swap_data.from_amount), (sol, 105));
+        assert_eq!((swap_data.to_mint, swap_data.to_amount), (token, 700));
+        swap_data.merge(&SwapData { to_amount: u64::MAX, to_mint: token, ..Default::default() });
+        assert_eq!(swap_data.to_amount, u64::MAX);
     }
 
-    if swap_data.from_mint != Pubkey::default()
-        || swap_data.to_mint != Pubkey::default()
-        || swap_data.from_amount != 0
-        || swap_data.to_amount != 0
-    {
-        Some(swap_data)
-    } else {
-        None
+    #[test]
+    fn test_decode_token_transfer_account_layout() {
+        // Token-2022 TransferChecked: (source, mint, destination, authority)
+        let accounts: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let (source, mint, destination, authority) = (0u8, 1u8, 2u8, 3u8);
+        let mut checked = vec![12u8];
+        checked.extend_from_slice(&1_500u64.to_le_bytes());
+        checked.push(6);
+        assert_eq!(
+            decode_token_transfer(&[source, mint, destination, authority], &checked, &accounts),
+            Some((accounts[0], accounts[2], 1_500))
+        );
+        assert_eq!(
+            decode_token_transfer_mint(
+                &[source, mint, destination, authority],
+                &checked,
+                &accounts
+            ),
+            Some(accounts[1])
+        );
+        // 缺少 authority 的 TransferChecked 不合法
+        assert_eq!(decode_token_transfer(&[source, mint, destination], &checked, &accounts), None);
+
+        // Transfer: (source, destination, authority)
+        let mut transfer = vec![3u8];
+        transfer.extend_from_slice(&700u64.to_le_bytes());
+        assert_eq!(
+            decode_token_transfer(&[source, destination, authority], &transfer, &accounts),
+            Some((accounts[0], accounts[2], 700))
+        );
+        // amount 不足 8 字节
+        assert_eq!(
+            decode_token_transfer(&[source, destination, authority], &transfer[..8], &accounts),
+            None
+        );
+
+        // TransferCheckedWithFee: amount 在 2..10，账户同 TransferChecked
+        let mut with_fee = vec![26u8, 1];
+        with_fee.extend_from_slice(&2_000u64.to_le_bytes());
+        with_fee.push(6);
+        with_fee.extend_from_slice(&20u64.to_le_bytes());
+        assert_eq!(
+            decode_token_transfer(&[source, mint, destination, authority], &with_fee, &accounts),
+            Some((accounts[0], accounts[2], 2_000))
+        );
+        // TransferFeeExtension 的其他子指令不是转账
+        with_fee[1] = 2;
+        assert_eq!(
+            decode_token_transfer(&[source, mint, destination, authority], &with_fee, &accounts),
+            None
+        );
     }
 }
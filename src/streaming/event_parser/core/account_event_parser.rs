@@ -1,9 +1,15 @@
-use crate::streaming::event_parser::common::filter::EventTypeFilter;
+use crate::streaming::event_parser::common::filter::{
+    AccountDiscriminatorFilter, AccountEventTypeIndex, EventTypeFilter,
+};
 use crate::streaming::event_parser::common::high_performance_clock::elapsed_micros_since;
-use crate::streaming::event_parser::common::{EventMetadata, EventType, ProtocolType};
+use crate::streaming::event_parser::common::{
+    AccountPretty, EventMetadata, EventType, ProtocolType,
+};
+use crate::streaming::event_parser::core::mint_registry::MintRegistry;
+use crate::streaming::event_parser::core::pool_fee_registry::PoolFeeRegistry;
 use crate::streaming::event_parser::core::traits::DexEvent;
+use crate::streaming::event_parser::protocols::meteora_dlmm::price::record_lb_pair_bin_step;
 use crate::streaming::event_parser::Protocol;
-use crate::streaming::grpc::AccountPretty;
 use serde::{Deserialize, Serialize};
 use solana_account_decoder::parse_nonce::parse_nonce;
 use solana_sdk::pubkey::Pubkey;
@@ -13,9 +19,11 @@ use spl_token_2022::{
     extension::StateWithExtensions,
     state::{Account as Account2022, Mint as Mint2022},
 };
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// 通用账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TokenAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -29,6 +37,7 @@ pub struct TokenAccountEvent {
 
 /// Nonce account event
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NonceAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -42,6 +51,7 @@ pub struct NonceAccountEvent {
 
 /// Nonce account event
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TokenInfoEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -53,13 +63,64 @@ pub struct TokenInfoEvent {
     pub decimals: u8,
 }
 
+/// 账户事件在解码之前的过滤选项
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AccountParseOptions<'a> {
+    /// 协议账户的 discriminator 白名单，不在白名单中的账户直接跳过
+    pub discriminator_filter: Option<&'a AccountDiscriminatorFilter>,
+    /// 按 owner 跳过订阅时未选择的账户类型
+    pub event_types: Option<&'a AccountEventTypeIndex>,
+}
+
+/// 解码后是否保留账户事件的 raw_account_data
+static RETAIN_RAW_ACCOUNT_DATA: AtomicBool = AtomicBool::new(true);
+
 pub struct AccountEventParser {}
 
 impl AccountEventParser {
+    /// 设置解码后是否保留 raw_account_data，关闭后协议账户事件的 raw_account_data 为空
+    pub fn set_retain_raw_account_data(retain: bool) {
+        RETAIN_RAW_ACCOUNT_DATA.store(retain, Ordering::Relaxed);
+    }
+
+    /// 解码后是否保留 raw_account_data
+    pub fn retain_raw_account_data() -> bool {
+        RETAIN_RAW_ACCOUNT_DATA.load(Ordering::Relaxed)
+    }
+
+    /// 记录池子的 mint 对，供不携带 mint 账户的 V1 swap 指令推导 from/to mint
+    fn record_pool_mints(event: &DexEvent) {
+        use crate::streaming::event_parser::core::global_state::set_pool_mints;
+        match event {
+            DexEvent::RaydiumClmmPoolStateAccountEvent(e) => {
+                set_pool_mints(e.pubkey, e.pool_state.token_mint0, e.pool_state.token_mint1)
+            }
+            DexEvent::WhirlpoolAccountEvent(e) => {
+                set_pool_mints(e.pubkey, e.whirlpool.token_mint_a, e.whirlpool.token_mint_b)
+            }
+            _ => {}
+        }
+    }
+
     pub fn parse_account_event(
         protocols: &[Protocol],
         account: AccountPretty,
         event_type_filter: Option<&EventTypeFilter>,
+    ) -> Option<DexEvent> {
+        Self::parse_account_event_with_options(
+            protocols,
+            account,
+            event_type_filter,
+            AccountParseOptions::default(),
+        )
+    }
+
+    /// 解析账户事件，按 `options` 在解码之前跳过不需要的协议账户
+    pub fn parse_account_event_with_options(
+        protocols: &[Protocol],
+        account: AccountPretty,
+        event_type_filter: Option<&EventTypeFilter>,
+        options: AccountParseOptions<'_>,
     ) -> Option<DexEvent> {
         use crate::streaming::event_parser::core::dispatcher::EventDispatcher;
 
@@ -71,6 +132,18 @@ impl AccountEventParser {
             if let Some(protocol) = EventDispatcher::match_protocol_by_program_id(&account.owner) {
                 // 检查是否在请求的协议列表中
                 if protocols.contains(&protocol) {
+                    if options
+                        .discriminator_filter
+                        .is_some_and(|filter| !filter.allows(&account.data))
+                    {
+                        return None;
+                    }
+                    if options
+                        .event_types
+                        .is_some_and(|index| !index.allows(&account.owner, &account.data))
+                    {
+                        return None;
+                    }
                     // 构建临时元数据（protocol会被dispatcher设置，event_type会在parser中设置）
                     let metadata = EventMetadata {
                         slot: account.slot,
@@ -86,12 +159,21 @@ impl AccountEventParser {
                     };
 
                     // 使用 dispatcher 解析
-                    if let Some(event) = EventDispatcher::dispatch_account(
+                    if let Some(mut event) = EventDispatcher::dispatch_account(
                         protocol,
                         &discriminator,
                         account,
                         metadata,
                     ) {
+                        if !Self::retain_raw_account_data() {
+                            if let Some(raw_account_data) = event.raw_account_data_mut() {
+                                *raw_account_data = Vec::new();
+                            }
+                        }
+                        Self::record_pool_mints(&event);
+                        record_lb_pair_bin_step(&event);
+                        MintRegistry::global().record_from_event(&event);
+                        PoolFeeRegistry::global().record_from_event(&event);
                         // 应用事件类型过滤
                         if let Some(filter) = event_type_filter {
                             if filter.include.contains(&event.metadata().event_type) {
@@ -136,6 +218,7 @@ impl AccountEventParser {
 
         // 尝试解析 Token 账户
         if let Some(event) = Self::parse_token_account_event(&account, metadata) {
+            MintRegistry::global().record_from_event(&event);
             if let Some(filter) = event_type_filter {
                 if filter.include.contains(&event.metadata().event_type) {
                     return Some(event);
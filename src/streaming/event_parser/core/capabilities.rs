@@ -0,0 +1,519 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::{
+    common::EventType,
+    protocols::{
+        bonk::events as bonk, meteora_damm_v2::events as meteora_damm_v2,
+        meteora_dlmm::events as meteora_dlmm, pancakeswap::discriminators as pancakeswap_events,
+        pancakeswap::events as pancakeswap, pumpfun::events as pumpfun,
+        pumpswap::events as pumpswap, raydium_amm_v4::events as raydium_amm_v4,
+        raydium_clmm::discriminators as raydium_clmm_events, raydium_clmm::events as raydium_clmm,
+        raydium_cpmm::discriminators as raydium_cpmm_events, raydium_cpmm::events as raydium_cpmm,
+        whirlpool::discriminators as whirlpool_events, whirlpool::events as whirlpool,
+    },
+    Protocol,
+};
+
+/// 事件来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CapabilityKind {
+    /// 外层或 CPI 指令数据
+    Instruction,
+    /// emit_cpi inner instruction 事件，合并到对应的指令事件中
+    InnerInstruction,
+    /// 账户数据
+    Account,
+}
+
+/// 一个可解析的 discriminator 及其产出的事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventCapability {
+    pub kind: CapabilityKind,
+    pub discriminator: &'static [u8],
+    /// inner instruction 事件列出其可合并到的指令事件类型
+    pub event_types: &'static [EventType],
+}
+
+impl EventCapability {
+    pub const fn instruction(
+        discriminator: &'static [u8],
+        event_types: &'static [EventType],
+    ) -> Self {
+        Self { kind: CapabilityKind::Instruction, discriminator, event_types }
+    }
+
+    pub const fn inner_instruction(
+        discriminator: &'static [u8],
+        event_types: &'static [EventType],
+    ) -> Self {
+        Self { kind: CapabilityKind::InnerInstruction, discriminator, event_types }
+    }
+
+    pub const fn account(discriminator: &'static [u8], event_types: &'static [EventType]) -> Self {
+        Self { kind: CapabilityKind::Account, discriminator, event_types }
+    }
+}
+
+/// 协议的解析能力，可用于生成文档或检查升级后新增的覆盖
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolCapability {
+    pub protocol: Protocol,
+    pub program_id: Pubkey,
+    pub events: Vec<EventCapability>,
+}
+
+impl ProtocolCapability {
+    /// 指定来源的能力
+    pub fn of_kind(&self, kind: CapabilityKind) -> impl Iterator<Item = &EventCapability> {
+        self.events.iter().filter(move |e| e.kind == kind)
+    }
+}
+
+impl Protocol {
+    /// 该协议解析器支持的 discriminator 与事件类型，与是否启用对应的 feature 无关
+    pub const fn capabilities(&self) -> &'static [EventCapability] {
+        match self {
+            Protocol::PancakeSwap => pancakeswap::CAPABILITIES,
+            Protocol::PumpSwap => pumpswap::CAPABILITIES,
+            Protocol::PumpFun => pumpfun::CAPABILITIES,
+            Protocol::Bonk => bonk::CAPABILITIES,
+            Protocol::RaydiumCpmm => raydium_cpmm::CAPABILITIES,
+            Protocol::RaydiumClmm => raydium_clmm::CAPABILITIES,
+            Protocol::RaydiumAmmV4 => raydium_amm_v4::CAPABILITIES,
+            Protocol::MeteoraDammV2 => meteora_damm_v2::CAPABILITIES,
+            Protocol::MeteoraDlmm => meteora_dlmm::CAPABILITIES,
+            Protocol::Whirlpool => whirlpool::CAPABILITIES,
+        }
+    }
+
+    /// 只从 `Program data:` 日志解码、用于补全指令事件的事件 discriminator
+    pub const fn program_data_discriminators(&self) -> &'static [&'static [u8]] {
+        match self {
+            Protocol::PancakeSwap => &[pancakeswap_events::SWAP_EVENT],
+            Protocol::RaydiumCpmm => &[raydium_cpmm_events::SWAP_EVENT],
+            Protocol::RaydiumClmm => &[raydium_clmm_events::SWAP_EVENT],
+            Protocol::Whirlpool => &[whirlpool_events::TRADED_EVENT],
+            _ => &[],
+        }
+    }
+
+    /// `Program data:` 日志中 8 字节的事件 discriminator 能否被解析器解码：
+    /// emit_cpi 事件的 discriminator（去掉 event ix tag）或 `program_data_discriminators`
+    pub fn is_known_program_data(&self, discriminator: &[u8]) -> bool {
+        discriminator.len() == 8
+            && (self.program_data_discriminators().contains(&discriminator)
+                || self.capabilities().iter().any(|capability| {
+                    capability.kind == CapabilityKind::InnerInstruction
+                        && capability.discriminator.ends_with(discriminator)
+                }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::{
+        common::{
+            extract_program_failure, AccountPretty, EventMetadata, ProtocolType, ALL_EVENT_TYPES,
+        },
+        core::{
+            account_event_parser::AccountEventParser, common_event_parser::CommonEventParser,
+            dispatcher::EventDispatcher, event_parser::EventParser,
+            sol_transfer_watchlist::SolTransferWatchlist,
+        },
+        protocols::{block::slot_rolled_back_event::SlotRolledBackEvent, types::ALL_PROTOCOLS},
+        DexEvent,
+    };
+    use solana_sdk::signature::Signature;
+    use std::collections::HashSet;
+
+    /// 零填充数据长度，覆盖各解析器对指令数据与账户数据的最小长度要求
+    const ZERO_DATA_LEN: usize = 20_000;
+
+    /// 指令账户列表；Meteora DLMM swap 通过 [event_authority, program] 定位账户布局
+    fn instruction_accounts(protocol: Protocol) -> Vec<Pubkey> {
+        let mut accounts: Vec<Pubkey> = (0..40).map(|_| Pubkey::new_unique()).collect();
+        if protocol == Protocol::MeteoraDlmm {
+            let program_id = protocol.program_id();
+            accounts[12] = Pubkey::find_program_address(&[b"__event_authority"], &program_id).0;
+            accounts[13] = program_id;
+        }
+        accounts
+    }
+
+    /// 账户数据长度；Raydium AMM V4 账户没有 discriminator，按数据长度识别 AmmInfo
+    fn account_data_len(protocol: &Protocol) -> usize {
+        if protocol == &Protocol::RaydiumAmmV4 {
+            crate::streaming::event_parser::protocols::raydium_amm_v4::types::AMM_INFO_SIZE
+        } else {
+            ZERO_DATA_LEN
+        }
+    }
+
+    /// 用零填充的数据把声明的 discriminator 交给 EventDispatcher 解析
+    fn dispatch_capability(protocol: Protocol, capability: &EventCapability) -> Option<DexEvent> {
+        let zeros = vec![0u8; ZERO_DATA_LEN];
+        let metadata = EventMetadata::default();
+        match capability.kind {
+            CapabilityKind::Instruction => EventDispatcher::dispatch_instruction(
+                protocol.clone(),
+                capability.discriminator,
+                &zeros,
+                &instruction_accounts(protocol),
+                metadata,
+            ),
+            CapabilityKind::InnerInstruction => EventDispatcher::dispatch_inner_instruction(
+                protocol,
+                capability.discriminator,
+                &zeros,
+                metadata,
+            ),
+            CapabilityKind::Account => {
+                // 与 AccountEventParser 一致，传入账户数据的前 8 字节
+                let mut data = capability.discriminator.to_vec();
+                data.resize(account_data_len(&protocol), 0);
+                let discriminator = data[..8].to_vec();
+                let account =
+                    AccountPretty { owner: protocol.program_id(), data, ..Default::default() };
+                EventDispatcher::dispatch_account(protocol, &discriminator, account, metadata)
+            }
+        }
+    }
+
+    /// 已启用协议的每个能力及其经 dispatcher 解析出的事件
+    fn dispatched_capabilities() -> Vec<(Protocol, &'static EventCapability, Option<DexEvent>)> {
+        ALL_PROTOCOLS
+            .iter()
+            .filter(|protocol| protocol.is_enabled())
+            .flat_map(|protocol| {
+                protocol.capabilities().iter().map(move |capability| {
+                    (
+                        protocol.clone(),
+                        capability,
+                        dispatch_capability(protocol.clone(), capability),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// 不属于任何协议的事件，经通用解析路径实际解析或创建
+    ///
+    /// 与各协议经 dispatcher 实际解析出的事件一起覆盖解析器可能产出的全部事件类型
+    fn common_events() -> Vec<DexEvent> {
+        let metadata = EventMetadata::default;
+        let accounts: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        let watchlist = SolTransferWatchlist::new();
+        watchlist.configure(Some(&accounts[..1]));
+        let mut transfer = 2u32.to_le_bytes().to_vec();
+        transfer.extend_from_slice(&1_000u64.to_le_bytes());
+        // Versions::Current(State::Initialized(Data { authority, durable_nonce, fee_calculator }))
+        let mut nonce = [1u32.to_le_bytes(), 1u32.to_le_bytes()].concat();
+        nonce.extend_from_slice(accounts[0].as_ref());
+        nonce.extend_from_slice(&[7u8; 32]);
+        nonce.extend_from_slice(&5_000u64.to_le_bytes());
+        let failure = extract_program_failure(&[
+            format!("Program {} invoke [1]", accounts[0]),
+            format!("Program {} failed: custom program error: 0x1", accounts[0]),
+        ])
+        .expect("program failure");
+
+        #[allow(unused_mut)]
+        let mut events = vec![
+            // AccountEventParser：不属于已订阅协议的 Token 账户 / mint、nonce 账户
+            AccountEventParser::parse_account_event(
+                &[],
+                AccountPretty { data: vec![0u8; 165], ..Default::default() },
+                None,
+            ),
+            AccountEventParser::parse_account_event(
+                &[],
+                AccountPretty { data: nonce, ..Default::default() },
+                None,
+            ),
+            // CommonEventParser：Compute Budget / ATA / 系统转账指令
+            CommonEventParser::parse_compute_budget_instruction(&[2, 0, 0, 0, 0], metadata()),
+            CommonEventParser::parse_compute_budget_instruction(&[3; 9], metadata()),
+            CommonEventParser::parse_compute_budget_instruction(&[4, 0, 0, 0, 0], metadata()),
+            CommonEventParser::parse_associated_token_instruction(&[1], &accounts, metadata()),
+            CommonEventParser::parse_system_transfer_instruction(
+                &transfer,
+                &accounts,
+                metadata(),
+                &watchlist,
+            ),
+            // EventParser 通过 CommonEventParser 创建：交易汇总、失败交易、未识别指令、原始 Program data
+            Some(DexEvent::TransactionSummaryEvent(
+                CommonEventParser::new_transaction_summary_event(
+                    Signature::default(),
+                    0,
+                    0,
+                    0,
+                    None,
+                    0,
+                    None,
+                    None,
+                ),
+            )),
+            Some(DexEvent::SwapFailedEvent(CommonEventParser::new_swap_failed_event(
+                failure,
+                ProtocolType::Common,
+                metadata(),
+            ))),
+            Some(DexEvent::UnknownInstructionEvent(
+                CommonEventParser::new_unknown_instruction_event(
+                    ProtocolType::Common,
+                    &[0u8; 8],
+                    8,
+                    metadata(),
+                ),
+            )),
+            Some(DexEvent::RawProgramDataEvent(CommonEventParser::new_raw_program_data_event(
+                ProtocolType::Common,
+                accounts[0],
+                String::new(),
+                metadata(),
+            ))),
+            // SlotReorderBuffer 回滚 slot
+            Some(DexEvent::SlotRolledBackEvent(SlotRolledBackEvent::new(1, 2))),
+        ];
+        #[cfg(feature = "grpc")]
+        events.push(Some(CommonEventParser::generate_block_meta_event(Default::default(), 0)));
+
+        events
+            .into_iter()
+            .enumerate()
+            .map(|(i, event)| event.unwrap_or_else(|| panic!("common event #{i} was not parsed")))
+            .collect()
+    }
+
+    /// 关闭 feature 的协议声明的事件类型，以及依赖关闭的 feature 的通用事件类型
+    fn disabled_event_types() -> HashSet<EventType> {
+        #[allow(unused_mut)]
+        let mut disabled: HashSet<EventType> = ALL_PROTOCOLS
+            .iter()
+            .filter(|protocol| !protocol.is_enabled())
+            .flat_map(|protocol| protocol.capabilities())
+            .flat_map(|capability| capability.event_types.iter().cloned())
+            .collect();
+        // BlockMeta 只来自 gRPC 区块元数据
+        #[cfg(not(feature = "grpc"))]
+        disabled.insert(EventType::BlockMeta);
+        disabled
+    }
+
+    /// 没有解析器产出的事件类型，接入解析器后需要从这里移除
+    const UNREACHABLE_EVENT_TYPES: &[EventType] = &[
+        // 默认值占位，不对应任何事件
+        EventType::Unknown,
+        // 尚未实现 Bonk VestingRecord 账户解析，也没有对应的 DexEvent 变体
+        EventType::AccountBonkVestingRecord,
+    ];
+
+    /// 每个 DexEvent 变体及其 metadata 中可能出现的事件类型
+    ///
+    /// 生成的 match 没有通配分支，新增变体而未在这里登记时编译失败
+    macro_rules! dex_event_types {
+        ($($variant:ident => [$($event_type:ident),+ $(,)?]),+ $(,)?) => {
+            fn dex_event_types(event: &DexEvent) -> &'static [EventType] {
+                match event {
+                    $(DexEvent::$variant(_) => &[$(EventType::$event_type),+],)+
+                }
+            }
+
+            fn all_dex_events() -> Vec<(&'static str, DexEvent)> {
+                vec![$((stringify!($variant), DexEvent::$variant(Default::default()))),+]
+            }
+        };
+    }
+
+    dex_event_types! {
+        PancakeSwapSwapEvent => [PancakeSwapSwap],
+        PancakeSwapSwapV2Event => [PancakeSwapSwapV2],
+        PancakeSwapPoolStateAccountEvent => [AccountPancakeSwapPoolState],
+        PancakeSwapTickArrayStateAccountEvent => [AccountPancakeSwapTickArrayState],
+        PancakeSwapTickArrayBitmapExtensionAccountEvent => [
+            AccountPancakeSwapTickArrayBitmapExtension,
+        ],
+        BonkTradeEvent => [BonkBuyExactIn, BonkBuyExactOut, BonkSellExactIn, BonkSellExactOut],
+        BonkPoolCreateEvent => [BonkInitialize, BonkInitializeV2, BonkInitializeWithToken2022],
+        BonkMigrateToAmmEvent => [BonkMigrateToAmm],
+        BonkMigrateToCpswapEvent => [BonkMigrateToCpswap],
+        BonkClaimVestedTokenEvent => [BonkClaimVestedToken],
+        BonkClaimPlatformFeeEvent => [BonkClaimPlatformFee],
+        BonkPoolStateAccountEvent => [AccountBonkPoolState],
+        BonkGlobalConfigAccountEvent => [AccountBonkGlobalConfig],
+        BonkPlatformConfigAccountEvent => [AccountBonkPlatformConfig],
+        PumpFunCreateTokenEvent => [PumpFunCreateToken],
+        PumpFunCreateV2TokenEvent => [PumpFunCreateV2Token],
+        PumpFunTradeEvent => [PumpFunBuy, PumpFunSell],
+        PumpFunMigrateEvent => [PumpFunMigrate],
+        PumpFunClaimCreatorFeeEvent => [PumpFunClaimCreatorFee],
+        PumpFunBondingCurveAccountEvent => [AccountPumpFunBondingCurve],
+        PumpFunGlobalAccountEvent => [AccountPumpFunGlobal],
+        PumpSwapBuyEvent => [PumpSwapBuy],
+        PumpSwapBuyExactQuoteInEvent => [PumpSwapBuyExactQuoteIn],
+        PumpSwapSellEvent => [PumpSwapSell],
+        PumpSwapCreatePoolEvent => [PumpSwapCreatePool],
+        PumpSwapDepositEvent => [PumpSwapDeposit],
+        PumpSwapWithdrawEvent => [PumpSwapWithdraw],
+        PumpSwapGlobalConfigAccountEvent => [AccountPumpSwapGlobalConfig],
+        PumpSwapPoolAccountEvent => [AccountPumpSwapPool],
+        RaydiumAmmV4SwapEvent => [RaydiumAmmV4SwapBaseIn, RaydiumAmmV4SwapBaseOut],
+        RaydiumAmmV4DepositEvent => [RaydiumAmmV4Deposit],
+        RaydiumAmmV4WithdrawEvent => [RaydiumAmmV4Withdraw],
+        RaydiumAmmV4WithdrawPnlEvent => [RaydiumAmmV4WithdrawPnl],
+        RaydiumAmmV4Initialize2Event => [RaydiumAmmV4Initialize2],
+        RaydiumAmmV4AmmInfoAccountEvent => [AccountRaydiumAmmV4AmmInfo],
+        RaydiumClmmSwapEvent => [RaydiumClmmSwap],
+        RaydiumClmmSwapV2Event => [RaydiumClmmSwapV2],
+        RaydiumClmmSwapRouterBaseInEvent => [RaydiumClmmSwapRouterBaseIn],
+        RaydiumClmmClosePositionEvent => [RaydiumClmmClosePosition],
+        RaydiumClmmIncreaseLiquidityV2Event => [RaydiumClmmIncreaseLiquidityV2],
+        RaydiumClmmDecreaseLiquidityV2Event => [RaydiumClmmDecreaseLiquidityV2],
+        RaydiumClmmCreatePoolEvent => [RaydiumClmmCreatePool],
+        RaydiumClmmOpenPositionWithToken22NftEvent => [RaydiumClmmOpenPositionWithToken22Nft],
+        RaydiumClmmOpenPositionV2Event => [RaydiumClmmOpenPositionV2],
+        RaydiumClmmAmmConfigAccountEvent => [AccountRaydiumClmmAmmConfig],
+        RaydiumClmmPoolStateAccountEvent => [AccountRaydiumClmmPoolState],
+        RaydiumClmmTickArrayStateAccountEvent => [AccountRaydiumClmmTickArrayState],
+        RaydiumClmmTickArrayBitmapExtensionAccountEvent => [
+            AccountRaydiumClmmTickArrayBitmapExtension,
+        ],
+        RaydiumCpmmSwapEvent => [RaydiumCpmmSwapBaseInput, RaydiumCpmmSwapBaseOutput],
+        RaydiumCpmmDepositEvent => [RaydiumCpmmDeposit],
+        RaydiumCpmmWithdrawEvent => [RaydiumCpmmWithdraw],
+        RaydiumCpmmInitializeEvent => [RaydiumCpmmInitialize],
+        RaydiumCpmmAmmConfigAccountEvent => [AccountRaydiumCpmmAmmConfig],
+        RaydiumCpmmPoolStateAccountEvent => [AccountRaydiumCpmmPoolState],
+        MeteoraDammV2SwapEvent => [MeteoraDammV2Swap],
+        MeteoraDammV2Swap2Event => [MeteoraDammV2Swap2],
+        MeteoraDammV2InitializePoolEvent => [MeteoraDammV2InitializePool],
+        MeteoraDammV2InitializeCustomizablePoolEvent => [MeteoraDammV2InitializeCustomizablePool],
+        MeteoraDammV2InitializePoolWithDynamicConfigEvent => [
+            MeteoraDammV2InitializePoolWithDynamicConfig,
+        ],
+        MeteoraDammV2ClaimFeeEvent => [
+            MeteoraDammV2ClaimPositionFee,
+            MeteoraDammV2ClaimPartnerFee,
+            MeteoraDammV2ClaimProtocolFee,
+        ],
+        MeteoraDlmmSwapEvent => [MeteoraDlmmSwap],
+        MeteoraDlmmSwap2Event => [MeteoraDlmmSwap2],
+        MeteoraDlmmInitializeLbPairEvent => [MeteoraDlmmInitializeLbPair],
+        MeteoraDlmmLbPairAccountEvent => [AccountMeteoraDlmmLbPair],
+        MeteoraDlmmBinArrayAccountEvent => [AccountMeteoraDlmmBinArray],
+        MeteoraDlmmBinArrayBitmapExtensionAccountEvent => [
+            AccountMeteoraDlmmBinArrayBitmapExtension,
+        ],
+        WhirlpoolSwapEvent => [WhirlpoolSwap],
+        WhirlpoolSwapV2Event => [WhirlpoolSwapV2],
+        WhirlpoolAccountEvent => [AccountWhirlpool],
+        WhirlpoolTickArrayAccountEvent => [AccountWhirlpoolTickArray],
+        TokenAccountEvent => [TokenAccount],
+        NonceAccountEvent => [NonceAccount],
+        TokenInfoEvent => [TokenAccount],
+        BlockMetaEvent => [BlockMeta],
+        SetComputeUnitLimitEvent => [SetComputeUnitLimit],
+        SetComputeUnitPriceEvent => [SetComputeUnitPrice],
+        SetLoadedAccountsDataSizeLimitEvent => [SetLoadedAccountsDataSizeLimit],
+        AtaCreateEvent => [AtaCreate],
+        SolTransferEvent => [SolTransfer],
+        TransactionSummaryEvent => [TransactionSummary],
+        SlotRolledBackEvent => [SlotRolledBack],
+        SwapFailedEvent => [SwapFailed],
+        UnknownInstructionEvent => [UnknownInstruction],
+        RawProgramDataEvent => [RawProgramData],
+    }
+
+    #[test]
+    fn test_every_protocol_reports_capabilities() {
+        for protocol in ALL_PROTOCOLS {
+            let capabilities = protocol.capabilities();
+            assert!(!capabilities.is_empty(), "{protocol} has no capabilities");
+            for capability in capabilities {
+                assert!(!capability.event_types.is_empty(), "{protocol} {capability:?}");
+                let len = capability.discriminator.len();
+                match capability.kind {
+                    CapabilityKind::Instruction => assert_eq!(len, protocol.discriminator_len()),
+                    CapabilityKind::InnerInstruction => {
+                        assert_eq!(Some(len), protocol.inner_discriminator_len())
+                    }
+                    // Raydium AMM V4 账户没有 discriminator（空前缀），其余为 8 字节 Anchor discriminator
+                    CapabilityKind::Account => {
+                        assert_eq!(len, if protocol == &Protocol::RaydiumAmmV4 { 0 } else { 8 })
+                    }
+                }
+            }
+        }
+
+        for capability in EventParser::capabilities() {
+            assert!(capability.protocol.is_enabled());
+            assert_eq!(capability.program_id, capability.protocol.program_id());
+            assert_eq!(capability.events, capability.protocol.capabilities());
+        }
+    }
+
+    #[test]
+    fn test_capabilities_dispatch_to_parsers() {
+        for (protocol, capability, event) in dispatched_capabilities() {
+            let Some(event) = event else {
+                panic!("{protocol} {capability:?} falls through the dispatcher");
+            };
+            // inner instruction 事件沿用传入的 metadata，事件类型在合并时确定
+            if capability.kind != CapabilityKind::InnerInstruction {
+                let event_type = &event.metadata().event_type;
+                assert!(
+                    capability.event_types.contains(event_type),
+                    "{protocol} {capability:?} produced {event_type}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_dex_event_variant_is_reachable() {
+        // 关闭部分协议的 feature 时只检查已启用协议和通用路径的事件类型
+        let disabled = disabled_event_types();
+
+        // dispatcher 实际解析出的指令事件与账户事件；inner instruction 事件只合并到指令事件中
+        let reachable: HashSet<EventType> = dispatched_capabilities()
+            .into_iter()
+            .filter(|(_, capability, _)| capability.kind != CapabilityKind::InnerInstruction)
+            .filter_map(|(_, _, event)| event)
+            .chain(common_events())
+            .map(|event| event.metadata().event_type.clone())
+            .collect();
+
+        let mut claimed = HashSet::new();
+        for (variant, event) in all_dex_events() {
+            for event_type in dex_event_types(&event) {
+                if disabled.contains(event_type) {
+                    assert!(!reachable.contains(event_type), "{event_type} is disabled");
+                    continue;
+                }
+                assert!(
+                    reachable.contains(event_type),
+                    "DexEvent::{variant} ({event_type}) is not produced by any parser"
+                );
+                claimed.insert(event_type.clone());
+            }
+        }
+
+        for event_type in ALL_EVENT_TYPES {
+            if disabled.contains(event_type) {
+                continue;
+            }
+            if UNREACHABLE_EVENT_TYPES.contains(event_type) {
+                assert!(!reachable.contains(event_type), "{event_type} is reachable now");
+                assert!(!claimed.contains(event_type), "{event_type} has a DexEvent variant now");
+            } else {
+                assert!(
+                    reachable.contains(event_type),
+                    "{event_type} is not produced by any parser"
+                );
+                assert!(claimed.contains(event_type), "{event_type} has no DexEvent variant");
+            }
+        }
+    }
+}
@@ -1,18 +1,31 @@
+#[cfg(feature = "grpc")]
 use crate::streaming::event_parser::common::high_performance_clock::elapsed_micros_since;
 use crate::streaming::event_parser::common::types::{EventType, ProtocolType};
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, ProgramFailure};
+use crate::streaming::event_parser::core::sol_transfer_watchlist::SolTransferWatchlist;
 use crate::streaming::event_parser::core::traits::DexEvent;
+#[cfg(feature = "grpc")]
 use crate::streaming::event_parser::protocols::block::block_meta_event::BlockMetaEvent;
+#[cfg(feature = "grpc")]
+use crate::streaming::grpc::BlockMetaPretty;
 use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
-use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
 
 // Compute Budget Program ID
 pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("ComputeBudget111111111111111111111111111111");
 
+// Associated Token Account Program ID
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("ATokenGPvbdGVxr1b2hQ2Kc4bnfXixjrJaUtfJfWWRo");
+
+// System Program ID
+pub const SYSTEM_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("11111111111111111111111111111111");
+
 /// SetComputeUnitLimit 事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct SetComputeUnitLimitEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -22,6 +35,7 @@ pub struct SetComputeUnitLimitEvent {
 
 /// SetComputeUnitPrice 事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct SetComputeUnitPriceEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -29,18 +43,294 @@ pub struct SetComputeUnitPriceEvent {
     pub micro_lamports: u64,
 }
 
+/// SetLoadedAccountsDataSizeLimit 事件
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
+pub struct SetLoadedAccountsDataSizeLimitEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    /// 交易可加载账户数据的总字节数上限
+    pub bytes: u32,
+}
+
+/// Associated Token Account 创建事件（Create / CreateIdempotent 指令）
+///
+/// CreateIdempotent 在 ATA 已存在时不会创建新账户，`idempotent` 为 true 时
+/// 不能单凭该事件判断账户是新建的。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AtaCreateEvent {
+    pub metadata: EventMetadata,
+    pub ata: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    /// 支付租金的账户
+    pub payer: Pubkey,
+    pub token_program: Pubkey,
+    pub idempotent: bool,
+}
+
+/// SOL 转账事件（外层 System Program Transfer 指令）
+///
+/// 只在客户端的 `SolTransferWatchlist` 开启且 `from` 或 `to` 被关注时发出，inner instruction
+/// 中的转账（例如协议 CPI 转出的 SOL）不会发出该事件。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SolTransferEvent {
+    pub metadata: EventMetadata,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub lamports: u64,
+}
+
+/// 失败交易中协议指令的失败原因，从 log_messages 中提取
+///
+/// `metadata.program_id` / `metadata.protocol` 为调用栈中离失败点最近的已订阅协议程序，
+/// `failed_program` 为实际失败的最内层程序（例如余额不足时的 Token 程序）。
+/// 只在解析失败交易（`meta.err` 非空）、带有日志且失败的指令被解析为 swap 时发出；
+/// 失败交易的指令没有生效，其中的指令事件（包括 swap）都不会发出。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SwapFailedEvent {
+    pub metadata: EventMetadata,
+    pub failed_program: Pubkey,
+    /// `Program <id> failed: <reason>` 中的原因，例如 `custom program error: 0x1771`
+    pub error: String,
+    /// 失败程序在失败前输出的最后一条 `Program log:`，通常是可读的错误信息
+    pub error_log: Option<String>,
+}
+
+/// 已订阅协议程序中解析器不认识的指令
+///
+/// 只在开启 `ParseOptions::emit_unknown` 时发出，discriminator 不属于该协议已解析的任何指令、
+/// 也不是 emit_cpi 事件时视为未知，通常意味着协议升级新增了指令。
+/// `metadata.protocol` 为匹配到的协议。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnknownInstructionEvent {
+    pub metadata: EventMetadata,
+    pub program_id: Pubkey,
+    /// 按协议 discriminator 长度截取的指令数据前缀
+    pub discriminator: Vec<u8>,
+    /// 指令数据总长度（含 discriminator）
+    pub data_len: usize,
+}
+
+/// 已订阅协议输出的、解析器无法解码的 `Program data:` 日志
+///
+/// 只在开启 `ParseOptions::emit_raw_program_data` 时发出，在交易的其他事件之后按日志顺序发出。
+/// 前 8 字节不是该协议任何已解析事件的 discriminator 时视为无法解码，
+/// 可在 crate 支持新事件之前自行解码。`metadata.protocol` 为匹配到的协议，
+/// `outer_index` / `inner_index` 为输出该日志的调用。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RawProgramDataEvent {
+    pub metadata: EventMetadata,
+    pub program_id: Pubkey,
+    /// 日志中 `Program data: ` 之后的 base64 内容
+    pub base64: String,
+}
+
+/// 交易级汇总事件，在一笔交易的全部事件回调之后发出
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransactionSummaryEvent {
+    pub metadata: EventMetadata,
+    pub signature: Signature,
+    pub slot: u64,
+    /// 本交易发出的事件数量（不含汇总事件本身）
+    pub num_events: u64,
+    /// 按首次出现顺序去重的协议列表，不含 Common
+    pub protocols_touched: Vec<ProtocolType>,
+    /// 所有 SOL/WSOL 一侧成交额之和 (lamports)
+    pub total_sol_volume: u64,
+    /// 交易手续费 (lamports)
+    pub fee: u64,
+    pub compute_units: Option<u64>,
+    /// SetComputeUnitPrice 指令设置的 CU 价格 (micro-lamports)，未设置时为 None
+    pub compute_unit_price: Option<u64>,
+    /// 按实际消耗 CU 折算的优先费 (lamports)：`compute_unit_price * compute_units / 1_000_000`
+    ///
+    /// 运行时按请求的 CU 上限收取优先费，实际扣除的金额已包含在 `fee` 中；
+    /// 该字段衡量实际用到的计算量对应的优先费，缺少价格或消耗 CU 时为 None
+    pub priority_fee_lamports: Option<u64>,
+}
+
+impl TransactionSummaryEvent {
+    /// 按 CU 价格 (micro-lamports) 与 CU 数量计算优先费 (lamports)，向下取整
+    pub fn priority_fee_lamports(compute_unit_price: u64, compute_units: u64) -> u64 {
+        let micro_lamports = compute_unit_price as u128 * compute_units as u128;
+        (micro_lamports / 1_000_000).min(u64::MAX as u128) as u64
+    }
+
+    /// 累加一个事件到汇总
+    pub fn record(&mut self, event: &DexEvent) {
+        self.num_events += 1;
+        let protocol = &event.metadata().protocol;
+        if *protocol != ProtocolType::Common && !self.protocols_touched.contains(protocol) {
+            self.protocols_touched.push(protocol.clone());
+        }
+        if let Some(volume) = event.sol_volume_lamports() {
+            self.total_sol_volume = self.total_sol_volume.saturating_add(volume);
+        }
+    }
+}
+
 pub struct CommonEventParser {}
 
 impl CommonEventParser {
-    pub fn generate_block_meta_event(
+    #[cfg(feature = "grpc")]
+    pub fn generate_block_meta_event(block_meta: BlockMetaPretty, block_time_ms: i64) -> DexEvent {
+        let recv_us = block_meta.recv_us;
+        let mut block_meta_event =
+            BlockMetaEvent::new(block_meta.slot, block_meta.block_hash, block_time_ms, recv_us);
+        block_meta_event.parent_slot = block_meta.parent_slot;
+        block_meta_event.parent_block_hash = block_meta.parent_block_hash;
+        block_meta_event.block_height = block_meta.block_height;
+        block_meta_event.executed_transaction_count = block_meta.executed_transaction_count;
+        block_meta_event.entries_count = block_meta.entries_count;
+        block_meta_event.metadata.handle_us = elapsed_micros_since(recv_us);
+        DexEvent::BlockMetaEvent(block_meta_event)
+    }
+
+    /// 创建空的交易汇总事件，事件数量等统计通过 `record` 累加
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_transaction_summary_event(
+        signature: Signature,
         slot: u64,
-        block_hash: String,
         block_time_ms: i64,
         recv_us: i64,
-    ) -> DexEvent {
-        let mut block_meta_event = BlockMetaEvent::new(slot, block_hash, block_time_ms, recv_us);
-        block_meta_event.metadata.handle_us = elapsed_micros_since(recv_us);
-        DexEvent::BlockMetaEvent(block_meta_event)
+        transaction_index: Option<u64>,
+        fee: u64,
+        compute_units: Option<u64>,
+        compute_unit_price: Option<u64>,
+    ) -> TransactionSummaryEvent {
+        let metadata = EventMetadata::new(
+            signature,
+            slot,
+            block_time_ms / 1000,
+            block_time_ms,
+            ProtocolType::Common,
+            EventType::TransactionSummary,
+            Pubkey::default(),
+            0,
+            None,
+            recv_us,
+            transaction_index,
+        );
+        TransactionSummaryEvent {
+            metadata,
+            signature,
+            slot,
+            fee,
+            compute_units,
+            compute_unit_price,
+            priority_fee_lamports: compute_unit_price
+                .zip(compute_units)
+                .map(|(price, units)| TransactionSummaryEvent::priority_fee_lamports(price, units)),
+            ..Default::default()
+        }
+    }
+
+    /// 根据日志中的失败信息创建 SwapFailedEvent，`metadata.program_id` 由调用方设置为协议程序
+    pub fn new_swap_failed_event(
+        failure: ProgramFailure,
+        protocol: ProtocolType,
+        mut metadata: EventMetadata,
+    ) -> SwapFailedEvent {
+        metadata.protocol = protocol;
+        metadata.event_type = EventType::SwapFailed;
+        metadata.outer_index = failure.outer_index as i64;
+        metadata.inner_index = None;
+        SwapFailedEvent {
+            metadata,
+            failed_program: failure.program_id,
+            error: failure.error,
+            error_log: failure.error_log,
+        }
+    }
+
+    /// 创建 UnknownInstructionEvent，`discriminator_len` 为协议 discriminator 长度
+    pub fn new_unknown_instruction_event(
+        protocol: ProtocolType,
+        instruction_data: &[u8],
+        discriminator_len: usize,
+        mut metadata: EventMetadata,
+    ) -> UnknownInstructionEvent {
+        metadata.protocol = protocol;
+        metadata.event_type = EventType::UnknownInstruction;
+        let discriminator_len = discriminator_len.min(instruction_data.len());
+        UnknownInstructionEvent {
+            program_id: metadata.program_id,
+            metadata,
+            discriminator: instruction_data[..discriminator_len].to_vec(),
+            data_len: instruction_data.len(),
+        }
+    }
+
+    /// 创建 RawProgramDataEvent，`metadata` 中的调用位置由调用方设置
+    pub fn new_raw_program_data_event(
+        protocol: ProtocolType,
+        program_id: Pubkey,
+        base64: String,
+        mut metadata: EventMetadata,
+    ) -> RawProgramDataEvent {
+        metadata.protocol = protocol;
+        metadata.event_type = EventType::RawProgramData;
+        metadata.program_id = program_id;
+        RawProgramDataEvent { metadata, program_id, base64 }
+    }
+
+    /// 解析 Associated Token Account 指令，只处理 Create / CreateIdempotent
+    pub fn parse_associated_token_instruction(
+        instruction_data: &[u8],
+        accounts: &[Pubkey],
+        mut metadata: EventMetadata,
+    ) -> Option<DexEvent> {
+        // 空数据为旧版 Create 指令，1 为 CreateIdempotent，2 为 RecoverNested
+        let idempotent = match instruction_data.first() {
+            None | Some(0) => false,
+            Some(1) => true,
+            _ => return None,
+        };
+        if accounts.len() < 6 {
+            return None;
+        }
+
+        metadata.protocol = ProtocolType::Common;
+        metadata.event_type = EventType::AtaCreate;
+        Some(DexEvent::AtaCreateEvent(AtaCreateEvent {
+            metadata,
+            payer: accounts[0],
+            ata: accounts[1],
+            owner: accounts[2],
+            mint: accounts[3],
+            token_program: accounts[5],
+            idempotent,
+        }))
+    }
+
+    /// 解析 System Program Transfer 指令 `[2u32, lamports: u64]`，账户为 (from, to)
+    ///
+    /// 其他 System 指令、以及 `from` / `to` 都不在 `watchlist` 中的转账返回 None
+    pub fn parse_system_transfer_instruction(
+        instruction_data: &[u8],
+        accounts: &[Pubkey],
+        mut metadata: EventMetadata,
+        watchlist: &SolTransferWatchlist,
+    ) -> Option<DexEvent> {
+        if instruction_data.get(..4)? != 2u32.to_le_bytes() || accounts.len() < 2 {
+            return None;
+        }
+        let lamports = u64::from_le_bytes(instruction_data.get(4..12)?.try_into().ok()?);
+        let (from, to) = (accounts[0], accounts[1]);
+        if !watchlist.matches(&from, &to) {
+            return None;
+        }
+
+        metadata.protocol = ProtocolType::Common;
+        metadata.event_type = EventType::SolTransfer;
+        Some(DexEvent::SolTransferEvent(SolTransferEvent { metadata, from, to, lamports }))
     }
 
     /// 解析 Compute Budget 指令
@@ -69,15 +359,90 @@ impl CommonEventParser {
             }
             // SetComputeUnitPrice: discriminator = 3
             3 => {
-                if instruction_data.len() < 9 {
-                    return None;
-                }
-                let micro_lamports = u64::from_le_bytes(instruction_data[1..9].try_into().ok()?);
+                let micro_lamports = Self::parse_compute_unit_price(instruction_data)?;
                 metadata.event_type = EventType::SetComputeUnitPrice;
                 let event = SetComputeUnitPriceEvent { metadata, micro_lamports };
                 Some(DexEvent::SetComputeUnitPriceEvent(event))
             }
+            // SetLoadedAccountsDataSizeLimit: discriminator = 4
+            4 => {
+                if instruction_data.len() < 5 {
+                    return None;
+                }
+                let bytes = u32::from_le_bytes(instruction_data[1..5].try_into().ok()?);
+                metadata.event_type = EventType::SetLoadedAccountsDataSizeLimit;
+                let event = SetLoadedAccountsDataSizeLimitEvent { metadata, bytes };
+                Some(DexEvent::SetLoadedAccountsDataSizeLimitEvent(event))
+            }
+            _ => None,
+        }
+    }
+
+    /// 解析 SetComputeUnitPrice 指令数据中的 CU 价格 (micro-lamports)，其他指令返回 None
+    pub fn parse_compute_unit_price(instruction_data: &[u8]) -> Option<u64> {
+        match instruction_data {
+            [3, price @ ..] if price.len() >= 8 => {
+                Some(u64::from_le_bytes(price[..8].try_into().ok()?))
+            }
             _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_priority_fee_from_price_and_consumed_units() {
+        let mut data = vec![3];
+        data.extend_from_slice(&250_000u64.to_le_bytes());
+        assert_eq!(CommonEventParser::parse_compute_unit_price(&data), Some(250_000));
+        assert_eq!(CommonEventParser::parse_compute_unit_price(&data[..8]), None);
+        assert_eq!(CommonEventParser::parse_compute_unit_price(&[2, 0, 0, 0, 0]), None);
+
+        let summary = CommonEventParser::new_transaction_summary_event(
+            Signature::default(),
+            1,
+            0,
+            0,
+            None,
+            5_000,
+            Some(120_001),
+            Some(250_000),
+        );
+        // 250_000 * 120_001 / 1_000_000 = 30_000.25，向下取整
+        assert_eq!(summary.priority_fee_lamports, Some(30_000));
+
+        let summary = CommonEventParser::new_transaction_summary_event(
+            Signature::default(),
+            1,
+            0,
+            0,
+            None,
+            5_000,
+            Some(120_001),
+            None,
+        );
+        assert_eq!(summary.priority_fee_lamports, None);
+        assert_eq!(TransactionSummaryEvent::priority_fee_lamports(u64::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_parse_loaded_accounts_data_size_limit() {
+        let mut data = vec![4];
+        data.extend_from_slice(&(64 * 1024u32).to_le_bytes());
+        let event =
+            CommonEventParser::parse_compute_budget_instruction(&data, EventMetadata::default())
+                .unwrap();
+        let DexEvent::SetLoadedAccountsDataSizeLimitEvent(e) = &event else { panic!("{event:?}") };
+        assert_eq!(e.bytes, 65_536);
+        assert_eq!(e.metadata.event_type, EventType::SetLoadedAccountsDataSizeLimit);
+        assert_eq!(e.metadata.protocol, ProtocolType::Common);
+        assert!(CommonEventParser::parse_compute_budget_instruction(
+            &data[..4],
+            EventMetadata::default()
+        )
+        .is_none());
+    }
+}
@@ -7,19 +7,62 @@
 //! - **灵活性**: 调用方可以选择是否合并，或自定义合并逻辑
 //! - **可测试性**: 每个函数都可以独立测试
 
+use crate::streaming::common::MetricsManager;
+#[cfg(feature = "protocol-bonk")]
+use crate::streaming::event_parser::protocols::bonk::parser as bonk;
+#[cfg(feature = "protocol-meteora-damm-v2")]
+use crate::streaming::event_parser::protocols::meteora_damm_v2::parser as meteora_damm_v2;
+#[cfg(feature = "protocol-meteora-dlmm")]
+use crate::streaming::event_parser::protocols::meteora_dlmm::parser as meteora_dlmm;
+#[cfg(feature = "protocol-pancakeswap")]
+use crate::streaming::event_parser::protocols::pancakeswap::parser as pancakeswap;
+#[cfg(feature = "protocol-pumpfun")]
+use crate::streaming::event_parser::protocols::pumpfun::parser as pumpfun;
+#[cfg(feature = "protocol-pumpswap")]
+use crate::streaming::event_parser::protocols::pumpswap::parser as pumpswap;
+#[cfg(feature = "protocol-raydium-amm-v4")]
+use crate::streaming::event_parser::protocols::raydium_amm_v4::parser as raydium_amm_v4;
+#[cfg(feature = "protocol-raydium-clmm")]
+use crate::streaming::event_parser::protocols::raydium_clmm::parser as raydium_clmm;
+#[cfg(feature = "protocol-raydium-cpmm")]
+use crate::streaming::event_parser::protocols::raydium_cpmm::parser as raydium_cpmm;
+#[cfg(feature = "protocol-whirlpool")]
+use crate::streaming::event_parser::protocols::whirlpool::parser as whirlpool;
 use crate::streaming::event_parser::{
     common::EventMetadata,
-    core::common_event_parser::{CommonEventParser, COMPUTE_BUDGET_PROGRAM_ID},
+    core::common_event_parser::{
+        CommonEventParser, ASSOCIATED_TOKEN_PROGRAM_ID, COMPUTE_BUDGET_PROGRAM_ID,
+        SYSTEM_PROGRAM_ID,
+    },
+    core::sol_transfer_watchlist::SolTransferWatchlist,
     protocols::{
-        bonk::parser as bonk, meteora_damm_v2::parser as meteora_damm_v2,
-        meteora_dlmm::parser as meteora_dlmm, pancakeswap::parser as pancakeswap,
-        pumpfun::parser as pumpfun, pumpswap::parser as pumpswap,
-        raydium_amm_v4::parser as raydium_amm_v4, raydium_clmm::parser as raydium_clmm,
-        raydium_cpmm::parser as raydium_cpmm, whirlpool::parser as whirlpool,
+        BONK_PROGRAM_ID, METEORA_DAMM_V2_PROGRAM_ID, METEORA_DLMM_PROGRAM_ID,
+        PANCAKESWAP_PROGRAM_ID, PUMPFUN_PROGRAM_ID, PUMPSWAP_PROGRAM_ID, RAYDIUM_AMM_V4_PROGRAM_ID,
+        RAYDIUM_CLMM_PROGRAM_ID, RAYDIUM_CPMM_PROGRAM_ID, WHIRLPOOL_PROGRAM_ID,
     },
     DexEvent, Protocol,
 };
+use dashmap::{mapref::entry::Entry, DashMap};
+use once_cell::sync::Lazy;
 use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 运行时注册的程序 ID（例如 fork 部署的同构程序）-> 协议
+static REGISTERED_PROGRAM_IDS: Lazy<DashMap<Pubkey, Protocol>> = Lazy::new(DashMap::new);
+
+/// 是否注册过程序 ID，未注册时匹配不查询 `REGISTERED_PROGRAM_IDS`
+static HAS_REGISTERED_PROGRAM_IDS: AtomicBool = AtomicBool::new(false);
+
+/// 注册程序 ID 时与已有映射冲突
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ProgramIdConflict {
+    /// 程序 ID 是其他协议的内置程序 ID
+    #[error("program id {program_id} is the built-in program id of {existing:?}")]
+    BuiltIn { program_id: Pubkey, existing: Protocol },
+    /// 程序 ID 已注册给其他协议
+    #[error("program id {program_id} is already registered for {existing:?}")]
+    Registered { program_id: Pubkey, existing: Protocol },
+}
 
 /// 中心事件解析调度器
 ///
@@ -47,82 +90,83 @@ impl EventDispatcher {
         mut metadata: EventMetadata,
     ) -> Option<DexEvent> {
         // 根据协议类型设置 metadata.protocol
-        use crate::streaming::event_parser::common::ProtocolType;
-        metadata.protocol = match protocol {
-            Protocol::PancakeSwap => ProtocolType::PancakeSwap,
-            Protocol::PumpFun => ProtocolType::PumpFun,
-            Protocol::PumpSwap => ProtocolType::PumpSwap,
-            Protocol::Bonk => ProtocolType::Bonk,
-            Protocol::RaydiumCpmm => ProtocolType::RaydiumCpmm,
-            Protocol::RaydiumClmm => ProtocolType::RaydiumClmm,
-            Protocol::RaydiumAmmV4 => ProtocolType::RaydiumAmmV4,
-            Protocol::MeteoraDammV2 => ProtocolType::MeteoraDammV2,
-            Protocol::MeteoraDlmm => ProtocolType::MeteoraDlmm,
-            Protocol::Whirlpool => ProtocolType::Whirlpool,
-        };
+        metadata.protocol = (&protocol).into();
+        let protocol_type = metadata.protocol.clone();
 
-        match protocol {
+        MetricsManager::global().time_decode(&protocol_type, move || match protocol {
+            #[cfg(feature = "protocol-pancakeswap")]
             Protocol::PancakeSwap => pancakeswap::parse_pancakeswap_instruction_data(
                 instruction_discriminator,
                 instruction_data,
                 accounts,
                 metadata,
             ),
+            #[cfg(feature = "protocol-pumpfun")]
             Protocol::PumpFun => pumpfun::parse_pumpfun_instruction_data(
                 instruction_discriminator,
                 instruction_data,
                 accounts,
                 metadata,
             ),
+            #[cfg(feature = "protocol-pumpswap")]
             Protocol::PumpSwap => pumpswap::parse_pumpswap_instruction_data(
                 instruction_discriminator,
                 instruction_data,
                 accounts,
                 metadata,
             ),
+            #[cfg(feature = "protocol-bonk")]
             Protocol::Bonk => bonk::parse_bonk_instruction_data(
                 instruction_discriminator,
                 instruction_data,
                 accounts,
                 metadata,
             ),
+            #[cfg(feature = "protocol-raydium-cpmm")]
             Protocol::RaydiumCpmm => raydium_cpmm::parse_raydium_cpmm_instruction_data(
                 instruction_discriminator,
                 instruction_data,
                 accounts,
                 metadata,
             ),
+            #[cfg(feature = "protocol-raydium-clmm")]
             Protocol::RaydiumClmm => raydium_clmm::parse_raydium_clmm_instruction_data(
                 instruction_discriminator,
                 instruction_data,
                 accounts,
                 metadata,
             ),
+            #[cfg(feature = "protocol-raydium-amm-v4")]
             Protocol::RaydiumAmmV4 => raydium_amm_v4::parse_raydium_amm_v4_instruction_data(
                 instruction_discriminator,
                 instruction_data,
                 accounts,
                 metadata,
             ),
+            #[cfg(feature = "protocol-meteora-damm-v2")]
             Protocol::MeteoraDammV2 => meteora_damm_v2::parse_meteora_damm_v2_instruction_data(
                 instruction_discriminator,
                 instruction_data,
                 accounts,
                 metadata,
             ),
+            #[cfg(feature = "protocol-meteora-dlmm")]
             Protocol::MeteoraDlmm => meteora_dlmm::parse_meteora_dlmm_instruction_data(
                 instruction_discriminator,
                 instruction_data,
                 accounts,
                 metadata,
             ),
+            #[cfg(feature = "protocol-whirlpool")]
             Protocol::Whirlpool => whirlpool::parse_whirlpool_instruction_data(
                 instruction_discriminator,
                 instruction_data,
                 accounts,
                 metadata,
             ),
-        }
+            #[allow(unreachable_patterns)]
+            _ => None,
+        })
     }
 
     /// 解析 inner instruction 事件（只解析，不合并）
@@ -143,56 +187,53 @@ impl EventDispatcher {
         mut metadata: EventMetadata,
     ) -> Option<DexEvent> {
         // 根据协议类型设置 metadata.protocol
-        use crate::streaming::event_parser::common::ProtocolType;
-        metadata.protocol = match protocol {
-            Protocol::PancakeSwap => ProtocolType::PancakeSwap,
-            Protocol::PumpFun => ProtocolType::PumpFun,
-            Protocol::PumpSwap => ProtocolType::PumpSwap,
-            Protocol::Bonk => ProtocolType::Bonk,
-            Protocol::RaydiumCpmm => ProtocolType::RaydiumCpmm,
-            Protocol::RaydiumClmm => ProtocolType::RaydiumClmm,
-            Protocol::RaydiumAmmV4 => ProtocolType::RaydiumAmmV4,
-            Protocol::MeteoraDammV2 => ProtocolType::MeteoraDammV2,
-            Protocol::MeteoraDlmm => ProtocolType::MeteoraDlmm,
-            Protocol::Whirlpool => ProtocolType::Whirlpool,
-        };
+        metadata.protocol = (&protocol).into();
+        let protocol_type = metadata.protocol.clone();
 
-        match protocol {
+        MetricsManager::global().time_decode(&protocol_type, move || match protocol {
+            #[cfg(feature = "protocol-pancakeswap")]
             Protocol::PancakeSwap => pancakeswap::parse_pancakeswap_inner_instruction_data(
                 inner_instruction_discriminator,
                 inner_instruction_data,
                 metadata,
             ),
+            #[cfg(feature = "protocol-pumpfun")]
             Protocol::PumpFun => pumpfun::parse_pumpfun_inner_instruction_data(
                 inner_instruction_discriminator,
                 inner_instruction_data,
                 metadata,
             ),
+            #[cfg(feature = "protocol-pumpswap")]
             Protocol::PumpSwap => pumpswap::parse_pumpswap_inner_instruction_data(
                 inner_instruction_discriminator,
                 inner_instruction_data,
                 metadata,
             ),
+            #[cfg(feature = "protocol-bonk")]
             Protocol::Bonk => bonk::parse_bonk_inner_instruction_data(
                 inner_instruction_discriminator,
                 inner_instruction_data,
                 metadata,
             ),
+            #[cfg(feature = "protocol-raydium-cpmm")]
             Protocol::RaydiumCpmm => raydium_cpmm::parse_raydium_cpmm_inner_instruction_data(
                 inner_instruction_discriminator,
                 inner_instruction_data,
                 metadata,
             ),
+            #[cfg(feature = "protocol-raydium-clmm")]
             Protocol::RaydiumClmm => raydium_clmm::parse_raydium_clmm_inner_instruction_data(
                 inner_instruction_discriminator,
                 inner_instruction_data,
                 metadata,
             ),
+            #[cfg(feature = "protocol-raydium-amm-v4")]
             Protocol::RaydiumAmmV4 => raydium_amm_v4::parse_raydium_amm_v4_inner_instruction_data(
                 inner_instruction_discriminator,
                 inner_instruction_data,
                 metadata,
             ),
+            #[cfg(feature = "protocol-meteora-damm-v2")]
             Protocol::MeteoraDammV2 => {
                 meteora_damm_v2::parse_meteora_damm_v2_inner_instruction_data(
                     inner_instruction_discriminator,
@@ -200,41 +241,103 @@ impl EventDispatcher {
                     metadata,
                 )
             }
+            #[cfg(feature = "protocol-meteora-dlmm")]
             Protocol::MeteoraDlmm => meteora_dlmm::parse_meteora_dlmm_inner_instruction_data(
                 inner_instruction_discriminator,
                 inner_instruction_data,
                 metadata,
             ),
+            #[cfg(feature = "protocol-whirlpool")]
             Protocol::Whirlpool => whirlpool::parse_whirlpool_inner_instruction_data(
                 inner_instruction_discriminator,
                 inner_instruction_data,
                 metadata,
             ),
-        }
+            #[allow(unreachable_patterns)]
+            _ => None,
+        })
     }
 
     /// 通过 program_id 匹配协议类型
+    ///
+    /// 先匹配内置程序 ID，再匹配 [`Self::register_program_id`] 注册的程序 ID；
+    /// 未通过 cargo feature 启用的协议返回 `None`
     #[inline]
     pub fn match_protocol_by_program_id(program_id: &Pubkey) -> Option<Protocol> {
-        if program_id == &pancakeswap::PANCAKESWAP_PROGRAM_ID {
+        Self::builtin_protocol_by_program_id(program_id)
+            .or_else(|| Self::registered_protocol_by_program_id(program_id))
+            .filter(Protocol::is_enabled)
+    }
+
+    /// 把程序 ID 映射到协议，用于解析 fork 部署、指令和账户布局与原协议相同的程序
+    ///
+    /// 内置程序 ID 总是优先：程序 ID 是其他协议的内置 ID、或已注册给其他协议时返回
+    /// [`ProgramIdConflict`] 且不修改映射，重复注册相同的映射返回 `Ok`。
+    /// 注册只影响解析，订阅时仍需把程序 ID 加入 `TransactionFilter` / `AccountFilter`
+    pub fn register_program_id(
+        program_id: Pubkey,
+        protocol: Protocol,
+    ) -> Result<(), ProgramIdConflict> {
+        if let Some(existing) = Self::builtin_protocol_by_program_id(&program_id) {
+            return if existing == protocol {
+                Ok(())
+            } else {
+                Err(ProgramIdConflict::BuiltIn { program_id, existing })
+            };
+        }
+        match REGISTERED_PROGRAM_IDS.entry(program_id) {
+            Entry::Occupied(entry) if *entry.get() != protocol => {
+                Err(ProgramIdConflict::Registered { program_id, existing: entry.get().clone() })
+            }
+            Entry::Occupied(_) => Ok(()),
+            Entry::Vacant(entry) => {
+                entry.insert(protocol);
+                HAS_REGISTERED_PROGRAM_IDS.store(true, Ordering::Release);
+                Ok(())
+            }
+        }
+    }
+
+    /// 移除注册的程序 ID，返回原来映射的协议；内置程序 ID 不能移除
+    pub fn unregister_program_id(program_id: &Pubkey) -> Option<Protocol> {
+        REGISTERED_PROGRAM_IDS.remove(program_id).map(|(_, protocol)| protocol)
+    }
+
+    /// 所有注册的程序 ID 及其协议
+    pub fn registered_program_ids() -> Vec<(Pubkey, Protocol)> {
+        REGISTERED_PROGRAM_IDS.iter().map(|entry| (*entry.key(), entry.value().clone())).collect()
+    }
+
+    #[inline]
+    fn registered_protocol_by_program_id(program_id: &Pubkey) -> Option<Protocol> {
+        if !HAS_REGISTERED_PROGRAM_IDS.load(Ordering::Acquire) {
+            return None;
+        }
+        REGISTERED_PROGRAM_IDS.get(program_id).map(|entry| entry.value().clone())
+    }
+
+    /// 内置程序 ID 对应的协议，不考虑 cargo feature
+    #[inline]
+    fn builtin_protocol_by_program_id(program_id: &Pubkey) -> Option<Protocol> {
+        if program_id == &PANCAKESWAP_PROGRAM_ID {
             Some(Protocol::PancakeSwap)
-        } else if program_id == &pumpfun::PUMPFUN_PROGRAM_ID {
+        } else if program_id == &PUMPFUN_PROGRAM_ID {
             Some(Protocol::PumpFun)
-        } else if program_id == &pumpswap::PUMPSWAP_PROGRAM_ID {
+        } else if program_id == &PUMPSWAP_PROGRAM_ID {
             Some(Protocol::PumpSwap)
-        } else if program_id == &bonk::BONK_PROGRAM_ID {
+        } else if program_id == &BONK_PROGRAM_ID {
             Some(Protocol::Bonk)
-        } else if program_id == &raydium_cpmm::RAYDIUM_CPMM_PROGRAM_ID {
+        } else if program_id == &RAYDIUM_CPMM_PROGRAM_ID {
             Some(Protocol::RaydiumCpmm)
-        } else if program_id == &raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID {
+        } else if program_id == &RAYDIUM_CLMM_PROGRAM_ID {
             Some(Protocol::RaydiumClmm)
-        } else if program_id == &raydium_amm_v4::RAYDIUM_AMM_V4_PROGRAM_ID {
+        } else if program_id == &RAYDIUM_AMM_V4_PROGRAM_ID {
             Some(Protocol::RaydiumAmmV4)
-        } else if program_id == &meteora_damm_v2::METEORA_DAMM_V2_PROGRAM_ID {
+        } else if program_id == &METEORA_DAMM_V2_PROGRAM_ID {
             Some(Protocol::MeteoraDammV2)
-        } else if program_id == &meteora_dlmm::METEORA_DLMM_PROGRAM_ID {
+        } else if program_id == &METEORA_DLMM_PROGRAM_ID {
             Some(Protocol::MeteoraDlmm)
-        } else if program_id == &whirlpool::WHIRLPOOL_PROGRAM_ID {
+        } else if program_id == &WHIRLPOOL_PROGRAM_ID {
             Some(Protocol::Whirlpool)
         } else {
             None
@@ -247,6 +350,42 @@ impl EventDispatcher {
         program_id == &COMPUTE_BUDGET_PROGRAM_ID
     }
 
+    /// 检查是否为不属于任何协议、但总是解析的通用程序（Compute Budget / Associated Token Account）
+    #[inline]
+    pub fn is_common_program(program_id: &Pubkey) -> bool {
+        program_id == &COMPUTE_BUDGET_PROGRAM_ID || program_id == &ASSOCIATED_TOKEN_PROGRAM_ID
+    }
+
+    /// 解析通用程序指令，按 program_id 路由
+    ///
+    /// System Program 不属于 [`Self::is_common_program`]，只有调用方在关注 SOL 转账时才会传入，
+    /// 转账按 `sol_transfer_watchlist` 过滤
+    #[inline]
+    pub fn dispatch_common_instruction(
+        program_id: &Pubkey,
+        instruction_data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+        sol_transfer_watchlist: Option<&SolTransferWatchlist>,
+    ) -> Option<DexEvent> {
+        if program_id == &ASSOCIATED_TOKEN_PROGRAM_ID {
+            CommonEventParser::parse_associated_token_instruction(
+                instruction_data,
+                accounts,
+                metadata,
+            )
+        } else if program_id == &SYSTEM_PROGRAM_ID {
+            CommonEventParser::parse_system_transfer_instruction(
+                instruction_data,
+                accounts,
+                metadata,
+                sol_transfer_watchlist?,
+            )
+        } else {
+            Self::dispatch_compute_budget_instruction(instruction_data, metadata)
+        }
+    }
+
     /// 解析 Compute Budget 指令
     ///
     /// # 参数
@@ -267,16 +406,16 @@ impl EventDispatcher {
     #[inline]
     pub fn get_program_id(protocol: Protocol) -> Pubkey {
         match protocol {
-            Protocol::PancakeSwap => pancakeswap::PANCAKESWAP_PROGRAM_ID,
-            Protocol::PumpFun => pumpfun::PUMPFUN_PROGRAM_ID,
-            Protocol::PumpSwap => pumpswap::PUMPSWAP_PROGRAM_ID,
-            Protocol::Bonk => bonk::BONK_PROGRAM_ID,
-            Protocol::RaydiumCpmm => raydium_cpmm::RAYDIUM_CPMM_PROGRAM_ID,
-            Protocol::RaydiumClmm => raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID,
-            Protocol::RaydiumAmmV4 => raydium_amm_v4::RAYDIUM_AMM_V4_PROGRAM_ID,
-            Protocol::MeteoraDammV2 => meteora_damm_v2::METEORA_DAMM_V2_PROGRAM_ID,
-            Protocol::MeteoraDlmm => meteora_dlmm::METEORA_DLMM_PROGRAM_ID,
-            Protocol::Whirlpool => whirlpool::WHIRLPOOL_PROGRAM_ID,
+            Protocol::PancakeSwap => PANCAKESWAP_PROGRAM_ID,
+            Protocol::PumpFun => PUMPFUN_PROGRAM_ID,
+            Protocol::PumpSwap => PUMPSWAP_PROGRAM_ID,
+            Protocol::Bonk => BONK_PROGRAM_ID,
+            Protocol::RaydiumCpmm => RAYDIUM_CPMM_PROGRAM_ID,
+            Protocol::RaydiumClmm => RAYDIUM_CLMM_PROGRAM_ID,
+            Protocol::RaydiumAmmV4 => RAYDIUM_AMM_V4_PROGRAM_ID,
+            Protocol::MeteoraDammV2 => METEORA_DAMM_V2_PROGRAM_ID,
+            Protocol::MeteoraDlmm => METEORA_DLMM_PROGRAM_ID,
+            Protocol::Whirlpool => WHIRLPOOL_PROGRAM_ID,
         }
     }
 
@@ -300,54 +439,97 @@ impl EventDispatcher {
     pub fn dispatch_account(
         protocol: Protocol,
         discriminator: &[u8],
-        account: crate::streaming::grpc::AccountPretty,
+        account: crate::streaming::event_parser::common::AccountPretty,
         mut metadata: crate::streaming::event_parser::common::EventMetadata,
     ) -> Option<DexEvent> {
         // 根据协议类型设置 metadata.protocol
-        use crate::streaming::event_parser::common::ProtocolType;
-        metadata.protocol = match protocol {
-            Protocol::PancakeSwap => ProtocolType::PancakeSwap,
-            Protocol::PumpFun => ProtocolType::PumpFun,
-            Protocol::PumpSwap => ProtocolType::PumpSwap,
-            Protocol::Bonk => ProtocolType::Bonk,
-            Protocol::RaydiumCpmm => ProtocolType::RaydiumCpmm,
-            Protocol::RaydiumClmm => ProtocolType::RaydiumClmm,
-            Protocol::RaydiumAmmV4 => ProtocolType::RaydiumAmmV4,
-            Protocol::MeteoraDammV2 => ProtocolType::MeteoraDammV2,
-            Protocol::MeteoraDlmm => ProtocolType::MeteoraDlmm,
-            Protocol::Whirlpool => ProtocolType::Whirlpool,
-        };
+        metadata.protocol = (&protocol).into();
 
         match protocol {
+            #[cfg(feature = "protocol-pancakeswap")]
             Protocol::PancakeSwap => {
                 pancakeswap::parse_pancakeswap_account_data(discriminator, account, metadata)
             }
+            #[cfg(feature = "protocol-pumpfun")]
             Protocol::PumpFun => {
                 pumpfun::parse_pumpfun_account_data(discriminator, account, metadata)
             }
+            #[cfg(feature = "protocol-pumpswap")]
             Protocol::PumpSwap => {
                 pumpswap::parse_pumpswap_account_data(discriminator, account, metadata)
             }
+            #[cfg(feature = "protocol-bonk")]
             Protocol::Bonk => bonk::parse_bonk_account_data(discriminator, account, metadata),
+            #[cfg(feature = "protocol-raydium-cpmm")]
             Protocol::RaydiumCpmm => {
                 raydium_cpmm::parse_raydium_cpmm_account_data(discriminator, account, metadata)
             }
+            #[cfg(feature = "protocol-raydium-clmm")]
             Protocol::RaydiumClmm => {
                 raydium_clmm::parse_raydium_clmm_account_data(discriminator, account, metadata)
             }
+            #[cfg(feature = "protocol-raydium-amm-v4")]
             Protocol::RaydiumAmmV4 => {
                 raydium_amm_v4::parse_raydium_amm_v4_account_data(discriminator, account, metadata)
             }
+            #[cfg(feature = "protocol-meteora-damm-v2")]
             Protocol::MeteoraDammV2 => {
                 // Meteora DAMM 目前不需要解析账户数据，返回 None
                 None
             }
+            #[cfg(feature = "protocol-meteora-dlmm")]
             Protocol::MeteoraDlmm => {
                 meteora_dlmm::parse_meteora_dlmm_account_data(discriminator, account, metadata)
             }
+            #[cfg(feature = "protocol-whirlpool")]
             Protocol::Whirlpool => {
                 whirlpool::parse_whirlpool_account_data(discriminator, account, metadata)
             }
+            #[allow(unreachable_patterns)]
+            _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_program_id_cannot_shadow_builtin() {
+        let conflict = EventDispatcher::register_program_id(PUMPFUN_PROGRAM_ID, Protocol::PumpSwap);
+        assert_eq!(
+            conflict,
+            Err(ProgramIdConflict::BuiltIn {
+                program_id: PUMPFUN_PROGRAM_ID,
+                existing: Protocol::PumpFun,
+            })
+        );
+        assert!(EventDispatcher::register_program_id(PUMPFUN_PROGRAM_ID, Protocol::PumpFun).is_ok());
+        assert!(!EventDispatcher::registered_program_ids()
+            .iter()
+            .any(|(program_id, _)| *program_id == PUMPFUN_PROGRAM_ID));
+        assert_eq!(
+            EventDispatcher::match_protocol_by_program_id(&PUMPFUN_PROGRAM_ID),
+            Some(Protocol::PumpFun).filter(Protocol::is_enabled)
+        );
+
+        // fork 程序 ID：第一次注册生效，注册给其他协议被拒绝
+        let fork = Pubkey::new_unique();
+        assert_eq!(EventDispatcher::register_program_id(fork, Protocol::RaydiumCpmm), Ok(()));
+        assert_eq!(EventDispatcher::register_program_id(fork, Protocol::RaydiumCpmm), Ok(()));
+        assert_eq!(
+            EventDispatcher::register_program_id(fork, Protocol::Whirlpool),
+            Err(ProgramIdConflict::Registered {
+                program_id: fork,
+                existing: Protocol::RaydiumCpmm,
+            })
+        );
+        assert_eq!(
+            EventDispatcher::match_protocol_by_program_id(&fork),
+            Some(Protocol::RaydiumCpmm).filter(Protocol::is_enabled)
+        );
+        assert_eq!(EventDispatcher::unregister_program_id(&fork), Some(Protocol::RaydiumCpmm));
+        assert_eq!(EventDispatcher::match_protocol_by_program_id(&fork), None);
+    }
+}
@@ -1,31 +1,48 @@
+use crate::streaming::common::MetricsManager;
+#[cfg(feature = "grpc")]
+use crate::streaming::event_parser::core::common_event_parser::COMPUTE_BUDGET_PROGRAM_ID;
 use crate::streaming::event_parser::{
     common::{
-        build_program_data_index, build_swap_cu_index, filter::EventTypeFilter,
-        high_performance_clock::elapsed_micros_since, EventMetadata, ProgramDataIndex, SwapCuIndex,
-        SwapCuParseConfig,
+        build_program_data_index, build_swap_cu_index, decode_token_transfer,
+        extract_program_failure, filter::EventTypeFilter,
+        high_performance_clock::elapsed_micros_since, parse_swap_data_from_following_instructions,
+        EventMetadata, EventType, InnerInstructionsView, InstructionView, ProgramDataIndex,
+        SwapCuIndex, SwapCuParseConfig, SwapData, SwapDirection,
     },
     core::{
+        capabilities::{CapabilityKind, ProtocolCapability},
+        common_event_parser::{CommonEventParser, SYSTEM_PROGRAM_ID},
         dispatcher::EventDispatcher,
         global_state::{
-            add_bonk_dev_address, add_dev_address, is_bonk_dev_address_in_signature,
-            is_dev_address_in_signature,
+            add_bonk_dev_address, add_dev_address, get_pool_mints,
+            is_bonk_dev_address_in_signature, is_dev_address_in_signature,
         },
         merger_event::merge,
+        mint_registry::apply_mint_registry,
+        parse_options::ParseOptions,
+        pool_fee_registry::PoolFeeRegistry,
+    },
+    protocols::{
+        meteora_dlmm::price::record_lb_pair_bin_step,
+        pumpfun::discriminators as pumpfun_discriminators, types::ALL_PROTOCOLS,
     },
-    protocols::raydium_amm_v4::parser::RAYDIUM_AMM_V4_PROGRAM_ID,
     DexEvent, Protocol, TxDexEvents,
 };
+use base64::{engine::general_purpose::STANDARD, Engine};
 use parking_lot::Mutex;
 use prost_types::Timestamp;
-use solana_sdk::{
-    message::compiled_instruction::CompiledInstruction, pubkey::Pubkey, signature::Signature,
-    transaction::VersionedTransaction,
-};
+use rayon::prelude::*;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
 use solana_transaction_status::InnerInstructions;
+#[cfg(feature = "grpc")]
+use std::collections::HashSet;
 use std::sync::Arc;
+#[cfg(feature = "grpc")]
 use yellowstone_grpc_proto::geyser::SubscribeUpdateTransactionInfo;
 
-const SYSTEM_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("11111111111111111111111111111111");
+const TOKEN_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 const JITO_TIP_ACCOUNTS: &[Pubkey] = &[
     solana_sdk::pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
     solana_sdk::pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
@@ -37,8 +54,24 @@ const JITO_TIP_ACCOUNTS: &[Pubkey] = &[
     solana_sdk::pubkey!("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
 ];
 
+/// Anchor emit_cpi 事件的 8 字节 event ix tag，带该前缀的 inner instruction 是事件而不是指令
+const EMIT_CPI_EVENT_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+
 pub struct EventParser {}
 
+/// `EventParser::parse_transactions_parallel` 的单笔交易输入
+#[derive(Clone, Copy)]
+pub struct VersionedTransactionInput<'a> {
+    pub transaction: &'a VersionedTransaction,
+    pub signature: Signature,
+    pub slot: Option<u64>,
+    pub block_time: Option<Timestamp>,
+    /// 已展开地址查找表的完整账户列表
+    pub accounts: &'a [Pubkey],
+    pub inner_instructions: &'a [InnerInstructions],
+    pub transaction_index: Option<u64>,
+}
+
 #[derive(Clone, Copy)]
 struct MintLeg {
     from_mint: Pubkey,
@@ -51,7 +84,155 @@ struct AccountLeg {
     to_account: Pubkey,
 }
 
+/// 单笔交易内共用的解析上下文，日志索引按需构建且每笔交易最多构建一次
+struct TransactionContext<'a, I, G> {
+    compiled_instructions: &'a [I],
+    all_inner_instructions: &'a [G],
+    log_messages: &'a [String],
+    swap_cu_parse_config: Option<&'a SwapCuParseConfig>,
+    options: &'a ParseOptions,
+    /// 补齐前的账户数量，指令引用不小于该值的索引时对应账户为补齐的 `Pubkey::default()`
+    resolved_accounts_len: usize,
+    program_data_index: Option<ProgramDataIndex>,
+    swap_cu_index: Option<SwapCuIndex>,
+}
+
+impl<'a, I: InstructionView, G: InnerInstructionsView> TransactionContext<'a, I, G> {
+    fn new(
+        compiled_instructions: &'a [I],
+        all_inner_instructions: &'a [G],
+        log_messages: &'a [String],
+        swap_cu_parse_config: Option<&'a SwapCuParseConfig>,
+        options: &'a ParseOptions,
+        resolved_accounts_len: usize,
+    ) -> Self {
+        Self {
+            compiled_instructions,
+            all_inner_instructions,
+            log_messages,
+            swap_cu_parse_config,
+            options,
+            resolved_accounts_len,
+            program_data_index: None,
+            swap_cu_index: None,
+        }
+    }
+
+    /// 当前指令需要 program data 时构建索引，返回已构建的索引
+    fn program_data_index(
+        &mut self,
+        protocol: &Protocol,
+        data: &[u8],
+    ) -> Option<&ProgramDataIndex> {
+        if self.program_data_index.is_none()
+            && EventParser::instruction_needs_program_data(protocol, data)
+        {
+            return self.full_program_data_index();
+        }
+        self.program_data_index.as_ref()
+    }
+
+    /// 无论当前指令是否需要都构建 program data 索引，交易没有日志时返回 None
+    fn full_program_data_index(&mut self) -> Option<&ProgramDataIndex> {
+        if self.program_data_index.is_none() && !self.log_messages.is_empty() {
+            self.program_data_index = Some(build_program_data_index(
+                self.log_messages,
+                self.compiled_instructions.len(),
+                self.all_inner_instructions,
+            ));
+        }
+        self.program_data_index.as_ref()
+    }
+
+    /// 目标 swap 指令消耗的 CU，未开启或没有日志时为 None
+    fn swap_compute_units(
+        &mut self,
+        protocol: &Protocol,
+        program_id: &Pubkey,
+        data: &[u8],
+        accounts: &[Pubkey],
+        outer_index: i64,
+        inner_index: Option<i64>,
+    ) -> Option<u32> {
+        let config = self.swap_cu_parse_config.filter(|config| {
+            config.enabled
+                && !self.log_messages.is_empty()
+                && config.is_target_swap(protocol, program_id, data)
+        })?;
+        self.swap_cu_index
+            .get_or_insert_with(|| {
+                build_swap_cu_index(
+                    config,
+                    self.log_messages,
+                    self.compiled_instructions,
+                    accounts,
+                    self.all_inner_instructions,
+                )
+            })
+            .get(outer_index, inner_index)
+    }
+}
+
 impl EventParser {
+    /// 重建解析器解析 gRPC 交易时使用的账户列表
+    ///
+    /// 顺序为静态账户、地址查找表加载的 writable 账户、readonly 账户，之后按外层和 inner
+    /// instruction 引用的最大索引用 `Pubkey::default()` 补齐，与事件中账户字段的取值来源一致
+    #[cfg(feature = "grpc")]
+    pub fn grpc_account_keys(grpc_tx: &SubscribeUpdateTransactionInfo) -> Vec<Pubkey> {
+        let Some(message) = grpc_tx.transaction.as_ref().and_then(|tx| tx.message.as_ref()) else {
+            return Vec::new();
+        };
+        let accounts = Self::grpc_loaded_account_keys(&message.account_keys, grpc_tx.meta.as_ref());
+        let inner_instructions =
+            grpc_tx.meta.as_ref().map(|meta| meta.inner_instructions.as_slice()).unwrap_or(&[]);
+        Self::pad_account_keys(&accounts, &message.instructions, inner_instructions)
+    }
+
+    /// 交易外层 SetComputeUnitPrice 指令设置的 CU 价格 (micro-lamports)
+    ///
+    /// 程序 ID 只能来自静态账户列表，无需解析 address lookup table
+    #[cfg(feature = "grpc")]
+    pub fn grpc_compute_unit_price(grpc_tx: &SubscribeUpdateTransactionInfo) -> Option<u64> {
+        let message = grpc_tx.transaction.as_ref()?.message.as_ref()?;
+        let compute_budget: &[u8] = COMPUTE_BUDGET_PROGRAM_ID.as_ref();
+        message
+            .instructions
+            .iter()
+            .filter(|ix| {
+                message.account_keys.get(ix.program_id_index as usize).map(Vec::as_slice)
+                    == Some(compute_budget)
+            })
+            .find_map(|ix| CommonEventParser::parse_compute_unit_price(&ix.data))
+    }
+
+    /// 重建解析器解析 VersionedTransaction 时使用的账户列表
+    ///
+    /// `accounts` 与传给 `parse_instruction_events_from_versioned_transaction` 的相同，
+    /// 返回值为按指令引用的最大索引用 `Pubkey::default()` 补齐后的列表
+    pub fn versioned_account_keys(
+        transaction: &VersionedTransaction,
+        accounts: &[Pubkey],
+        inner_instructions: &[InnerInstructions],
+    ) -> Vec<Pubkey> {
+        Self::pad_account_keys(accounts, transaction.message.instructions(), inner_instructions)
+    }
+
+    /// 当前构建可解析的协议及其指令 / inner instruction / 账户 discriminator 与事件类型
+    ///
+    /// 只包含启用了对应 `protocol-*` feature 的协议，可用于生成文档或比较不同版本的覆盖范围
+    pub fn capabilities() -> Vec<ProtocolCapability> {
+        ALL_PROTOCOLS
+            .iter()
+            .filter(|protocol| protocol.is_enabled())
+            .map(|protocol| ProtocolCapability {
+                protocol: protocol.clone(),
+                program_id: protocol.program_id(),
+                events: protocol.capabilities().to_vec(),
+            })
+            .collect()
+    }
+
     fn summarize_compute_budget(events: &[DexEvent]) -> (u64, Option<u32>, bool) {
         let mut price = 0;
         let mut limit = None;
@@ -91,6 +272,7 @@ impl EventParser {
     ///
     /// This is the main entry point for parsing transactions received from gRPC streams.
     /// It extracts account keys, inner instructions, and delegates to instruction parsing.
+    #[cfg(feature = "grpc")]
     pub async fn parse_grpc_transaction(
         protocols: &[Protocol],
         event_type_filter: Option<&EventTypeFilter>,
@@ -104,52 +286,60 @@ impl EventParser {
         swap_cu_parse_config: Option<&SwapCuParseConfig>,
         callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
     ) -> anyhow::Result<()> {
-        // 创建适配器回调，将所有权回调转换为引用回调
-        let adapter_callback = Arc::new(move |event: &DexEvent| {
-            callback(event.clone());
-        });
+        Self::parse_grpc_transaction_with_options(
+            protocols,
+            event_type_filter,
+            grpc_tx,
+            signature,
+            slot,
+            block_time,
+            recv_us,
+            bot_wallet,
+            transaction_index,
+            swap_cu_parse_config,
+            callback,
+            ParseOptions::disabled(),
+        )
+        .await
+    }
+
+    /// `parse_grpc_transaction` with per-client parse options
+    #[allow(clippy::too_many_arguments)]
+    #[cfg(feature = "grpc")]
+    pub async fn parse_grpc_transaction_with_options(
+        protocols: &[Protocol],
+        event_type_filter: Option<&EventTypeFilter>,
+        grpc_tx: SubscribeUpdateTransactionInfo,
+        signature: Signature,
+        slot: Option<u64>,
+        block_time: Option<Timestamp>,
+        recv_us: i64,
+        bot_wallet: Option<Pubkey>,
+        transaction_index: Option<u64>,
+        swap_cu_parse_config: Option<&SwapCuParseConfig>,
+        callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+        options: &ParseOptions,
+    ) -> anyhow::Result<()> {
         if let Some(transition) = grpc_tx.transaction {
             if let Some(message) = &transition.message {
-                let mut address_table_lookups: Vec<Vec<u8>> = vec![];
-                let mut inner_instructions: Vec<
-                    yellowstone_grpc_proto::solana::storage::confirmed_block::InnerInstructions,
-                > = vec![];
-                let mut log_messages: Vec<String> = vec![];
-
-                if let Some(meta) = grpc_tx.meta {
-                    inner_instructions = meta.inner_instructions;
-                    log_messages = meta.log_messages;
-                    address_table_lookups.reserve(
-                        meta.loaded_writable_addresses.len() + meta.loaded_readonly_addresses.len(),
-                    );
-                    let loaded_writable_addresses = meta.loaded_writable_addresses;
-                    let loaded_readonly_addresses = meta.loaded_readonly_addresses;
-                    address_table_lookups.extend(
-                        loaded_writable_addresses.into_iter().chain(loaded_readonly_addresses),
-                    );
-                }
-
-                let mut accounts_bytes: Vec<Vec<u8>> =
-                    Vec::with_capacity(message.account_keys.len() + address_table_lookups.len());
-                accounts_bytes.extend_from_slice(&message.account_keys);
-                accounts_bytes.extend(address_table_lookups);
-                // 转换为 Pubkey
-                let accounts: Vec<Pubkey> = accounts_bytes
-                    .iter()
-                    .filter_map(|account| {
-                        if account.len() == 32 {
-                            Some(Pubkey::try_from(account.as_slice()).unwrap_or_default())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
+                let accounts =
+                    Self::grpc_loaded_account_keys(&message.account_keys, grpc_tx.meta.as_ref());
+                let (inner_instructions, log_messages, tx_failed) = match grpc_tx.meta {
+                    Some(meta) => (meta.inner_instructions, meta.log_messages, meta.err.is_some()),
+                    None => (vec![], vec![], false),
+                };
+                let (parse_callback, failed_swaps) = if tx_failed {
+                    let (parse_callback, failed_swaps) = Self::suppress_instruction_events();
+                    (parse_callback, Some(failed_swaps))
+                } else {
+                    (callback.clone(), None)
+                };
                 // 解析指令事件
                 let instructions = &message.instructions;
-                Self::parse_instruction_events_from_grpc_transaction(
+                Self::parse_instruction_events(
                     protocols,
                     event_type_filter,
-                    &instructions,
+                    instructions,
                     signature,
                     slot,
                     block_time,
@@ -160,9 +350,23 @@ impl EventParser {
                     bot_wallet,
                     transaction_index,
                     swap_cu_parse_config,
-                    adapter_callback,
-                )
-                .await?;
+                    options,
+                    &mut |event| parse_callback(event),
+                )?;
+                if let Some(failed_swaps) = failed_swaps {
+                    if let Some(event) = Self::parse_swap_failed_event(
+                        protocols,
+                        &log_messages,
+                        &failed_swaps.lock(),
+                        signature,
+                        slot,
+                        block_time,
+                        recv_us,
+                        transaction_index,
+                    ) {
+                        callback(event);
+                    }
+                }
             }
         }
 
@@ -171,6 +375,7 @@ impl EventParser {
 
     /// Collect all DEX events parsed from one gRPC transaction without reordering them.
     #[allow(clippy::too_many_arguments)]
+    #[cfg(feature = "grpc")]
     pub async fn parse_grpc_transaction_to_events(
         protocols: &[Protocol],
         event_type_filter: Option<&EventTypeFilter>,
@@ -182,6 +387,38 @@ impl EventParser {
         bot_wallet: Option<Pubkey>,
         transaction_index: Option<u64>,
         swap_cu_parse_config: Option<&SwapCuParseConfig>,
+    ) -> anyhow::Result<Option<TxDexEvents>> {
+        Self::parse_grpc_transaction_to_events_with_options(
+            protocols,
+            event_type_filter,
+            grpc_tx,
+            signature,
+            slot,
+            block_time,
+            recv_us,
+            bot_wallet,
+            transaction_index,
+            swap_cu_parse_config,
+            ParseOptions::disabled(),
+        )
+        .await
+    }
+
+    /// `parse_grpc_transaction_to_events` with per-client parse options
+    #[allow(clippy::too_many_arguments)]
+    #[cfg(feature = "grpc")]
+    pub async fn parse_grpc_transaction_to_events_with_options(
+        protocols: &[Protocol],
+        event_type_filter: Option<&EventTypeFilter>,
+        grpc_tx: SubscribeUpdateTransactionInfo,
+        signature: Signature,
+        slot: Option<u64>,
+        block_time: Option<Timestamp>,
+        recv_us: i64,
+        bot_wallet: Option<Pubkey>,
+        transaction_index: Option<u64>,
+        swap_cu_parse_config: Option<&SwapCuParseConfig>,
+        options: &ParseOptions,
     ) -> anyhow::Result<Option<TxDexEvents>> {
         let has_jito_tip = Self::grpc_transaction_has_jito_tip(&grpc_tx);
         let events = Arc::new(Mutex::new(Vec::new()));
@@ -190,7 +427,7 @@ impl EventParser {
             collected.lock().push(event);
         });
 
-        Self::parse_grpc_transaction(
+        Self::parse_grpc_transaction_with_options(
             protocols,
             event_type_filter,
             grpc_tx,
@@ -202,14 +439,16 @@ impl EventParser {
             transaction_index,
             swap_cu_parse_config,
             callback,
+            options,
         )
         .await?;
 
-        let events = events.lock().clone();
+        let events = std::mem::take(&mut *events.lock());
         if events.is_empty() {
             return Ok(None);
         }
         let is_arb = Self::is_arb_inner_swap_events(&events);
+        let is_self_trade = !is_arb && Self::is_self_trade_swap_events(&events);
         let (compute_unit_price_micro_lamports, compute_unit_limit, compute_unit_price_set) =
             Self::summarize_compute_budget(&events);
 
@@ -221,6 +460,7 @@ impl EventParser {
             tx_index_in_entry: None,
             recv_us,
             is_arb,
+            is_self_trade,
             compute_unit_price_micro_lamports,
             compute_unit_limit,
             compute_unit_price_set,
@@ -229,6 +469,27 @@ impl EventParser {
         }))
     }
 
+    /// 交易涉及的协议（仅扫描账户列表，不解析指令）
+    ///
+    /// 包含地址查找表加载的账户，因此 CPI 调用的协议也会被识别；比完整解析便宜得多，
+    /// 可用于在解析前按协议分发交易。账户中出现程序 ID 不代表一定会解析出事件。
+    #[cfg(feature = "grpc")]
+    pub fn detect_protocols(grpc_tx: &SubscribeUpdateTransactionInfo) -> HashSet<Protocol> {
+        let static_keys = grpc_tx
+            .transaction
+            .iter()
+            .filter_map(|tx| tx.message.as_ref())
+            .flat_map(|message| message.account_keys.iter());
+        let loaded_keys = grpc_tx.meta.iter().flat_map(|meta| {
+            meta.loaded_writable_addresses.iter().chain(meta.loaded_readonly_addresses.iter())
+        });
+        static_keys
+            .chain(loaded_keys)
+            .filter_map(|key| Pubkey::try_from(key.as_slice()).ok())
+            .filter_map(|key| EventDispatcher::match_protocol_by_program_id(&key))
+            .collect()
+    }
+
     /// Parse transaction from VersionedTransaction
     ///
     /// This is the entry point for parsing VersionedTransaction objects.
@@ -246,78 +507,63 @@ impl EventParser {
         inner_instructions: &[InnerInstructions],
         bot_wallet: Option<Pubkey>,
         transaction_index: Option<u64>,
-        _swap_cu_parse_config: Option<&SwapCuParseConfig>,
+        swap_cu_parse_config: Option<&SwapCuParseConfig>,
         callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
     ) -> anyhow::Result<()> {
-        // 创建适配器回调，将所有权回调转换为引用回调
-        let adapter_callback = Arc::new(move |event: &DexEvent| {
-            callback(event.clone());
-        });
-        // 获取交易的指令和账户
-        let compiled_instructions = transaction.message.instructions();
-        let mut accounts: Vec<Pubkey> = accounts.to_vec();
-        // 检查交易中是否包含程序
-        let has_program = accounts
-            .iter()
-            .any(|account| Self::should_handle(protocols, event_type_filter, account));
-        if has_program {
-            // 解析每个指令
-            for (index, instruction) in compiled_instructions.iter().enumerate() {
-                if let Some(program_id) = accounts.get(instruction.program_id_index as usize) {
-                    let program_id = *program_id; // 克隆程序ID，避免借用冲突
-                    let inner_instructions = inner_instructions
-                        .iter()
-                        .find(|inner_instruction| inner_instruction.index == index as u8);
-                    if Self::should_handle(protocols, event_type_filter, &program_id) {
-                        let max_idx = instruction.accounts.iter().max().unwrap_or(&0);
-                        // 补齐accounts(使用Pubkey::default())
-                        if *max_idx as usize >= accounts.len() {
-                            accounts.resize(*max_idx as usize + 1, Pubkey::default());
-                        }
-                        Self::parse_events_from_instruction(
-                            protocols,
-                            event_type_filter,
-                            instruction,
-                            &accounts,
-                            signature,
-                            slot.unwrap_or(0),
-                            block_time,
-                            recv_us,
-                            index as i64,
-                            None,
-                            bot_wallet,
-                            transaction_index,
-                            inner_instructions,
-                            adapter_callback.clone(),
-                        )?;
-                    }
-                    // Immediately process inner instructions for correct ordering
-                    if let Some(inner_instructions) = inner_instructions {
-                        for (inner_index, inner_instruction) in
-                            inner_instructions.instructions.iter().enumerate()
-                        {
-                            Self::parse_events_from_instruction(
-                                protocols,
-                                event_type_filter,
-                                &inner_instruction.instruction,
-                                &accounts,
-                                signature,
-                                slot.unwrap_or(0),
-                                block_time,
-                                recv_us,
-                                index as i64,
-                                Some(inner_index as i64),
-                                bot_wallet,
-                                transaction_index,
-                                Some(&inner_instructions),
-                                adapter_callback.clone(),
-                            )?;
-                        }
-                    }
-                }
-            }
-        }
-        Ok(())
+        Self::parse_instruction_events_from_versioned_transaction_with_options(
+            protocols,
+            event_type_filter,
+            transaction,
+            signature,
+            slot,
+            block_time,
+            recv_us,
+            accounts,
+            inner_instructions,
+            bot_wallet,
+            transaction_index,
+            swap_cu_parse_config,
+            callback,
+            ParseOptions::disabled(),
+        )
+        .await
+    }
+
+    /// `parse_instruction_events_from_versioned_transaction` with per-client parse options
+    #[allow(clippy::too_many_arguments)]
+    pub async fn parse_instruction_events_from_versioned_transaction_with_options(
+        protocols: &[Protocol],
+        event_type_filter: Option<&EventTypeFilter>,
+        transaction: &VersionedTransaction,
+        signature: Signature,
+        slot: Option<u64>,
+        block_time: Option<Timestamp>,
+        recv_us: i64,
+        accounts: &[Pubkey],
+        inner_instructions: &[InnerInstructions],
+        bot_wallet: Option<Pubkey>,
+        transaction_index: Option<u64>,
+        swap_cu_parse_config: Option<&SwapCuParseConfig>,
+        callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+        options: &ParseOptions,
+    ) -> anyhow::Result<()> {
+        Self::parse_instruction_events(
+            protocols,
+            event_type_filter,
+            transaction.message.instructions(),
+            signature,
+            slot,
+            block_time,
+            recv_us,
+            accounts,
+            inner_instructions,
+            &[],
+            bot_wallet,
+            transaction_index,
+            swap_cu_parse_config,
+            options,
+            &mut |event| callback(event),
+        )
     }
 
     /// Collect all DEX events parsed from one VersionedTransaction without reordering them.
@@ -337,6 +583,45 @@ impl EventParser {
         entry_index: Option<u64>,
         tx_index_in_entry: Option<u64>,
         swap_cu_parse_config: Option<&SwapCuParseConfig>,
+    ) -> anyhow::Result<Option<TxDexEvents>> {
+        Self::parse_versioned_transaction_to_events_with_options(
+            protocols,
+            event_type_filter,
+            transaction,
+            signature,
+            slot,
+            block_time,
+            recv_us,
+            accounts,
+            inner_instructions,
+            bot_wallet,
+            transaction_index,
+            entry_index,
+            tx_index_in_entry,
+            swap_cu_parse_config,
+            ParseOptions::disabled(),
+        )
+        .await
+    }
+
+    /// `parse_versioned_transaction_to_events` with per-client parse options
+    #[allow(clippy::too_many_arguments)]
+    pub async fn parse_versioned_transaction_to_events_with_options(
+        protocols: &[Protocol],
+        event_type_filter: Option<&EventTypeFilter>,
+        transaction: &VersionedTransaction,
+        signature: Signature,
+        slot: Option<u64>,
+        block_time: Option<Timestamp>,
+        recv_us: i64,
+        accounts: &[Pubkey],
+        inner_instructions: &[InnerInstructions],
+        bot_wallet: Option<Pubkey>,
+        transaction_index: Option<u64>,
+        entry_index: Option<u64>,
+        tx_index_in_entry: Option<u64>,
+        swap_cu_parse_config: Option<&SwapCuParseConfig>,
+        options: &ParseOptions,
     ) -> anyhow::Result<Option<TxDexEvents>> {
         let has_jito_tip =
             Self::versioned_transaction_has_jito_tip(transaction, accounts, inner_instructions);
@@ -346,7 +631,7 @@ impl EventParser {
             collected.lock().push(event);
         });
 
-        Self::parse_instruction_events_from_versioned_transaction(
+        Self::parse_instruction_events_from_versioned_transaction_with_options(
             protocols,
             event_type_filter,
             transaction,
@@ -360,14 +645,16 @@ impl EventParser {
             transaction_index,
             swap_cu_parse_config,
             callback,
+            options,
         )
         .await?;
 
-        let events = events.lock().clone();
+        let events = std::mem::take(&mut *events.lock());
         if events.is_empty() {
             return Ok(None);
         }
         let is_arb = Self::is_arb_inner_swap_events(&events);
+        let is_self_trade = !is_arb && Self::is_self_trade_swap_events(&events);
         let (compute_unit_price_micro_lamports, compute_unit_limit, compute_unit_price_set) =
             Self::summarize_compute_budget(&events);
 
@@ -379,6 +666,7 @@ impl EventParser {
             tx_index_in_entry,
             recv_us,
             is_arb,
+            is_self_trade,
             compute_unit_price_micro_lamports,
             compute_unit_limit,
             compute_unit_price_set,
@@ -387,27 +675,210 @@ impl EventParser {
         }))
     }
 
-    fn grpc_transaction_has_jito_tip(grpc_tx: &SubscribeUpdateTransactionInfo) -> bool {
-        let Some(transaction) = grpc_tx.transaction.as_ref() else {
-            return false;
-        };
-        let Some(message) = transaction.message.as_ref() else {
-            return false;
+    /// Parse a transaction delivered to a validator Geyser plugin
+    ///
+    /// Adapts `ReplicaTransactionInfoV2` (from `notify_transaction`) to
+    /// `parse_instruction_events`, the same entry point as `parse_grpc_transaction`. Field mapping:
+    /// - `signature` -> `signature`
+    /// - `transaction` -> `message().instructions()`; the account list is
+    ///   `message().account_keys()`, i.e. static keys followed by the loaded writable and
+    ///   readonly addresses, the same order as the gRPC path
+    /// - `transaction_status_meta.inner_instructions` -> `inner_instructions` (empty when `None`)
+    /// - `transaction_status_meta.log_messages` -> `log_messages` (empty when `None`), used for
+    ///   `Program data:` enrichment and swap CU parsing as in `parse_grpc_transaction`
+    /// - `transaction_status_meta.status` -> when the transaction failed, instruction events are
+    ///   dropped and a swap failed event is emitted if the failing instruction is a swap
+    /// - `index` -> `transaction_index`
+    /// - `slot` is the `slot` argument of `notify_transaction`; Geyser provides no block time,
+    ///   so `block_time` is `None`
+    ///
+    /// Vote transactions (`is_vote`) are skipped.
+    #[cfg(feature = "geyser-plugin")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn parse_replica_transaction(
+        protocols: &[Protocol],
+        event_type_filter: Option<&EventTypeFilter>,
+        transaction: &agave_geyser_plugin_interface::geyser_plugin_interface::ReplicaTransactionInfoV2<'_>,
+        slot: u64,
+        recv_us: i64,
+        bot_wallet: Option<Pubkey>,
+        swap_cu_parse_config: Option<&SwapCuParseConfig>,
+        callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+        options: &ParseOptions,
+    ) -> anyhow::Result<()> {
+        if transaction.is_vote {
+            return Ok(());
+        }
+        let signature = *transaction.signature;
+        let transaction_index = Some(transaction.index as u64);
+        let meta = transaction.transaction_status_meta;
+        let message = transaction.transaction.message();
+        let accounts: Vec<Pubkey> = message.account_keys().iter().copied().collect();
+        let log_messages = meta.log_messages.as_deref().unwrap_or(&[]);
+        let (parse_callback, failed_swaps) = if meta.status.is_err() {
+            let (parse_callback, failed_swaps) = Self::suppress_instruction_events();
+            (parse_callback, Some(failed_swaps))
+        } else {
+            (callback.clone(), None)
         };
-        let mut accounts = message
-            .account_keys
-            .iter()
-            .filter_map(|account| Pubkey::try_from(account.as_slice()).ok())
-            .collect::<Vec<_>>();
-        if let Some(meta) = grpc_tx.meta.as_ref() {
-            accounts.extend(
-                meta.loaded_writable_addresses
-                    .iter()
-                    .chain(meta.loaded_readonly_addresses.iter())
-                    .filter_map(|account| Pubkey::try_from(account.as_slice()).ok()),
-            );
+
+        Self::parse_instruction_events(
+            protocols,
+            event_type_filter,
+            message.instructions(),
+            signature,
+            Some(slot),
+            None,
+            recv_us,
+            &accounts,
+            meta.inner_instructions.as_deref().unwrap_or(&[]),
+            log_messages,
+            bot_wallet,
+            transaction_index,
+            swap_cu_parse_config,
+            options,
+            &mut |event| parse_callback(event),
+        )?;
+        if let Some(failed_swaps) = failed_swaps {
+            if let Some(event) = Self::parse_swap_failed_event(
+                protocols,
+                log_messages,
+                &failed_swaps.lock(),
+                signature,
+                Some(slot),
+                None,
+                recv_us,
+                transaction_index,
+            ) {
+                callback(event);
+            }
         }
-        if message.instructions.iter().any(|instruction| {
+        Ok(())
+    }
+
+    /// 并行解析一批 VersionedTransaction（回补 / 离线场景）
+    ///
+    /// 返回值与 `transactions` 一一对应，内层为该交易按指令顺序排列的事件；解析失败的交易返回空列表。
+    /// 交易之间没有先后顺序，依赖跨交易状态的字段（例如 dev 地址识别）可能与顺序解析不同。
+    pub fn parse_transactions_parallel(
+        protocols: &[Protocol],
+        event_type_filter: Option<&EventTypeFilter>,
+        transactions: &[VersionedTransactionInput<'_>],
+        recv_us: i64,
+        bot_wallet: Option<Pubkey>,
+        swap_cu_parse_config: Option<&SwapCuParseConfig>,
+        options: &ParseOptions,
+    ) -> Vec<Vec<DexEvent>> {
+        transactions
+            .par_iter()
+            .map(|tx| {
+                let mut events = Vec::new();
+                if let Err(e) = Self::parse_instruction_events(
+                    protocols,
+                    event_type_filter,
+                    tx.transaction.message.instructions(),
+                    tx.signature,
+                    tx.slot,
+                    tx.block_time,
+                    recv_us,
+                    tx.accounts,
+                    tx.inner_instructions,
+                    &[],
+                    bot_wallet,
+                    tx.transaction_index,
+                    swap_cu_parse_config,
+                    options,
+                    &mut |event| events.push(event),
+                ) {
+                    log::warn!("Failed to parse transaction {}: {}", tx.signature, e);
+                    return Vec::new();
+                }
+                events
+            })
+            .collect()
+    }
+
+    /// 失败交易的所有指令都没有生效：指令事件一律不转发，只记录 swap 事件的
+    /// `(outer_index, program_id)`，用于判断失败的指令是否为 swap
+    fn suppress_instruction_events(
+    ) -> (Arc<dyn Fn(DexEvent) + Send + Sync>, Arc<Mutex<Vec<(i64, Pubkey)>>>) {
+        let failed_swaps = Arc::new(Mutex::new(Vec::new()));
+        let recorded = failed_swaps.clone();
+        let callback = Arc::new(move |event: DexEvent| {
+            let metadata = event.metadata();
+            if metadata.event_type.is_swap() {
+                recorded.lock().push((metadata.outer_index, metadata.program_id));
+            }
+        });
+        (callback, failed_swaps)
+    }
+
+    /// 从失败交易的日志中提取失败原因
+    ///
+    /// 调用栈中离失败点最近的已订阅协议程序作为事件的协议；没有已订阅协议、
+    /// 或该程序在失败的外层指令中没有被解析为 swap（`failed_swaps` 中没有对应记录）时返回 None
+    #[allow(clippy::too_many_arguments)]
+    fn parse_swap_failed_event(
+        protocols: &[Protocol],
+        log_messages: &[String],
+        failed_swaps: &[(i64, Pubkey)],
+        signature: Signature,
+        slot: Option<u64>,
+        block_time: Option<Timestamp>,
+        recv_us: i64,
+        transaction_index: Option<u64>,
+    ) -> Option<DexEvent> {
+        let failure = extract_program_failure(log_messages)?;
+        let (program_id, protocol) = failure.call_stack.iter().rev().find_map(|program_id| {
+            let protocol = EventDispatcher::match_protocol_by_program_id(program_id)?;
+            protocols.contains(&protocol).then_some((*program_id, protocol))
+        })?;
+        if !failed_swaps.contains(&(failure.outer_index as i64, program_id)) {
+            return None;
+        }
+        let timestamp = block_time.unwrap_or(Timestamp { seconds: 0, nanos: 0 });
+        let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
+        let metadata = EventMetadata::new(
+            signature,
+            slot.unwrap_or(0),
+            timestamp.seconds,
+            block_time_ms,
+            Default::default(),
+            Default::default(),
+            program_id,
+            0,
+            None,
+            recv_us,
+            transaction_index,
+        );
+        let mut event =
+            CommonEventParser::new_swap_failed_event(failure, (&protocol).into(), metadata);
+        event.metadata.handle_us = elapsed_micros_since(recv_us);
+        Some(DexEvent::SwapFailedEvent(event))
+    }
+
+    #[cfg(feature = "grpc")]
+    fn grpc_transaction_has_jito_tip(grpc_tx: &SubscribeUpdateTransactionInfo) -> bool {
+        let Some(transaction) = grpc_tx.transaction.as_ref() else {
+            return false;
+        };
+        let Some(message) = transaction.message.as_ref() else {
+            return false;
+        };
+        let mut accounts = message
+            .account_keys
+            .iter()
+            .filter_map(|account| Pubkey::try_from(account.as_slice()).ok())
+            .collect::<Vec<_>>();
+        if let Some(meta) = grpc_tx.meta.as_ref() {
+            accounts.extend(
+                meta.loaded_writable_addresses
+                    .iter()
+                    .chain(meta.loaded_readonly_addresses.iter())
+                    .filter_map(|account| Pubkey::try_from(account.as_slice()).ok()),
+            );
+        }
+        if message.instructions.iter().any(|instruction| {
             Self::is_system_transfer_to_jito(
                 instruction.program_id_index as usize,
                 &instruction.accounts,
@@ -459,151 +930,184 @@ impl EventParser {
     }
 
     // ================================================================================================
-    // gRPC Transaction Processing
+    // Shared Instruction Processing
     // ================================================================================================
 
-    /// Parse instruction events from gRPC transaction format
+    /// Parse instruction events shared by the gRPC and VersionedTransaction paths
     ///
-    /// Iterates through all instructions in a gRPC transaction, checks if they should be handled,
-    /// and delegates to instruction-level parsing for both outer and inner instructions.
+    /// Iterates through all outer instructions, checks if they should be handled, and parses
+    /// both outer and inner instructions. Inner events of one outer instruction are emitted
+    /// after the outer event. `log_messages` may be empty (e.g. VersionedTransaction without
+    /// meta), in which case program data enrichment and swap CU lookup are skipped.
+    /// Events are moved into `callback`, so no event is cloned on the way to the user.
     #[allow(clippy::too_many_arguments)]
-    async fn parse_instruction_events_from_grpc_transaction(
+    fn parse_instruction_events<I: InstructionView, G: InnerInstructionsView>(
         protocols: &[Protocol],
         event_type_filter: Option<&EventTypeFilter>,
-        compiled_instructions: &[yellowstone_grpc_proto::prelude::CompiledInstruction],
+        compiled_instructions: &[I],
         signature: Signature,
         slot: Option<u64>,
         block_time: Option<Timestamp>,
         recv_us: i64,
         accounts: &[Pubkey],
-        all_inner_instructions: &[yellowstone_grpc_proto::prelude::InnerInstructions],
+        all_inner_instructions: &[G],
         log_messages: &[String],
         bot_wallet: Option<Pubkey>,
         transaction_index: Option<u64>,
         swap_cu_parse_config: Option<&SwapCuParseConfig>,
-        callback: Arc<dyn for<'a> Fn(&'a DexEvent) + Send + Sync>,
+        options: &ParseOptions,
+        callback: &mut dyn FnMut(DexEvent),
     ) -> anyhow::Result<()> {
-        // 获取交易的指令和账户
-        let mut accounts = accounts.to_vec();
-        // 检查交易中是否包含程序
+        // 检查交易中是否包含程序。`accounts` 包含地址查找表加载的账户，
+        // 只通过 CPI 调用、程序 ID 仅出现在 loaded addresses 中的协议同样会被识别；
+        // 关注 SOL 转账时，涉及关注钱包的交易也需要解析
         let has_program = accounts
             .iter()
-            .any(|account| Self::should_handle(protocols, event_type_filter, account));
-        if has_program {
-            // 解析每个指令
-            let mut program_data_index: Option<ProgramDataIndex> = None;
-            let mut swap_cu_index: Option<SwapCuIndex> = None;
-            for (index, instruction) in compiled_instructions.iter().enumerate() {
-                if let Some(program_id) = accounts.get(instruction.program_id_index as usize) {
-                    let program_id = *program_id; // 克隆程序ID，避免借用冲突
-                    if program_data_index.is_none() && !log_messages.is_empty() {
-                        if let Some(protocol) =
-                            EventDispatcher::match_protocol_by_program_id(&program_id)
-                        {
-                            if Self::instruction_needs_program_data(&protocol, &instruction.data) {
-                                program_data_index = Some(build_program_data_index(
-                                    log_messages,
-                                    compiled_instructions.len(),
-                                    all_inner_instructions,
-                                ));
-                            }
-                        }
+            .any(|account| Self::should_handle(protocols, event_type_filter, account))
+            || options.sol_transfer_watchlist.as_ref().is_some_and(|w| w.touches(accounts));
+        if !has_program {
+            return Ok(());
+        }
+        let resolved_len = accounts.len();
+        let accounts =
+            Self::pad_account_keys(accounts, compiled_instructions, all_inner_instructions);
+        if options.log_account_keys {
+            Self::log_resolved_account_keys(signature, &accounts, resolved_len);
+        }
+        let mut ctx = TransactionContext::new(
+            compiled_instructions,
+            all_inner_instructions,
+            log_messages,
+            swap_cu_parse_config,
+            options,
+            resolved_len,
+        );
+        let parse_logs_only = options.parse_logs_only && !log_messages.is_empty();
+        // 指令解析已产出事件的调用位置，日志补出的事件跳过这些位置
+        let mut parsed_positions: Vec<(i64, Option<i64>)> = Vec::new();
+        // 解析每个指令
+        for (index, instruction) in compiled_instructions.iter().enumerate() {
+            let Some(program_id) = accounts.get(instruction.program_id_index()).copied() else {
+                continue;
+            };
+            let inner_instructions = all_inner_instructions
+                .iter()
+                .find(|inner_instruction| inner_instruction.index() == index);
+            if Self::should_handle(protocols, event_type_filter, &program_id)
+                || Self::should_handle_sol_transfer(options, &program_id, None)
+            {
+                if let Some(event) = Self::parse_event_from_instruction(
+                    protocols,
+                    event_type_filter,
+                    instruction,
+                    &accounts,
+                    signature,
+                    slot.unwrap_or(0),
+                    block_time,
+                    recv_us,
+                    index as i64,
+                    None,
+                    bot_wallet,
+                    transaction_index,
+                    inner_instructions,
+                    &mut ctx,
+                )? {
+                    if parse_logs_only {
+                        parsed_positions.push((index as i64, None));
                     }
-                    let inner_instructions = all_inner_instructions
-                        .iter()
-                        .find(|inner_instruction| inner_instruction.index == index as u32);
-                    let max_idx = instruction.accounts.iter().max().unwrap_or(&0);
-                    // 补齐accounts(使用Pubkey::default())
-                    if *max_idx as usize >= accounts.len() {
-                        accounts.resize(*max_idx as usize + 1, Pubkey::default());
+                    callback(event);
+                }
+            }
+            // Immediately process inner instructions for correct ordering
+            if let Some(inner_instructions) = inner_instructions {
+                // 外层指令不是 DEX 协议时，inner 事件来自聚合器 / 路由的 CPI
+                let via_router = (EventDispatcher::match_protocol_by_program_id(&program_id)
+                    .is_none()
+                    && !EventDispatcher::is_common_program(&program_id))
+                .then_some(program_id);
+                let mut inner_events: Vec<DexEvent> =
+                    Vec::with_capacity(inner_instructions.instructions().len());
+                for (inner_index, inner_instruction) in
+                    inner_instructions.instructions().iter().enumerate()
+                {
+                    if let Some(mut inner_event) = Self::parse_event_from_instruction(
+                        protocols,
+                        event_type_filter,
+                        inner_instruction,
+                        &accounts,
+                        signature,
+                        slot.unwrap_or(0),
+                        block_time,
+                        recv_us,
+                        index as i64,
+                        Some(inner_index as i64),
+                        bot_wallet,
+                        transaction_index,
+                        Some(inner_instructions),
+                        &mut ctx,
+                    )? {
+                        inner_event.metadata_mut().via_router = via_router;
+                        inner_events.push(inner_event);
                     }
-                    if Self::should_handle(protocols, event_type_filter, &program_id) {
-                        Self::parse_events_from_grpc_instruction(
-                            protocols,
-                            event_type_filter,
-                            instruction,
-                            &accounts,
-                            signature,
-                            slot.unwrap_or(0),
-                            block_time,
-                            recv_us,
-                            index as i64,
-                            None,
-                            bot_wallet,
-                            transaction_index,
-                            inner_instructions,
-                            program_data_index.as_ref(),
-                            swap_cu_parse_config,
-                            &mut swap_cu_index,
-                            log_messages,
-                            compiled_instructions,
-                            all_inner_instructions,
-                            callback.clone(),
-                        )?;
+                }
+
+                for inner_event in inner_events {
+                    if parse_logs_only {
+                        let metadata = inner_event.metadata();
+                        parsed_positions.push((metadata.outer_index, metadata.inner_index));
                     }
-                    // Immediately process inner instructions for correct ordering
-                    if let Some(inner_instructions) = inner_instructions {
-                        let mut inner_events: Vec<DexEvent> =
-                            Vec::with_capacity(inner_instructions.instructions.len());
-                        for (inner_index, inner_instruction) in
-                            inner_instructions.instructions.iter().enumerate()
-                        {
-                            let inner_accounts = &inner_instruction.accounts;
-                            let data = &inner_instruction.data;
-                            let instruction =
-                                yellowstone_grpc_proto::prelude::CompiledInstruction {
-                                    program_id_index: inner_instruction.program_id_index,
-                                    accounts: inner_accounts.to_vec(),
-                                    data: data.to_vec(),
-                                };
-                            if program_data_index.is_none() && !log_messages.is_empty() {
-                                if let Some(program_id) =
-                                    accounts.get(instruction.program_id_index as usize)
-                                {
-                                    if let Some(protocol) =
-                                        EventDispatcher::match_protocol_by_program_id(program_id)
-                                    {
-                                        if Self::instruction_needs_program_data(
-                                            &protocol,
-                                            &instruction.data,
-                                        ) {
-                                            program_data_index = Some(build_program_data_index(
-                                                log_messages,
-                                                compiled_instructions.len(),
-                                                all_inner_instructions,
-                                            ));
-                                        }
-                                    }
-                                }
-                            }
-                            if let Some(inner_event) = Self::parse_event_from_grpc_instruction(
-                                protocols,
-                                event_type_filter,
-                                &instruction,
-                                &accounts,
-                                signature,
-                                slot.unwrap_or(0),
-                                block_time,
-                                recv_us,
-                                inner_instructions.index as i64,
-                                Some(inner_index as i64),
-                                bot_wallet,
-                                transaction_index,
-                                Some(&inner_instructions),
-                                program_data_index.as_ref(),
-                                swap_cu_parse_config,
-                                &mut swap_cu_index,
-                                log_messages,
-                                compiled_instructions,
-                                all_inner_instructions,
-                            )? {
-                                inner_events.push(inner_event);
+                    callback(inner_event);
+                }
+            }
+        }
+        let emit_raw_program_data = options.emit_raw_program_data;
+        if parse_logs_only || emit_raw_program_data {
+            let log_messages = ctx.log_messages;
+            if let Some(index) = ctx.full_program_data_index() {
+                let timestamp = block_time.unwrap_or(Timestamp { seconds: 0, nanos: 0 });
+                let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
+                let metadata = EventMetadata::new(
+                    signature,
+                    slot.unwrap_or(0),
+                    timestamp.seconds,
+                    block_time_ms,
+                    Default::default(),
+                    EventType::RawProgramData,
+                    Pubkey::default(),
+                    0,
+                    None,
+                    recv_us,
+                    transaction_index,
+                );
+                if parse_logs_only {
+                    for mut event in Self::log_swap_events(
+                        protocols,
+                        index,
+                        log_messages,
+                        &metadata,
+                        &parsed_positions,
+                    ) {
+                        let outer_program_id = compiled_instructions
+                            .get(event.metadata().outer_index as usize)
+                            .and_then(|instruction| accounts.get(instruction.program_id_index()));
+                        if let Some(&outer_program_id) = outer_program_id {
+                            let metadata = event.metadata_mut();
+                            if metadata.inner_index.is_some()
+                                && EventDispatcher::match_protocol_by_program_id(&outer_program_id)
+                                    .is_none()
+                                && !EventDispatcher::is_common_program(&outer_program_id)
+                            {
+                                metadata.via_router = Some(outer_program_id);
                             }
                         }
-
-                        for inner_event in inner_events.iter() {
-                            callback(inner_event);
-                        }
+                        callback(event);
+                    }
+                }
+                if emit_raw_program_data {
+                    for event in
+                        Self::raw_program_data_events(protocols, index, log_messages, &metadata)
+                    {
+                        callback(event);
                     }
                 }
             }
@@ -611,65 +1115,96 @@ impl EventParser {
         Ok(())
     }
 
-    /// Parse events from gRPC instruction
-    ///
-    /// Core parsing logic for a single gRPC instruction. Extracts discriminator, dispatches
-    /// to protocol-specific parsers, handles inner instructions, and processes swap data.
-    #[allow(clippy::too_many_arguments)]
-    fn parse_events_from_grpc_instruction(
+    /// 从只依赖日志的协议的 Program data 中生成 swap 事件，跳过 `parsed_positions` 中
+    /// 指令解析已产出事件的调用位置；同一调用输出多条事件日志（如 CLMM 多跳路由）时每条生成一个事件
+    fn log_swap_events(
         protocols: &[Protocol],
-        event_type_filter: Option<&EventTypeFilter>,
-        instruction: &yellowstone_grpc_proto::prelude::CompiledInstruction,
-        accounts: &[Pubkey],
-        signature: Signature,
-        slot: u64,
-        block_time: Option<Timestamp>,
-        recv_us: i64,
-        outer_index: i64,
-        inner_index: Option<i64>,
-        bot_wallet: Option<Pubkey>,
-        transaction_index: Option<u64>,
-        inner_instructions: Option<&yellowstone_grpc_proto::prelude::InnerInstructions>,
-        program_data_index: Option<&ProgramDataIndex>,
-        swap_cu_parse_config: Option<&SwapCuParseConfig>,
-        swap_cu_index: &mut Option<SwapCuIndex>,
+        index: &ProgramDataIndex,
         log_messages: &[String],
-        compiled_instructions: &[yellowstone_grpc_proto::prelude::CompiledInstruction],
-        all_inner_instructions: &[yellowstone_grpc_proto::prelude::InnerInstructions],
-        callback: Arc<dyn for<'a> Fn(&'a DexEvent) + Send + Sync>,
-    ) -> anyhow::Result<()> {
-        if let Some(event) = Self::parse_event_from_grpc_instruction(
-            protocols,
-            event_type_filter,
-            instruction,
-            accounts,
-            signature,
-            slot,
-            block_time,
-            recv_us,
-            outer_index,
-            inner_index,
-            bot_wallet,
-            transaction_index,
-            inner_instructions,
-            program_data_index,
-            swap_cu_parse_config,
-            swap_cu_index,
-            log_messages,
-            compiled_instructions,
-            all_inner_instructions,
-        )? {
-            callback(&event);
+        metadata: &EventMetadata,
+        parsed_positions: &[(i64, Option<i64>)],
+    ) -> Vec<DexEvent> {
+        let mut events = Vec::new();
+        for (outer_index, inner_index, item) in index.iter() {
+            if parsed_positions.contains(&(outer_index, inner_index)) {
+                continue;
+            }
+            let Some(protocol) = EventDispatcher::match_protocol_by_program_id(&item.program_id)
+            else {
+                continue;
+            };
+            if !protocols.contains(&protocol) {
+                continue;
+            }
+            let mut metadata = metadata.clone();
+            metadata.protocol = (&protocol).into();
+            metadata.program_id = item.program_id;
+            metadata.outer_index = outer_index;
+            metadata.inner_index = inner_index;
+            for base64 in item.all_program_data(log_messages) {
+                if let Some(event) = log_swap_event(&protocol, base64, &metadata) {
+                    events.push(event);
+                }
+            }
         }
+        events
+    }
 
-        Ok(())
+    /// 已订阅协议输出的、前 8 字节不是已知事件 discriminator 的 Program data 日志，按调用顺序排列
+    fn raw_program_data_events(
+        protocols: &[Protocol],
+        index: &ProgramDataIndex,
+        log_messages: &[String],
+        metadata: &EventMetadata,
+    ) -> Vec<DexEvent> {
+        let mut events = Vec::new();
+        for (outer_index, inner_index, item) in index.iter() {
+            let Some(protocol) = EventDispatcher::match_protocol_by_program_id(&item.program_id)
+            else {
+                continue;
+            };
+            if !protocols.contains(&protocol) {
+                continue;
+            }
+            for base64 in item.all_program_data(log_messages) {
+                let known = STANDARD
+                    .decode(base64)
+                    .ok()
+                    .and_then(|data| data.get(..8).map(|d| protocol.is_known_program_data(d)))
+                    .unwrap_or(false);
+                if known {
+                    continue;
+                }
+                let mut metadata = metadata.clone();
+                metadata.outer_index = outer_index;
+                metadata.inner_index = inner_index;
+                events.push(DexEvent::RawProgramDataEvent(
+                    CommonEventParser::new_raw_program_data_event(
+                        (&protocol).into(),
+                        item.program_id,
+                        base64.to_string(),
+                        metadata,
+                    ),
+                ));
+            }
+        }
+        events
     }
 
+    /// Parse event from a single instruction
+    ///
+    /// Core parsing logic for a single outer or inner instruction. Extracts discriminator,
+    /// dispatches to protocol-specific parsers, merges inner instruction events, and
+    /// processes swap data.
     #[allow(clippy::too_many_arguments)]
-    fn parse_event_from_grpc_instruction(
+    fn parse_event_from_instruction<
+        X: InstructionView,
+        I: InstructionView,
+        G: InnerInstructionsView,
+    >(
         protocols: &[Protocol],
         event_type_filter: Option<&EventTypeFilter>,
-        instruction: &yellowstone_grpc_proto::prelude::CompiledInstruction,
+        instruction: &X,
         accounts: &[Pubkey],
         signature: Signature,
         slot: u64,
@@ -679,39 +1214,33 @@ impl EventParser {
         inner_index: Option<i64>,
         bot_wallet: Option<Pubkey>,
         transaction_index: Option<u64>,
-        inner_instructions: Option<&yellowstone_grpc_proto::prelude::InnerInstructions>,
-        program_data_index: Option<&ProgramDataIndex>,
-        swap_cu_parse_config: Option<&SwapCuParseConfig>,
-        swap_cu_index: &mut Option<SwapCuIndex>,
-        log_messages: &[String],
-        compiled_instructions: &[yellowstone_grpc_proto::prelude::CompiledInstruction],
-        all_inner_instructions: &[yellowstone_grpc_proto::prelude::InnerInstructions],
+        inner_instructions: Option<&G>,
+        ctx: &mut TransactionContext<'_, I, G>,
     ) -> anyhow::Result<Option<DexEvent>> {
         // 添加边界检查以防止越界访问
-        let program_id_index = instruction.program_id_index as usize;
-        if program_id_index >= accounts.len() {
+        let Some(program_id) = accounts.get(instruction.program_id_index()).copied() else {
             return Ok(None);
-        }
-        let program_id = accounts[program_id_index];
-        if !Self::should_handle(protocols, event_type_filter, &program_id) {
+        };
+        let is_sol_transfer =
+            Self::should_handle_sol_transfer(ctx.options, &program_id, inner_index);
+        if !is_sol_transfer && !Self::should_handle(protocols, event_type_filter, &program_id) {
             return Ok(None);
         }
 
-        let is_cu_program = EventDispatcher::is_compute_budget_program(&program_id);
+        let is_common_program = is_sol_transfer || EventDispatcher::is_common_program(&program_id);
 
-        let disc_len = match program_id {
-            RAYDIUM_AMM_V4_PROGRAM_ID => 1,
-            _ => 8,
-        };
+        let protocol = EventDispatcher::match_protocol_by_program_id(&program_id);
+        let disc_len = protocol.as_ref().map_or(8, Protocol::discriminator_len);
+        let data = instruction.data();
 
         // 检查指令数据长度（至少需要 disc_len 字节的 discriminator）
-        if !is_cu_program && instruction.data.len() < disc_len {
+        if !is_common_program && data.len() < disc_len {
             return Ok(None);
         }
         // 创建元数据
         let timestamp = block_time.unwrap_or(Timestamp { seconds: 0, nanos: 0 });
         let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
-        let metadata = EventMetadata::new(
+        let mut metadata = EventMetadata::new(
             signature,
             slot,
             timestamp.seconds,
@@ -724,30 +1253,53 @@ impl EventParser {
             recv_us,
             transaction_index,
         );
+        // 引用了补齐的账户时标记事件，避免把默认值当作真实地址使用
+        if instruction.accounts().iter().any(|&idx| idx as usize >= ctx.resolved_accounts_len) {
+            log::debug!(
+                "instruction {}/{:?} of {} references accounts beyond the {} resolved keys, padded with Pubkey::default()",
+                outer_index,
+                inner_index,
+                signature,
+                ctx.resolved_accounts_len
+            );
+            metadata.has_padded_accounts = true;
+        }
 
-        if is_cu_program {
-            return Ok(EventDispatcher::dispatch_compute_budget_instruction(
-                &instruction.data,
-                metadata.clone(),
+        // 账户公钥列表，确定要解析该指令后才构建
+        let account_pubkeys = || -> Vec<Pubkey> {
+            instruction
+                .accounts()
+                .iter()
+                .filter_map(|&idx| accounts.get(idx as usize).copied())
+                .collect()
+        };
+
+        if is_common_program {
+            // Compute Budget 指令不读取账户，只有 ATA / System 指令需要账户列表
+            let account_pubkeys = if program_id == COMPUTE_BUDGET_PROGRAM_ID {
+                Vec::new()
+            } else {
+                account_pubkeys()
+            };
+            return Ok(EventDispatcher::dispatch_common_instruction(
+                &program_id,
+                data,
+                &account_pubkeys,
+                metadata,
+                ctx.options.sol_transfer_watchlist.as_deref(),
             ));
         }
 
-        // 使用 EventDispatcher 匹配协议
-        let protocol = match EventDispatcher::match_protocol_by_program_id(&program_id) {
+        // 非通用程序指令必须属于已知协议
+        let protocol = match protocol {
             Some(p) => p,
             None => return Ok(None),
         };
+        let account_pubkeys = account_pubkeys();
 
         // 提取 discriminator 和数据
-        let instruction_discriminator = &instruction.data[..disc_len];
-        let instruction_data = &instruction.data[disc_len..];
-
-        // 构建账户公钥列表
-        let account_pubkeys: Vec<Pubkey> = instruction
-            .accounts
-            .iter()
-            .filter_map(|&idx| accounts.get(idx as usize).copied())
-            .collect();
+        let instruction_discriminator = &data[..disc_len];
+        let instruction_data = &data[disc_len..];
 
         // 使用 EventDispatcher 解析 instruction 事件
         let mut event = match EventDispatcher::dispatch_instruction(
@@ -758,52 +1310,56 @@ impl EventParser {
             metadata.clone(),
         ) {
             Some(e) => e,
+            None if ctx.options.emit_unknown => {
+                return Ok(Self::unknown_instruction_event(&protocol, data, metadata))
+            }
             None => return Ok(None),
         };
 
-        if let Some(config) = swap_cu_parse_config.filter(|config| {
-            config.enabled
-                && config.is_target_swap(&protocol, &program_id, &instruction.data)
-                && !log_messages.is_empty()
-        }) {
-            if swap_cu_index.is_none() {
-                *swap_cu_index = Some(build_swap_cu_index(
-                    config,
-                    log_messages,
-                    compiled_instructions,
-                    accounts,
-                    all_inner_instructions,
-                ));
-            }
-            if let Some(cu) =
-                swap_cu_index.as_ref().and_then(|index| index.get(outer_index, inner_index))
-            {
-                event.metadata_mut().swap_compute_units = Some(cu);
-            }
+        if let Some(cu) =
+            ctx.swap_compute_units(&protocol, &program_id, data, accounts, outer_index, inner_index)
+        {
+            event.metadata_mut().swap_compute_units = Some(cu);
         }
 
+        let log_messages = ctx.log_messages;
         enrich_event_from_program_data(
             &mut event,
             &protocol,
-            program_data_index,
+            ctx.program_data_index(&protocol, data),
+            log_messages,
             outer_index,
             inner_index,
         );
 
-        // 处理 inner instructions（默认不提取 swap_data，保持 metadata.swap_data=None）
+        // inner instruction 中位于当前指令之后的部分
+        let start_idx = inner_index
+            .and_then(|i| if i >= 0 { Some((i as usize).saturating_add(1)) } else { None })
+            .unwrap_or(0);
+        let following_instructions = inner_instructions
+            .map_or(&[][..], |inner| inner.instructions())
+            .get(start_idx..)
+            .unwrap_or(&[]);
+
+        // 处理 inner instructions（swap_data 的转账提取见 `extract_swap_data`）
         let mut inner_instruction_event: Option<DexEvent> = None;
-        if let Some(inner_instructions_ref) = inner_instructions {
-            let start_idx = inner_index
-                .and_then(|i| if i >= 0 { Some((i as usize).saturating_add(1)) } else { None })
-                .unwrap_or(0);
-            for inner_instruction in inner_instructions_ref.instructions.iter().skip(start_idx) {
-                let inner_data = &inner_instruction.data;
-                // 检查长度（需要 16 字节的 discriminator）
-                if inner_data.len() < 16 {
+        if let Some(inner_disc_len) = protocol.inner_discriminator_len() {
+            for inner_instruction in following_instructions {
+                let inner_data = inner_instruction.data();
+                // 检查长度（需要 inner_disc_len 字节的 discriminator）
+                if inner_data.len() < inner_disc_len {
+                    // 只统计当前协议程序自己的 inner instruction，Token 转账等其他程序的指令本来就短
+                    if accounts
+                        .get(inner_instruction.program_id_index())
+                        .and_then(EventDispatcher::match_protocol_by_program_id)
+                        .is_some_and(|inner_protocol| inner_protocol == protocol)
+                    {
+                        MetricsManager::global().increment_short_inner_instructions();
+                    }
                     continue;
                 }
-                let inner_discriminator = &inner_data[..16];
-                let inner_instruction_data = &inner_data[16..];
+                let inner_discriminator = &inner_data[..inner_disc_len];
+                let inner_instruction_data = &inner_data[inner_disc_len..];
                 if let Some(inner_event) = EventDispatcher::dispatch_inner_instruction(
                     protocol.clone(),
                     inner_discriminator,
@@ -816,13 +1372,11 @@ impl EventParser {
             }
         }
 
-        // 特殊处理: PumpFun MIGRATE 指令需要 inner instruction data
-        if matches!(protocol, Protocol::PumpFun) {
-            const PUMPFUN_MIGRATE_IX: &[u8] = &[155, 234, 231, 146, 236, 158, 162, 30];
-            if instruction_discriminator == PUMPFUN_MIGRATE_IX && inner_instruction_event.is_none()
-            {
-                return Ok(None);
-            }
+        // 特殊处理: 部分指令（如 PumpFun MIGRATE）缺少 inner instruction 事件时直接丢弃
+        if inner_instruction_event.is_none()
+            && requires_inner_event(&protocol, instruction_discriminator)
+        {
+            return Ok(None);
         }
 
         // 合并事件
@@ -830,166 +1384,125 @@ impl EventParser {
             merge(&mut event, inner_instruction_event);
         }
 
+        if ctx.options.extract_swap_data {
+            // 同一层级中位于 swap 之前的指令，用于识别 swap 前的 SOL 包装
+            let swap_data = match inner_index {
+                Some(inner_index) => parse_swap_data_from_following_instructions(
+                    &event,
+                    inner_instructions
+                        .map_or(&[][..], |inner| inner.instructions())
+                        .get(..inner_index.max(0) as usize)
+                        .unwrap_or(&[]),
+                    following_instructions,
+                    accounts,
+                    ctx.options.verify_transfer_mints,
+                ),
+                None => parse_swap_data_from_following_instructions(
+                    &event,
+                    ctx.compiled_instructions.get(..outer_index.max(0) as usize).unwrap_or(&[]),
+                    following_instructions,
+                    accounts,
+                    ctx.options.verify_transfer_mints,
+                ),
+            };
+            if let Some(swap_data) = swap_data {
+                event.metadata_mut().swap_data = Some(swap_data);
+            }
+        }
+        fill_swap_mints_from_direction(&mut event);
+
+        fill_claim_amount_from_transfers(
+            &mut event,
+            accounts,
+            following_instructions
+                .iter()
+                .map(|ix| (ix.program_id_index(), ix.accounts(), ix.data())),
+        );
+
         // 设置处理时间（使用高性能时钟）
         event.metadata_mut().handle_us = elapsed_micros_since(recv_us);
         event = Self::process_event(event, bot_wallet);
+        apply_mint_registry(&mut event);
+        PoolFeeRegistry::global().annotate_event(&mut event);
+        record_lb_pair_bin_step(&event);
         Ok(Some(event))
     }
 
     // ================================================================================================
-    // Standard Instruction Processing
+    // Helper Functions
     // ================================================================================================
 
-    /// Parse events from standard Solana instruction
-    ///
-    /// Similar to gRPC instruction parsing but works with standard CompiledInstruction format.
-    /// Used when parsing VersionedTransaction or RPC data.
-    #[allow(clippy::too_many_arguments)]
-    fn parse_events_from_instruction(
-        protocols: &[Protocol],
-        event_type_filter: Option<&EventTypeFilter>,
-        instruction: &CompiledInstruction,
-        accounts: &[Pubkey],
-        signature: Signature,
-        slot: u64,
-        block_time: Option<Timestamp>,
-        recv_us: i64,
-        outer_index: i64,
-        inner_index: Option<i64>,
-        bot_wallet: Option<Pubkey>,
-        transaction_index: Option<u64>,
-        inner_instructions: Option<&InnerInstructions>,
-        callback: Arc<dyn for<'a> Fn(&'a DexEvent) + Send + Sync>,
-    ) -> anyhow::Result<()> {
-        // 添加边界检查以防止越界访问
-        let program_id_index = instruction.program_id_index as usize;
-        if program_id_index >= accounts.len() {
-            return Ok(());
-        }
-        let program_id = accounts[program_id_index];
-        if !Self::should_handle(protocols, event_type_filter, &program_id) {
-            return Ok(());
-        }
-
-        let is_cu_program = EventDispatcher::is_compute_budget_program(&program_id);
-
-        let disc_len = match program_id {
-            RAYDIUM_AMM_V4_PROGRAM_ID => 1,
-            _ => 8,
-        };
-
-        // 检查指令数据长度（至少需要 8 字节的 discriminator）
-        if !is_cu_program && instruction.data.len() < disc_len {
-            return Ok(());
-        }
-
-        // 创建元数据
-        let timestamp = block_time.unwrap_or(Timestamp { seconds: 0, nanos: 0 });
-        let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
-        let metadata = EventMetadata::new(
-            signature,
-            slot,
-            timestamp.seconds,
-            block_time_ms,
-            Default::default(), // protocol will be set by dispatcher
-            Default::default(), // event_type will be set by dispatcher
-            program_id,
-            outer_index,
-            inner_index,
-            recv_us,
-            transaction_index,
-        );
+    /// 按 gRPC 交易重建账户列表：静态账户、loaded writable、loaded readonly，跳过长度不为 32 的条目
+    #[cfg(feature = "grpc")]
+    fn grpc_loaded_account_keys(
+        account_keys: &[Vec<u8>],
+        meta: Option<&yellowstone_grpc_proto::prelude::TransactionStatusMeta>,
+    ) -> Vec<Pubkey> {
+        let loaded = meta.into_iter().flat_map(|meta| {
+            meta.loaded_writable_addresses.iter().chain(&meta.loaded_readonly_addresses)
+        });
+        account_keys
+            .iter()
+            .chain(loaded)
+            .filter(|account| account.len() == 32)
+            .map(|account| Pubkey::try_from(account.as_slice()).unwrap_or_default())
+            .collect()
+    }
 
-        if is_cu_program {
-            if let Some(event) = EventDispatcher::dispatch_compute_budget_instruction(
-                &instruction.data,
-                metadata.clone(),
-            ) {
-                callback(&event);
-            }
-            return Ok(());
+    /// 补齐 accounts（使用 Pubkey::default()），覆盖外层和 inner instruction 引用的所有索引，
+    /// 避免越界索引被跳过后账户位置错位
+    fn pad_account_keys<I: InstructionView, G: InnerInstructionsView>(
+        accounts: &[Pubkey],
+        compiled_instructions: &[I],
+        all_inner_instructions: &[G],
+    ) -> Vec<Pubkey> {
+        let max_idx = compiled_instructions
+            .iter()
+            .map(Self::max_account_index)
+            .chain(
+                all_inner_instructions
+                    .iter()
+                    .flat_map(|group| group.instructions().iter().map(Self::max_account_index)),
+            )
+            .max()
+            .unwrap_or(0);
+        let mut accounts = accounts.to_vec();
+        if max_idx >= accounts.len() {
+            accounts.resize(max_idx + 1, Pubkey::default());
         }
+        accounts
+    }
 
-        // 使用 EventDispatcher 匹配协议
-        let protocol = match EventDispatcher::match_protocol_by_program_id(&program_id) {
-            Some(p) => p,
-            None => return Ok(()),
-        };
-
-        // 提取 discriminator 和数据
-        let instruction_discriminator = &instruction.data[..disc_len];
-        let instruction_data = &instruction.data[disc_len..];
-
-        // 构建账户公钥列表
-        let account_pubkeys: Vec<Pubkey> = instruction
-            .accounts
+    /// 打印重建的账户列表，`resolved_len` 之后的条目为补齐的默认值
+    fn log_resolved_account_keys(signature: Signature, accounts: &[Pubkey], resolved_len: usize) {
+        let keys = accounts
             .iter()
-            .filter_map(|&idx| accounts.get(idx as usize).copied())
-            .collect();
-
-        // 使用 EventDispatcher 解析 instruction 事件
-        let mut event = match EventDispatcher::dispatch_instruction(
-            protocol.clone(),
-            instruction_discriminator,
-            instruction_data,
-            &account_pubkeys,
-            metadata.clone(),
-        ) {
-            Some(e) => e,
-            None => return Ok(()),
-        };
-
-        // 处理 inner instructions（默认不提取 swap_data，保持 metadata.swap_data=None）
-        let mut inner_instruction_event: Option<DexEvent> = None;
-        if let Some(inner_instructions_ref) = inner_instructions {
-            let start_idx = inner_index
-                .and_then(|i| if i >= 0 { Some((i as usize).saturating_add(1)) } else { None })
-                .unwrap_or(0);
-            for inner_instruction in inner_instructions_ref.instructions.iter().skip(start_idx) {
-                let inner_data = &inner_instruction.instruction.data;
-                // 检查长度（需要 16 字节的 discriminator）
-                if inner_data.len() < 16 {
-                    continue;
-                }
-                let inner_discriminator = &inner_data[..16];
-                let inner_instruction_data = &inner_data[16..];
-                if let Some(inner_event) = EventDispatcher::dispatch_inner_instruction(
-                    protocol.clone(),
-                    inner_discriminator,
-                    inner_instruction_data,
-                    metadata.clone(),
-                ) {
-                    inner_instruction_event = Some(inner_event);
-                    break;
+            .enumerate()
+            .map(|(index, account)| {
+                if index < resolved_len {
+                    format!("{index}: {account}")
+                } else {
+                    format!("{index}: {account} (padding)")
                 }
-            }
-        }
-
-        // 特殊处理: PumpFun MIGRATE 指令需要 inner instruction data
-        if matches!(protocol, Protocol::PumpFun) {
-            const PUMPFUN_MIGRATE_IX: &[u8] = &[155, 234, 231, 146, 236, 158, 162, 30];
-            if instruction_discriminator == PUMPFUN_MIGRATE_IX && inner_instruction_event.is_none()
-            {
-                return Ok(());
-            }
-        }
-
-        // 合并事件
-        if let Some(inner_instruction_event) = inner_instruction_event {
-            merge(&mut event, inner_instruction_event);
-        }
-
-        // 设置处理时间（使用高性能时钟）
-        event.metadata_mut().handle_us = elapsed_micros_since(recv_us);
-        event = Self::process_event(event, bot_wallet);
-        callback(&event);
-
-        Ok(())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        log::info!(
+            "account keys for {}: {} resolved, {} padded [{}]",
+            signature,
+            resolved_len,
+            accounts.len() - resolved_len,
+            keys
+        );
     }
 
-    // ================================================================================================
-    // Helper Functions
-    // ================================================================================================
+    /// 指令账户引用的最大索引
+    ///
+    /// 不含程序 ID：越界的程序 ID 不能补成默认值（即 System Program），而是直接跳过该指令
+    #[inline]
+    fn max_account_index<X: InstructionView>(instruction: &X) -> usize {
+        instruction.accounts().iter().max().copied().unwrap_or(0) as usize
+    }
 
     /// Check if instruction should be processed based on protocol filter
     ///
@@ -1002,13 +1515,49 @@ impl EventParser {
         // 使用 EventDispatcher 来匹配协议
         if let Some(protocol) = EventDispatcher::match_protocol_by_program_id(program_id) {
             protocols.contains(&protocol)
-        } else if EventDispatcher::is_compute_budget_program(program_id) {
+        } else if EventDispatcher::is_common_program(program_id) {
             return true;
         } else {
             false
         }
     }
 
+    /// 协议解析器没有产出事件的指令中，discriminator 不属于该协议任何已解析指令的视为未知；
+    /// 已知指令因数据不足等原因解析失败、emit_cpi 事件不会被报告
+    fn unknown_instruction_event(
+        protocol: &Protocol,
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<DexEvent> {
+        let known = data.starts_with(&EMIT_CPI_EVENT_TAG)
+            || protocol.capabilities().iter().any(|capability| {
+                capability.kind == CapabilityKind::Instruction
+                    && data.starts_with(capability.discriminator)
+            });
+        if known {
+            return None;
+        }
+        MetricsManager::global().increment_unknown_instructions(&metadata.program_id);
+        Some(DexEvent::UnknownInstructionEvent(CommonEventParser::new_unknown_instruction_event(
+            protocol.into(),
+            data,
+            protocol.discriminator_len(),
+            metadata,
+        )))
+    }
+
+    /// 外层 System Program 指令只在 `options` 带有开启的 `SolTransferWatchlist` 时解析
+    #[inline]
+    fn should_handle_sol_transfer(
+        options: &ParseOptions,
+        program_id: &Pubkey,
+        inner_index: Option<i64>,
+    ) -> bool {
+        inner_index.is_none()
+            && program_id == &SYSTEM_PROGRAM_ID
+            && options.sol_transfer_watchlist.as_ref().is_some_and(|w| w.is_enabled())
+    }
+
     #[inline]
     fn extract_swap_mints(event: &DexEvent) -> Option<(Pubkey, Pubkey)> {
         let (from_mint, to_mint) = match event {
@@ -1180,6 +1729,45 @@ impl EventParser {
         Self::is_arb_account_segment(&account_legs) || Self::is_arb_mint_segment(&mint_legs)
     }
 
+    /// 同一交易内同一钱包在同一池子中双向交易同一 mint 对（买入后又卖出同一代币），常见的刷量模式
+    ///
+    /// 按 `DexEvent::trader()` 与 `pool_id()` 比较，两者任一未知的 swap 不参与判断；
+    /// 在不同池子之间买卖的往返（跨池套利）不算。
+    fn is_self_trade_swap_events(events: &[DexEvent]) -> bool {
+        let mut legs: Vec<(Pubkey, Pubkey, MintLeg)> = Vec::new();
+        for event in events {
+            let (Some(trader), Some(pool)) = (event.trader(), event.pool_id()) else {
+                continue;
+            };
+            let mints = event
+                .metadata()
+                .swap_data
+                .as_ref()
+                .filter(|swap_data| {
+                    swap_data.from_mint != Pubkey::default()
+                        && swap_data.to_mint != Pubkey::default()
+                })
+                .map(|swap_data| (swap_data.from_mint, swap_data.to_mint))
+                .or_else(|| Self::extract_swap_mints(event));
+            let Some((from_mint, to_mint)) = mints else {
+                continue;
+            };
+            if from_mint == to_mint {
+                continue;
+            }
+            if legs.iter().any(|(leg_trader, leg_pool, leg)| {
+                *leg_trader == trader
+                    && *leg_pool == pool
+                    && leg.from_mint == to_mint
+                    && leg.to_mint == from_mint
+            }) {
+                return true;
+            }
+            legs.push((trader, pool, MintLeg { from_mint, to_mint }));
+        }
+        false
+    }
+
     #[inline]
     fn is_arb_mint_segment(legs: &[MintLeg]) -> bool {
         legs.len() >= 2
@@ -1200,6 +1788,7 @@ impl EventParser {
 
     fn instruction_needs_program_data(protocol: &Protocol, data: &[u8]) -> bool {
         match protocol {
+            #[cfg(feature = "protocol-pancakeswap")]
             Protocol::PancakeSwap => {
                 if data.len() < 8 {
                     return false;
@@ -1208,6 +1797,7 @@ impl EventParser {
                     &data[..8],
                 )
             }
+            #[cfg(feature = "protocol-raydium-cpmm")]
             Protocol::RaydiumCpmm => {
                 if data.len() < 8 {
                     return false;
@@ -1216,6 +1806,7 @@ impl EventParser {
                     &data[..8],
                 )
             }
+            #[cfg(feature = "protocol-raydium-clmm")]
             Protocol::RaydiumClmm => {
                 if data.len() < 8 {
                     return false;
@@ -1224,6 +1815,7 @@ impl EventParser {
                     &data[..8],
                 )
             }
+            #[cfg(feature = "protocol-whirlpool")]
             Protocol::Whirlpool => {
                 if data.len() < 8 {
                     return false;
@@ -1294,17 +1886,11 @@ impl EventParser {
                 DexEvent::PumpSwapBuyEvent(trade_info)
             }
             DexEvent::PumpSwapBuyExactQuoteInEvent(mut trade_info) => {
+                let from_amount = trade_info.resolved_quote_in();
+                let to_amount = trade_info.resolved_base_out();
                 if let Some(swap_data) = trade_info.metadata.swap_data.as_mut() {
-                    swap_data.from_amount = if trade_info.user_quote_amount_in > 0 {
-                        trade_info.user_quote_amount_in
-                    } else {
-                        trade_info.quote_amount_in
-                    };
-                    swap_data.to_amount = if trade_info.actual_base_amount_out > 0 {
-                        trade_info.actual_base_amount_out
-                    } else {
-                        trade_info.min_base_amount_out
-                    };
+                    swap_data.from_amount = from_amount;
+                    swap_data.to_amount = to_amount;
                 }
                 DexEvent::PumpSwapBuyExactQuoteInEvent(trade_info)
             }
@@ -1357,6 +1943,36 @@ impl EventParser {
                 trade_info.is_bot = Some(trade_info.payer) == bot_wallet;
                 DexEvent::BonkTradeEvent(trade_info)
             }
+            DexEvent::RaydiumClmmSwapEvent(mut trade_info) => {
+                if let Some(swap_data) = trade_info.metadata.swap_data.as_mut() {
+                    (swap_data.from_amount, swap_data.to_amount) =
+                        SwapDirection::from_a_to_b(trade_info.zero_for_one)
+                            .order(trade_info.amount_0, trade_info.amount_1);
+                }
+                DexEvent::RaydiumClmmSwapEvent(trade_info)
+            }
+            DexEvent::RaydiumClmmSwapV2Event(mut trade_info) => {
+                if let Some(swap_data) = trade_info.metadata.swap_data.as_mut() {
+                    (swap_data.from_amount, swap_data.to_amount) =
+                        SwapDirection::from_a_to_b(trade_info.zero_for_one)
+                            .order(trade_info.amount_0, trade_info.amount_1);
+                }
+                DexEvent::RaydiumClmmSwapV2Event(trade_info)
+            }
+            DexEvent::RaydiumClmmSwapRouterBaseInEvent(mut trade_info) => {
+                let amounts = trade_info
+                    .hops
+                    .first()
+                    .zip(trade_info.amount_out())
+                    .map(|(first, amount_out)| (first.amounts().0, amount_out));
+                if let (Some(swap_data), Some((from_amount, to_amount))) =
+                    (trade_info.metadata.swap_data.as_mut(), amounts)
+                {
+                    swap_data.from_amount = from_amount;
+                    swap_data.to_amount = to_amount;
+                }
+                DexEvent::RaydiumClmmSwapRouterBaseInEvent(trade_info)
+            }
             DexEvent::WhirlpoolSwapEvent(mut trade_info) => {
                 if let Some(swap_data) = trade_info.metadata.swap_data.as_mut() {
                     swap_data.from_amount = trade_info.input_amount;
@@ -1376,11 +1992,153 @@ impl EventParser {
     }
 }
 
+/// 是否必须由 inner instruction 事件补全才能输出该指令事件
+///
+/// PumpFun MIGRATE 的外层指令只有账户，迁移数额全部来自 CPI 事件，
+/// 两条解析路径（gRPC / VersionedTransaction）都通过这里判断，避免规则不一致
+pub(crate) fn requires_inner_event(protocol: &Protocol, discriminator: &[u8]) -> bool {
+    match protocol {
+        Protocol::PumpFun => discriminator == pumpfun_discriminators::MIGRATE_IX,
+        _ => false,
+    }
+}
+
+/// 根据交易方向和池子 mint 对填充 CLMM / Whirlpool 的 swap_data.from_mint/to_mint
+///
+/// - V2 指令自带 mint 账户，直接使用
+/// - V1 指令依赖从池子状态账户更新中学到的 mint 对，未见过该池子时不填充
+fn fill_swap_mints_from_direction(event: &mut DexEvent) {
+    let mints = match event {
+        // CLMM V2 的 input/output vault mint 已经体现了方向
+        DexEvent::RaydiumClmmSwapV2Event(e) => Some((e.input_vault_mint, e.output_vault_mint)),
+        DexEvent::RaydiumClmmSwapEvent(e) => {
+            // zero_for_one 仅在解析到日志后可信
+            if e.amount_0 == 0 && e.amount_1 == 0 {
+                None
+            } else {
+                get_pool_mints(&e.pool_state).map(|(mint_0, mint_1)| {
+                    SwapDirection::from_a_to_b(e.zero_for_one).order(mint_0, mint_1)
+                })
+            }
+        }
+        // 输出 mint 取自最后一跳池子的 mint 对
+        DexEvent::RaydiumClmmSwapRouterBaseInEvent(e) => e.hops.last().and_then(|hop| {
+            get_pool_mints(&hop.pool_state).map(|(mint_0, mint_1)| {
+                (
+                    e.input_token_mint,
+                    SwapDirection::from_a_to_b(hop.zero_for_one).order(mint_0, mint_1).1,
+                )
+            })
+        }),
+        DexEvent::WhirlpoolSwapV2Event(e) => {
+            Some(SwapDirection::from_a_to_b(e.a_to_b).order(e.token_mint_a, e.token_mint_b))
+        }
+        DexEvent::WhirlpoolSwapEvent(e) => get_pool_mints(&e.whirlpool)
+            .map(|(mint_a, mint_b)| SwapDirection::from_a_to_b(e.a_to_b).order(mint_a, mint_b)),
+        _ => None,
+    };
+
+    let Some((from_mint, to_mint)) = mints else {
+        return;
+    };
+    if from_mint == Pubkey::default() || to_mint == Pubkey::default() {
+        return;
+    }
+    let swap_data = event.metadata_mut().swap_data.get_or_insert_with(SwapData::default);
+    swap_data.from_mint = from_mint;
+    swap_data.to_mint = to_mint;
+}
+
+/// Bonk claim 指令不带数量参数，从 vault 转出的第一笔 SPL Token 转账中取实际领取数量
+///
+/// `instructions` 为 (program_id_index, account indices, data)
+fn fill_claim_amount_from_transfers<'a>(
+    event: &mut DexEvent,
+    accounts: &[Pubkey],
+    instructions: impl Iterator<Item = (usize, &'a [u8], &'a [u8])>,
+) {
+    let (vault, amount) = match event {
+        DexEvent::BonkClaimVestedTokenEvent(e) => (e.base_vault, &mut e.amount),
+        DexEvent::BonkClaimPlatformFeeEvent(e) => (e.quote_vault, &mut e.amount),
+        _ => return,
+    };
+    for (program_id_index, account_indices, data) in instructions {
+        let Some(program_id) = accounts.get(program_id_index) else {
+            continue;
+        };
+        if *program_id != TOKEN_PROGRAM_ID && *program_id != TOKEN_2022_PROGRAM_ID {
+            continue;
+        }
+        if let Some((source, _, transfer_amount)) =
+            decode_token_transfer(account_indices, data, accounts)
+        {
+            if source == vault {
+                *amount = transfer_amount;
+                return;
+            }
+        }
+    }
+}
+
+/// 把一条 Program data 日志解码为 swap 事件，协议不只依赖日志或日志不是 swap 事件时返回 None
+fn log_swap_event(protocol: &Protocol, base64: &str, metadata: &EventMetadata) -> Option<DexEvent> {
+    match protocol {
+        #[cfg(feature = "protocol-raydium-clmm")]
+        Protocol::RaydiumClmm => {
+            use crate::streaming::event_parser::protocols::raydium_clmm::events::RaydiumClmmSwapEvent;
+            use crate::streaming::event_parser::protocols::raydium_clmm::parser::parse_swap_event_from_log;
+            let log_data = parse_swap_event_from_log(base64)?;
+            let mut metadata = metadata.clone();
+            metadata.event_type = EventType::RaydiumClmmSwap;
+            Some(DexEvent::RaydiumClmmSwapEvent(RaydiumClmmSwapEvent {
+                metadata,
+                sender: log_data.sender,
+                token_account_0: log_data.token_account_0,
+                token_account_1: log_data.token_account_1,
+                amount_0: log_data.amount_0,
+                transfer_fee_0: log_data.transfer_fee_0,
+                amount_1: log_data.amount_1,
+                transfer_fee_1: log_data.transfer_fee_1,
+                zero_for_one: log_data.zero_for_one,
+                sqrt_price_x64: log_data.sqrt_price_x64,
+                liquidity: log_data.liquidity,
+                tick: log_data.tick,
+                pool_state: log_data.pool_state,
+                ..Default::default()
+            }))
+        }
+        #[cfg(feature = "protocol-whirlpool")]
+        Protocol::Whirlpool => {
+            use crate::streaming::event_parser::protocols::whirlpool::events::WhirlpoolSwapEvent;
+            use crate::streaming::event_parser::protocols::whirlpool::parser::parse_traded_event_from_log;
+            let log_data = parse_traded_event_from_log(base64)?;
+            let mut metadata = metadata.clone();
+            metadata.event_type = EventType::WhirlpoolSwap;
+            Some(DexEvent::WhirlpoolSwapEvent(WhirlpoolSwapEvent {
+                metadata,
+                a_to_b: log_data.a_to_b,
+                pre_sqrt_price: log_data.pre_sqrt_price,
+                post_sqrt_price: log_data.post_sqrt_price,
+                input_amount: log_data.input_amount,
+                output_amount: log_data.output_amount,
+                input_transfer_fee: log_data.input_transfer_fee,
+                output_transfer_fee: log_data.output_transfer_fee,
+                lp_fee: log_data.lp_fee,
+                protocol_fee: log_data.protocol_fee,
+                whirlpool: log_data.whirlpool,
+                ..Default::default()
+            }))
+        }
+        _ => None,
+    }
+}
+
 /// 根据协议类型，从 program data 日志中提取额外字段并填充到事件中
 fn enrich_event_from_program_data(
     event: &mut DexEvent,
     protocol: &Protocol,
     program_data_index: Option<&ProgramDataIndex>,
+    log_messages: &[String],
     outer_index: i64,
     inner_index: Option<i64>,
 ) {
@@ -1399,6 +2157,7 @@ fn enrich_event_from_program_data(
     };
 
     match protocol {
+        #[cfg(feature = "protocol-pancakeswap")]
         Protocol::PancakeSwap => {
             use crate::streaming::event_parser::protocols::pancakeswap::parser::parse_swap_event_from_program_data;
             match event {
@@ -1441,6 +2200,7 @@ fn enrich_event_from_program_data(
                 _ => {}
             }
         }
+        #[cfg(feature = "protocol-raydium-cpmm")]
         Protocol::RaydiumCpmm => {
             use crate::streaming::event_parser::protocols::raydium_cpmm::parser::parse_swap_event_from_program_data;
             if let DexEvent::RaydiumCpmmSwapEvent(swap_event) = event {
@@ -1454,15 +2214,29 @@ fn enrich_event_from_program_data(
                     swap_event.input_transfer_fee = log_data.input_transfer_fee;
                     swap_event.output_transfer_fee = log_data.output_transfer_fee;
                     swap_event.base_input = log_data.base_input;
+                    if log_data.base_input != swap_event.swap_base_input {
+                        log::warn!(
+                            "Raydium CPMM swap direction mismatch: instruction swap_base_input={}, log base_input={}, signature={}",
+                            swap_event.swap_base_input,
+                            log_data.base_input,
+                            swap_event.metadata.signature
+                        );
+                    }
                     swap_event.trade_fee = log_data.trade_fee;
                     swap_event.creator_fee = log_data.creator_fee;
                     swap_event.creator_fee_on_input = log_data.creator_fee_on_input;
                 }
             }
         }
+        #[cfg(feature = "protocol-raydium-clmm")]
         Protocol::RaydiumClmm => {
-            use crate::streaming::event_parser::protocols::raydium_clmm::parser::parse_swap_event_from_program_data;
+            use crate::streaming::event_parser::protocols::raydium_clmm::parser::{
+                parse_route_hops_from_program_data, parse_swap_event_from_program_data,
+            };
             match event {
+                DexEvent::RaydiumClmmSwapRouterBaseInEvent(route_event) => {
+                    route_event.hops = parse_route_hops_from_program_data(item, log_messages);
+                }
                 DexEvent::RaydiumClmmSwapEvent(swap_event) => {
                     if let Some(log_data) =
                         parse_swap_event_from_program_data(item, &swap_event.pool_state)
@@ -1500,6 +2274,7 @@ fn enrich_event_from_program_data(
                 _ => {}
             }
         }
+        #[cfg(feature = "protocol-whirlpool")]
         Protocol::Whirlpool => {
             use crate::streaming::event_parser::protocols::whirlpool::parser::parse_traded_event_from_program_data;
             match event {
@@ -1539,3 +2314,1141 @@ fn enrich_event_from_program_data(
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::core::sol_transfer_watchlist::SolTransferWatchlist;
+    use crate::streaming::event_parser::protocols::pumpfun::PUMPFUN_PROGRAM_ID;
+    use crate::streaming::event_parser::protocols::raydium_cpmm::RAYDIUM_CPMM_PROGRAM_ID;
+    use solana_sdk::message::compiled_instruction::CompiledInstruction;
+
+    /// 单独解析一条指令（没有 inner instructions 和日志）
+    fn parse_single<X: InstructionView>(
+        protocols: &[Protocol],
+        instruction: &X,
+        accounts: &[Pubkey],
+        inner_index: Option<i64>,
+    ) -> Option<DexEvent> {
+        let mut ctx = TransactionContext::<X, InnerInstructions>::new(
+            &[],
+            &[],
+            &[],
+            None,
+            ParseOptions::disabled(),
+            accounts.len(),
+        );
+        EventParser::parse_event_from_instruction(
+            protocols,
+            None,
+            instruction,
+            accounts,
+            Signature::default(),
+            0,
+            None,
+            0,
+            0,
+            inner_index,
+            None,
+            None,
+            None,
+            &mut ctx,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_requires_inner_event() {
+        assert!(requires_inner_event(&Protocol::PumpFun, pumpfun_discriminators::MIGRATE_IX));
+        assert!(!requires_inner_event(&Protocol::PumpFun, pumpfun_discriminators::BUY_IX));
+        assert!(!requires_inner_event(&Protocol::PumpSwap, pumpfun_discriminators::MIGRATE_IX));
+    }
+
+    #[cfg(feature = "protocol-pumpfun")]
+    #[cfg(feature = "grpc")]
+    #[test]
+    fn test_migrate_without_inner_event_dropped_in_both_paths() {
+        // PumpFun MIGRATE 指令：program 位于 index 0，后跟 24 个账户，没有 inner instructions
+        let mut accounts = vec![PUMPFUN_PROGRAM_ID];
+        accounts.extend((0..24).map(|_| Pubkey::new_unique()));
+        let account_indices: Vec<u8> = (1..=24).collect();
+        let data = pumpfun_discriminators::MIGRATE_IX.to_vec();
+        let protocols = [Protocol::PumpFun];
+
+        // 外层指令本身可以解析，丢弃完全由 requires_inner_event 决定
+        let account_pubkeys: Vec<Pubkey> =
+            account_indices.iter().map(|&idx| accounts[idx as usize]).collect();
+        assert!(EventDispatcher::dispatch_instruction(
+            Protocol::PumpFun,
+            &data[..8],
+            &data[8..],
+            &account_pubkeys,
+            EventMetadata::default(),
+        )
+        .is_some());
+
+        let grpc_instruction = yellowstone_grpc_proto::prelude::CompiledInstruction {
+            program_id_index: 0,
+            accounts: account_indices.clone(),
+            data: data.clone(),
+        };
+        assert!(parse_single(&protocols, &grpc_instruction, &accounts, None).is_none());
+
+        let instruction =
+            CompiledInstruction { program_id_index: 0, accounts: account_indices, data };
+        assert!(parse_single(&protocols, &instruction, &accounts, None).is_none());
+    }
+
+    #[cfg(feature = "protocol-pumpfun")]
+    #[test]
+    fn test_pumpfun_collect_creator_fee_merges_cpi_event() {
+        use solana_transaction_status::InnerInstruction;
+
+        // 0: creator，1: creator vault，2: system program，3: event authority，4: PumpFun
+        let mut accounts: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        accounts.push(PUMPFUN_PROGRAM_ID);
+        let mut cpi_event = pumpfun_discriminators::COLLECT_CREATOR_FEE_EVENT.to_vec();
+        cpi_event.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        cpi_event.extend_from_slice(accounts[0].as_ref());
+        cpi_event.extend_from_slice(&12_345u64.to_le_bytes());
+        let outer = vec![CompiledInstruction {
+            program_id_index: 4,
+            accounts: (0..5).collect(),
+            data: pumpfun_discriminators::COLLECT_CREATOR_FEE_IX.to_vec(),
+        }];
+        let inner = vec![InnerInstructions {
+            index: 0,
+            instructions: vec![InnerInstruction {
+                instruction: CompiledInstruction {
+                    program_id_index: 4,
+                    accounts: vec![3],
+                    data: cpi_event,
+                },
+                stack_height: Some(2),
+            }],
+        }];
+
+        let mut events = Vec::new();
+        EventParser::parse_instruction_events(
+            &[Protocol::PumpFun],
+            None,
+            &outer,
+            Signature::default(),
+            None,
+            None,
+            0,
+            &accounts,
+            &inner,
+            &[],
+            None,
+            None,
+            None,
+            ParseOptions::disabled(),
+            &mut |event| events.push(event),
+        )
+        .unwrap();
+
+        assert_eq!(events.len(), 1);
+        let DexEvent::PumpFunClaimCreatorFeeEvent(claim) = &events[0] else {
+            panic!("{:?}", events[0])
+        };
+        assert_eq!(claim.metadata.event_type, EventType::PumpFunClaimCreatorFee);
+        assert_eq!((claim.creator, claim.creator_vault), (accounts[0], accounts[1]));
+        assert_eq!((claim.amount, claim.timestamp), (12_345, 1_700_000_000));
+        assert_eq!(events[0].creator(), Some(accounts[0]));
+    }
+
+    #[test]
+    fn test_unknown_instruction_event_skips_known_discriminators() {
+        use crate::streaming::event_parser::common::{EventType, ProtocolType};
+
+        let program_id = Pubkey::new_unique();
+        let metadata = EventMetadata { program_id, ..Default::default() };
+        let unknown = |protocol: Protocol, data: &[u8]| {
+            EventParser::unknown_instruction_event(&protocol, data, metadata.clone())
+        };
+
+        // 已解析的指令（即使数据不足）和 emit_cpi 事件不算未知
+        assert!(unknown(Protocol::PumpFun, pumpfun_discriminators::BUY_IX).is_none());
+        assert!(unknown(Protocol::PumpFun, pumpfun_discriminators::TRADE_EVENT).is_none());
+
+        let data = [[0xAB; 8].as_slice(), &[1, 2, 3]].concat();
+        let Some(DexEvent::UnknownInstructionEvent(e)) = unknown(Protocol::PumpFun, &data) else {
+            panic!("expected UnknownInstructionEvent");
+        };
+        assert_eq!(e.metadata.event_type, EventType::UnknownInstruction);
+        assert_eq!(e.metadata.protocol, ProtocolType::PumpFun);
+        assert_eq!(
+            (e.program_id, e.discriminator.as_slice(), e.data_len),
+            (program_id, &[0xAB; 8][..], 11)
+        );
+
+        // Raydium AMM V4 使用 1 字节 discriminator
+        let Some(DexEvent::UnknownInstructionEvent(e)) = unknown(Protocol::RaydiumAmmV4, &[200, 1])
+        else {
+            panic!("expected UnknownInstructionEvent");
+        };
+        assert_eq!((e.discriminator, e.data_len), (vec![200], 2));
+    }
+
+    #[test]
+    fn test_swap_failed_event_attributed_to_subscribed_protocol() {
+        let token_program = TOKEN_PROGRAM_ID;
+        let logs = vec![
+            format!("Program {} invoke [1]", PUMPFUN_PROGRAM_ID),
+            "Program log: Instruction: Buy".to_string(),
+            format!("Program {} invoke [2]", token_program),
+            "Program log: Error: insufficient funds".to_string(),
+            format!("Program {} failed: custom program error: 0x1", token_program),
+            format!("Program {} failed: custom program error: 0x1", PUMPFUN_PROGRAM_ID),
+        ];
+
+        let failed_swaps = [(0, PUMPFUN_PROGRAM_ID)];
+        let Some(DexEvent::SwapFailedEvent(event)) = EventParser::parse_swap_failed_event(
+            &[Protocol::PumpFun],
+            &logs,
+            &failed_swaps,
+            Signature::default(),
+            Some(7),
+            None,
+            0,
+            None,
+        ) else {
+            panic!("expected SwapFailedEvent");
+        };
+        assert_eq!(event.metadata.program_id, PUMPFUN_PROGRAM_ID);
+        assert_eq!(event.metadata.outer_index, 0);
+        assert_eq!(event.failed_program, token_program);
+        assert_eq!(event.error, "custom program error: 0x1");
+        assert_eq!(event.error_log.as_deref(), Some("Error: insufficient funds"));
+
+        // 调用栈中没有已订阅协议
+        assert!(EventParser::parse_swap_failed_event(
+            &[Protocol::PumpSwap],
+            &logs,
+            &failed_swaps,
+            Signature::default(),
+            None,
+            None,
+            0,
+            None,
+        )
+        .is_none());
+
+        // 失败的指令不是 swap（例如 create）
+        assert!(EventParser::parse_swap_failed_event(
+            &[Protocol::PumpFun],
+            &logs,
+            &[],
+            Signature::default(),
+            None,
+            None,
+            0,
+            None,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_failed_transaction_suppresses_instruction_events() {
+        use crate::streaming::event_parser::common::EventType;
+        use crate::streaming::event_parser::core::common_event_parser::SetComputeUnitLimitEvent;
+        use crate::streaming::event_parser::protocols::pumpfun::PumpFunTradeEvent;
+
+        let (callback, failed_swaps) = EventParser::suppress_instruction_events();
+
+        // 非 swap 事件不转发也不记录，swap 事件只记录位置
+        callback(DexEvent::SetComputeUnitLimitEvent(SetComputeUnitLimitEvent {
+            metadata: EventMetadata {
+                event_type: EventType::SetComputeUnitLimit,
+                ..Default::default()
+            },
+            units: 200_000,
+        }));
+        callback(DexEvent::PumpFunTradeEvent(PumpFunTradeEvent {
+            metadata: EventMetadata {
+                event_type: EventType::PumpFunBuy,
+                program_id: PUMPFUN_PROGRAM_ID,
+                outer_index: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        }));
+
+        assert_eq!(*failed_swaps.lock(), vec![(2, PUMPFUN_PROGRAM_ID)]);
+    }
+
+    #[cfg(feature = "grpc")]
+    #[tokio::test]
+    async fn test_failed_transaction_emits_no_instruction_events() {
+        use yellowstone_grpc_proto::prelude as grpc;
+
+        // 0: 关注的钱包, 1: 对手方, 2: System Program
+        let mut keys: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+        keys.push(SYSTEM_PROGRAM_ID);
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&5_000u64.to_le_bytes());
+        let grpc_tx = |err: Option<grpc::TransactionError>| SubscribeUpdateTransactionInfo {
+            transaction: Some(grpc::Transaction {
+                message: Some(grpc::Message {
+                    account_keys: keys.iter().map(|key| key.to_bytes().to_vec()).collect(),
+                    instructions: vec![grpc::CompiledInstruction {
+                        program_id_index: 2,
+                        accounts: vec![0, 1],
+                        data: data.clone(),
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            meta: Some(grpc::TransactionStatusMeta { err, ..Default::default() }),
+            ..Default::default()
+        };
+
+        let watchlist = Arc::new(SolTransferWatchlist::new());
+        watchlist.set_enabled(true);
+        watchlist.watch(keys[0]);
+        let options =
+            ParseOptions { sol_transfer_watchlist: Some(watchlist), ..Default::default() };
+        let parse = |grpc_tx| {
+            EventParser::parse_grpc_transaction_to_events_with_options(
+                &[Protocol::PumpFun],
+                None,
+                grpc_tx,
+                Signature::default(),
+                None,
+                None,
+                0,
+                None,
+                None,
+                None,
+                &options,
+            )
+        };
+
+        let succeeded = parse(grpc_tx(None)).await.unwrap().expect("sol transfer event");
+        assert!(matches!(succeeded.events.as_slice(), [DexEvent::SolTransferEvent(_)]));
+
+        // 失败交易中的转账没有生效，不发出非 swap 的指令事件
+        let failed = grpc_tx(Some(grpc::TransactionError { err: vec![1] }));
+        assert!(parse(failed).await.unwrap().is_none());
+    }
+
+    #[cfg(feature = "grpc")]
+    #[test]
+    fn test_ata_create_parsed_without_protocol() {
+        use crate::streaming::event_parser::common::EventType;
+        use crate::streaming::event_parser::core::common_event_parser::ASSOCIATED_TOKEN_PROGRAM_ID;
+
+        // payer, ata, owner, mint, system program, token program, ATA program
+        let mut accounts: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        accounts.push(ASSOCIATED_TOKEN_PROGRAM_ID);
+        let parse = |data: Vec<u8>| {
+            let instruction = yellowstone_grpc_proto::prelude::CompiledInstruction {
+                program_id_index: 6,
+                accounts: (0..6).collect(),
+                data,
+            };
+            parse_single(&[], &instruction, &accounts, Some(1))
+        };
+
+        let Some(DexEvent::AtaCreateEvent(event)) = parse(vec![1]) else {
+            panic!("expected AtaCreateEvent");
+        };
+        assert_eq!(event.metadata.event_type, EventType::AtaCreate);
+        assert_eq!((event.payer, event.ata, event.owner), (accounts[0], accounts[1], accounts[2]));
+        assert_eq!((event.mint, event.token_program), (accounts[3], accounts[5]));
+        assert!(event.idempotent);
+
+        let Some(DexEvent::AtaCreateEvent(event)) = parse(vec![]) else {
+            panic!("expected AtaCreateEvent");
+        };
+        assert!(!event.idempotent);
+
+        // RecoverNested 不是创建指令
+        assert!(parse(vec![2]).is_none());
+    }
+
+    #[cfg(feature = "grpc")]
+    #[tokio::test]
+    async fn test_sol_transfer_only_for_watched_wallets() {
+        use crate::streaming::event_parser::common::EventType;
+        use yellowstone_grpc_proto::prelude as grpc;
+
+        // 0: 关注的钱包, 1: 对手方, 2/3: 无关钱包, 4: System Program；交易不包含任何协议
+        let mut keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        keys.push(SYSTEM_PROGRAM_ID);
+        let transfer = |from: u8, to: u8, lamports: u64| {
+            let mut data = 2u32.to_le_bytes().to_vec();
+            data.extend_from_slice(&lamports.to_le_bytes());
+            (vec![from, to], data)
+        };
+        let grpc_tx = || {
+            let outer = [transfer(0, 1, 5_000), transfer(2, 3, 7_000), transfer(1, 0, 9_000)];
+            let (accounts, data) = transfer(0, 3, 1_000);
+            SubscribeUpdateTransactionInfo {
+                transaction: Some(grpc::Transaction {
+                    message: Some(grpc::Message {
+                        account_keys: keys.iter().map(|key| key.to_bytes().to_vec()).collect(),
+                        instructions: outer
+                            .into_iter()
+                            .map(|(accounts, data)| grpc::CompiledInstruction {
+                                program_id_index: 4,
+                                accounts,
+                                data,
+                            })
+                            .collect(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                meta: Some(grpc::TransactionStatusMeta {
+                    inner_instructions: vec![grpc::InnerInstructions {
+                        index: 0,
+                        instructions: vec![grpc::InnerInstruction {
+                            program_id_index: 4,
+                            accounts,
+                            data,
+                            stack_height: Some(2),
+                        }],
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+        };
+        let parse = |grpc_tx, options: &ParseOptions| {
+            let options = options.clone();
+            async move {
+                EventParser::parse_grpc_transaction_to_events_with_options(
+                    &[Protocol::PumpFun],
+                    None,
+                    grpc_tx,
+                    Signature::default(),
+                    None,
+                    None,
+                    0,
+                    None,
+                    None,
+                    None,
+                    &options,
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        let watchlist = Arc::new(SolTransferWatchlist::new());
+        watchlist.set_enabled(true);
+        let options =
+            ParseOptions { sol_transfer_watchlist: Some(watchlist.clone()), ..Default::default() };
+        assert!(parse(grpc_tx(), &options).await.is_none());
+
+        // 只发出涉及关注钱包的外层转账，inner instruction 中的转账不发出
+        watchlist.watch(keys[0]);
+        let tx = parse(grpc_tx(), &options).await.expect("sol transfer events");
+        let transfers: Vec<_> = tx
+            .events
+            .iter()
+            .map(|event| match event {
+                DexEvent::SolTransferEvent(e) => {
+                    assert_eq!(e.metadata.event_type, EventType::SolTransfer);
+                    (e.from, e.to, e.lamports, e.metadata.outer_index)
+                }
+                _ => panic!("expected SolTransferEvent, got {event:?}"),
+            })
+            .collect();
+        assert_eq!(transfers, vec![(keys[0], keys[1], 5_000, 0), (keys[1], keys[0], 9_000, 2)]);
+
+        // 关注列表属于传入的选项，没有关注列表的客户端不受影响
+        assert!(parse(grpc_tx(), ParseOptions::disabled()).await.is_none());
+    }
+
+    #[cfg(feature = "grpc")]
+    #[test]
+    fn test_detect_protocols() {
+        use yellowstone_grpc_proto::prelude as grpc;
+
+        let grpc_tx = SubscribeUpdateTransactionInfo {
+            transaction: Some(grpc::Transaction {
+                message: Some(grpc::Message {
+                    account_keys: vec![
+                        Pubkey::new_unique().to_bytes().to_vec(),
+                        PUMPFUN_PROGRAM_ID.to_bytes().to_vec(),
+                    ],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            meta: Some(grpc::TransactionStatusMeta {
+                loaded_readonly_addresses: vec![RAYDIUM_CPMM_PROGRAM_ID.to_bytes().to_vec()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            EventParser::detect_protocols(&grpc_tx),
+            HashSet::from([Protocol::PumpFun, Protocol::RaydiumCpmm])
+        );
+        assert!(
+            EventParser::detect_protocols(&SubscribeUpdateTransactionInfo::default()).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_inner_event_via_router() {
+        use crate::streaming::event_parser::core::common_event_parser::ASSOCIATED_TOKEN_PROGRAM_ID;
+        use solana_transaction_status::InnerInstruction;
+
+        // 0..6: ATA 创建账户，6: ATA program，7: 路由程序
+        let router = Pubkey::new_unique();
+        let mut accounts: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        accounts.extend([ASSOCIATED_TOKEN_PROGRAM_ID, router]);
+        let create_ata =
+            CompiledInstruction { program_id_index: 6, accounts: (0..6).collect(), data: vec![1] };
+        let outer =
+            vec![create_ata.clone(), CompiledInstruction::new_from_raw_parts(7, vec![], vec![])];
+        let inner = vec![InnerInstructions {
+            index: 1,
+            instructions: vec![InnerInstruction { instruction: create_ata, stack_height: Some(2) }],
+        }];
+
+        let mut events = Vec::new();
+        EventParser::parse_instruction_events(
+            &[],
+            None,
+            &outer,
+            Signature::default(),
+            None,
+            None,
+            0,
+            &accounts,
+            &inner,
+            &[],
+            None,
+            None,
+            None,
+            ParseOptions::disabled(),
+            &mut |event| events.push(event),
+        )
+        .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].metadata().via_router, None);
+        assert_eq!(events[1].metadata().via_router, Some(router));
+    }
+
+    #[cfg(feature = "protocol-raydium-cpmm")]
+    #[cfg(feature = "grpc")]
+    #[tokio::test]
+    async fn test_cpi_only_program_in_loaded_addresses() {
+        use crate::streaming::event_parser::protocols::raydium_cpmm::discriminators;
+        use yellowstone_grpc_proto::prelude as grpc;
+
+        // 静态账户 0..11 + 路由程序 11；CPMM 程序和 observation_state 只在地址查找表中加载，
+        // 外层指令只调用路由程序，CPMM swap 仅以 CPI 出现
+        let mut static_keys: Vec<Pubkey> = (0..11).map(|_| Pubkey::new_unique()).collect();
+        let router = Pubkey::new_unique();
+        static_keys.push(router);
+        let observation_state = Pubkey::new_unique();
+
+        let mut data = discriminators::SWAP_BASE_IN.to_vec();
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&900u64.to_le_bytes());
+        let grpc_tx = |swap_accounts: Vec<u8>| SubscribeUpdateTransactionInfo {
+            transaction: Some(grpc::Transaction {
+                message: Some(grpc::Message {
+                    account_keys: static_keys.iter().map(|key| key.to_bytes().to_vec()).collect(),
+                    instructions: vec![grpc::CompiledInstruction {
+                        program_id_index: 11,
+                        accounts: vec![],
+                        data: vec![],
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            meta: Some(grpc::TransactionStatusMeta {
+                loaded_writable_addresses: vec![observation_state.to_bytes().to_vec()],
+                loaded_readonly_addresses: vec![RAYDIUM_CPMM_PROGRAM_ID.to_bytes().to_vec()],
+                inner_instructions: vec![grpc::InnerInstructions {
+                    index: 0,
+                    instructions: vec![grpc::InnerInstruction {
+                        program_id_index: 13,
+                        accounts: swap_accounts,
+                        data: data.clone(),
+                        stack_height: Some(2),
+                    }],
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let parse = |grpc_tx| async move {
+            EventParser::parse_grpc_transaction_to_events(
+                &[Protocol::RaydiumCpmm],
+                None,
+                grpc_tx,
+                Signature::default(),
+                None,
+                None,
+                0,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap()
+            .expect("cpi swap event")
+        };
+
+        let mut swap_accounts: Vec<u8> = (0..11).collect();
+        swap_accounts.extend([0, 12]);
+        let tx = parse(grpc_tx(swap_accounts.clone())).await;
+        assert_eq!(tx.events.len(), 1);
+        let DexEvent::RaydiumCpmmSwapEvent(swap) = &tx.events[0] else {
+            panic!("expected RaydiumCpmmSwapEvent");
+        };
+        assert_eq!((swap.amount_in, swap.minimum_amount_out), (1_000, 900));
+        assert_eq!(swap.observation_state, observation_state);
+        assert_eq!(swap.metadata.inner_index, Some(0));
+        assert_eq!(swap.metadata.via_router, Some(router));
+        assert!(!swap.metadata.has_padded_accounts);
+
+        // inner instruction 引用了超出账户列表的索引时补默认值，其余账户位置不变
+        swap_accounts[12] = 40;
+        let keys = EventParser::grpc_account_keys(&grpc_tx(swap_accounts.clone()));
+        assert_eq!(keys.len(), 41);
+        assert_eq!(keys[..12], static_keys[..]);
+        assert_eq!((keys[12], keys[13]), (observation_state, RAYDIUM_CPMM_PROGRAM_ID));
+        assert!(keys[14..].iter().all(|key| *key == Pubkey::default()));
+        let tx = parse(grpc_tx(swap_accounts)).await;
+        let DexEvent::RaydiumCpmmSwapEvent(swap) = &tx.events[0] else {
+            panic!("expected RaydiumCpmmSwapEvent");
+        };
+        assert_eq!(swap.observation_state, Pubkey::default());
+        assert_eq!(swap.output_token_mint, static_keys[0]);
+        assert!(swap.metadata.has_padded_accounts);
+    }
+
+    #[cfg(feature = "protocol-meteora-damm-v2")]
+    #[cfg(feature = "grpc")]
+    #[tokio::test]
+    async fn test_grpc_and_versioned_paths_yield_identical_events() {
+        use crate::streaming::event_parser::core::common_event_parser::{
+            ASSOCIATED_TOKEN_PROGRAM_ID, COMPUTE_BUDGET_PROGRAM_ID,
+        };
+        use crate::streaming::event_parser::protocols::meteora_damm_v2::{
+            discriminators, METEORA_DAMM_V2_PROGRAM_ID,
+        };
+        use solana_sdk::message::{Message, VersionedMessage};
+        use solana_transaction_status::InnerInstruction;
+        use yellowstone_grpc_proto::prelude as grpc;
+
+        // 0..15: claim_position_fee 账户，15: DAMM v2，16: Compute Budget，17: ATA program
+        let mut accounts: Vec<Pubkey> = (0..15).map(|_| Pubkey::new_unique()).collect();
+        accounts.extend([
+            METEORA_DAMM_V2_PROGRAM_ID,
+            COMPUTE_BUDGET_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+        ]);
+
+        let mut set_limit = vec![2u8];
+        set_limit.extend_from_slice(&200_000u32.to_le_bytes());
+        let outer = vec![
+            CompiledInstruction { program_id_index: 16, accounts: vec![], data: set_limit },
+            CompiledInstruction {
+                program_id_index: 15,
+                accounts: (0..15).collect(),
+                data: discriminators::CLAIM_POSITION_FEE_IX.to_vec(),
+            },
+        ];
+
+        let mut cpi_event = discriminators::CLAIM_POSITION_FEE_EVENT.to_vec();
+        for account in [accounts[1], accounts[2], accounts[10]] {
+            cpi_event.extend_from_slice(account.as_ref());
+        }
+        cpi_event.extend_from_slice(&1_500u64.to_le_bytes());
+        cpi_event.extend_from_slice(&42u64.to_le_bytes());
+        let inner = vec![
+            CompiledInstruction {
+                program_id_index: 17,
+                accounts: vec![10, 7, 10, 5, 0, 13],
+                data: vec![1],
+            },
+            CompiledInstruction { program_id_index: 15, accounts: vec![14], data: cpi_event },
+        ];
+
+        let protocols = [Protocol::MeteoraDammV2];
+        let signature = Signature::new_unique();
+
+        let transaction = VersionedTransaction {
+            signatures: vec![signature],
+            message: VersionedMessage::Legacy(Message {
+                account_keys: accounts.clone(),
+                instructions: outer.clone(),
+                ..Default::default()
+            }),
+        };
+        let inner_instructions = vec![InnerInstructions {
+            index: 1,
+            instructions: inner
+                .iter()
+                .map(|ix| InnerInstruction { instruction: ix.clone(), stack_height: Some(2) })
+                .collect(),
+        }];
+        let versioned = EventParser::parse_versioned_transaction_to_events(
+            &protocols,
+            None,
+            &transaction,
+            signature,
+            Some(1),
+            None,
+            0,
+            &accounts,
+            &inner_instructions,
+            None,
+            Some(3),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .expect("versioned events");
+
+        let grpc_tx = SubscribeUpdateTransactionInfo {
+            signature: signature.as_ref().to_vec(),
+            transaction: Some(grpc::Transaction {
+                signatures: vec![signature.as_ref().to_vec()],
+                message: Some(grpc::Message {
+                    account_keys: accounts.iter().map(|key| key.to_bytes().to_vec()).collect(),
+                    instructions: outer
+                        .iter()
+                        .map(|ix| grpc::CompiledInstruction {
+                            program_id_index: ix.program_id_index as u32,
+                            accounts: ix.accounts.clone(),
+                            data: ix.data.clone(),
+                        })
+                        .collect(),
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(grpc::TransactionStatusMeta {
+                inner_instructions: vec![grpc::InnerInstructions {
+                    index: 1,
+                    instructions: inner
+                        .iter()
+                        .map(|ix| grpc::InnerInstruction {
+                            program_id_index: ix.program_id_index as u32,
+                            accounts: ix.accounts.clone(),
+                            data: ix.data.clone(),
+                            stack_height: Some(2),
+                        })
+                        .collect(),
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let grpc = EventParser::parse_grpc_transaction_to_events(
+            &protocols,
+            None,
+            grpc_tx,
+            signature,
+            Some(1),
+            None,
+            0,
+            None,
+            Some(3),
+            None,
+        )
+        .await
+        .unwrap()
+        .expect("grpc events");
+
+        let normalize = |mut tx: TxDexEvents| {
+            for event in tx.events.iter_mut() {
+                event.metadata_mut().handle_us = 0;
+            }
+            tx
+        };
+        let (grpc, versioned) = (normalize(grpc), normalize(versioned));
+        assert_eq!(grpc, versioned);
+
+        // compute budget、合并了 CPI 事件的 claim fee、inner 中的 ATA 创建
+        assert_eq!(grpc.events.len(), 3);
+        assert_eq!(grpc.compute_unit_limit, Some(200_000));
+        let DexEvent::MeteoraDammV2ClaimFeeEvent(claim) = &grpc.events[1] else {
+            panic!("expected MeteoraDammV2ClaimFeeEvent");
+        };
+        assert_eq!((claim.fee_a_claimed, claim.fee_b_claimed), (1_500, 42));
+        assert!(matches!(grpc.events[2], DexEvent::AtaCreateEvent(_)));
+
+        // 并行批量解析：结果按输入顺序排列，与单笔解析一致
+        let unrelated = VersionedTransaction::default();
+        let input = VersionedTransactionInput {
+            transaction: &transaction,
+            signature,
+            slot: Some(1),
+            block_time: None,
+            accounts: &accounts,
+            inner_instructions: &inner_instructions,
+            transaction_index: Some(3),
+        };
+        let batch = EventParser::parse_transactions_parallel(
+            &protocols,
+            None,
+            &[VersionedTransactionInput { transaction: &unrelated, accounts: &[], ..input }, input],
+            0,
+            None,
+            None,
+            ParseOptions::disabled(),
+        );
+        assert_eq!(batch.len(), 2);
+        assert!(batch[0].is_empty());
+        let mut parallel = batch[1].clone();
+        for event in parallel.iter_mut() {
+            event.metadata_mut().handle_us = 0;
+        }
+        assert_eq!(parallel, versioned.events);
+    }
+
+    #[cfg(feature = "geyser-plugin")]
+    #[cfg(feature = "protocol-raydium-cpmm")]
+    #[cfg(feature = "grpc")]
+    #[tokio::test]
+    async fn test_replica_and_grpc_paths_yield_identical_events() {
+        use crate::streaming::event_parser::protocols::raydium_cpmm::discriminators;
+        use agave_geyser_plugin_interface::geyser_plugin_interface::ReplicaTransactionInfoV2;
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        use solana_sdk::message::{Message, MessageHeader};
+        use solana_sdk::transaction::{SanitizedTransaction, Transaction};
+        use yellowstone_grpc_proto::prelude as grpc;
+
+        // 0..13: swap_base_input 账户（3 为 pool_state），13: CPMM
+        let mut accounts: Vec<Pubkey> = (0..13).map(|_| Pubkey::new_unique()).collect();
+        accounts.push(RAYDIUM_CPMM_PROGRAM_ID);
+        let mut data = discriminators::SWAP_BASE_IN.to_vec();
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&900u64.to_le_bytes());
+        let swap = CompiledInstruction { program_id_index: 13, accounts: (0..13).collect(), data };
+
+        // 实际成交量只在 SwapEvent 日志中
+        let mut swap_event = discriminators::SWAP_EVENT.to_vec();
+        swap_event.extend_from_slice(accounts[3].as_ref());
+        for amount in [10_000u64, 20_000, 1_000, 950, 0, 0] {
+            swap_event.extend_from_slice(&amount.to_le_bytes());
+        }
+        swap_event.push(1);
+        swap_event.extend_from_slice(&[0; 64]);
+        for fee in [3u64, 0] {
+            swap_event.extend_from_slice(&fee.to_le_bytes());
+        }
+        swap_event.push(1);
+        let logs = vec![
+            format!("Program {} invoke [1]", RAYDIUM_CPMM_PROGRAM_ID),
+            "Program log: Instruction: SwapBaseInput".to_string(),
+            format!("Program data: {}", STANDARD.encode(&swap_event)),
+            format!("Program {} success", RAYDIUM_CPMM_PROGRAM_ID),
+        ];
+
+        let protocols = [Protocol::RaydiumCpmm];
+        let signature = Signature::new_unique();
+        let grpc_tx = SubscribeUpdateTransactionInfo {
+            signature: signature.as_ref().to_vec(),
+            transaction: Some(grpc::Transaction {
+                signatures: vec![signature.as_ref().to_vec()],
+                message: Some(grpc::Message {
+                    account_keys: accounts.iter().map(|key| key.to_bytes().to_vec()).collect(),
+                    instructions: vec![grpc::CompiledInstruction {
+                        program_id_index: 13,
+                        accounts: swap.accounts.clone(),
+                        data: swap.data.clone(),
+                    }],
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(grpc::TransactionStatusMeta {
+                log_messages: logs.clone(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let grpc = EventParser::parse_grpc_transaction_to_events(
+            &protocols,
+            None,
+            grpc_tx,
+            signature,
+            Some(1),
+            None,
+            0,
+            None,
+            Some(3),
+            None,
+        )
+        .await
+        .unwrap()
+        .expect("grpc events");
+
+        let transaction = SanitizedTransaction::from_transaction_for_tests(Transaction {
+            signatures: vec![signature],
+            message: Message {
+                header: MessageHeader {
+                    num_required_signatures: 1,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 1,
+                },
+                account_keys: accounts.clone(),
+                instructions: vec![swap],
+                ..Default::default()
+            },
+        });
+        let meta = solana_transaction_status::TransactionStatusMeta {
+            log_messages: Some(logs),
+            ..Default::default()
+        };
+        let replica = ReplicaTransactionInfoV2 {
+            signature: &signature,
+            is_vote: false,
+            transaction: &transaction,
+            transaction_status_meta: &meta,
+            index: 3,
+        };
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let collected = events.clone();
+        EventParser::parse_replica_transaction(
+            &protocols,
+            None,
+            &replica,
+            1,
+            0,
+            None,
+            None,
+            Arc::new(move |event: DexEvent| collected.lock().push(event)),
+            ParseOptions::disabled(),
+        )
+        .await
+        .unwrap();
+
+        let normalize = |mut events: Vec<DexEvent>| {
+            for event in events.iter_mut() {
+                event.metadata_mut().handle_us = 0;
+            }
+            events
+        };
+        let replica_events = normalize(std::mem::take(&mut *events.lock()));
+        let grpc_events = normalize(grpc.events);
+        assert_eq!(replica_events, grpc_events);
+
+        assert_eq!(grpc_events.len(), 1);
+        let DexEvent::RaydiumCpmmSwapEvent(swap) = &grpc_events[0] else {
+            panic!("expected RaydiumCpmmSwapEvent");
+        };
+        assert_eq!((swap.input_amount, swap.output_amount, swap.trade_fee), (1_000, 950, 3));
+    }
+
+    #[test]
+    fn test_self_trade_detection() {
+        use crate::streaming::event_parser::common::WSOL_MINT;
+        use crate::streaming::event_parser::protocols::pumpfun::PumpFunTradeEvent;
+
+        let (token, user, pool) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let trade_on = |pool: Pubkey, outer_index: i64, from_mint: Pubkey, to_mint: Pubkey| {
+            let mut event = DexEvent::PumpFunTradeEvent(PumpFunTradeEvent {
+                user,
+                bonding_curve: pool,
+                ..Default::default()
+            });
+            let metadata = event.metadata_mut();
+            metadata.outer_index = outer_index;
+            metadata.swap_data = Some(SwapData {
+                from_mint,
+                to_mint,
+                from_amount: 1,
+                to_amount: 1,
+                ..Default::default()
+            });
+            event
+        };
+        let trade =
+            |outer_index, from_mint, to_mint| trade_on(pool, outer_index, from_mint, to_mint);
+
+        // 买入后在同一池子中卖出同一代币
+        let events = vec![trade(0, WSOL_MINT, token), trade(1, token, WSOL_MINT)];
+        assert!(EventParser::is_self_trade_swap_events(&events));
+
+        // 同方向的多次买入不算
+        let events = vec![trade(0, WSOL_MINT, token), trade(1, WSOL_MINT, token)];
+        assert!(!EventParser::is_self_trade_swap_events(&events));
+        assert!(!EventParser::is_self_trade_swap_events(&events[..1]));
+
+        // 在另一个池子卖出是跨池套利，不是刷量
+        let other_pool = Pubkey::new_unique();
+        let events = vec![trade(0, WSOL_MINT, token), trade_on(other_pool, 1, token, WSOL_MINT)];
+        assert!(!EventParser::is_self_trade_swap_events(&events));
+
+        // 不同钱包在同一池子中的买卖不算
+        let mut sell = trade(1, token, WSOL_MINT);
+        if let DexEvent::PumpFunTradeEvent(e) = &mut sell {
+            e.user = Pubkey::new_unique();
+        }
+        let events = vec![trade(0, WSOL_MINT, token), sell];
+        assert!(!EventParser::is_self_trade_swap_events(&events));
+    }
+
+    #[test]
+    fn test_fill_claim_amount_from_transfers() {
+        use crate::streaming::event_parser::protocols::bonk::{
+            BonkClaimPlatformFeeEvent, BonkClaimVestedTokenEvent,
+        };
+
+        let (base_vault, quote_vault, user_token, authority, other_program) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let accounts =
+            vec![TOKEN_PROGRAM_ID, base_vault, quote_vault, user_token, authority, other_program];
+        // SPL Token Transfer: [3, amount]，账户为 source / destination / authority
+        let transfer = |amount: u64| {
+            let mut data = vec![3u8];
+            data.extend_from_slice(&amount.to_le_bytes());
+            data
+        };
+        let (skipped, from_quote, from_base, second_from_base) =
+            (transfer(9), transfer(7), transfer(1_000), transfer(2_000));
+        let (from_base_vault, from_quote_vault): (&[u8], &[u8]) = (&[1, 3, 4], &[2, 3, 4]);
+        let instructions: Vec<(usize, &[u8], &[u8])> = vec![
+            // 非 Token 程序的指令不参与匹配
+            (5, from_base_vault, skipped.as_slice()),
+            (0, from_quote_vault, from_quote.as_slice()),
+            (0, from_base_vault, from_base.as_slice()),
+            (0, from_base_vault, second_from_base.as_slice()),
+        ];
+
+        // 取从 vault 转出的第一笔转账
+        let mut event = DexEvent::BonkClaimVestedTokenEvent(BonkClaimVestedTokenEvent {
+            base_vault,
+            ..Default::default()
+        });
+        fill_claim_amount_from_transfers(&mut event, &accounts, instructions.clone().into_iter());
+        let DexEvent::BonkClaimVestedTokenEvent(claim) = &event else { unreachable!() };
+        assert_eq!(claim.amount, 1_000);
+
+        let mut event = DexEvent::BonkClaimPlatformFeeEvent(BonkClaimPlatformFeeEvent {
+            quote_vault,
+            ..Default::default()
+        });
+        fill_claim_amount_from_transfers(&mut event, &accounts, instructions.clone().into_iter());
+        let DexEvent::BonkClaimPlatformFeeEvent(claim) = &event else { unreachable!() };
+        assert_eq!(claim.amount, 7);
+
+        // vault 没有转出时保持 0
+        let mut event = DexEvent::BonkClaimVestedTokenEvent(BonkClaimVestedTokenEvent {
+            base_vault: Pubkey::new_unique(),
+            ..Default::default()
+        });
+        fill_claim_amount_from_transfers(&mut event, &accounts, instructions.into_iter());
+        let DexEvent::BonkClaimVestedTokenEvent(claim) = &event else { unreachable!() };
+        assert_eq!(claim.amount, 0);
+    }
+
+    #[test]
+    fn test_raw_program_data_events_skip_known_discriminators() {
+        use crate::streaming::event_parser::common::ProgramDataItem;
+        use crate::streaming::event_parser::protocols::pumpfun::discriminators;
+
+        let item = |program_id: Pubkey, data: &[u8], additional: Vec<usize>| ProgramDataItem {
+            base64: STANDARD.encode(data),
+            program_id,
+            depth: 1,
+            log_index: 0,
+            additional,
+        };
+        let unknown = STANDARD.encode([9u8; 16]);
+        let index = ProgramDataIndex {
+            outer: vec![
+                Some(item(PUMPFUN_PROGRAM_ID, &discriminators::TRADE_EVENT[8..], vec![])),
+                Some(item(RAYDIUM_CPMM_PROGRAM_ID, &[9u8; 16], vec![])),
+            ],
+            inner: vec![vec![None, Some(item(PUMPFUN_PROGRAM_ID, &[9u8; 16], vec![1]))], vec![]],
+        };
+        let metadata =
+            EventMetadata { event_type: EventType::RawProgramData, ..Default::default() };
+
+        // 已知的 TradeEvent 被跳过，未订阅协议（CPMM）的日志不发出
+        let logs = vec![
+            format!("Program data: {}", STANDARD.encode([9u8; 16])),
+            format!("Program data: {unknown}"),
+        ];
+        let events =
+            EventParser::raw_program_data_events(&[Protocol::PumpFun], &index, &logs, &metadata);
+        assert_eq!(events.len(), 2);
+        for event in &events {
+            let DexEvent::RawProgramDataEvent(raw) = event else { panic!("{event:?}") };
+            assert_eq!(raw.program_id, PUMPFUN_PROGRAM_ID);
+            assert_eq!(raw.base64, unknown);
+            assert_eq!(raw.metadata.event_type, EventType::RawProgramData);
+            assert_eq!((raw.metadata.outer_index, raw.metadata.inner_index), (0, Some(1)));
+        }
+    }
+
+    #[cfg(all(feature = "protocol-raydium-clmm", feature = "protocol-whirlpool"))]
+    #[test]
+    fn test_log_swap_events_fill_unparsed_positions() {
+        use crate::streaming::event_parser::common::ProgramDataItem;
+        use crate::streaming::event_parser::protocols::raydium_clmm::discriminators as clmm;
+        use crate::streaming::event_parser::protocols::raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID;
+        use crate::streaming::event_parser::protocols::whirlpool::WHIRLPOOL_PROGRAM_ID;
+
+        let pool_state = Pubkey::new_unique();
+        let mut data = clmm::SWAP_EVENT.to_vec();
+        data.extend_from_slice(pool_state.as_ref());
+        data.extend_from_slice(&[0u8; 96]);
+        for amount in [1_000u64, 0, 450, 0] {
+            data.extend_from_slice(&amount.to_le_bytes());
+        }
+        data.push(1);
+        data.extend_from_slice(&[0u8; 36]);
+        let swap_log = STANDARD.encode(data);
+        let item = |program_id: Pubkey, base64: String| ProgramDataItem {
+            base64,
+            program_id,
+            depth: 2,
+            log_index: 0,
+            additional: Vec::new(),
+        };
+        let index = ProgramDataIndex {
+            outer: vec![Some(item(RAYDIUM_CLMM_PROGRAM_ID, swap_log.clone())), None],
+            inner: vec![
+                vec![],
+                vec![
+                    Some(item(RAYDIUM_CLMM_PROGRAM_ID, swap_log.clone())),
+                    Some(item(WHIRLPOOL_PROGRAM_ID, STANDARD.encode([9u8; 16]))),
+                    Some(item(RAYDIUM_CPMM_PROGRAM_ID, swap_log)),
+                ],
+            ],
+        };
+        let metadata = EventMetadata::default();
+
+        // 外层指令已解析出事件；非 Traded 日志和未订阅协议的日志被跳过
+        let events = EventParser::log_swap_events(
+            &[Protocol::RaydiumClmm, Protocol::Whirlpool],
+            &index,
+            &[],
+            &metadata,
+            &[(0, None)],
+        );
+        assert_eq!(events.len(), 1);
+        let DexEvent::RaydiumClmmSwapEvent(swap) = &events[0] else { panic!("{events:?}") };
+        assert_eq!(swap.metadata.event_type, EventType::RaydiumClmmSwap);
+        assert_eq!(swap.metadata.program_id, RAYDIUM_CLMM_PROGRAM_ID);
+        assert_eq!((swap.metadata.outer_index, swap.metadata.inner_index), (1, Some(0)));
+        assert_eq!(swap.pool_state, pool_state);
+        assert_eq!((swap.amount_0, swap.amount_1, swap.zero_for_one), (1_000, 450, true));
+    }
+}
@@ -6,6 +6,8 @@ use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 const MAX_SIGNATURES: usize = 1000;
 const CLEANUP_BATCH_SIZE: usize = 100;
+const MAX_POOL_MINTS: usize = 100_000;
+const MAX_LB_PAIR_BIN_STEPS: usize = 100_000;
 
 /// Signature-based trader addresses, completely lock-free
 #[derive(Default)]
@@ -24,6 +26,10 @@ pub struct GlobalState {
     signature_count: AtomicUsize,
     /// Generation counter to handle cleanup races
     generation: AtomicU64,
+    /// Pool -> (mint_a, mint_b) learned from pool state account updates
+    pool_mints: DashMap<Pubkey, (Pubkey, Pubkey)>,
+    /// Meteora DLMM lb_pair -> bin_step learned from pair creation and LbPair account updates
+    lb_pair_bin_steps: DashMap<Pubkey, u16>,
 }
 
 impl GlobalState {
@@ -33,6 +39,8 @@ impl GlobalState {
             signature_data: DashMap::new(),
             signature_count: AtomicUsize::new(0),
             generation: AtomicU64::new(0),
+            pool_mints: DashMap::new(),
+            lb_pair_bin_steps: DashMap::new(),
         }
     }
 
@@ -172,6 +180,34 @@ impl GlobalState {
             .unwrap_or_default()
     }
 
+    /// Record the ordered mint pair of a pool (token0/token1 or token_a/token_b)
+    pub fn set_pool_mints(&self, pool: Pubkey, mint_a: Pubkey, mint_b: Pubkey) {
+        if self.pool_mints.len() >= MAX_POOL_MINTS && !self.pool_mints.contains_key(&pool) {
+            return;
+        }
+        self.pool_mints.insert(pool, (mint_a, mint_b));
+    }
+
+    /// Get the ordered mint pair of a pool, if its state account has been seen
+    pub fn get_pool_mints(&self, pool: &Pubkey) -> Option<(Pubkey, Pubkey)> {
+        self.pool_mints.get(pool).map(|entry| *entry)
+    }
+
+    /// Record the bin_step of a Meteora DLMM lb_pair
+    pub fn set_lb_pair_bin_step(&self, lb_pair: Pubkey, bin_step: u16) {
+        if self.lb_pair_bin_steps.len() >= MAX_LB_PAIR_BIN_STEPS
+            && !self.lb_pair_bin_steps.contains_key(&lb_pair)
+        {
+            return;
+        }
+        self.lb_pair_bin_steps.insert(lb_pair, bin_step);
+    }
+
+    /// Get the bin_step of a Meteora DLMM lb_pair, if its creation or account has been seen
+    pub fn get_lb_pair_bin_step(&self, lb_pair: &Pubkey) -> Option<u16> {
+        self.lb_pair_bin_steps.get(lb_pair).map(|entry| *entry)
+    }
+
     /// Get current signature count
     pub fn get_signature_count(&self) -> usize {
         self.signature_count.load(Ordering::Relaxed)
@@ -182,6 +218,8 @@ impl GlobalState {
         self.signature_data.clear();
         self.signature_count.store(0, Ordering::Relaxed);
         self.generation.store(0, Ordering::Relaxed);
+        self.pool_mints.clear();
+        self.lb_pair_bin_steps.clear();
     }
 }
 
@@ -254,3 +292,23 @@ pub fn is_dev_address_in_signature(signature: &Signature, address: &Pubkey) -> b
 pub fn is_bonk_dev_address_in_signature(signature: &Signature, address: &Pubkey) -> bool {
     get_global_state().is_bonk_dev_address_in_signature(signature, address)
 }
+
+/// Convenience function: Record the ordered mint pair of a pool
+pub fn set_pool_mints(pool: Pubkey, mint_a: Pubkey, mint_b: Pubkey) {
+    get_global_state().set_pool_mints(pool, mint_a, mint_b);
+}
+
+/// Convenience function: Get the ordered mint pair of a pool
+pub fn get_pool_mints(pool: &Pubkey) -> Option<(Pubkey, Pubkey)> {
+    get_global_state().get_pool_mints(pool)
+}
+
+/// Convenience function: Record the bin_step of a Meteora DLMM lb_pair
+pub fn set_lb_pair_bin_step(lb_pair: Pubkey, bin_step: u16) {
+    get_global_state().set_lb_pair_bin_step(lb_pair, bin_step);
+}
+
+/// Convenience function: Get the bin_step of a Meteora DLMM lb_pair
+pub fn get_lb_pair_bin_step(lb_pair: &Pubkey) -> Option<u16> {
+    get_global_state().get_lb_pair_bin_step(lb_pair)
+}
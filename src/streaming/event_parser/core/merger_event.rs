@@ -1,4 +1,5 @@
 use crate::streaming::event_parser::DexEvent;
+use solana_sdk::pubkey::Pubkey;
 
 pub fn merge(instruction_event: &mut DexEvent, cpi_log_event: DexEvent) {
     match instruction_event {
@@ -69,6 +70,14 @@ pub fn merge(instruction_event: &mut DexEvent, cpi_log_event: DexEvent) {
             }
             _ => {}
         },
+        DexEvent::PumpFunClaimCreatorFeeEvent(e) => match cpi_log_event {
+            DexEvent::PumpFunClaimCreatorFeeEvent(cpie) => {
+                e.timestamp = cpie.timestamp;
+                e.creator = cpie.creator;
+                e.amount = cpie.amount;
+            }
+            _ => {}
+        },
 
         // Bonk events
         DexEvent::BonkTradeEvent(e) => match cpi_log_event {
@@ -194,6 +203,7 @@ pub fn merge(instruction_event: &mut DexEvent, cpi_log_event: DexEvent) {
                 e.total_claimed_tokens = cpie.total_claimed_tokens;
                 e.current_sol_volume = cpie.current_sol_volume;
                 e.last_update_timestamp = cpie.last_update_timestamp;
+                e.has_cpi_log = true;
             }
             _ => {}
         },
@@ -490,7 +500,167 @@ pub fn merge(instruction_event: &mut DexEvent, cpi_log_event: DexEvent) {
             }
             _ => {}
         },
+        DexEvent::MeteoraDammV2ClaimFeeEvent(e) => match cpi_log_event {
+            DexEvent::MeteoraDammV2ClaimFeeEvent(cpie)
+                if cpie.metadata.event_type == e.metadata.event_type =>
+            {
+                e.pool = cpie.pool;
+                e.fee_a_claimed = cpie.fee_a_claimed;
+                e.fee_b_claimed = cpie.fee_b_claimed;
+                // partner / protocol 领取事件不含仓位信息，保留指令账户
+                if cpie.position != Pubkey::default() {
+                    e.position = cpie.position;
+                    e.owner = cpie.owner;
+                }
+            }
+            _ => {}
+        },
 
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::protocols::pumpswap::{
+        AmountSource, PumpSwapBuyEvent, PumpSwapBuyExactQuoteInEvent, PumpSwapDepositEvent,
+        PumpSwapWithdrawEvent,
+    };
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_pumpswap_deposit_merge_fills_lp_mint_supply() {
+        let base_mint = Pubkey::new_unique();
+        let instruction =
+            PumpSwapDepositEvent { lp_token_amount_out: 250, base_mint, ..Default::default() };
+        assert_eq!(instruction.lp_share_fraction(), None);
+
+        let mut event = DexEvent::PumpSwapDepositEvent(instruction);
+        let cpi = PumpSwapDepositEvent {
+            lp_token_amount_out: 250,
+            lp_mint_supply: 1_000,
+            ..Default::default()
+        };
+        merge(&mut event, DexEvent::PumpSwapDepositEvent(cpi));
+
+        let DexEvent::PumpSwapDepositEvent(merged) = event else {
+            panic!("unexpected event variant");
+        };
+        assert_eq!(merged.lp_mint_supply, 1_000);
+        assert_eq!(merged.base_mint, base_mint);
+        assert_eq!(merged.lp_share_fraction(), Some(0.25));
+    }
+
+    #[test]
+    fn test_pumpswap_withdraw_merge_fills_lp_mint_supply() {
+        let mut event = DexEvent::PumpSwapWithdrawEvent(PumpSwapWithdrawEvent {
+            lp_token_amount_in: 50,
+            ..Default::default()
+        });
+        let cpi = PumpSwapWithdrawEvent {
+            lp_token_amount_in: 50,
+            lp_mint_supply: 200,
+            ..Default::default()
+        };
+        merge(&mut event, DexEvent::PumpSwapWithdrawEvent(cpi));
+
+        let DexEvent::PumpSwapWithdrawEvent(merged) = event else {
+            panic!("unexpected event variant");
+        };
+        assert_eq!(merged.lp_mint_supply, 200);
+        assert_eq!(merged.lp_share_fraction(), Some(0.25));
+    }
+
+    #[test]
+    fn test_pumpswap_buy_exact_quote_in_resolved_amounts() {
+        let instruction = PumpSwapBuyExactQuoteInEvent {
+            quote_amount_in: 1_000,
+            min_base_amount_out: 90,
+            user_quote_amount_in: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(instruction.amount_source(), AmountSource::Estimate);
+        assert_eq!(instruction.resolved_quote_in(), 1_000);
+        assert_eq!(instruction.resolved_base_out(), 90);
+
+        let mut event = DexEvent::PumpSwapBuyExactQuoteInEvent(instruction);
+        let cpi = PumpSwapBuyEvent {
+            base_amount_out: 95,
+            min_base_amount_out: 90,
+            quote_amount_in: 985,
+            user_quote_amount_in: 998,
+            ..Default::default()
+        };
+        merge(&mut event, DexEvent::PumpSwapBuyEvent(cpi));
+
+        let DexEvent::PumpSwapBuyExactQuoteInEvent(merged) = event else {
+            panic!("unexpected event variant");
+        };
+        assert_eq!(merged.amount_source(), AmountSource::Actual);
+        assert_eq!(merged.resolved_quote_in(), 998);
+        assert_eq!(merged.resolved_base_out(), 95);
+    }
+
+    #[test]
+    fn test_dlmm_legacy_swap_merged_into_swap2() {
+        use crate::streaming::event_parser::protocols::meteora_dlmm::{
+            MeteoraDlmmSwap2Event, MeteoraDlmmSwapEvent, MeteoraDlmmSwapResult,
+        };
+
+        let user = Pubkey::new_unique();
+        let lb_pair = Pubkey::new_unique();
+        // 指令事件中残留的分项必须被清零，不能与旧日志的数值混在一起
+        let stale = MeteoraDlmmSwapResult {
+            amount_left: 1,
+            lp_mm_fee: 2,
+            lp_limit_order_fee: 3,
+            limit_order_filled_amount: 4,
+            limit_order_swapped_amount: 5,
+            ..Default::default()
+        };
+        let instruction = MeteoraDlmmSwap2Event {
+            amount_in: 1_000,
+            min_amount_out: 900,
+            user,
+            swap_result: stale,
+            ..Default::default()
+        };
+        let mut event = DexEvent::MeteoraDlmmSwap2Event(instruction);
+        let cpi = MeteoraDlmmSwapEvent {
+            lb_pair,
+            start_bin_id: -3,
+            end_bin_id: -1,
+            cpi_amount_in: 1_000,
+            cpi_amount_out: 950,
+            swap_for_y: true,
+            fee: 10,
+            protocol_fee: 2,
+            fee_bps: 25,
+            host_fee: 1,
+            ..Default::default()
+        };
+        merge(&mut event, DexEvent::MeteoraDlmmSwapEvent(cpi));
+
+        let DexEvent::MeteoraDlmmSwap2Event(merged) = event else {
+            panic!("unexpected event variant");
+        };
+        assert_eq!(
+            merged.swap_result,
+            MeteoraDlmmSwapResult {
+                amount_in: 1_000,
+                amount_out: 950,
+                total_fee: 10,
+                protocol_fee: 2,
+                host_fee: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(merged.swap_result.total_fees(), 3);
+        assert_eq!((merged.lb_pair, merged.start_bin_id, merged.end_bin_id), (lb_pair, -3, -1));
+        assert!(merged.swap_for_y);
+        assert_eq!(merged.fee_bps, 25);
+        // 指令参数和账户保持不变
+        assert_eq!((merged.amount_in, merged.min_amount_out, merged.user), (1_000, 900, user));
+    }
+}
@@ -0,0 +1,216 @@
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::streaming::event_parser::common::types::{SwapData, WSOL_MINT};
+use crate::streaming::event_parser::DexEvent;
+
+const MAX_MINTS: usize = 200_000;
+
+/// Mint -> decimals 注册表
+///
+/// 使用方可以预先写入已知 mint 的精度；解析器也会从携带精度的创建/初始化事件
+/// （PumpSwap create_pool、Bonk initialize、池子状态账户、Mint 账户）中自动学习。
+/// 开启 `StreamClientConfig::attach_ui_amounts` 后，swap 事件的 `SwapData`
+/// 会被填充 `from_decimals` / `to_decimals`，可通过 `from_amount_ui()` / `to_amount_ui()` 读取。
+pub struct MintRegistry {
+    decimals: DashMap<Pubkey, u8>,
+    attach_ui_amounts: AtomicBool,
+}
+
+impl MintRegistry {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self { decimals: DashMap::new(), attach_ui_amounts: AtomicBool::new(false) }
+    }
+
+    /// 全局注册表，预置 WSOL 精度
+    pub fn global() -> &'static MintRegistry {
+        &GLOBAL_MINT_REGISTRY
+    }
+
+    /// 写入 mint 精度，超出容量时只更新已存在的 mint
+    pub fn insert(&self, mint: Pubkey, decimals: u8) {
+        if mint == Pubkey::default() {
+            return;
+        }
+        if self.decimals.len() >= MAX_MINTS && !self.decimals.contains_key(&mint) {
+            return;
+        }
+        self.decimals.insert(mint, decimals);
+    }
+
+    /// 批量写入 mint 精度
+    pub fn extend<I: IntoIterator<Item = (Pubkey, u8)>>(&self, entries: I) {
+        for (mint, decimals) in entries {
+            self.insert(mint, decimals);
+        }
+    }
+
+    /// 查询 mint 精度
+    pub fn get(&self, mint: &Pubkey) -> Option<u8> {
+        self.decimals.get(mint).map(|entry| *entry)
+    }
+
+    /// 已登记的 mint 数量
+    pub fn len(&self) -> usize {
+        self.decimals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.decimals.is_empty()
+    }
+
+    /// 清空所有已登记的 mint
+    pub fn clear(&self) {
+        self.decimals.clear();
+    }
+
+    /// 开启/关闭 SwapData 精度填充
+    pub fn set_attach_ui_amounts(&self, enabled: bool) {
+        self.attach_ui_amounts.store(enabled, Ordering::Relaxed);
+    }
+
+    /// 是否开启 SwapData 精度填充
+    pub fn attach_ui_amounts(&self) -> bool {
+        self.attach_ui_amounts.load(Ordering::Relaxed)
+    }
+
+    /// 从携带精度的事件中学习 mint 精度
+    pub fn record_from_event(&self, event: &DexEvent) {
+        match event {
+            DexEvent::PumpSwapCreatePoolEvent(e) => {
+                self.insert(e.base_mint, e.base_mint_decimals);
+                self.insert(e.quote_mint, e.quote_mint_decimals);
+            }
+            DexEvent::BonkPoolCreateEvent(e) => {
+                self.insert(e.base_mint, e.base_mint_param.decimals);
+            }
+            DexEvent::BonkPoolStateAccountEvent(e) => {
+                self.insert(e.pool_state.base_mint, e.pool_state.base_decimals);
+                self.insert(e.pool_state.quote_mint, e.pool_state.quote_decimals);
+            }
+            DexEvent::RaydiumClmmPoolStateAccountEvent(e) => {
+                self.insert(e.pool_state.token_mint0, e.pool_state.mint_decimals0);
+                self.insert(e.pool_state.token_mint1, e.pool_state.mint_decimals1);
+            }
+            DexEvent::RaydiumCpmmPoolStateAccountEvent(e) => {
+                self.insert(e.pool_state.token_0_mint, e.pool_state.mint_0_decimals);
+                self.insert(e.pool_state.token_1_mint, e.pool_state.mint_1_decimals);
+            }
+            DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => {
+                if let Ok(decimals) = u8::try_from(e.amm_info.coin_decimals) {
+                    self.insert(e.amm_info.coin_mint, decimals);
+                }
+                if let Ok(decimals) = u8::try_from(e.amm_info.pc_decimals) {
+                    self.insert(e.amm_info.pc_mint, decimals);
+                }
+            }
+            DexEvent::TokenInfoEvent(e) => self.insert(e.pubkey, e.decimals),
+            _ => {}
+        }
+    }
+
+    /// 按已知精度填充 SwapData 的 from_decimals / to_decimals
+    pub fn annotate_swap_data(&self, swap_data: &mut SwapData) {
+        if swap_data.from_decimals.is_none() {
+            swap_data.from_decimals = self.get(&swap_data.from_mint);
+        }
+        if swap_data.to_decimals.is_none() {
+            swap_data.to_decimals = self.get(&swap_data.to_mint);
+        }
+    }
+
+    /// SwapData 的成交价格（每 1 个 from_mint 换得的 to_mint），
+    /// 优先使用 SwapData 已填充的精度，否则查询注册表；任一 mint 精度未知时返回 None
+    pub fn swap_price(&self, swap_data: &SwapData) -> Option<f64> {
+        let from_decimals = swap_data.from_decimals.or_else(|| self.get(&swap_data.from_mint))?;
+        let to_decimals = swap_data.to_decimals.or_else(|| self.get(&swap_data.to_mint))?;
+        swap_data.price(from_decimals, to_decimals)
+    }
+}
+
+impl Default for MintRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static GLOBAL_MINT_REGISTRY: once_cell::sync::Lazy<MintRegistry> =
+    once_cell::sync::Lazy::new(|| {
+        let registry = MintRegistry::new();
+        registry.insert(WSOL_MINT, 9);
+        registry
+    });
+
+/// 学习事件中的 mint 精度，并在开启时为 SwapData 填充精度
+pub(crate) fn apply_mint_registry(event: &mut DexEvent) {
+    let registry = MintRegistry::global();
+    registry.record_from_event(event);
+    if !registry.attach_ui_amounts() {
+        return;
+    }
+    if let Some(swap_data) = event.metadata_mut().swap_data.as_mut() {
+        registry.annotate_swap_data(swap_data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotate_swap_data_with_known_mints() {
+        let registry = MintRegistry::new();
+        let mint = Pubkey::new_unique();
+        registry.extend([(WSOL_MINT, 9), (mint, 6)]);
+
+        let mut swap_data = SwapData {
+            from_mint: WSOL_MINT,
+            to_mint: mint,
+            from_amount: 1_500_000_000,
+            to_amount: 2_500_000,
+            ..Default::default()
+        };
+        registry.annotate_swap_data(&mut swap_data);
+
+        assert_eq!(swap_data.from_decimals, Some(9));
+        assert_eq!(swap_data.to_decimals, Some(6));
+        assert_eq!(swap_data.from_amount_ui(), Some(1.5));
+        assert_eq!(swap_data.to_amount_ui(), Some(2.5));
+    }
+
+    #[test]
+    fn test_swap_price_with_known_mints() {
+        let registry = MintRegistry::new();
+        let mint = Pubkey::new_unique();
+        registry.extend([(WSOL_MINT, 9), (mint, 6)]);
+
+        let mut swap_data = SwapData {
+            from_mint: WSOL_MINT,
+            to_mint: mint,
+            from_amount: 1_500_000_000,
+            to_amount: 3_000_000,
+            ..Default::default()
+        };
+        assert_eq!(swap_data.ui_price(), None);
+        assert_eq!(registry.swap_price(&swap_data), Some(2.0));
+
+        registry.annotate_swap_data(&mut swap_data);
+        assert_eq!(swap_data.ui_price(), Some(2.0));
+        assert_eq!(swap_data.price(6, 6), Some(0.002));
+        assert_eq!(SwapData { from_amount: 0, ..swap_data }.ui_price(), None);
+    }
+
+    #[test]
+    fn test_unknown_mint_leaves_ui_amount_empty() {
+        let registry = MintRegistry::new();
+        let mut swap_data =
+            SwapData { from_mint: Pubkey::new_unique(), from_amount: 42, ..Default::default() };
+        registry.annotate_swap_data(&mut swap_data);
+
+        assert_eq!(swap_data.from_decimals, None);
+        assert_eq!(swap_data.from_amount_ui(), None);
+        assert_eq!(registry.swap_price(&swap_data), None);
+    }
+}
@@ -1,12 +1,24 @@
 pub mod account_event_parser;
+pub mod capabilities;
 pub mod common_event_parser;
 pub mod dispatcher;
 pub mod global_state;
+pub mod mint_registry;
+pub mod parse_options;
 pub mod parser_cache;
+pub mod pool_fee_registry;
+pub mod quote_mints;
+pub mod sol_transfer_watchlist;
 pub mod traits;
 
-pub use dispatcher::EventDispatcher;
-pub use traits::DexEvent;
+pub use capabilities::{CapabilityKind, EventCapability, ProtocolCapability};
+pub use dispatcher::{EventDispatcher, ProgramIdConflict};
+pub use mint_registry::MintRegistry;
+pub use parse_options::ParseOptions;
+pub use pool_fee_registry::{PoolFeeInfo, PoolFeeRegistry};
+pub use quote_mints::{NormalizedTrade, QuoteLeg, QuoteMints};
+pub use sol_transfer_watchlist::SolTransferWatchlist;
+pub use traits::{DexEvent, EventOrderingKey};
 
 pub mod event_parser;
 pub mod merger_event;
@@ -0,0 +1,58 @@
+use crate::streaming::event_parser::core::sol_transfer_watchlist::SolTransferWatchlist;
+use std::sync::Arc;
+
+/// 解析器的按客户端选项
+///
+/// 每个客户端按自己的 `StreamClientConfig` 构建一份，随每笔交易传给 `EventParser` 的
+/// `*_with_options` 入口；同一进程中的多个客户端互不影响。不带 options 的入口使用默认值（全部关闭）。
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// 从 swap 指令之后的 token / system 转账中提取 swap_data
+    ///
+    /// 开启后每个 swap 事件额外扫描一遍其后的 inner instructions（遇到非转账指令即停止），
+    /// 并为 swap_data 分配一次；关闭时只有能从指令账户推导出 mint 的事件才有 swap_data，且不含数量。
+    pub extract_swap_data: bool,
+    /// 提取 swap_data 时核对 mint
+    ///
+    /// 普通 Transfer 不带 mint，swap_data 的 mint 按事件中的 vault 推断；TransferChecked /
+    /// TransferCheckedWithFee 的账户中带有 mint，开启后与推断的 mint 比对，不一致时设置
+    /// `SwapData::mint_mismatch`，用于发现解析器假设了错误 vault 的情况。只在开启
+    /// `extract_swap_data` 时生效
+    pub verify_transfer_mints: bool,
+    /// 以 info 级别记录解析器为每笔包含已订阅协议的交易重建的账户列表（含补齐的默认值），
+    /// 用于排查事件账户位置错位；每笔交易一条日志，只应在调试时开启
+    pub log_account_keys: bool,
+    /// 为已订阅协议中 discriminator 不认识的指令发出 `UnknownInstructionEvent`，
+    /// 并按程序计入 metrics，用于及时发现协议升级新增的指令
+    pub emit_unknown: bool,
+    /// 为已订阅协议输出的、解析器无法解码的 `Program data:` 日志发出 `RawProgramDataEvent`
+    pub emit_raw_program_data: bool,
+    /// 只依据日志解析事件全部写在 Anchor 事件日志中的协议（Raydium CLMM SwapEvent、Whirlpool Traded）
+    ///
+    /// 开启后，指令解析没有产出事件的调用位置上，这些协议输出的 `Program data:` 日志会直接生成
+    /// swap 事件，用于聚合器以无法识别的方式 CPI 调用 DEX、但 DEX 仍输出事件日志的交易。
+    /// 这类事件只有日志中的字段，指令参数和指令账户（除池子地址外）为默认值
+    pub parse_logs_only: bool,
+    /// 为外层 System Program 转账发出 `SolTransferEvent` 的关注列表，None 时不解析 SOL 转账
+    ///
+    /// 客户端按 `StreamClientConfig::sol_transfer_wallets` 创建一份并在订阅之间共享，
+    /// 运行时增减钱包对后续交易生效
+    pub sol_transfer_watchlist: Option<Arc<SolTransferWatchlist>>,
+}
+
+impl ParseOptions {
+    /// 全部关闭的选项，供不带 options 的入口引用
+    pub fn disabled() -> &'static ParseOptions {
+        &DISABLED_PARSE_OPTIONS
+    }
+}
+
+static DISABLED_PARSE_OPTIONS: ParseOptions = ParseOptions {
+    extract_swap_data: false,
+    verify_transfer_mints: false,
+    log_account_keys: false,
+    emit_unknown: false,
+    emit_raw_program_data: false,
+    parse_logs_only: false,
+    sol_transfer_watchlist: None,
+};
@@ -9,13 +9,10 @@
 //! - **高性能缓存**：避免重复初始化和内存分配
 //! - **易于扩展**：通过 dispatcher 动态派发
 
-use crate::streaming::{
-    event_parser::{
-        common::{filter::EventTypeFilter, EventMetadata, EventType, ProtocolType},
-        core::dispatcher::EventDispatcher,
-        DexEvent, Protocol,
-    },
-    grpc::AccountPretty,
+use crate::streaming::event_parser::{
+    common::{filter::EventTypeFilter, AccountPretty, EventMetadata, EventType, ProtocolType},
+    core::dispatcher::EventDispatcher,
+    DexEvent, Protocol,
 };
 use solana_sdk::pubkey::Pubkey;
 use std::{
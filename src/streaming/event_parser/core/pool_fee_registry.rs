@@ -0,0 +1,144 @@
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::DexEvent;
+
+const MAX_POOLS: usize = 100_000;
+
+/// 池子费率信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolFeeInfo {
+    /// Whirlpool 费率，单位为百分之一个基点（3000 = 0.3%）
+    pub fee_rate: u16,
+    pub tick_spacing: u16,
+}
+
+/// Whirlpool 池子 -> 费率/tick spacing 注册表
+///
+/// 解析器会从 `WhirlpoolAccountEvent` 中自动学习，使用方也可以预先写入关心的池子，
+/// 之后该池子的 `WhirlpoolSwapEvent` / `WhirlpoolSwapV2Event` 会被填充 `fee_rate` / `tick_spacing`，
+/// 无需并行订阅池子账户即可计算手续费。
+pub struct PoolFeeRegistry {
+    pools: DashMap<Pubkey, PoolFeeInfo>,
+}
+
+impl PoolFeeRegistry {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self { pools: DashMap::new() }
+    }
+
+    /// 全局注册表
+    pub fn global() -> &'static PoolFeeRegistry {
+        &GLOBAL_POOL_FEE_REGISTRY
+    }
+
+    /// 写入池子费率，超出容量时只更新已存在的池子
+    pub fn insert(&self, pool: Pubkey, info: PoolFeeInfo) {
+        if self.pools.len() >= MAX_POOLS && !self.pools.contains_key(&pool) {
+            return;
+        }
+        self.pools.insert(pool, info);
+    }
+
+    /// 批量写入池子费率
+    pub fn extend<I: IntoIterator<Item = (Pubkey, PoolFeeInfo)>>(&self, entries: I) {
+        for (pool, info) in entries {
+            self.insert(pool, info);
+        }
+    }
+
+    /// 查询池子费率
+    pub fn get(&self, pool: &Pubkey) -> Option<PoolFeeInfo> {
+        self.pools.get(pool).map(|entry| *entry)
+    }
+
+    /// 已登记的池子数量
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+
+    /// 清空所有已登记的池子
+    pub fn clear(&self) {
+        self.pools.clear();
+    }
+
+    /// 从池子账户事件中学习费率
+    pub fn record_from_event(&self, event: &DexEvent) {
+        if let DexEvent::WhirlpoolAccountEvent(e) = event {
+            self.insert(
+                e.pubkey,
+                PoolFeeInfo {
+                    fee_rate: e.whirlpool.fee_rate,
+                    tick_spacing: e.whirlpool.tick_spacing,
+                },
+            );
+        }
+    }
+
+    /// 按已知费率填充 swap 事件的 fee_rate / tick_spacing
+    pub fn annotate_event(&self, event: &mut DexEvent) {
+        let (whirlpool, fee_rate, tick_spacing) = match event {
+            DexEvent::WhirlpoolSwapEvent(e) => (e.whirlpool, &mut e.fee_rate, &mut e.tick_spacing),
+            DexEvent::WhirlpoolSwapV2Event(e) => {
+                (e.whirlpool, &mut e.fee_rate, &mut e.tick_spacing)
+            }
+            _ => return,
+        };
+        if let Some(info) = self.get(&whirlpool) {
+            *fee_rate = Some(info.fee_rate);
+            *tick_spacing = Some(info.tick_spacing);
+        }
+    }
+}
+
+impl Default for PoolFeeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static GLOBAL_POOL_FEE_REGISTRY: once_cell::sync::Lazy<PoolFeeRegistry> =
+    once_cell::sync::Lazy::new(PoolFeeRegistry::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::protocols::whirlpool::{
+        types::Whirlpool, WhirlpoolAccountEvent, WhirlpoolSwapV2Event,
+    };
+
+    #[test]
+    fn test_annotate_swap_from_seen_pool() {
+        let registry = PoolFeeRegistry::new();
+        let pool = Pubkey::new_unique();
+        registry.record_from_event(&DexEvent::WhirlpoolAccountEvent(WhirlpoolAccountEvent {
+            pubkey: pool,
+            whirlpool: Whirlpool { fee_rate: 3000, tick_spacing: 64, ..Default::default() },
+            ..Default::default()
+        }));
+
+        let mut event = DexEvent::WhirlpoolSwapV2Event(WhirlpoolSwapV2Event {
+            whirlpool: pool,
+            ..Default::default()
+        });
+        registry.annotate_event(&mut event);
+        let DexEvent::WhirlpoolSwapV2Event(swap) = &event else { unreachable!() };
+        assert_eq!(swap.fee_rate, Some(3000));
+        assert_eq!(swap.tick_spacing, Some(64));
+        assert_eq!(swap.fee_rate_bps(), Some(30.0));
+
+        // 未知池子不填充
+        let mut unknown = DexEvent::WhirlpoolSwapV2Event(WhirlpoolSwapV2Event {
+            whirlpool: Pubkey::new_unique(),
+            ..Default::default()
+        });
+        registry.annotate_event(&mut unknown);
+        let DexEvent::WhirlpoolSwapV2Event(swap) = &unknown else { unreachable!() };
+        assert_eq!(swap.fee_rate_bps(), None);
+    }
+}
@@ -0,0 +1,138 @@
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use crate::streaming::event_parser::common::types::{USDC_MINT, USDT_MINT, WSOL_MINT};
+
+/// 计价币 (quote) mint 集合
+///
+/// 用于判断交易方向和成交额一侧：交易两侧中属于该集合的一侧视为 quote，另一侧视为 base。
+/// 两侧都属于集合时（例如 SOL/USDC 池子）取列表中靠前的 mint 作为 quote。
+/// 默认包含 WSOL、USDC、USDT。
+///
+/// `StreamClientConfig::quote_mints` 会在创建客户端时写入全局集合，
+/// 独立使用辅助函数时可以直接传入自定义集合，或通过 `QuoteMints::with_global()` 借用全局集合。
+/// 不接收集合参数的辅助函数（`sol_volume_lamports`、`total_fee_lamports`）读取全局集合。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteMints {
+    mints: Vec<Pubkey>,
+}
+
+impl Default for QuoteMints {
+    fn default() -> Self {
+        Self { mints: vec![WSOL_MINT, USDC_MINT, USDT_MINT] }
+    }
+}
+
+impl QuoteMints {
+    /// 按优先级创建集合，重复的 mint 只保留第一次出现的位置
+    pub fn new<I: IntoIterator<Item = Pubkey>>(mints: I) -> Self {
+        let mut quote_mints = Self { mints: Vec::new() };
+        for mint in mints {
+            quote_mints.insert(mint);
+        }
+        quote_mints
+    }
+
+    /// 只包含 WSOL 的集合
+    pub fn sol_only() -> Self {
+        Self { mints: vec![WSOL_MINT] }
+    }
+
+    /// 追加 mint（优先级最低），已存在时不变
+    pub fn insert(&mut self, mint: Pubkey) {
+        if !self.mints.contains(&mint) {
+            self.mints.push(mint);
+        }
+    }
+
+    /// 移除 mint
+    pub fn remove(&mut self, mint: &Pubkey) {
+        self.mints.retain(|m| m != mint);
+    }
+
+    /// 是否为 quote mint
+    #[inline]
+    pub fn contains(&self, mint: &Pubkey) -> bool {
+        self.mints.contains(mint)
+    }
+
+    /// 按优先级排列的 quote mint
+    pub fn mints(&self) -> &[Pubkey] {
+        &self.mints
+    }
+
+    /// 选出交易对中作为 quote 的 mint，两侧都不是 quote 时返回 None
+    pub fn quote_of(&self, mint_a: &Pubkey, mint_b: &Pubkey) -> Option<Pubkey> {
+        self.mints.iter().find(|m| *m == mint_a || *m == mint_b).copied()
+    }
+
+    /// 全局集合的快照
+    ///
+    /// 会复制整个集合，逐事件调用时使用 [`QuoteMints::with_global`]
+    pub fn global() -> QuoteMints {
+        GLOBAL_QUOTE_MINTS.read().clone()
+    }
+
+    /// 持有读锁借用全局集合，不复制
+    ///
+    /// `f` 内不能调用 [`QuoteMints::set_global`]
+    pub fn with_global<R>(f: impl FnOnce(&QuoteMints) -> R) -> R {
+        f(&GLOBAL_QUOTE_MINTS.read())
+    }
+
+    /// 替换全局集合
+    pub fn set_global(quote_mints: QuoteMints) {
+        *GLOBAL_QUOTE_MINTS.write() = quote_mints;
+    }
+}
+
+/// 交易中 quote 一侧的成交
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteLeg {
+    pub mint: Pubkey,
+    /// quote 一侧的原始数量（未按精度换算）
+    pub amount: u64,
+    /// quote 为输入一侧，即用 quote 买入 base
+    pub is_buy: bool,
+}
+
+/// 按 quote 集合归一化的成交：base 为交易的代币，quote 为计价币
+///
+/// 数量均为原始数量（未按精度换算），不含手续费。crate 不负责持久化，
+/// 需要写入 Parquet / Arrow 等列式格式时可交给基于 serde 的 writer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NormalizedTrade {
+    pub signature: Signature,
+    pub slot: u64,
+    /// 发起交易的钱包
+    pub trader: Pubkey,
+    pub base_mint: Pubkey,
+    pub base_amount: u64,
+    pub quote_mint: Pubkey,
+    pub quote_amount: u64,
+    /// 用 quote 买入 base
+    pub is_buy: bool,
+}
+
+static GLOBAL_QUOTE_MINTS: once_cell::sync::Lazy<RwLock<QuoteMints>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(QuoteMints::default()));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_of_prefers_earlier_mint() {
+        let token = Pubkey::new_unique();
+        let quote_mints = QuoteMints::default();
+        assert_eq!(quote_mints.quote_of(&token, &USDC_MINT), Some(USDC_MINT));
+        assert_eq!(quote_mints.quote_of(&USDC_MINT, &WSOL_MINT), Some(WSOL_MINT));
+        assert_eq!(quote_mints.quote_of(&token, &Pubkey::new_unique()), None);
+
+        let quote_mints = QuoteMints::new([USDC_MINT, WSOL_MINT, USDC_MINT]);
+        assert_eq!(quote_mints.mints(), &[USDC_MINT, WSOL_MINT]);
+        assert_eq!(quote_mints.quote_of(&WSOL_MINT, &USDC_MINT), Some(USDC_MINT));
+        assert!(!QuoteMints::sol_only().contains(&USDC_MINT));
+    }
+}
@@ -0,0 +1,97 @@
+use dashmap::DashSet;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// SOL 转账关注列表
+///
+/// 开启后解析器会解析外层 System Program `Transfer` 指令，`from` 或 `to` 在列表中时发出
+/// `SolTransferEvent`。System 转账几乎出现在每笔交易中，列表为空时不发出任何事件。
+/// 不包含已订阅协议、但涉及关注钱包的交易也会被解析；只有订阅的交易才会到达解析器，
+/// 需要把关注的钱包加入 `TransactionFilter::account_include`。
+///
+/// 每个客户端持有自己的列表（`YellowstoneGrpc::sol_transfer_watchlist`），通过
+/// `ParseOptions::sol_transfer_watchlist` 传给解析器。
+#[derive(Debug)]
+pub struct SolTransferWatchlist {
+    wallets: DashSet<Pubkey>,
+    enabled: AtomicBool,
+}
+
+impl SolTransferWatchlist {
+    /// 创建空列表（关闭）
+    pub fn new() -> Self {
+        Self { wallets: DashSet::new(), enabled: AtomicBool::new(false) }
+    }
+
+    /// 设置是否解析 SOL 转账
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// 是否解析 SOL 转账
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// 按 `StreamClientConfig::sol_transfer_wallets` 配置：None 关闭，Some 开启并关注这些钱包
+    /// （已关注的钱包保留）
+    pub fn configure(&self, wallets: Option<&[Pubkey]>) {
+        if let Some(wallets) = wallets {
+            self.extend(wallets.iter().copied());
+        }
+        self.set_enabled(wallets.is_some());
+    }
+
+    /// 关注钱包
+    pub fn watch(&self, wallet: Pubkey) {
+        self.wallets.insert(wallet);
+    }
+
+    /// 批量关注钱包
+    pub fn extend<I: IntoIterator<Item = Pubkey>>(&self, wallets: I) {
+        for wallet in wallets {
+            self.watch(wallet);
+        }
+    }
+
+    /// 取消关注钱包
+    pub fn unwatch(&self, wallet: &Pubkey) {
+        self.wallets.remove(wallet);
+    }
+
+    /// 是否关注该钱包
+    pub fn is_watched(&self, wallet: &Pubkey) -> bool {
+        self.wallets.contains(wallet)
+    }
+
+    /// 所有关注的钱包
+    pub fn wallets(&self) -> Vec<Pubkey> {
+        self.wallets.iter().map(|wallet| *wallet).collect()
+    }
+
+    /// 清空关注列表
+    pub fn clear(&self) {
+        self.wallets.clear();
+    }
+
+    /// 开启且 `from` 或 `to` 被关注时返回 true
+    #[inline]
+    pub fn matches(&self, from: &Pubkey, to: &Pubkey) -> bool {
+        self.is_enabled() && (self.is_watched(from) || self.is_watched(to))
+    }
+
+    /// 开启且账户列表中有被关注的钱包时返回 true，用于判断没有已订阅协议的交易是否需要解析
+    #[inline]
+    pub(crate) fn touches(&self, accounts: &[Pubkey]) -> bool {
+        self.is_enabled()
+            && !self.wallets.is_empty()
+            && accounts.iter().any(|account| self.is_watched(account))
+    }
+}
+
+impl Default for SolTransferWatchlist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,12 +1,17 @@
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, SwapDirection, WSOL_MINT};
 use crate::streaming::event_parser::core::account_event_parser::{
     NonceAccountEvent, TokenAccountEvent, TokenInfoEvent,
 };
 use crate::streaming::event_parser::core::common_event_parser::{
-    SetComputeUnitLimitEvent, SetComputeUnitPriceEvent,
+    AtaCreateEvent, RawProgramDataEvent, SetComputeUnitLimitEvent, SetComputeUnitPriceEvent,
+    SetLoadedAccountsDataSizeLimitEvent, SolTransferEvent, SwapFailedEvent,
+    TransactionSummaryEvent, UnknownInstructionEvent,
 };
+use crate::streaming::event_parser::core::quote_mints::{NormalizedTrade, QuoteLeg, QuoteMints};
 use crate::streaming::event_parser::protocols::block::block_meta_event::BlockMetaEvent;
+use crate::streaming::event_parser::protocols::block::slot_rolled_back_event::SlotRolledBackEvent;
 use crate::streaming::event_parser::protocols::bonk::events::*;
+use crate::streaming::event_parser::protocols::bonk::types::TradeDirection;
 use crate::streaming::event_parser::protocols::meteora_damm_v2::events::*;
 use crate::streaming::event_parser::protocols::meteora_dlmm::events::*;
 use crate::streaming::event_parser::protocols::pancakeswap::events::*;
@@ -17,10 +22,17 @@ use crate::streaming::event_parser::protocols::raydium_clmm::events::*;
 use crate::streaming::event_parser::protocols::raydium_cpmm::events::*;
 use crate::streaming::event_parser::protocols::whirlpool::events::*;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use smallvec::{smallvec, SmallVec};
+use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
 use std::fmt::Debug;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// `DexEvent::ordering_key` 的返回值：`(slot, transaction_index, outer_index, inner_index, seq)`
+pub type EventOrderingKey = (u64, Option<u64>, i64, Option<i64>, u64);
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TxDexEvents {
     pub signature: Signature,
     pub slot: u64,
@@ -30,6 +42,10 @@ pub struct TxDexEvents {
     pub recv_us: i64,
     /// True when an outer custom-program instruction CPI-calls DEX swaps that form a cycle.
     pub is_arb: bool,
+    /// Heuristic wash-trade flag: the same trader swaps a mint pair in both directions in the
+    /// same pool (e.g. buys and sells the same token) without forming an arbitrage cycle.
+    #[serde(default)]
+    pub is_self_trade: bool,
     /// Effective transaction CU price in micro-lamports/CU; zero when not set.
     #[serde(default)]
     pub compute_unit_price_micro_lamports: u64,
@@ -46,7 +62,12 @@ pub struct TxDexEvents {
 }
 
 /// Unified Event Enum - Replaces the trait-based approach with a type-safe enum
+///
+/// New protocols and events are added as new variants in minor releases, so the enum is
+/// `#[non_exhaustive]`: matches outside this crate need a wildcard arm. Prefer the accessors
+/// (`metadata`, `pool_id`, `mints`, `trader`, `quote_leg`, ...) over exhaustive matches.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum DexEvent {
     // PancakeSwap events
     PancakeSwapSwapEvent(PancakeSwapSwapEvent),
@@ -62,6 +83,8 @@ pub enum DexEvent {
     BonkPoolCreateEvent(BonkPoolCreateEvent),
     BonkMigrateToAmmEvent(BonkMigrateToAmmEvent),
     BonkMigrateToCpswapEvent(BonkMigrateToCpswapEvent),
+    BonkClaimVestedTokenEvent(BonkClaimVestedTokenEvent),
+    BonkClaimPlatformFeeEvent(BonkClaimPlatformFeeEvent),
     BonkPoolStateAccountEvent(BonkPoolStateAccountEvent),
     BonkGlobalConfigAccountEvent(BonkGlobalConfigAccountEvent),
     BonkPlatformConfigAccountEvent(BonkPlatformConfigAccountEvent),
@@ -71,6 +94,7 @@ pub enum DexEvent {
     PumpFunCreateV2TokenEvent(PumpFunCreateV2TokenEvent),
     PumpFunTradeEvent(PumpFunTradeEvent),
     PumpFunMigrateEvent(PumpFunMigrateEvent),
+    PumpFunClaimCreatorFeeEvent(PumpFunClaimCreatorFeeEvent),
     PumpFunBondingCurveAccountEvent(PumpFunBondingCurveAccountEvent),
     PumpFunGlobalAccountEvent(PumpFunGlobalAccountEvent),
 
@@ -95,6 +119,7 @@ pub enum DexEvent {
     // Raydium CLMM events
     RaydiumClmmSwapEvent(RaydiumClmmSwapEvent),
     RaydiumClmmSwapV2Event(RaydiumClmmSwapV2Event),
+    RaydiumClmmSwapRouterBaseInEvent(RaydiumClmmSwapRouterBaseInEvent),
     RaydiumClmmClosePositionEvent(RaydiumClmmClosePositionEvent),
     RaydiumClmmIncreaseLiquidityV2Event(RaydiumClmmIncreaseLiquidityV2Event),
     RaydiumClmmDecreaseLiquidityV2Event(RaydiumClmmDecreaseLiquidityV2Event),
@@ -124,10 +149,12 @@ pub enum DexEvent {
     MeteoraDammV2InitializePoolWithDynamicConfigEvent(
         MeteoraDammV2InitializePoolWithDynamicConfigEvent,
     ),
+    MeteoraDammV2ClaimFeeEvent(MeteoraDammV2ClaimFeeEvent),
 
     // Meteora DLMM events
     MeteoraDlmmSwapEvent(MeteoraDlmmSwapEvent),
     MeteoraDlmmSwap2Event(MeteoraDlmmSwap2Event),
+    MeteoraDlmmInitializeLbPairEvent(MeteoraDlmmInitializeLbPairEvent),
     MeteoraDlmmLbPairAccountEvent(MeteoraDlmmLbPairAccountEvent),
     MeteoraDlmmBinArrayAccountEvent(MeteoraDlmmBinArrayAccountEvent),
     MeteoraDlmmBinArrayBitmapExtensionAccountEvent(MeteoraDlmmBinArrayBitmapExtensionAccountEvent),
@@ -145,6 +172,14 @@ pub enum DexEvent {
     BlockMetaEvent(BlockMetaEvent),
     SetComputeUnitLimitEvent(SetComputeUnitLimitEvent),
     SetComputeUnitPriceEvent(SetComputeUnitPriceEvent),
+    SetLoadedAccountsDataSizeLimitEvent(SetLoadedAccountsDataSizeLimitEvent),
+    AtaCreateEvent(AtaCreateEvent),
+    SolTransferEvent(SolTransferEvent),
+    TransactionSummaryEvent(TransactionSummaryEvent),
+    SlotRolledBackEvent(SlotRolledBackEvent),
+    SwapFailedEvent(SwapFailedEvent),
+    UnknownInstructionEvent(UnknownInstructionEvent),
+    RawProgramDataEvent(RawProgramDataEvent),
 }
 
 impl DexEvent {
@@ -159,6 +194,8 @@ impl DexEvent {
             DexEvent::BonkPoolCreateEvent(e) => &e.metadata,
             DexEvent::BonkMigrateToAmmEvent(e) => &e.metadata,
             DexEvent::BonkMigrateToCpswapEvent(e) => &e.metadata,
+            DexEvent::BonkClaimVestedTokenEvent(e) => &e.metadata,
+            DexEvent::BonkClaimPlatformFeeEvent(e) => &e.metadata,
             DexEvent::BonkPoolStateAccountEvent(e) => &e.metadata,
             DexEvent::BonkGlobalConfigAccountEvent(e) => &e.metadata,
             DexEvent::BonkPlatformConfigAccountEvent(e) => &e.metadata,
@@ -166,6 +203,7 @@ impl DexEvent {
             DexEvent::PumpFunCreateV2TokenEvent(e) => &e.metadata,
             DexEvent::PumpFunTradeEvent(e) => &e.metadata,
             DexEvent::PumpFunMigrateEvent(e) => &e.metadata,
+            DexEvent::PumpFunClaimCreatorFeeEvent(e) => &e.metadata,
             DexEvent::PumpFunBondingCurveAccountEvent(e) => &e.metadata,
             DexEvent::PumpFunGlobalAccountEvent(e) => &e.metadata,
             DexEvent::PumpSwapBuyEvent(e) => &e.metadata,
@@ -184,6 +222,7 @@ impl DexEvent {
             DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => &e.metadata,
             DexEvent::RaydiumClmmSwapEvent(e) => &e.metadata,
             DexEvent::RaydiumClmmSwapV2Event(e) => &e.metadata,
+            DexEvent::RaydiumClmmSwapRouterBaseInEvent(e) => &e.metadata,
             DexEvent::RaydiumClmmClosePositionEvent(e) => &e.metadata,
             DexEvent::RaydiumClmmIncreaseLiquidityV2Event(e) => &e.metadata,
             DexEvent::RaydiumClmmDecreaseLiquidityV2Event(e) => &e.metadata,
@@ -203,10 +242,12 @@ impl DexEvent {
             DexEvent::MeteoraDammV2SwapEvent(e) => &e.metadata,
             DexEvent::MeteoraDammV2Swap2Event(e) => &e.metadata,
             DexEvent::MeteoraDammV2InitializePoolEvent(e) => &e.metadata,
+            DexEvent::MeteoraDammV2ClaimFeeEvent(e) => &e.metadata,
             DexEvent::MeteoraDammV2InitializeCustomizablePoolEvent(e) => &e.metadata,
             DexEvent::MeteoraDammV2InitializePoolWithDynamicConfigEvent(e) => &e.metadata,
             DexEvent::MeteoraDlmmSwapEvent(e) => &e.metadata,
             DexEvent::MeteoraDlmmSwap2Event(e) => &e.metadata,
+            DexEvent::MeteoraDlmmInitializeLbPairEvent(e) => &e.metadata,
             DexEvent::MeteoraDlmmLbPairAccountEvent(e) => &e.metadata,
             DexEvent::MeteoraDlmmBinArrayAccountEvent(e) => &e.metadata,
             DexEvent::MeteoraDlmmBinArrayBitmapExtensionAccountEvent(e) => &e.metadata,
@@ -220,6 +261,14 @@ impl DexEvent {
             DexEvent::BlockMetaEvent(e) => &e.metadata,
             DexEvent::SetComputeUnitLimitEvent(e) => &e.metadata,
             DexEvent::SetComputeUnitPriceEvent(e) => &e.metadata,
+            DexEvent::SetLoadedAccountsDataSizeLimitEvent(e) => &e.metadata,
+            DexEvent::AtaCreateEvent(e) => &e.metadata,
+            DexEvent::SolTransferEvent(e) => &e.metadata,
+            DexEvent::TransactionSummaryEvent(e) => &e.metadata,
+            DexEvent::SlotRolledBackEvent(e) => &e.metadata,
+            DexEvent::SwapFailedEvent(e) => &e.metadata,
+            DexEvent::UnknownInstructionEvent(e) => &e.metadata,
+            DexEvent::RawProgramDataEvent(e) => &e.metadata,
         }
     }
 
@@ -234,6 +283,8 @@ impl DexEvent {
             DexEvent::BonkPoolCreateEvent(e) => &mut e.metadata,
             DexEvent::BonkMigrateToAmmEvent(e) => &mut e.metadata,
             DexEvent::BonkMigrateToCpswapEvent(e) => &mut e.metadata,
+            DexEvent::BonkClaimVestedTokenEvent(e) => &mut e.metadata,
+            DexEvent::BonkClaimPlatformFeeEvent(e) => &mut e.metadata,
             DexEvent::BonkPoolStateAccountEvent(e) => &mut e.metadata,
             DexEvent::BonkGlobalConfigAccountEvent(e) => &mut e.metadata,
             DexEvent::BonkPlatformConfigAccountEvent(e) => &mut e.metadata,
@@ -241,6 +292,7 @@ impl DexEvent {
             DexEvent::PumpFunCreateV2TokenEvent(e) => &mut e.metadata,
             DexEvent::PumpFunTradeEvent(e) => &mut e.metadata,
             DexEvent::PumpFunMigrateEvent(e) => &mut e.metadata,
+            DexEvent::PumpFunClaimCreatorFeeEvent(e) => &mut e.metadata,
             DexEvent::PumpFunBondingCurveAccountEvent(e) => &mut e.metadata,
             DexEvent::PumpFunGlobalAccountEvent(e) => &mut e.metadata,
             DexEvent::PumpSwapBuyEvent(e) => &mut e.metadata,
@@ -259,6 +311,7 @@ impl DexEvent {
             DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => &mut e.metadata,
             DexEvent::RaydiumClmmSwapEvent(e) => &mut e.metadata,
             DexEvent::RaydiumClmmSwapV2Event(e) => &mut e.metadata,
+            DexEvent::RaydiumClmmSwapRouterBaseInEvent(e) => &mut e.metadata,
             DexEvent::RaydiumClmmClosePositionEvent(e) => &mut e.metadata,
             DexEvent::RaydiumClmmIncreaseLiquidityV2Event(e) => &mut e.metadata,
             DexEvent::RaydiumClmmDecreaseLiquidityV2Event(e) => &mut e.metadata,
@@ -278,10 +331,12 @@ impl DexEvent {
             DexEvent::MeteoraDammV2SwapEvent(e) => &mut e.metadata,
             DexEvent::MeteoraDammV2Swap2Event(e) => &mut e.metadata,
             DexEvent::MeteoraDammV2InitializePoolEvent(e) => &mut e.metadata,
+            DexEvent::MeteoraDammV2ClaimFeeEvent(e) => &mut e.metadata,
             DexEvent::MeteoraDammV2InitializeCustomizablePoolEvent(e) => &mut e.metadata,
             DexEvent::MeteoraDammV2InitializePoolWithDynamicConfigEvent(e) => &mut e.metadata,
             DexEvent::MeteoraDlmmSwapEvent(e) => &mut e.metadata,
             DexEvent::MeteoraDlmmSwap2Event(e) => &mut e.metadata,
+            DexEvent::MeteoraDlmmInitializeLbPairEvent(e) => &mut e.metadata,
             DexEvent::MeteoraDlmmLbPairAccountEvent(e) => &mut e.metadata,
             DexEvent::MeteoraDlmmBinArrayAccountEvent(e) => &mut e.metadata,
             DexEvent::MeteoraDlmmBinArrayBitmapExtensionAccountEvent(e) => &mut e.metadata,
@@ -295,6 +350,993 @@ impl DexEvent {
             DexEvent::BlockMetaEvent(e) => &mut e.metadata,
             DexEvent::SetComputeUnitLimitEvent(e) => &mut e.metadata,
             DexEvent::SetComputeUnitPriceEvent(e) => &mut e.metadata,
+            DexEvent::SetLoadedAccountsDataSizeLimitEvent(e) => &mut e.metadata,
+            DexEvent::AtaCreateEvent(e) => &mut e.metadata,
+            DexEvent::SolTransferEvent(e) => &mut e.metadata,
+            DexEvent::TransactionSummaryEvent(e) => &mut e.metadata,
+            DexEvent::SlotRolledBackEvent(e) => &mut e.metadata,
+            DexEvent::SwapFailedEvent(e) => &mut e.metadata,
+            DexEvent::UnknownInstructionEvent(e) => &mut e.metadata,
+            DexEvent::RawProgramDataEvent(e) => &mut e.metadata,
+        }
+    }
+
+    /// 账户事件的原始账户数据，非账户事件返回 None
+    pub fn raw_account_data_mut(&mut self) -> Option<&mut Vec<u8>> {
+        match self {
+            DexEvent::PancakeSwapPoolStateAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::PancakeSwapTickArrayStateAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::PancakeSwapTickArrayBitmapExtensionAccountEvent(e) => {
+                Some(&mut e.raw_account_data)
+            }
+            DexEvent::BonkPoolStateAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::BonkGlobalConfigAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::BonkPlatformConfigAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::PumpFunBondingCurveAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::PumpFunGlobalAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::PumpSwapGlobalConfigAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::PumpSwapPoolAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::RaydiumClmmAmmConfigAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::RaydiumClmmPoolStateAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::RaydiumClmmTickArrayStateAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::RaydiumClmmTickArrayBitmapExtensionAccountEvent(e) => {
+                Some(&mut e.raw_account_data)
+            }
+            DexEvent::RaydiumCpmmAmmConfigAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::RaydiumCpmmPoolStateAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::MeteoraDlmmLbPairAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::MeteoraDlmmBinArrayAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::MeteoraDlmmBinArrayBitmapExtensionAccountEvent(e) => {
+                Some(&mut e.raw_account_data)
+            }
+            DexEvent::WhirlpoolAccountEvent(e) => Some(&mut e.raw_account_data),
+            DexEvent::WhirlpoolTickArrayAccountEvent(e) => Some(&mut e.raw_account_data),
+            _ => None,
+        }
+    }
+
+    /// 账户事件对应的账户地址，非账户事件返回 None
+    pub fn account_pubkey(&self) -> Option<Pubkey> {
+        match self {
+            DexEvent::PancakeSwapPoolStateAccountEvent(e) => Some(e.pubkey),
+            DexEvent::PancakeSwapTickArrayStateAccountEvent(e) => Some(e.pubkey),
+            DexEvent::PancakeSwapTickArrayBitmapExtensionAccountEvent(e) => Some(e.pubkey),
+            DexEvent::BonkPoolStateAccountEvent(e) => Some(e.pubkey),
+            DexEvent::BonkGlobalConfigAccountEvent(e) => Some(e.pubkey),
+            DexEvent::BonkPlatformConfigAccountEvent(e) => Some(e.pubkey),
+            DexEvent::PumpFunBondingCurveAccountEvent(e) => Some(e.pubkey),
+            DexEvent::PumpFunGlobalAccountEvent(e) => Some(e.pubkey),
+            DexEvent::PumpSwapGlobalConfigAccountEvent(e) => Some(e.pubkey),
+            DexEvent::PumpSwapPoolAccountEvent(e) => Some(e.pubkey),
+            DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => Some(e.pubkey),
+            DexEvent::RaydiumClmmAmmConfigAccountEvent(e) => Some(e.pubkey),
+            DexEvent::RaydiumClmmPoolStateAccountEvent(e) => Some(e.pubkey),
+            DexEvent::RaydiumClmmTickArrayStateAccountEvent(e) => Some(e.pubkey),
+            DexEvent::RaydiumClmmTickArrayBitmapExtensionAccountEvent(e) => Some(e.pubkey),
+            DexEvent::RaydiumCpmmAmmConfigAccountEvent(e) => Some(e.pubkey),
+            DexEvent::RaydiumCpmmPoolStateAccountEvent(e) => Some(e.pubkey),
+            DexEvent::MeteoraDlmmLbPairAccountEvent(e) => Some(e.pubkey),
+            DexEvent::MeteoraDlmmBinArrayAccountEvent(e) => Some(e.pubkey),
+            DexEvent::MeteoraDlmmBinArrayBitmapExtensionAccountEvent(e) => Some(e.pubkey),
+            DexEvent::WhirlpoolAccountEvent(e) => Some(e.pubkey),
+            DexEvent::WhirlpoolTickArrayAccountEvent(e) => Some(e.pubkey),
+            DexEvent::TokenAccountEvent(e) => Some(e.pubkey),
+            DexEvent::NonceAccountEvent(e) => Some(e.pubkey),
+            DexEvent::TokenInfoEvent(e) => Some(e.pubkey),
+            _ => None,
+        }
+    }
+
+    /// 事件涉及的池子地址：指令事件取 pool_state / pool / amm / lb_pair / whirlpool / bonding_curve，
+    /// 池子账户事件取账户地址；不涉及池子的事件返回 None
+    pub fn pool_id(&self) -> Option<Pubkey> {
+        let pool = match self {
+            DexEvent::BonkTradeEvent(e) => e.pool_state,
+            DexEvent::BonkPoolCreateEvent(e) => e.pool_state,
+            DexEvent::BonkMigrateToAmmEvent(e) => e.pool_state,
+            DexEvent::BonkMigrateToCpswapEvent(e) => e.pool_state,
+            DexEvent::BonkClaimVestedTokenEvent(e) => e.pool_state,
+            DexEvent::BonkClaimPlatformFeeEvent(e) => e.pool_state,
+            DexEvent::BonkPoolStateAccountEvent(e) => e.pubkey,
+            DexEvent::PumpFunCreateTokenEvent(e) => e.bonding_curve,
+            DexEvent::PumpFunCreateV2TokenEvent(e) => e.bonding_curve,
+            DexEvent::PumpFunTradeEvent(e) => e.bonding_curve,
+            DexEvent::PumpFunMigrateEvent(e) => e.bonding_curve,
+            DexEvent::PumpFunBondingCurveAccountEvent(e) => e.pubkey,
+            DexEvent::PumpSwapBuyEvent(e) => e.pool,
+            DexEvent::PumpSwapBuyExactQuoteInEvent(e) => e.pool,
+            DexEvent::PumpSwapSellEvent(e) => e.pool,
+            DexEvent::PumpSwapCreatePoolEvent(e) => e.pool,
+            DexEvent::PumpSwapDepositEvent(e) => e.pool,
+            DexEvent::PumpSwapWithdrawEvent(e) => e.pool,
+            DexEvent::PumpSwapPoolAccountEvent(e) => e.pubkey,
+            DexEvent::RaydiumCpmmSwapEvent(e) => e.pool_state,
+            DexEvent::RaydiumCpmmDepositEvent(e) => e.pool_state,
+            DexEvent::RaydiumCpmmInitializeEvent(e) => e.pool_state,
+            DexEvent::RaydiumCpmmWithdrawEvent(e) => e.pool_state,
+            DexEvent::RaydiumCpmmPoolStateAccountEvent(e) => e.pubkey,
+            DexEvent::RaydiumClmmSwapEvent(e) => e.pool_state,
+            DexEvent::RaydiumClmmSwapV2Event(e) => e.pool_state,
+            DexEvent::RaydiumClmmSwapRouterBaseInEvent(e) => e.hops.first()?.pool_state,
+            DexEvent::RaydiumClmmCreatePoolEvent(e) => e.pool_state,
+            DexEvent::RaydiumClmmIncreaseLiquidityV2Event(e) => e.pool_state,
+            DexEvent::RaydiumClmmDecreaseLiquidityV2Event(e) => e.pool_state,
+            DexEvent::RaydiumClmmOpenPositionWithToken22NftEvent(e) => e.pool_state,
+            DexEvent::RaydiumClmmOpenPositionV2Event(e) => e.pool_state,
+            DexEvent::RaydiumClmmPoolStateAccountEvent(e) => e.pubkey,
+            DexEvent::PancakeSwapSwapEvent(e) => e.pool_state,
+            DexEvent::PancakeSwapSwapV2Event(e) => e.pool_state,
+            DexEvent::PancakeSwapPoolStateAccountEvent(e) => e.pubkey,
+            DexEvent::RaydiumAmmV4SwapEvent(e) => e.amm,
+            DexEvent::RaydiumAmmV4DepositEvent(e) => e.amm,
+            DexEvent::RaydiumAmmV4Initialize2Event(e) => e.amm,
+            DexEvent::RaydiumAmmV4WithdrawEvent(e) => e.amm,
+            DexEvent::RaydiumAmmV4WithdrawPnlEvent(e) => e.amm,
+            DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => e.pubkey,
+            DexEvent::MeteoraDlmmSwapEvent(e) => e.lb_pair,
+            DexEvent::MeteoraDlmmSwap2Event(e) => e.lb_pair,
+            DexEvent::MeteoraDlmmInitializeLbPairEvent(e) => e.lb_pair,
+            DexEvent::MeteoraDlmmLbPairAccountEvent(e) => e.pubkey,
+            DexEvent::MeteoraDammV2SwapEvent(e) => e.pool,
+            DexEvent::MeteoraDammV2Swap2Event(e) => e.pool,
+            DexEvent::MeteoraDammV2InitializePoolEvent(e) => e.pool,
+            DexEvent::MeteoraDammV2InitializeCustomizablePoolEvent(e) => e.pool,
+            DexEvent::MeteoraDammV2InitializePoolWithDynamicConfigEvent(e) => e.pool,
+            DexEvent::MeteoraDammV2ClaimFeeEvent(e) => e.pool,
+            DexEvent::WhirlpoolSwapEvent(e) => e.whirlpool,
+            DexEvent::WhirlpoolSwapV2Event(e) => e.whirlpool,
+            DexEvent::WhirlpoolAccountEvent(e) => e.pubkey,
+            _ => return None,
+        };
+        (pool != Pubkey::default()).then_some(pool)
+    }
+
+    /// 事件涉及的所有 token mint（base / quote / input / output 等，不区分角色）
+    ///
+    /// 按事件字段顺序返回，跳过默认值和重复项；事件本身不带 mint 时回退到 `metadata.swap_data`。
+    /// 可用于构建 mint -> 事件的索引，与 `pool_id()` 一起构成完整的索引键。
+    pub fn mints(&self) -> SmallVec<[Pubkey; 2]> {
+        let candidates: SmallVec<[Option<Pubkey>; 2]> = match self {
+            DexEvent::BonkTradeEvent(e) => {
+                smallvec![Some(e.base_token_mint), Some(e.quote_token_mint)]
+            }
+            DexEvent::BonkPoolCreateEvent(e) => smallvec![Some(e.base_mint), Some(e.quote_mint)],
+            DexEvent::BonkMigrateToAmmEvent(e) => {
+                smallvec![Some(e.base_mint), Some(e.quote_mint)]
+            }
+            DexEvent::BonkMigrateToCpswapEvent(e) => {
+                smallvec![Some(e.base_mint), Some(e.quote_mint)]
+            }
+            DexEvent::BonkClaimVestedTokenEvent(e) => smallvec![Some(e.base_token_mint)],
+            DexEvent::BonkClaimPlatformFeeEvent(e) => smallvec![Some(e.quote_mint)],
+            DexEvent::BonkPoolStateAccountEvent(e) => {
+                smallvec![Some(e.pool_state.base_mint), Some(e.pool_state.quote_mint)]
+            }
+            DexEvent::PumpFunCreateTokenEvent(e) => smallvec![Some(e.mint), Some(WSOL_MINT)],
+            DexEvent::PumpFunCreateV2TokenEvent(e) => smallvec![Some(e.mint), Some(WSOL_MINT)],
+            DexEvent::PumpFunTradeEvent(e) => smallvec![Some(e.mint), Some(WSOL_MINT)],
+            DexEvent::PumpFunMigrateEvent(e) => smallvec![Some(e.mint), Some(e.wsol_mint)],
+            DexEvent::PumpSwapBuyEvent(e) => smallvec![Some(e.base_mint), Some(e.quote_mint)],
+            DexEvent::PumpSwapBuyExactQuoteInEvent(e) => {
+                smallvec![Some(e.base_mint), Some(e.quote_mint)]
+            }
+            DexEvent::PumpSwapSellEvent(e) => smallvec![Some(e.base_mint), Some(e.quote_mint)],
+            DexEvent::PumpSwapCreatePoolEvent(e) => {
+                smallvec![Some(e.base_mint), Some(e.quote_mint)]
+            }
+            DexEvent::PumpSwapDepositEvent(e) => smallvec![Some(e.base_mint), Some(e.quote_mint)],
+            DexEvent::PumpSwapWithdrawEvent(e) => smallvec![Some(e.base_mint), Some(e.quote_mint)],
+            DexEvent::PumpSwapPoolAccountEvent(e) => {
+                smallvec![Some(e.pool.base_mint), Some(e.pool.quote_mint)]
+            }
+            DexEvent::RaydiumCpmmSwapEvent(e) => {
+                smallvec![Some(e.input_token_mint), Some(e.output_token_mint)]
+            }
+            DexEvent::RaydiumCpmmDepositEvent(e) => {
+                smallvec![Some(e.vault0_mint), Some(e.vault1_mint)]
+            }
+            DexEvent::RaydiumCpmmInitializeEvent(e) => {
+                smallvec![Some(e.token0_mint), Some(e.token1_mint)]
+            }
+            DexEvent::RaydiumCpmmWithdrawEvent(e) => {
+                smallvec![Some(e.vault0_mint), Some(e.vault1_mint)]
+            }
+            DexEvent::RaydiumCpmmPoolStateAccountEvent(e) => {
+                smallvec![Some(e.pool_state.token_0_mint), Some(e.pool_state.token_1_mint)]
+            }
+            DexEvent::RaydiumClmmSwapV2Event(e) => {
+                smallvec![Some(e.input_vault_mint), Some(e.output_vault_mint)]
+            }
+            DexEvent::RaydiumClmmSwapRouterBaseInEvent(e) => smallvec![Some(e.input_token_mint)],
+            DexEvent::RaydiumClmmCreatePoolEvent(e) => {
+                smallvec![Some(e.token_mint0), Some(e.token_mint1)]
+            }
+            DexEvent::RaydiumClmmIncreaseLiquidityV2Event(e) => {
+                smallvec![Some(e.vault0_mint), Some(e.vault1_mint)]
+            }
+            DexEvent::RaydiumClmmDecreaseLiquidityV2Event(e) => {
+                smallvec![Some(e.vault0_mint), Some(e.vault1_mint)]
+            }
+            DexEvent::RaydiumClmmOpenPositionWithToken22NftEvent(e) => {
+                smallvec![Some(e.vault0_mint), Some(e.vault1_mint)]
+            }
+            DexEvent::RaydiumClmmOpenPositionV2Event(e) => {
+                smallvec![Some(e.vault0_mint), Some(e.vault1_mint)]
+            }
+            DexEvent::RaydiumClmmPoolStateAccountEvent(e) => {
+                smallvec![Some(e.pool_state.token_mint0), Some(e.pool_state.token_mint1)]
+            }
+            DexEvent::PancakeSwapSwapV2Event(e) => {
+                smallvec![Some(e.input_mint), Some(e.output_mint)]
+            }
+            DexEvent::PancakeSwapPoolStateAccountEvent(e) => {
+                smallvec![Some(e.pool_state.token_mint0), Some(e.pool_state.token_mint1)]
+            }
+            DexEvent::RaydiumAmmV4Initialize2Event(e) => {
+                smallvec![Some(e.coin_mint), Some(e.pc_mint)]
+            }
+            DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => {
+                smallvec![Some(e.amm_info.coin_mint), Some(e.amm_info.pc_mint)]
+            }
+            DexEvent::MeteoraDlmmSwapEvent(e) => smallvec![e.token_x_mint, e.token_y_mint],
+            DexEvent::MeteoraDlmmSwap2Event(e) => smallvec![e.token_x_mint, e.token_y_mint],
+            DexEvent::MeteoraDlmmInitializeLbPairEvent(e) => {
+                smallvec![Some(e.token_x_mint), Some(e.token_y_mint)]
+            }
+            DexEvent::MeteoraDlmmLbPairAccountEvent(e) => {
+                smallvec![Some(e.lb_pair.token_x_mint), Some(e.lb_pair.token_y_mint)]
+            }
+            DexEvent::MeteoraDammV2SwapEvent(e) => {
+                smallvec![Some(e.token_a_mint), Some(e.token_b_mint)]
+            }
+            DexEvent::MeteoraDammV2Swap2Event(e) => {
+                smallvec![Some(e.token_a_mint), Some(e.token_b_mint)]
+            }
+            DexEvent::MeteoraDammV2InitializePoolEvent(e) => {
+                smallvec![Some(e.token_a_mint), Some(e.token_b_mint)]
+            }
+            DexEvent::MeteoraDammV2InitializeCustomizablePoolEvent(e) => {
+                smallvec![Some(e.token_a_mint), Some(e.token_b_mint)]
+            }
+            DexEvent::MeteoraDammV2InitializePoolWithDynamicConfigEvent(e) => {
+                smallvec![Some(e.token_a_mint), Some(e.token_b_mint)]
+            }
+            DexEvent::MeteoraDammV2ClaimFeeEvent(e) => {
+                smallvec![Some(e.token_a_mint), Some(e.token_b_mint)]
+            }
+            DexEvent::WhirlpoolSwapV2Event(e) => {
+                smallvec![Some(e.token_mint_a), Some(e.token_mint_b)]
+            }
+            DexEvent::WhirlpoolAccountEvent(e) => {
+                smallvec![Some(e.whirlpool.token_mint_a), Some(e.whirlpool.token_mint_b)]
+            }
+            DexEvent::AtaCreateEvent(e) => smallvec![Some(e.mint)],
+            DexEvent::TokenInfoEvent(e) => smallvec![Some(e.pubkey)],
+            _ => SmallVec::new(),
+        };
+
+        let mut mints = SmallVec::<[Pubkey; 2]>::new();
+        for mint in candidates.into_iter().flatten() {
+            if mint != Pubkey::default() && !mints.contains(&mint) {
+                mints.push(mint);
+            }
+        }
+        if mints.is_empty() {
+            if let Some(swap_data) = &self.metadata().swap_data {
+                for mint in [swap_data.from_mint, swap_data.to_mint] {
+                    if mint != Pubkey::default() && !mints.contains(&mint) {
+                        mints.push(mint);
+                    }
+                }
+            }
+        }
+        mints
+    }
+
+    /// 是否来自 inner instruction (CPI)
+    #[inline]
+    pub fn is_inner(&self) -> bool {
+        self.metadata().inner_index.is_some()
+    }
+
+    /// 所在外层指令的索引
+    #[inline]
+    pub fn outer_index(&self) -> i64 {
+        self.metadata().outer_index
+    }
+
+    /// 在外层指令中的 inner instruction 索引，外层事件为 None
+    #[inline]
+    pub fn inner_index(&self) -> Option<i64> {
+        self.metadata().inner_index
+    }
+
+    /// 确定性的事件 ID，可作为下游存储的主键
+    ///
+    /// 为 `SHA-256(signature ‖ outer_index ‖ inner_index ‖ event_type)`：signature 64 字节，
+    /// outer_index 为 i64 小端，inner_index 为 1 字节标记（0 = None，1 = Some）加 i64 小端，
+    /// event_type 为其 `EventType::stable_id` 的 u16 小端。只要这几个字段不变，ID 在不同 crate 版本间
+    /// 保持稳定，事件类型改名也不影响。
+    ///
+    /// 账户和区块事件没有指令位置，同一交易 / slot 内的多个此类事件 ID 会相同。
+    pub fn event_id(&self) -> [u8; 32] {
+        let metadata = self.metadata();
+        let mut hasher = Sha256::new();
+        hasher.update(metadata.signature.as_ref());
+        hasher.update(metadata.outer_index.to_le_bytes());
+        match metadata.inner_index {
+            Some(inner_index) => {
+                hasher.update([1u8]);
+                hasher.update(inner_index.to_le_bytes());
+            }
+            None => hasher.update([0u8]),
+        }
+        hasher.update(metadata.event_type.stable_id().to_le_bytes());
+        hasher.finalize().into()
+    }
+
+    /// `event_id` 的十六进制字符串
+    pub fn event_id_hex(&self) -> String {
+        hex::encode(self.event_id())
+    }
+
+    /// slot 内确定性全序的排序键：`(slot, transaction_index, outer_index, inner_index, seq)`
+    ///
+    /// - `transaction_index` 为交易在区块中的位置，来源未提供时为 None，排在同 slot 有索引的事件之前
+    /// - 外层指令事件（`inner_index` 为 None）排在其 CPI 事件之前
+    /// - `seq` 为账户事件的 `account_write_version`，其他事件为 0
+    ///
+    /// 同一指令位置产生的多个事件（例如 ComputeBudget 事件）键相同，排序时应使用稳定排序保持解析顺序。
+    #[inline]
+    pub fn ordering_key(&self) -> EventOrderingKey {
+        let metadata = self.metadata();
+        (
+            metadata.slot,
+            metadata.transaction_index,
+            metadata.outer_index,
+            metadata.inner_index,
+            metadata.account_write_version.unwrap_or(0),
+        )
+    }
+
+    /// CLMM / Whirlpool swap 相对池子 mint 对的方向
+    pub fn swap_direction(&self) -> Option<SwapDirection> {
+        match self {
+            DexEvent::RaydiumClmmSwapEvent(e) => Some(SwapDirection::from_a_to_b(e.zero_for_one)),
+            DexEvent::RaydiumClmmSwapV2Event(e) => Some(SwapDirection::from_a_to_b(e.zero_for_one)),
+            DexEvent::WhirlpoolSwapEvent(e) => Some(SwapDirection::from_a_to_b(e.a_to_b)),
+            DexEvent::WhirlpoolSwapV2Event(e) => Some(SwapDirection::from_a_to_b(e.a_to_b)),
+            _ => None,
+        }
+    }
+
+    /// 以 lamports 计的总手续费
+    ///
+    /// 按 `QuoteMints::global()` 判断收费一侧，仅当其为 SOL/WSOL 时返回 Some；
+    /// 全局集合不包含 WSOL 时始终返回 None
+    pub fn total_fee_lamports(&self) -> Option<u64> {
+        QuoteMints::with_global(|quote_mints| self.total_fee_quote(quote_mints))
+            .and_then(|(mint, fee)| (mint == WSOL_MINT).then_some(fee))
+    }
+
+    /// 以 quote 计的总手续费，返回 (收费 mint, 原始数量)
+    ///
+    /// 仅当收费一侧属于 `quote_mints` 时返回 Some
+    pub fn total_fee_quote(&self, quote_mints: &QuoteMints) -> Option<(Pubkey, u64)> {
+        let (fee_mint, fee) = match self {
+            // PumpFun 曲线交易的手续费始终以 SOL 计
+            DexEvent::PumpFunTradeEvent(e) => (WSOL_MINT, e.fee.saturating_add(e.creator_fee)),
+            // PumpSwap 手续费以 quote 计
+            DexEvent::PumpSwapBuyEvent(e) => (
+                e.quote_mint,
+                e.lp_fee.saturating_add(e.protocol_fee).saturating_add(e.coin_creator_fee),
+            ),
+            DexEvent::PumpSwapBuyExactQuoteInEvent(e) => (
+                e.quote_mint,
+                e.lp_fee.saturating_add(e.protocol_fee).saturating_add(e.coin_creator_fee),
+            ),
+            DexEvent::PumpSwapSellEvent(e) => (
+                e.quote_mint,
+                e.lp_fee.saturating_add(e.protocol_fee).saturating_add(e.coin_creator_fee),
+            ),
+            // Bonk 手续费以 quote 计
+            DexEvent::BonkTradeEvent(e) => (
+                e.quote_token_mint,
+                e.protocol_fee
+                    .saturating_add(e.platform_fee)
+                    .saturating_add(e.creator_fee)
+                    .saturating_add(e.share_fee),
+            ),
+            // CPMM trade_fee 在输入侧收取，creator_fee 视 creator_fee_on_input 而定
+            DexEvent::RaydiumCpmmSwapEvent(e) => {
+                if e.creator_fee_on_input {
+                    (e.input_token_mint, e.trade_fee.saturating_add(e.creator_fee))
+                } else if e.creator_fee == 0 {
+                    (e.input_token_mint, e.trade_fee)
+                } else {
+                    return None;
+                }
+            }
+            // DLMM 手续费在输入侧收取 (fee 已包含 protocol/host fee)
+            DexEvent::MeteoraDlmmSwapEvent(e) => {
+                let input_mint = if e.swap_for_y { e.token_x_mint } else { e.token_y_mint };
+                (input_mint?, e.fee)
+            }
+            DexEvent::MeteoraDlmmSwap2Event(e) => {
+                let input_mint = if e.swap_for_y { e.token_x_mint } else { e.token_y_mint };
+                (input_mint?, e.swap_result.total_fee)
+            }
+            _ => return None,
+        };
+        quote_mints.contains(&fee_mint).then_some((fee_mint, fee))
+    }
+
+    /// SOL/WSOL 一侧的成交额 (lamports)
+    ///
+    /// 按 `QuoteMints::global()` 选出 quote 一侧，仅当其为 SOL/WSOL 时返回 Some，
+    /// 例如 SOL/USDC 池子在 USDC 优先级更高时返回 None；未列出的协议回退到 swap_data
+    pub fn sol_volume_lamports(&self) -> Option<u64> {
+        QuoteMints::with_global(|quote_mints| self.quote_leg(quote_mints))
+            .and_then(|leg| (leg.mint == WSOL_MINT).then_some(leg.amount))
+    }
+
+    /// quote 一侧的成交额和交易方向
+    ///
+    /// 两侧都不属于 `quote_mints` 时返回 None；未列出的协议回退到 swap_data
+    pub fn quote_leg(&self, quote_mints: &QuoteMints) -> Option<QuoteLeg> {
+        let (from_mint, from_amount, to_mint, to_amount) = self.trade_legs()?;
+        let mint = quote_mints.quote_of(&from_mint, &to_mint)?;
+        let is_buy = mint == from_mint;
+        Some(QuoteLeg { mint, amount: if is_buy { from_amount } else { to_amount }, is_buy })
+    }
+
+    /// 按 `quote_mints` 归一化为 base / quote 两侧的成交
+    ///
+    /// 交易两侧或发起钱包无法确定、或两侧都不属于 `quote_mints` 时返回 None
+    pub fn to_normalized_trade(&self, quote_mints: &QuoteMints) -> Option<NormalizedTrade> {
+        let (from_mint, from_amount, to_mint, to_amount) = self.trade_legs()?;
+        let quote_mint = quote_mints.quote_of(&from_mint, &to_mint)?;
+        let is_buy = quote_mint == from_mint;
+        let (base_mint, base_amount, quote_amount) = if is_buy {
+            (to_mint, to_amount, from_amount)
+        } else {
+            (from_mint, from_amount, to_amount)
+        };
+        let metadata = self.metadata();
+        Some(NormalizedTrade {
+            signature: metadata.signature,
+            slot: metadata.slot,
+            trader: self.trader()?,
+            base_mint,
+            base_amount,
+            quote_mint,
+            quote_amount,
+            is_buy,
+        })
+    }
+
+    /// 发起 swap 的钱包（签名者 / 代币账户 owner），非 swap 事件返回 None
+    pub fn trader(&self) -> Option<Pubkey> {
+        let trader = match self {
+            DexEvent::PumpFunTradeEvent(e) => e.user,
+            DexEvent::PumpSwapBuyEvent(e) => e.user,
+            DexEvent::PumpSwapBuyExactQuoteInEvent(e) => e.user,
+            DexEvent::PumpSwapSellEvent(e) => e.user,
+            DexEvent::BonkTradeEvent(e) => e.payer,
+            DexEvent::RaydiumCpmmSwapEvent(e) => e.payer,
+            DexEvent::RaydiumClmmSwapEvent(e) => e.payer,
+            DexEvent::RaydiumClmmSwapV2Event(e) => e.payer,
+            DexEvent::RaydiumClmmSwapRouterBaseInEvent(e) => e.payer,
+            DexEvent::RaydiumAmmV4SwapEvent(e) => e.user_source_owner,
+            DexEvent::PancakeSwapSwapEvent(e) => e.payer,
+            DexEvent::PancakeSwapSwapV2Event(e) => e.payer,
+            DexEvent::WhirlpoolSwapEvent(e) => e.token_authority,
+            DexEvent::WhirlpoolSwapV2Event(e) => e.token_authority,
+            DexEvent::MeteoraDlmmSwapEvent(e) => e.user,
+            DexEvent::MeteoraDlmmSwap2Event(e) => e.user,
+            DexEvent::MeteoraDammV2SwapEvent(e) => e.payer,
+            DexEvent::MeteoraDammV2Swap2Event(e) => e.payer,
+            _ => return None,
+        };
+        (trader != Pubkey::default()).then_some(trader)
+    }
+
+    /// 代币或池子的创建者，未知时返回 None
+    ///
+    /// - 建币 / 建池事件：发起创建的钱包。PumpFun 建币取 `creator`（缺省时为 `user`），
+    ///   PumpSwap 建池优先取 `coin_creator`，迁移建的池 `creator` 是迁移程序的权限账户
+    /// - PumpFun / PumpSwap 交易：事件自带的代币创建者 `creator` / `coin_creator`
+    /// - Bonk 交易：没有创建者字段，只有 dev 交易（`is_dev_create_token_trade`，即 payer 在同一笔
+    ///   交易中建池）能确定创建者，此时返回 `payer`；其他 Bonk 交易返回 None
+    pub fn creator(&self) -> Option<Pubkey> {
+        let creator = match self {
+            DexEvent::PumpFunCreateTokenEvent(e) => non_default(e.creator).unwrap_or(e.user),
+            DexEvent::PumpFunCreateV2TokenEvent(e) => non_default(e.creator).unwrap_or(e.user),
+            DexEvent::PumpFunTradeEvent(e) => e.creator,
+            DexEvent::PumpFunClaimCreatorFeeEvent(e) => e.creator,
+            DexEvent::PumpSwapCreatePoolEvent(e) => {
+                non_default(e.coin_creator).unwrap_or(e.creator)
+            }
+            DexEvent::PumpSwapBuyEvent(e) => e.coin_creator,
+            DexEvent::PumpSwapBuyExactQuoteInEvent(e) => e.coin_creator,
+            DexEvent::PumpSwapSellEvent(e) => e.coin_creator,
+            DexEvent::BonkPoolCreateEvent(e) => e.creator,
+            DexEvent::BonkTradeEvent(e) if e.is_dev_create_token_trade => e.payer,
+            DexEvent::RaydiumCpmmInitializeEvent(e) => e.creator,
+            DexEvent::RaydiumClmmCreatePoolEvent(e) => e.pool_creator,
+            DexEvent::RaydiumAmmV4Initialize2Event(e) => e.user_wallet,
+            DexEvent::MeteoraDammV2InitializePoolEvent(e) => e.creator,
+            DexEvent::MeteoraDammV2InitializeCustomizablePoolEvent(e) => e.creator,
+            DexEvent::MeteoraDammV2InitializePoolWithDynamicConfigEvent(e) => e.creator,
+            DexEvent::MeteoraDlmmInitializeLbPairEvent(e) => e.funder,
+            _ => return None,
+        };
+        non_default(creator)
+    }
+
+    /// 交易两侧 (输入 mint, 输入数量, 输出 mint, 输出数量)
+    fn trade_legs(&self) -> Option<(Pubkey, u64, Pubkey, u64)> {
+        match self {
+            DexEvent::PumpFunTradeEvent(e) => Some(if e.is_buy {
+                (WSOL_MINT, e.sol_amount, e.mint, e.token_amount)
+            } else {
+                (e.mint, e.token_amount, WSOL_MINT, e.sol_amount)
+            }),
+            DexEvent::PumpSwapBuyEvent(e) => {
+                Some((e.quote_mint, e.quote_amount_in, e.base_mint, e.base_amount_out))
+            }
+            DexEvent::PumpSwapBuyExactQuoteInEvent(e) => Some((
+                e.quote_mint,
+                e.actual_quote_amount_in,
+                e.base_mint,
+                e.actual_base_amount_out,
+            )),
+            DexEvent::PumpSwapSellEvent(e) => {
+                Some((e.base_mint, e.base_amount_in, e.quote_mint, e.quote_amount_out))
+            }
+            // 未合并 CPI 日志时只有指令参数中的数量上下限，不是实际成交量
+            DexEvent::BonkTradeEvent(e) if !e.has_trade_log() => None,
+            DexEvent::BonkTradeEvent(e) => Some(match e.trade_direction {
+                TradeDirection::Buy => {
+                    (e.quote_token_mint, e.amount_in, e.base_token_mint, e.amount_out)
+                }
+                TradeDirection::Sell => {
+                    (e.base_token_mint, e.amount_in, e.quote_token_mint, e.amount_out)
+                }
+            }),
+            _ => {
+                let swap_data = self.metadata().swap_data.as_ref()?;
+                Some((
+                    swap_data.from_mint,
+                    swap_data.from_amount,
+                    swap_data.to_mint,
+                    swap_data.to_amount,
+                ))
+            }
         }
     }
+
+    /// 实际成交相对滑点上限的余量 (bps)
+    ///
+    /// - min-out 类指令: (actual_out - min_out) / actual_out
+    /// - max-in 类指令: (max_in - actual_in) / actual_in
+    ///
+    /// 指令参数或实际成交量任一缺失时返回 None
+    pub fn realized_slippage_bps(&self) -> Option<u64> {
+        match self {
+            DexEvent::PumpFunTradeEvent(e) => {
+                let fees = e.fee.saturating_add(e.creator_fee);
+                if e.is_buy {
+                    max_in_slippage_bps(e.max_sol_cost, e.sol_amount.saturating_add(fees))
+                } else {
+                    min_out_slippage_bps(e.min_sol_output, e.sol_amount.saturating_sub(fees))
+                }
+            }
+            DexEvent::PumpSwapBuyEvent(e) => {
+                max_in_slippage_bps(e.max_quote_amount_in, e.user_quote_amount_in)
+            }
+            DexEvent::PumpSwapBuyExactQuoteInEvent(e) => {
+                min_out_slippage_bps(e.min_base_amount_out, e.actual_base_amount_out)
+            }
+            DexEvent::PumpSwapSellEvent(e) => {
+                min_out_slippage_bps(e.min_quote_amount_out, e.user_quote_amount_out)
+            }
+            DexEvent::BonkTradeEvent(e) => {
+                if e.exact_in {
+                    min_out_slippage_bps(e.minimum_amount_out, e.amount_out)
+                } else {
+                    max_in_slippage_bps(e.maximum_amount_in, e.amount_in)
+                }
+            }
+            DexEvent::RaydiumCpmmSwapEvent(e) => {
+                if e.swap_base_input {
+                    min_out_slippage_bps(e.minimum_amount_out, e.output_amount)
+                } else {
+                    max_in_slippage_bps(e.max_amount_in, e.input_amount)
+                }
+            }
+            DexEvent::RaydiumClmmSwapEvent(e) => {
+                let (amount_in, amount_out) =
+                    SwapDirection::from_a_to_b(e.zero_for_one).order(e.amount_0, e.amount_1);
+                if e.is_base_input {
+                    min_out_slippage_bps(e.other_amount_threshold, amount_out)
+                } else {
+                    max_in_slippage_bps(e.other_amount_threshold, amount_in)
+                }
+            }
+            DexEvent::RaydiumClmmSwapRouterBaseInEvent(e) => {
+                min_out_slippage_bps(e.amount_out_minimum, e.amount_out()?)
+            }
+            DexEvent::RaydiumClmmSwapV2Event(e) => {
+                let (amount_in, amount_out) =
+                    SwapDirection::from_a_to_b(e.zero_for_one).order(e.amount_0, e.amount_1);
+                if e.is_base_input {
+                    min_out_slippage_bps(e.other_amount_threshold, amount_out)
+                } else {
+                    max_in_slippage_bps(e.other_amount_threshold, amount_in)
+                }
+            }
+            DexEvent::WhirlpoolSwapEvent(e) => {
+                if e.amount_specified_is_input {
+                    min_out_slippage_bps(e.other_amount_threshold, e.output_amount)
+                } else {
+                    max_in_slippage_bps(e.other_amount_threshold, e.input_amount)
+                }
+            }
+            DexEvent::WhirlpoolSwapV2Event(e) => {
+                if e.amount_specified_is_input {
+                    min_out_slippage_bps(e.other_amount_threshold, e.output_amount)
+                } else {
+                    max_in_slippage_bps(e.other_amount_threshold, e.input_amount)
+                }
+            }
+            DexEvent::MeteoraDlmmSwapEvent(e) => {
+                min_out_slippage_bps(e.min_amount_out, e.cpi_amount_out)
+            }
+            // swap_exact_out2 也会产出 Swap2 事件，但其参数不是 min-out，不能套用同一公式
+            DexEvent::MeteoraDlmmSwap2Event(e) if e.exact_in => {
+                min_out_slippage_bps(e.min_amount_out, e.swap_result.amount_out)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 单行摘要，例如 `PumpFunBuy sig=5VERv8NM.. slot=123 0.5 SOL -> 1.2M 7xKX..`
+///
+/// 成交数量取自 `metadata.swap_data`；精度未知时输出原始数量
+impl std::fmt::Display for DexEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let metadata = self.metadata();
+        let signature = metadata.signature.to_string();
+        write!(
+            f,
+            "{} sig={}.. slot={}",
+            metadata.event_type,
+            &signature[..signature.len().min(8)],
+            metadata.slot
+        )?;
+        if let Some(swap_data) = &metadata.swap_data {
+            write!(
+                f,
+                " {} {} -> {} {}",
+                format_amount(swap_data.from_amount, swap_data.from_decimals, &swap_data.from_mint),
+                format_mint(&swap_data.from_mint),
+                format_amount(swap_data.to_amount, swap_data.to_decimals, &swap_data.to_mint),
+                format_mint(&swap_data.to_mint)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn format_mint(mint: &Pubkey) -> String {
+    if *mint == WSOL_MINT {
+        return "SOL".to_string();
+    }
+    let mint = mint.to_string();
+    format!("{}..", &mint[..mint.len().min(4)])
+}
+
+/// 按精度换算为 UI 数量并以 K/M/B 缩写；SOL 固定 9 位精度
+fn format_amount(amount: u64, decimals: Option<u8>, mint: &Pubkey) -> String {
+    let decimals = match decimals {
+        Some(decimals) => decimals,
+        None if *mint == WSOL_MINT => 9,
+        None => return amount.to_string(),
+    };
+    let ui_amount = amount as f64 / 10f64.powi(decimals as i32);
+    let (value, suffix) = if ui_amount >= 1e9 {
+        (ui_amount / 1e9, "B")
+    } else if ui_amount >= 1e6 {
+        (ui_amount / 1e6, "M")
+    } else if ui_amount >= 1e3 {
+        (ui_amount / 1e3, "K")
+    } else {
+        (ui_amount, "")
+    };
+    let value = format!("{:.4}", value);
+    let value = value.trim_end_matches('0').trim_end_matches('.');
+    format!("{}{}", value, suffix)
+}
+
+/// min-out 约束下的滑点余量；min_out 为 0 表示未设保护，结果为 10000 bps
+#[inline]
+fn min_out_slippage_bps(min_out: u64, actual_out: u64) -> Option<u64> {
+    if actual_out == 0 {
+        return None;
+    }
+    let headroom = actual_out.saturating_sub(min_out) as u128;
+    Some((headroom * 10_000 / actual_out as u128) as u64)
+}
+
+/// max-in 约束下的滑点余量
+#[inline]
+fn max_in_slippage_bps(max_in: u64, actual_in: u64) -> Option<u64> {
+    if max_in == 0 || actual_in == 0 {
+        return None;
+    }
+    let headroom = max_in.saturating_sub(actual_in) as u128;
+    Some((headroom * 10_000 / actual_in as u128) as u64)
+}
+
+fn non_default(pubkey: Pubkey) -> Option<Pubkey> {
+    (pubkey != Pubkey::default()).then_some(pubkey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::common::{EventType, SwapData};
+
+    #[test]
+    fn test_event_id_is_deterministic() {
+        let mut event = DexEvent::PumpFunTradeEvent(PumpFunTradeEvent::default());
+        let metadata = event.metadata_mut();
+        metadata.signature = Signature::from([7u8; 64]);
+        metadata.event_type = EventType::PumpFunBuy;
+        metadata.outer_index = 2;
+        metadata.recv_us = 1;
+
+        let mut other = event.clone();
+        other.metadata_mut().recv_us = 2;
+        assert_eq!(event.event_id(), other.event_id());
+        // 固定编号参与哈希，事件类型改名不会改变 ID
+        assert_eq!(EventType::PumpFunBuy.stable_id(), 10);
+        assert_eq!(
+            event.event_id_hex(),
+            "3079f89da2cabc195e5b4b8f7da462fa932242d26c6eeedde5a1195513cdb785"
+        );
+
+        other.metadata_mut().inner_index = Some(0);
+        assert_ne!(event.event_id(), other.event_id());
+        other.metadata_mut().inner_index = None;
+        other.metadata_mut().event_type = EventType::PumpFunSell;
+        assert_ne!(event.event_id(), other.event_id());
+    }
+
+    #[test]
+    fn test_ordering_key_sorts_within_slot() {
+        let event = |slot: u64, transaction_index: Option<u64>, outer: i64, inner: Option<i64>| {
+            let mut event = DexEvent::PumpFunTradeEvent(PumpFunTradeEvent::default());
+            let metadata = event.metadata_mut();
+            metadata.slot = slot;
+            metadata.transaction_index = transaction_index;
+            metadata.outer_index = outer;
+            metadata.inner_index = inner;
+            event
+        };
+        let mut events = vec![
+            event(11, Some(0), 0, None),
+            event(10, Some(3), 1, Some(2)),
+            event(10, Some(3), 1, None),
+            event(10, Some(3), 0, Some(5)),
+            event(10, Some(1), 4, None),
+            event(10, None, 0, None),
+        ];
+        events.sort_by_key(DexEvent::ordering_key);
+
+        let keys: Vec<_> = events.iter().map(DexEvent::ordering_key).collect();
+        assert_eq!(
+            keys,
+            vec![
+                (10, None, 0, None, 0),
+                (10, Some(1), 4, None, 0),
+                (10, Some(3), 0, Some(5), 0),
+                (10, Some(3), 1, None, 0),
+                (10, Some(3), 1, Some(2), 0),
+                (11, Some(0), 0, None, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mints_and_pool_id() {
+        let base = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let event = DexEvent::PumpSwapBuyEvent(PumpSwapBuyEvent {
+            pool,
+            base_mint: base,
+            quote_mint: WSOL_MINT,
+            ..Default::default()
+        });
+        assert_eq!(event.mints().as_slice(), &[base, WSOL_MINT]);
+        assert_eq!(event.pool_id(), Some(pool));
+
+        // 未解析出的 mint 被跳过
+        let event = DexEvent::MeteoraDlmmSwapEvent(MeteoraDlmmSwapEvent {
+            token_x_mint: Some(base),
+            ..Default::default()
+        });
+        assert_eq!(event.mints().as_slice(), &[base]);
+        assert_eq!(event.pool_id(), None);
+
+        // 事件本身没有 mint 时回退到 swap_data
+        let quote = Pubkey::new_unique();
+        let mut event = DexEvent::RaydiumAmmV4SwapEvent(RaydiumAmmV4SwapEvent {
+            amm: pool,
+            ..Default::default()
+        });
+        event.metadata_mut().swap_data =
+            Some(SwapData { from_mint: quote, to_mint: base, ..Default::default() });
+        assert_eq!(event.mints().as_slice(), &[quote, base]);
+        assert_eq!(event.pool_id(), Some(pool));
+    }
+
+    #[test]
+    fn test_display_summary() {
+        let mint = Pubkey::new_unique();
+        let mut event = DexEvent::PumpFunTradeEvent(PumpFunTradeEvent::default());
+        let metadata = event.metadata_mut();
+        metadata.event_type = EventType::PumpFunBuy;
+        metadata.slot = 123;
+        metadata.swap_data = Some(SwapData {
+            from_mint: WSOL_MINT,
+            to_mint: mint,
+            from_amount: 500_000_000,
+            to_amount: 1_200_000_000_000,
+            to_decimals: Some(6),
+            ..Default::default()
+        });
+
+        let mint = mint.to_string();
+        assert_eq!(
+            event.to_string(),
+            format!("PumpFunBuy sig=11111111.. slot=123 0.5 SOL -> 1.2M {}..", &mint[..4])
+        );
+
+        event.metadata_mut().swap_data.as_mut().unwrap().to_decimals = None;
+        assert!(event.to_string().ends_with(&format!("-> 1200000000000 {}..", &mint[..4])));
+    }
+
+    #[test]
+    fn test_quote_leg_uses_quote_mints() {
+        use crate::streaming::event_parser::common::USDC_MINT;
+
+        let token = Pubkey::new_unique();
+        let mut event = DexEvent::PumpSwapSellEvent(PumpSwapSellEvent {
+            base_mint: token,
+            quote_mint: USDC_MINT,
+            base_amount_in: 1_000,
+            quote_amount_out: 25_000_000,
+            lp_fee: 10,
+            protocol_fee: 5,
+            ..Default::default()
+        });
+        let quote_mints = QuoteMints::default();
+        assert_eq!(
+            event.quote_leg(&quote_mints),
+            Some(QuoteLeg { mint: USDC_MINT, amount: 25_000_000, is_buy: false })
+        );
+        assert_eq!(event.total_fee_quote(&quote_mints), Some((USDC_MINT, 15)));
+        assert_eq!(event.sol_volume_lamports(), None);
+        assert_eq!(event.total_fee_lamports(), None);
+        assert_eq!(event.quote_leg(&QuoteMints::sol_only()), None);
+
+        // 反向池子：WSOL 作为 base 时仍以 WSOL 一侧计价
+        if let DexEvent::PumpSwapSellEvent(e) = &mut event {
+            e.base_mint = WSOL_MINT;
+            e.quote_mint = token;
+        }
+        assert_eq!(
+            event.quote_leg(&quote_mints),
+            Some(QuoteLeg { mint: WSOL_MINT, amount: 1_000, is_buy: true })
+        );
+        assert_eq!(event.sol_volume_lamports(), Some(1_000));
+
+        // SOL/USDC 池子按全局集合的优先级取 WSOL 一侧
+        if let DexEvent::PumpSwapSellEvent(e) = &mut event {
+            e.quote_mint = USDC_MINT;
+        }
+        assert_eq!(QuoteMints::global().quote_of(&WSOL_MINT, &USDC_MINT), Some(WSOL_MINT));
+        assert_eq!(event.sol_volume_lamports(), Some(1_000));
+        assert_eq!(event.total_fee_lamports(), None);
+    }
+
+    #[test]
+    fn test_bonk_trade_legs_require_trade_log() {
+        let token = Pubkey::new_unique();
+        let mut event = DexEvent::BonkTradeEvent(BonkTradeEvent {
+            base_token_mint: token,
+            quote_token_mint: WSOL_MINT,
+            amount_in: 1_000_000_000,
+            minimum_amount_out: 90,
+            exact_in: true,
+            trade_direction: TradeDirection::Buy,
+            ..Default::default()
+        });
+        assert_eq!(event.trade_legs(), None);
+        assert_eq!(event.sol_volume_lamports(), None);
+        assert_eq!(event.to_normalized_trade(&QuoteMints::default()), None);
+
+        // 合并 CPI 日志后 amount_in / amount_out 为实际成交量
+        if let DexEvent::BonkTradeEvent(e) = &mut event {
+            e.amount_out = 95;
+            e.virtual_base = 1;
+        }
+        assert_eq!(event.trade_legs(), Some((WSOL_MINT, 1_000_000_000, token, 95)));
+        assert_eq!(event.sol_volume_lamports(), Some(1_000_000_000));
+    }
+
+    #[test]
+    fn test_creator_across_protocols() {
+        let (user, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let create = PumpFunCreateTokenEvent { user, ..Default::default() };
+        assert_eq!(DexEvent::PumpFunCreateTokenEvent(create.clone()).creator(), Some(user));
+        let create = PumpFunCreateTokenEvent { creator, ..create };
+        assert_eq!(DexEvent::PumpFunCreateTokenEvent(create).creator(), Some(creator));
+
+        let pool =
+            PumpSwapCreatePoolEvent { creator: user, coin_creator: creator, ..Default::default() };
+        assert_eq!(DexEvent::PumpSwapCreatePoolEvent(pool).creator(), Some(creator));
+        let sell = PumpSwapSellEvent { coin_creator: creator, ..Default::default() };
+        assert_eq!(DexEvent::PumpSwapSellEvent(sell).creator(), Some(creator));
+        assert_eq!(DexEvent::PumpFunTradeEvent(PumpFunTradeEvent::default()).creator(), None);
+
+        // Bonk 交易只有 dev 交易能确定创建者
+        let trade = BonkTradeEvent { payer: creator, ..Default::default() };
+        assert_eq!(DexEvent::BonkTradeEvent(trade.clone()).creator(), None);
+        let trade = BonkTradeEvent { is_dev_create_token_trade: true, ..trade };
+        assert_eq!(DexEvent::BonkTradeEvent(trade).creator(), Some(creator));
+    }
+
+    #[test]
+    fn test_realized_slippage_bps() {
+        // min-out: 实际 1000，下限 950 -> 余量 5%
+        let sell = PumpSwapSellEvent {
+            min_quote_amount_out: 950,
+            user_quote_amount_out: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(DexEvent::PumpSwapSellEvent(sell).realized_slippage_bps(), Some(500));
+
+        // max-in: 实际 1000，上限 1010 -> 余量 1%
+        let buy = PumpSwapBuyEvent {
+            max_quote_amount_in: 1_010,
+            user_quote_amount_in: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(DexEvent::PumpSwapBuyEvent(buy.clone()).realized_slippage_bps(), Some(100));
+        let unfilled = PumpSwapBuyEvent { user_quote_amount_in: 0, ..buy };
+        assert_eq!(DexEvent::PumpSwapBuyEvent(unfilled).realized_slippage_bps(), None);
+
+        // DLMM Swap2 只有 exact-in 时才按 min-out 计算
+        let swap_result = MeteoraDlmmSwapResult { amount_out: 2_000, ..Default::default() };
+        let swap2 = MeteoraDlmmSwap2Event {
+            min_amount_out: 1_990,
+            exact_in: true,
+            swap_result,
+            ..Default::default()
+        };
+        assert_eq!(
+            DexEvent::MeteoraDlmmSwap2Event(swap2.clone()).realized_slippage_bps(),
+            Some(50)
+        );
+        let exact_out = MeteoraDlmmSwap2Event { exact_in: false, ..swap2 };
+        assert_eq!(DexEvent::MeteoraDlmmSwap2Event(exact_out).realized_slippage_bps(), None);
+
+        let create = PumpFunCreateTokenEvent::default();
+        assert_eq!(DexEvent::PumpFunCreateTokenEvent(create).realized_slippage_bps(), None);
+    }
 }
@@ -2,5 +2,6 @@ pub mod common;
 pub mod core;
 pub mod protocols;
 
+pub use common::EVENT_SCHEMA_VERSION;
 pub use core::traits::{DexEvent, TxDexEvents};
 pub use protocols::types::Protocol;
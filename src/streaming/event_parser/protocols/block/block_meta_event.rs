@@ -5,11 +5,24 @@ use solana_sdk::signature::Signature;
 
 /// Block元数据事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct BlockMetaEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
     pub slot: u64,
     pub block_hash: String,
+    /// 父 slot，slot - parent_slot > 1 表示中间存在跳过的 slot
+    #[serde(default)]
+    pub parent_slot: u64,
+    #[serde(default)]
+    pub parent_block_hash: String,
+    #[serde(default)]
+    pub block_height: Option<u64>,
+    /// 区块内已执行的交易数量
+    #[serde(default)]
+    pub executed_transaction_count: u64,
+    #[serde(default)]
+    pub entries_count: u64,
 }
 
 impl BlockMetaEvent {
@@ -27,6 +40,11 @@ impl BlockMetaEvent {
             recv_us,
             None,
         );
-        Self { metadata, slot, block_hash }
+        Self { metadata, slot, block_hash, ..Default::default() }
+    }
+
+    /// 父 slot 与当前 slot 之间被跳过的 slot 数量
+    pub fn skipped_slots(&self) -> u64 {
+        self.slot.saturating_sub(self.parent_slot).saturating_sub(1)
     }
 }
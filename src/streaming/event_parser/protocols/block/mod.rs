@@ -1 +1,2 @@
 pub mod block_meta_event;
+pub mod slot_rolled_back_event;
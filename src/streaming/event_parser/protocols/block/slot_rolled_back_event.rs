@@ -0,0 +1,41 @@
+use crate::streaming::event_parser::common::high_performance_clock::get_high_perf_clock;
+use crate::streaming::event_parser::common::{types::EventType, EventMetadata};
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::Signature;
+
+/// Slot 回滚事件，由 `SlotReorderBuffer` 在某个 slot 被标记为 dead，或所在分叉被更高的
+/// rooted slot 放弃时发出
+///
+/// 该 slot 上仍在缓冲区中的事件会被丢弃，不再发出；`released_events` 为回滚前
+/// 已经发出的事件数量，调用方需要撤销基于这些事件做出的操作。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlotRolledBackEvent {
+    pub metadata: EventMetadata,
+    pub slot: u64,
+    /// 放弃本 slot 所在分叉的 rooted slot；slot 被标记为 dead 时为 0
+    pub replaced_by_slot: u64,
+    /// 回滚前已经发出的事件数量
+    pub released_events: u64,
+    /// 仍在缓冲区中、因回滚而丢弃的事件数量
+    pub discarded_events: u64,
+}
+
+impl SlotRolledBackEvent {
+    pub fn new(slot: u64, replaced_by_slot: u64) -> Self {
+        let metadata = EventMetadata::new(
+            Signature::default(),
+            slot,
+            0,
+            0,
+            crate::streaming::event_parser::common::types::ProtocolType::Common,
+            EventType::SlotRolledBack,
+            solana_sdk::pubkey::Pubkey::default(),
+            0,
+            None,
+            get_high_perf_clock(),
+            None,
+        );
+        Self { metadata, slot, replaced_by_slot, ..Default::default() }
+    }
+}
@@ -1,4 +1,5 @@
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+use crate::streaming::event_parser::core::capabilities::EventCapability;
 use crate::streaming::event_parser::protocols::bonk::types::{
     CurveParams, MintParams, PoolStatus, TradeDirection, VestingParams,
 };
@@ -10,7 +11,16 @@ use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
 /// Trade event
+///
+/// 数量字段有两个来源：
+/// - 指令参数：exact-in（`BuyExactIn` / `SellExactIn`）写入 `amount_in` / `minimum_amount_out`，
+///   exact-out 写入 `amount_out` / `maximum_amount_in`，另一侧为 0
+/// - CPI 日志（TradeEvent）：合并后 `amount_in` / `amount_out` 被覆盖为实际成交量
+///
+/// `DexEvent::quote_leg` / `to_normalized_trade` 等只使用实际成交量，未合并 CPI 日志时返回 None；
+/// 需要指令中的数量时使用 `resolved_amount_in` / `resolved_amount_out`，不要直接读取这两个字段。
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct BonkTradeEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -71,6 +81,45 @@ pub struct BonkTradeEvent {
     pub creator_associated_account: Pubkey,
 }
 
+impl BonkTradeEvent {
+    /// 是否已合并 CPI 日志中的实际成交数据
+    ///
+    /// 日志中的虚拟 / 实际储备指令参数里没有，只有合并后才非 0
+    #[inline]
+    pub fn has_trade_log(&self) -> bool {
+        self.virtual_base != 0
+            || self.virtual_quote != 0
+            || self.real_base_after != 0
+            || self.real_quote_after != 0
+    }
+
+    /// 输入数量（Buy 为 quote，Sell 为 base）
+    ///
+    /// 有 CPI 日志时为实际成交量；否则 exact-in 取指令的 `amount_in`，
+    /// exact-out 取 `maximum_amount_in`（用户接受的上限，不是实际值）
+    #[inline]
+    pub fn resolved_amount_in(&self) -> u64 {
+        if self.has_trade_log() || self.exact_in {
+            self.amount_in
+        } else {
+            self.maximum_amount_in
+        }
+    }
+
+    /// 输出数量（Buy 为 base，Sell 为 quote）
+    ///
+    /// 有 CPI 日志时为实际成交量；否则 exact-out 取指令的 `amount_out`，
+    /// exact-in 取 `minimum_amount_out`（用户接受的下限，不是实际值）
+    #[inline]
+    pub fn resolved_amount_out(&self) -> u64 {
+        if self.has_trade_log() || !self.exact_in {
+            self.amount_out
+        } else {
+            self.minimum_amount_out
+        }
+    }
+}
+
 pub const BONK_TRADE_EVENT_LOG_SIZE: usize = 32 + 8 * 13 + 1 + 1 + 1;
 
 pub fn bonk_trade_event_log_decode(data: &[u8]) -> Option<BonkTradeEvent> {
@@ -82,6 +131,7 @@ pub fn bonk_trade_event_log_decode(data: &[u8]) -> Option<BonkTradeEvent> {
 
 /// Create pool event
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct BonkPoolCreateEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -119,6 +169,7 @@ pub fn bonk_pool_create_event_log_decode(data: &[u8]) -> Option<BonkPoolCreateEv
 
 /// Create pool event
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct BonkMigrateToAmmEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -193,6 +244,7 @@ pub struct BonkMigrateToAmmEvent {
 
 // Migrate to CP Swap event
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct BonkMigrateToCpswapEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -227,8 +279,45 @@ pub struct BonkMigrateToCpswapEvent {
     pub remaining_accounts: Vec<Pubkey>,
 }
 
+/// Claim vested token event
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
+pub struct BonkClaimVestedTokenEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    /// 领取人 (beneficiary)
+    pub payer: Pubkey,
+    pub authority: Pubkey,
+    pub pool_state: Pubkey,
+    pub vesting_record: Pubkey,
+    pub base_vault: Pubkey,
+    pub user_base_token: Pubkey,
+    pub base_token_mint: Pubkey,
+    /// 实际领取数量，指令本身不带数量，取自 base_vault 转出的 inner token 转账
+    pub amount: u64,
+}
+
+/// Claim platform fee event
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
+pub struct BonkClaimPlatformFeeEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    /// 平台手续费钱包 (platform_fee_wallet)
+    pub payer: Pubkey,
+    pub authority: Pubkey,
+    pub pool_state: Pubkey,
+    pub platform_config: Pubkey,
+    pub quote_vault: Pubkey,
+    pub recipient_token_account: Pubkey,
+    pub quote_mint: Pubkey,
+    /// 实际领取数量，指令本身不带数量，取自 quote_vault 转出的 inner token 转账
+    pub amount: u64,
+}
+
 /// 池状态
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BonkPoolStateAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -243,6 +332,7 @@ pub struct BonkPoolStateAccountEvent {
 
 /// 全局配置
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BonkGlobalConfigAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -257,6 +347,7 @@ pub struct BonkGlobalConfigAccountEvent {
 
 /// 平台配置
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BonkPlatformConfigAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -269,6 +360,69 @@ pub struct BonkPlatformConfigAccountEvent {
     pub platform_config: PlatformConfig,
 }
 
+/// Bonk Program ID（Raydium LaunchLab 程序）
+pub const BONK_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj");
+
+/// Raydium LaunchLab 程序 ID，与 [`BONK_PROGRAM_ID`] 相同，由 `Protocol::Bonk` 解析
+pub const RAYDIUM_LAUNCHLAB_PROGRAM_ID: Pubkey = BONK_PROGRAM_ID;
+
+/// Bonk 解析器支持的 discriminator 与事件类型
+pub const CAPABILITIES: &[EventCapability] = &[
+    EventCapability::instruction(discriminators::BUY_EXACT_IN, &[EventType::BonkBuyExactIn]),
+    EventCapability::instruction(discriminators::BUY_EXACT_OUT, &[EventType::BonkBuyExactOut]),
+    EventCapability::instruction(discriminators::SELL_EXACT_IN, &[EventType::BonkSellExactIn]),
+    EventCapability::instruction(discriminators::SELL_EXACT_OUT, &[EventType::BonkSellExactOut]),
+    EventCapability::instruction(discriminators::INITIALIZE, &[EventType::BonkInitialize]),
+    EventCapability::instruction(discriminators::INITIALIZE_V2, &[EventType::BonkInitializeV2]),
+    EventCapability::instruction(
+        discriminators::INITIALIZE_WITH_TOKEN_2022,
+        &[EventType::BonkInitializeWithToken2022],
+    ),
+    EventCapability::instruction(discriminators::MIGRATE_TO_AMM, &[EventType::BonkMigrateToAmm]),
+    EventCapability::instruction(
+        discriminators::MIGRATE_TO_CP_SWAP,
+        &[EventType::BonkMigrateToCpswap],
+    ),
+    EventCapability::instruction(
+        discriminators::CLAIM_VESTED_TOKEN,
+        &[EventType::BonkClaimVestedToken],
+    ),
+    EventCapability::instruction(
+        discriminators::CLAIM_PLATFORM_FEE,
+        &[EventType::BonkClaimPlatformFee],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::TRADE_EVENT,
+        &[
+            EventType::BonkBuyExactIn,
+            EventType::BonkBuyExactOut,
+            EventType::BonkSellExactIn,
+            EventType::BonkSellExactOut,
+        ],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::POOL_CREATE_EVENT,
+        &[
+            EventType::BonkInitialize,
+            EventType::BonkInitializeV2,
+            EventType::BonkInitializeWithToken2022,
+        ],
+    ),
+    EventCapability::account(
+        discriminators::POOL_STATE_ACCOUNT,
+        &[EventType::AccountBonkPoolState],
+    ),
+    EventCapability::account(
+        discriminators::GLOBAL_CONFIG_ACCOUNT,
+        &[EventType::AccountBonkGlobalConfig],
+    ),
+    EventCapability::account(
+        discriminators::PLATFORM_CONFIG_ACCOUNT,
+        &[EventType::AccountBonkPlatformConfig],
+    ),
+];
+
 /// Event discriminator constants
 pub mod discriminators {
     // Event discriminators
@@ -289,9 +443,41 @@ pub mod discriminators {
     pub const INITIALIZE_WITH_TOKEN_2022: &[u8] = &[37, 190, 126, 222, 44, 154, 171, 17];
     pub const MIGRATE_TO_AMM: &[u8] = &[207, 82, 192, 145, 254, 207, 145, 223];
     pub const MIGRATE_TO_CP_SWAP: &[u8] = &[136, 92, 200, 103, 28, 218, 144, 140];
+    pub const CLAIM_VESTED_TOKEN: &[u8] = &[49, 33, 104, 30, 189, 157, 79, 35];
+    pub const CLAIM_PLATFORM_FEE: &[u8] = &[156, 39, 208, 135, 76, 237, 61, 72];
 
     // 账户鉴别器
     pub const POOL_STATE_ACCOUNT: &[u8] = &[247, 237, 227, 245, 215, 195, 222, 70];
     pub const GLOBAL_CONFIG_ACCOUNT: &[u8] = &[149, 8, 156, 202, 160, 252, 176, 217];
     pub const PLATFORM_CONFIG_ACCOUNT: &[u8] = &[160, 78, 128, 0, 248, 83, 230, 160];
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolved_amounts_prefer_trade_log() {
+        // 只有指令参数时取用户设定的上下限
+        let exact_in = BonkTradeEvent {
+            amount_in: 1_000,
+            minimum_amount_out: 90,
+            exact_in: true,
+            ..Default::default()
+        };
+        assert!(!exact_in.has_trade_log());
+        assert_eq!((exact_in.resolved_amount_in(), exact_in.resolved_amount_out()), (1_000, 90));
+
+        let exact_out =
+            BonkTradeEvent { amount_out: 100, maximum_amount_in: 1_200, ..Default::default() };
+        assert_eq!((exact_out.resolved_amount_in(), exact_out.resolved_amount_out()), (1_200, 100));
+
+        // 合并 CPI 日志后取实际成交量
+        let merged =
+            BonkTradeEvent { amount_in: 1_100, amount_out: 100, virtual_base: 1, ..exact_out };
+        assert!(merged.has_trade_log());
+        assert_eq!((merged.resolved_amount_in(), merged.resolved_amount_out()), (1_100, 100));
+        let merged = BonkTradeEvent { amount_out: 95, virtual_base: 1, ..exact_in };
+        assert_eq!((merged.resolved_amount_in(), merged.resolved_amount_out()), (1_000, 95));
+    }
+}
@@ -4,16 +4,19 @@ use crate::streaming::event_parser::{
     common::{utils::*, EventMetadata, EventType},
     protocols::bonk::{
         bonk_pool_create_event_log_decode, bonk_trade_event_log_decode, discriminators, AmmFeeOn,
-        BonkMigrateToAmmEvent, BonkMigrateToCpswapEvent, BonkPoolCreateEvent, BonkTradeEvent,
-        ConstantCurve, CurveParams, FixedCurve, LinearCurve, MintParams, TradeDirection,
-        VestingParams,
+        BonkClaimPlatformFeeEvent, BonkClaimVestedTokenEvent, BonkMigrateToAmmEvent,
+        BonkMigrateToCpswapEvent, BonkPoolCreateEvent, BonkTradeEvent, ConstantCurve, CurveParams,
+        FixedCurve, LinearCurve, MintParams, TradeDirection, VestingParams,
     },
     DexEvent,
 };
 
-/// Bonk Program ID
-pub const BONK_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj");
+pub use crate::streaming::event_parser::protocols::bonk::events::{
+    BONK_PROGRAM_ID, CAPABILITIES, RAYDIUM_LAUNCHLAB_PROGRAM_ID,
+};
+
+/// 建池参数中 name / symbol / uri 的长度上限（字节），链上元数据远小于该值
+const MAX_MINT_STRING_LEN: usize = 256;
 
 /// 解析 Bonk instruction data
 ///
@@ -42,6 +45,12 @@ pub fn parse_bonk_instruction_data(
         discriminators::MIGRATE_TO_CP_SWAP => {
             parse_migrate_to_cpswap_instruction(data, accounts, metadata)
         }
+        discriminators::CLAIM_VESTED_TOKEN => {
+            parse_claim_vested_token_instruction(data, accounts, metadata)
+        }
+        discriminators::CLAIM_PLATFORM_FEE => {
+            parse_claim_platform_fee_instruction(data, accounts, metadata)
+        }
         _ => None,
     }
 }
@@ -66,7 +75,7 @@ pub fn parse_bonk_inner_instruction_data(
 /// 根据判别器路由到具体的账户解析函数
 pub fn parse_bonk_account_data(
     discriminator: &[u8],
-    account: crate::streaming::grpc::AccountPretty,
+    account: crate::streaming::event_parser::common::AccountPretty,
     metadata: crate::streaming::event_parser::common::EventMetadata,
 ) -> Option<crate::streaming::event_parser::DexEvent> {
     match discriminator {
@@ -142,6 +151,7 @@ fn parse_buy_exact_in_instruction(
         amount_in,
         minimum_amount_out,
         share_fee_rate,
+        exact_in: true,
         payer: accounts[0],
         global_config: accounts[2],
         platform_config: accounts[3],
@@ -222,6 +232,7 @@ fn parse_sell_exact_in_instruction(
         amount_in,
         minimum_amount_out,
         share_fee_rate,
+        exact_in: true,
         payer: accounts[0],
         global_config: accounts[2],
         platform_config: accounts[3],
@@ -405,34 +416,26 @@ fn parse_mint_params(data: &[u8], offset: &mut usize) -> Option<MintParams> {
     let decimals = read_u8(data, *offset)?;
     *offset += 1;
 
-    // Read name string length and content
-    let name_len = read_u32_le(data, *offset)? as usize;
-    *offset += 4;
-    if data.len() < *offset + name_len {
-        return None;
-    }
-    let name = String::from_utf8(data[*offset..*offset + name_len].to_vec()).ok()?;
-    *offset += name_len;
+    let name = read_mint_string(data, offset)?;
+    let symbol = read_mint_string(data, offset)?;
+    let uri = read_mint_string(data, offset)?;
 
-    // Read symbol string length and content
-    let symbol_len = read_u32_le(data, *offset)? as usize;
-    *offset += 4;
-    if data.len() < *offset + symbol_len {
-        return None;
-    }
-    let symbol = String::from_utf8(data[*offset..*offset + symbol_len].to_vec()).ok()?;
-    *offset += symbol_len;
+    Some(MintParams { decimals, name, symbol, uri })
+}
 
-    // Read uri string length and content
-    let uri_len = read_u32_le(data, *offset)? as usize;
-    *offset += 4;
-    if data.len() < *offset + uri_len {
+/// 读取 u32 长度前缀的字符串
+///
+/// 无效的 UTF-8 字节替换为 U+FFFD，不让个别脏字节导致整个建池事件丢失；
+/// 长度超过 `MAX_MINT_STRING_LEN` 时视为数据损坏
+fn read_mint_string(data: &[u8], offset: &mut usize) -> Option<String> {
+    let len = read_u32_le(data, *offset)? as usize;
+    if len > MAX_MINT_STRING_LEN {
         return None;
     }
-    let uri = String::from_utf8(data[*offset..*offset + uri_len].to_vec()).ok()?;
-    *offset += uri_len;
-
-    Some(MintParams { decimals, name, symbol, uri })
+    let start = *offset + 4;
+    let bytes = data.get(start..start + len)?;
+    *offset = start + len;
+    Some(String::from_utf8_lossy(bytes).into_owned())
 }
 
 /// Parse CurveParams structure
@@ -603,3 +606,140 @@ fn parse_migrate_to_cpswap_instruction(
         ..Default::default()
     }))
 }
+
+/// Parse claim vested token event
+fn parse_claim_vested_token_instruction(
+    _data: &[u8],
+    accounts: &[Pubkey],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    metadata.event_type = EventType::BonkClaimVestedToken;
+
+    if accounts.len() < 7 {
+        return None;
+    }
+
+    Some(DexEvent::BonkClaimVestedTokenEvent(BonkClaimVestedTokenEvent {
+        metadata,
+        payer: accounts[0],
+        authority: accounts[1],
+        pool_state: accounts[2],
+        vesting_record: accounts[3],
+        base_vault: accounts[4],
+        user_base_token: accounts[5],
+        base_token_mint: accounts[6],
+        ..Default::default()
+    }))
+}
+
+/// Parse claim platform fee event
+fn parse_claim_platform_fee_instruction(
+    _data: &[u8],
+    accounts: &[Pubkey],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    metadata.event_type = EventType::BonkClaimPlatformFee;
+
+    if accounts.len() < 7 {
+        return None;
+    }
+
+    Some(DexEvent::BonkClaimPlatformFeeEvent(BonkClaimPlatformFeeEvent {
+        metadata,
+        payer: accounts[0],
+        authority: accounts[1],
+        pool_state: accounts[2],
+        platform_config: accounts[3],
+        quote_vault: accounts[4],
+        recipient_token_account: accounts[5],
+        quote_mint: accounts[6],
+        ..Default::default()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mint_params_data(name: &[u8], symbol: &[u8], uri: &[u8]) -> Vec<u8> {
+        let mut data = vec![6];
+        for field in [name, symbol, uri] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field);
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_mint_params_tolerates_invalid_utf8() {
+        let data = mint_params_data(b"Bonk\xff", b"BNK", b"https://example.com");
+        let mut offset = 0;
+        let params = parse_mint_params(&data, &mut offset).unwrap();
+        assert_eq!(params.decimals, 6);
+        assert_eq!(params.name, "Bonk\u{FFFD}");
+        assert_eq!((params.symbol.as_str(), params.uri.as_str()), ("BNK", "https://example.com"));
+        assert_eq!(offset, data.len());
+
+        // 超长的长度前缀直接拒绝，不会按其分配内存
+        let mut data = vec![6];
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(parse_mint_params(&data, &mut 0).is_none());
+        let long_name = vec![b'a'; MAX_MINT_STRING_LEN + 1];
+        let data = mint_params_data(&long_name, b"BNK", b"");
+        assert!(parse_mint_params(&data, &mut 0).is_none());
+        // 长度前缀超出数据末尾
+        let data = mint_params_data(b"Bonk", b"BNK", b"uri");
+        assert!(parse_mint_params(&data[..data.len() - 1], &mut 0).is_none());
+    }
+
+    #[test]
+    fn test_parse_claim_instructions() {
+        let accounts: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+
+        let event = parse_bonk_instruction_data(
+            discriminators::CLAIM_VESTED_TOKEN,
+            &[],
+            &accounts,
+            EventMetadata::default(),
+        );
+        let Some(DexEvent::BonkClaimVestedTokenEvent(claim)) = event else {
+            panic!("unexpected event variant");
+        };
+        assert_eq!(claim.metadata.event_type, EventType::BonkClaimVestedToken);
+        assert_eq!(claim.payer, accounts[0]);
+        assert_eq!(claim.pool_state, accounts[2]);
+        assert_eq!(claim.vesting_record, accounts[3]);
+        assert_eq!(claim.base_vault, accounts[4]);
+        assert_eq!(claim.base_token_mint, accounts[6]);
+        // 数量由 inner instructions 中的转账补全
+        assert_eq!(claim.amount, 0);
+
+        let event = parse_bonk_instruction_data(
+            discriminators::CLAIM_PLATFORM_FEE,
+            &[],
+            &accounts,
+            EventMetadata::default(),
+        );
+        let Some(DexEvent::BonkClaimPlatformFeeEvent(claim)) = event else {
+            panic!("unexpected event variant");
+        };
+        assert_eq!(claim.metadata.event_type, EventType::BonkClaimPlatformFee);
+        assert_eq!(claim.platform_config, accounts[3]);
+        assert_eq!(claim.quote_vault, accounts[4]);
+        assert_eq!(claim.recipient_token_account, accounts[5]);
+        assert_eq!(claim.quote_mint, accounts[6]);
+
+        // 账户不足时不产出事件
+        for discriminator in
+            [discriminators::CLAIM_VESTED_TOKEN, discriminators::CLAIM_PLATFORM_FEE]
+        {
+            assert!(parse_bonk_instruction_data(
+                discriminator,
+                &[],
+                &accounts[..6],
+                EventMetadata::default()
+            )
+            .is_none());
+        }
+    }
+}
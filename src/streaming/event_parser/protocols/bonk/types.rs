@@ -2,15 +2,12 @@ use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::{
-    event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::bonk::{
-            BonkGlobalConfigAccountEvent, BonkPlatformConfigAccountEvent, BonkPoolStateAccountEvent,
-        },
-        DexEvent,
+use crate::streaming::event_parser::{
+    common::{note_account_data_len, AccountPretty, EventMetadata, EventType},
+    protocols::bonk::{
+        BonkGlobalConfigAccountEvent, BonkPlatformConfigAccountEvent, BonkPoolStateAccountEvent,
     },
-    grpc::AccountPretty,
+    DexEvent,
 };
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
@@ -29,6 +26,7 @@ pub enum PoolStatus {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct MintParams {
     pub decimals: u8,
     pub name: String,
@@ -37,6 +35,7 @@ pub struct MintParams {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct VestingParams {
     pub total_locked_amount: u64,
     pub cliff_period: u64,
@@ -51,6 +50,7 @@ pub enum AmmFeeOn {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct ConstantCurve {
     pub supply: u64,
     pub total_base_sell: u64,
@@ -59,6 +59,7 @@ pub struct ConstantCurve {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct FixedCurve {
     pub supply: u64,
     pub total_quote_fund_raising: u64,
@@ -66,6 +67,7 @@ pub struct FixedCurve {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct LinearCurve {
     pub supply: u64,
     pub total_quote_fund_raising: u64,
@@ -86,6 +88,7 @@ impl Default for CurveParams {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct VestingSchedule {
     pub total_locked_amount: u64,
     pub cliff_period: u64,
@@ -95,6 +98,7 @@ pub struct VestingSchedule {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PoolState {
     pub epoch: u64,
     pub auth_bump: u8,
@@ -134,6 +138,7 @@ pub fn pool_state_decode(data: &[u8]) -> Option<PoolState> {
 
 pub fn pool_state_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountBonkPoolState;
+    note_account_data_len(&metadata.event_type, POOL_STATE_SIZE + 8, account.data.len());
 
     if account.data.len() < POOL_STATE_SIZE + 8 {
         return None;
@@ -154,6 +159,7 @@ pub fn pool_state_parser(account: AccountPretty, mut metadata: EventMetadata) ->
     }
 }
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct GlobalConfig {
     pub epoch: u64,
     pub curve_type: u8,
@@ -188,6 +194,7 @@ pub fn global_config_parser(
     mut metadata: EventMetadata,
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountBonkGlobalConfig;
+    note_account_data_len(&metadata.event_type, GLOBAL_CONFIG_SIZE + 8, account.data.len());
 
     if account.data.len() < GLOBAL_CONFIG_SIZE + 8 {
         return None;
@@ -209,6 +216,7 @@ pub fn global_config_parser(
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PlatformConfig {
     pub epoch: u64,
     pub platform_fee_wallet: Pubkey,
@@ -237,6 +245,7 @@ pub fn platform_config_parser(
     mut metadata: EventMetadata,
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountBonkPlatformConfig;
+    note_account_data_len(&metadata.event_type, PLATFORM_CONFIG_SIZE + 8, account.data.len());
 
     if account.data.len() < PLATFORM_CONFIG_SIZE + 8 {
         return None;
@@ -2,10 +2,12 @@ use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+use crate::streaming::event_parser::core::capabilities::EventCapability;
 
 /// Base fee parameters
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct BaseFeeParameters {
     pub cliff_fee_numerator: u64,
     pub first_factor: u16,
@@ -16,6 +18,7 @@ pub struct BaseFeeParameters {
 
 /// Dynamic fee parameters
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct DynamicFeeParameters {
     pub bin_step: u16,
     pub bin_step_u128: u128,
@@ -28,6 +31,7 @@ pub struct DynamicFeeParameters {
 
 /// Pool fee parameters
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PoolFeeParameters {
     pub base_fee: BaseFeeParameters,
     pub padding: [u8; 3],
@@ -36,6 +40,7 @@ pub struct PoolFeeParameters {
 
 /// Meteora DAMM v2 Swap Event (对应 swap 指令)
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct MeteoraDammV2SwapEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -103,6 +108,7 @@ pub struct MeteoraDammV2SwapEvent {
 
 /// Meteora DAMM v2 Swap2 Event (对应 swap2 指令)
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct MeteoraDammV2Swap2Event {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -172,6 +178,7 @@ pub struct MeteoraDammV2Swap2Event {
 
 /// Meteora DAMM v2 Initialize Pool Event (对应 initialize_pool 指令)
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct MeteoraDammV2InitializePoolEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -238,6 +245,7 @@ pub struct MeteoraDammV2InitializePoolEvent {
 
 /// Meteora DAMM v2 Initialize Customizable Pool Event (对应 initialize_customizable_pool 指令)
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct MeteoraDammV2InitializeCustomizablePoolEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -306,6 +314,7 @@ pub struct MeteoraDammV2InitializeCustomizablePoolEvent {
 
 /// Meteora DAMM v2 Initialize Pool With Dynamic Config Event (对应 initialize_pool_with_dynamic_config 指令)
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct MeteoraDammV2InitializePoolWithDynamicConfigEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -374,6 +383,100 @@ pub struct MeteoraDammV2InitializePoolWithDynamicConfigEvent {
     pub config: Pubkey,
 }
 
+/// Meteora DAMM v2 Claim Fee Event
+///
+/// 对应 claim_position_fee / claim_partner_fee / claim_protocol_fee 三条指令，
+/// 通过 `metadata.event_type` 区分；partner / protocol 领取与仓位无关，`position` 为默认值
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeteoraDammV2ClaimFeeEvent {
+    pub metadata: EventMetadata,
+
+    // 来自 CPI Log Event 的数据
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    /// 领取人：仓位 owner / partner / 协议 operator
+    pub owner: Pubkey,
+    pub fee_a_claimed: u64,
+    pub fee_b_claimed: u64,
+
+    // 指令参数（仅 partner / protocol 领取）
+    pub max_amount_a: u64,
+    pub max_amount_b: u64,
+
+    // 来自 Input Accounts 的数据
+    pub pool_authority: Pubkey,
+    pub token_a_account: Pubkey,
+    pub token_b_account: Pubkey,
+    pub token_a_vault: Pubkey,
+    pub token_b_vault: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub position_nft_account: Pubkey,
+    pub token_a_program: Pubkey,
+    pub token_b_program: Pubkey,
+    pub event_authority: Pubkey,
+    pub program: Pubkey,
+}
+
+/// Meteora DAMM v2 程序ID
+pub const METEORA_DAMM_V2_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+
+/// Meteora DAMM v2 解析器支持的 discriminator 与事件类型
+pub const CAPABILITIES: &[EventCapability] = &[
+    EventCapability::instruction(discriminators::SWAP_IX, &[EventType::MeteoraDammV2Swap]),
+    EventCapability::instruction(discriminators::SWAP2_IX, &[EventType::MeteoraDammV2Swap2]),
+    EventCapability::instruction(
+        discriminators::INITIALIZE_POOL_IX,
+        &[EventType::MeteoraDammV2InitializePool],
+    ),
+    EventCapability::instruction(
+        discriminators::INITIALIZE_CUSTOMIZABLE_POOL_IX,
+        &[EventType::MeteoraDammV2InitializeCustomizablePool],
+    ),
+    EventCapability::instruction(
+        discriminators::INITIALIZE_POOL_WITH_DYNAMIC_CONFIG_IX,
+        &[EventType::MeteoraDammV2InitializePoolWithDynamicConfig],
+    ),
+    EventCapability::instruction(
+        discriminators::CLAIM_POSITION_FEE_IX,
+        &[EventType::MeteoraDammV2ClaimPositionFee],
+    ),
+    EventCapability::instruction(
+        discriminators::CLAIM_PARTNER_FEE_IX,
+        &[EventType::MeteoraDammV2ClaimPartnerFee],
+    ),
+    EventCapability::instruction(
+        discriminators::CLAIM_PROTOCOL_FEE_IX,
+        &[EventType::MeteoraDammV2ClaimProtocolFee],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::SWAP_EVENT,
+        &[EventType::MeteoraDammV2Swap, EventType::MeteoraDammV2Swap2],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::INITIALIZE_POOL_EVENT,
+        &[
+            EventType::MeteoraDammV2InitializePool,
+            EventType::MeteoraDammV2InitializeCustomizablePool,
+            EventType::MeteoraDammV2InitializePoolWithDynamicConfig,
+        ],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::CLAIM_POSITION_FEE_EVENT,
+        &[EventType::MeteoraDammV2ClaimPositionFee],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::CLAIM_PARTNER_FEE_EVENT,
+        &[EventType::MeteoraDammV2ClaimPartnerFee],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::CLAIM_PROTOCOL_FEE_EVENT,
+        &[EventType::MeteoraDammV2ClaimProtocolFee],
+    ),
+];
+
 /// Event discriminators
 pub mod discriminators {
     // Instruction discriminators
@@ -385,6 +488,9 @@ pub mod discriminators {
     pub const INITIALIZE_POOL_IX: &[u8] = &[0x5f, 0xb4, 0x0a, 0xac, 0x54, 0xae, 0xe8, 0x28]; // initialize_pool
     pub const INITIALIZE_POOL_WITH_DYNAMIC_CONFIG_IX: &[u8] =
         &[0x95, 0x52, 0x48, 0xc5, 0xfd, 0xfc, 0x44, 0x0f]; // initialize_pool_with_dynamic_config
+    pub const CLAIM_POSITION_FEE_IX: &[u8] = &[0xb4, 0x26, 0x9a, 0x11, 0x85, 0x21, 0xa2, 0xd3]; // claim_position_fee
+    pub const CLAIM_PARTNER_FEE_IX: &[u8] = &[0x61, 0xce, 0x27, 0x69, 0x5e, 0x5e, 0x7e, 0x94]; // claim_partner_fee
+    pub const CLAIM_PROTOCOL_FEE_IX: &[u8] = &[0xa5, 0xe4, 0x85, 0x30, 0x63, 0xf9, 0xff, 0x21]; // claim_protocol_fee
 
     // Event discriminators (CPI Log Event)
     // e445a52e51cb9a1d 是 Meteora 的事件前缀
@@ -397,6 +503,18 @@ pub mod discriminators {
         0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d, 0xe4, 0x32, 0xf6, 0x55, 0xcb, 0x42, 0x86,
         0x25,
     ]; // initialize pool event
+    pub const CLAIM_POSITION_FEE_EVENT: &[u8] = &[
+        0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d, 0xc6, 0xb6, 0xb7, 0x34, 0x61, 0x0c, 0x31,
+        0x38,
+    ]; // claim position fee event
+    pub const CLAIM_PARTNER_FEE_EVENT: &[u8] = &[
+        0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d, 0x76, 0x63, 0x4d, 0x0a, 0xe2, 0x01, 0x01,
+        0x57,
+    ]; // claim partner fee event
+    pub const CLAIM_PROTOCOL_FEE_EVENT: &[u8] = &[
+        0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d, 0xba, 0xf4, 0x4b, 0xfb, 0xbc, 0x0d, 0x19,
+        0x21,
+    ]; // claim protocol fee event
 }
 
 /// Decode swap event from CPI log
@@ -415,3 +533,39 @@ pub fn meteora_damm_v2_initialize_pool_event_decode(
 ) -> Option<MeteoraDammV2InitializePoolEvent> {
     borsh::from_slice::<MeteoraDammV2InitializePoolEvent>(&data).ok()
 }
+
+/// Decode claim position fee event from CPI log
+/// 结构: pool + position + owner + fee_a_claimed + fee_b_claimed
+pub const METEORA_DAMM_V2_CLAIM_POSITION_FEE_EVENT_LOG_SIZE: usize = 112;
+pub fn meteora_damm_v2_claim_position_fee_event_decode(
+    data: &[u8],
+) -> Option<MeteoraDammV2ClaimFeeEvent> {
+    if data.len() < METEORA_DAMM_V2_CLAIM_POSITION_FEE_EVENT_LOG_SIZE {
+        return None;
+    }
+    Some(MeteoraDammV2ClaimFeeEvent {
+        pool: Pubkey::new_from_array(data[0..32].try_into().ok()?),
+        position: Pubkey::new_from_array(data[32..64].try_into().ok()?),
+        owner: Pubkey::new_from_array(data[64..96].try_into().ok()?),
+        fee_a_claimed: u64::from_le_bytes(data[96..104].try_into().ok()?),
+        fee_b_claimed: u64::from_le_bytes(data[104..112].try_into().ok()?),
+        ..Default::default()
+    })
+}
+
+/// Decode claim partner / protocol fee event from CPI log
+/// 结构: pool + token_a_amount + token_b_amount
+pub const METEORA_DAMM_V2_CLAIM_POOL_FEE_EVENT_LOG_SIZE: usize = 48;
+pub fn meteora_damm_v2_claim_pool_fee_event_decode(
+    data: &[u8],
+) -> Option<MeteoraDammV2ClaimFeeEvent> {
+    if data.len() < METEORA_DAMM_V2_CLAIM_POOL_FEE_EVENT_LOG_SIZE {
+        return None;
+    }
+    Some(MeteoraDammV2ClaimFeeEvent {
+        pool: Pubkey::new_from_array(data[0..32].try_into().ok()?),
+        fee_a_claimed: u64::from_le_bytes(data[32..40].try_into().ok()?),
+        fee_b_claimed: u64::from_le_bytes(data[40..48].try_into().ok()?),
+        ..Default::default()
+    })
+}
@@ -1,4 +1,5 @@
 pub mod events;
+#[cfg(feature = "protocol-meteora-damm-v2")]
 pub mod parser;
 pub mod types;
 
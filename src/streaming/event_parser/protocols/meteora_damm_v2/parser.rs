@@ -1,8 +1,10 @@
 use crate::streaming::event_parser::{
     common::{EventMetadata, EventType},
     protocols::meteora_damm_v2::{
-        discriminators, meteora_damm_v2_initialize_pool_event_decode,
-        meteora_damm_v2_swap_event_decode, MeteoraDammV2InitializeCustomizablePoolEvent,
+        discriminators, meteora_damm_v2_claim_pool_fee_event_decode,
+        meteora_damm_v2_claim_position_fee_event_decode,
+        meteora_damm_v2_initialize_pool_event_decode, meteora_damm_v2_swap_event_decode,
+        MeteoraDammV2ClaimFeeEvent, MeteoraDammV2InitializeCustomizablePoolEvent,
         MeteoraDammV2InitializePoolEvent, MeteoraDammV2InitializePoolWithDynamicConfigEvent,
         MeteoraDammV2Swap2Event, MeteoraDammV2SwapEvent,
     },
@@ -10,9 +12,9 @@ use crate::streaming::event_parser::{
 };
 use solana_sdk::pubkey::Pubkey;
 
-/// Meteora DAMM v2 程序ID
-pub const METEORA_DAMM_V2_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+pub use crate::streaming::event_parser::protocols::meteora_damm_v2::events::{
+    CAPABILITIES, METEORA_DAMM_V2_PROGRAM_ID,
+};
 
 /// 解析 Meteora DAMM v2 instruction data
 ///
@@ -35,6 +37,15 @@ pub fn parse_meteora_damm_v2_instruction_data(
         discriminators::INITIALIZE_POOL_WITH_DYNAMIC_CONFIG_IX => {
             parse_initialize_pool_with_dynamic_config_instruction(data, accounts, metadata)
         }
+        discriminators::CLAIM_POSITION_FEE_IX => {
+            parse_claim_position_fee_instruction(data, accounts, metadata)
+        }
+        discriminators::CLAIM_PARTNER_FEE_IX => {
+            parse_claim_partner_fee_instruction(data, accounts, metadata)
+        }
+        discriminators::CLAIM_PROTOCOL_FEE_IX => {
+            parse_claim_protocol_fee_instruction(data, accounts, metadata)
+        }
         _ => None,
     }
 }
@@ -52,6 +63,19 @@ pub fn parse_meteora_damm_v2_inner_instruction_data(
         discriminators::INITIALIZE_POOL_EVENT => {
             parse_initialize_pool_inner_instruction(data, metadata)
         }
+        discriminators::CLAIM_POSITION_FEE_EVENT => {
+            parse_claim_position_fee_inner_instruction(data, metadata)
+        }
+        discriminators::CLAIM_PARTNER_FEE_EVENT => parse_claim_pool_fee_inner_instruction(
+            data,
+            metadata,
+            EventType::MeteoraDammV2ClaimPartnerFee,
+        ),
+        discriminators::CLAIM_PROTOCOL_FEE_EVENT => parse_claim_pool_fee_inner_instruction(
+            data,
+            metadata,
+            EventType::MeteoraDammV2ClaimProtocolFee,
+        ),
         _ => None,
     }
 }
@@ -418,6 +442,106 @@ fn parse_initialize_pool_with_dynamic_config_instruction(
     ))
 }
 
+/// 解析 claim_position_fee 指令
+fn parse_claim_position_fee_instruction(
+    _data: &[u8],
+    accounts: &[Pubkey],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    metadata.event_type = EventType::MeteoraDammV2ClaimPositionFee;
+
+    if accounts.len() < 15 {
+        return None;
+    }
+
+    Some(DexEvent::MeteoraDammV2ClaimFeeEvent(MeteoraDammV2ClaimFeeEvent {
+        metadata,
+        pool_authority: accounts[0],
+        pool: accounts[1],
+        position: accounts[2],
+        token_a_account: accounts[3],
+        token_b_account: accounts[4],
+        token_a_vault: accounts[5],
+        token_b_vault: accounts[6],
+        token_a_mint: accounts[7],
+        token_b_mint: accounts[8],
+        position_nft_account: accounts[9],
+        owner: accounts[10],
+        token_a_program: accounts[11],
+        token_b_program: accounts[12],
+        event_authority: accounts[13],
+        program: accounts[14],
+        ..Default::default()
+    }))
+}
+
+/// 解析 claim_partner_fee 指令
+fn parse_claim_partner_fee_instruction(
+    data: &[u8],
+    accounts: &[Pubkey],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    metadata.event_type = EventType::MeteoraDammV2ClaimPartnerFee;
+
+    if data.len() < 16 || accounts.len() < 13 {
+        return None;
+    }
+
+    Some(DexEvent::MeteoraDammV2ClaimFeeEvent(MeteoraDammV2ClaimFeeEvent {
+        metadata,
+        pool_authority: accounts[0],
+        pool: accounts[1],
+        token_a_account: accounts[2],
+        token_b_account: accounts[3],
+        token_a_vault: accounts[4],
+        token_b_vault: accounts[5],
+        token_a_mint: accounts[6],
+        token_b_mint: accounts[7],
+        owner: accounts[8],
+        token_a_program: accounts[9],
+        token_b_program: accounts[10],
+        event_authority: accounts[11],
+        program: accounts[12],
+        max_amount_a: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+        max_amount_b: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+        ..Default::default()
+    }))
+}
+
+/// 解析 claim_protocol_fee 指令
+fn parse_claim_protocol_fee_instruction(
+    data: &[u8],
+    accounts: &[Pubkey],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    metadata.event_type = EventType::MeteoraDammV2ClaimProtocolFee;
+
+    if data.len() < 16 || accounts.len() < 14 {
+        return None;
+    }
+
+    // accounts[8] 为 claim_fee_operator 账户，accounts[9] 为签名的 operator
+    Some(DexEvent::MeteoraDammV2ClaimFeeEvent(MeteoraDammV2ClaimFeeEvent {
+        metadata,
+        pool_authority: accounts[0],
+        pool: accounts[1],
+        token_a_vault: accounts[2],
+        token_b_vault: accounts[3],
+        token_a_mint: accounts[4],
+        token_b_mint: accounts[5],
+        token_a_account: accounts[6],
+        token_b_account: accounts[7],
+        owner: accounts[9],
+        token_a_program: accounts[10],
+        token_b_program: accounts[11],
+        event_authority: accounts[12],
+        program: accounts[13],
+        max_amount_a: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+        max_amount_b: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+        ..Default::default()
+    }))
+}
+
 /// 解析 swap inner instruction (CPI event)
 fn parse_swap_inner_instruction(data: &[u8], metadata: EventMetadata) -> Option<DexEvent> {
     if let Some(event) = meteora_damm_v2_swap_event_decode(data) {
@@ -442,3 +566,83 @@ fn parse_initialize_pool_inner_instruction(
         None
     }
 }
+
+/// 解析 claim position fee inner instruction (CPI event)
+fn parse_claim_position_fee_inner_instruction(
+    data: &[u8],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    metadata.event_type = EventType::MeteoraDammV2ClaimPositionFee;
+    meteora_damm_v2_claim_position_fee_event_decode(data).map(|event| {
+        DexEvent::MeteoraDammV2ClaimFeeEvent(MeteoraDammV2ClaimFeeEvent { metadata, ..event })
+    })
+}
+
+/// 解析 claim partner / protocol fee inner instruction (CPI event)
+fn parse_claim_pool_fee_inner_instruction(
+    data: &[u8],
+    mut metadata: EventMetadata,
+    event_type: EventType,
+) -> Option<DexEvent> {
+    metadata.event_type = event_type;
+    meteora_damm_v2_claim_pool_fee_event_decode(data).map(|event| {
+        DexEvent::MeteoraDammV2ClaimFeeEvent(MeteoraDammV2ClaimFeeEvent { metadata, ..event })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_claim_position_fee_with_cpi_log() {
+        let accounts: Vec<Pubkey> = (0..15).map(|_| Pubkey::new_unique()).collect();
+        let event = parse_meteora_damm_v2_instruction_data(
+            discriminators::CLAIM_POSITION_FEE_IX,
+            &[],
+            &accounts,
+            EventMetadata::default(),
+        )
+        .expect("claim_position_fee instruction");
+        let DexEvent::MeteoraDammV2ClaimFeeEvent(ix) = &event else { panic!("unexpected event") };
+        assert_eq!(ix.metadata.event_type, EventType::MeteoraDammV2ClaimPositionFee);
+        assert_eq!(ix.position, accounts[2]);
+        assert_eq!(ix.owner, accounts[10]);
+
+        let mut log = Vec::new();
+        log.extend_from_slice(accounts[1].as_ref());
+        log.extend_from_slice(accounts[2].as_ref());
+        log.extend_from_slice(accounts[10].as_ref());
+        log.extend_from_slice(&1_500u64.to_le_bytes());
+        log.extend_from_slice(&42u64.to_le_bytes());
+        let cpi = parse_meteora_damm_v2_inner_instruction_data(
+            discriminators::CLAIM_POSITION_FEE_EVENT,
+            &log,
+            EventMetadata::default(),
+        )
+        .expect("claim position fee event");
+        let DexEvent::MeteoraDammV2ClaimFeeEvent(cpi) = cpi else { panic!("unexpected event") };
+        assert_eq!(cpi.pool, accounts[1]);
+        assert_eq!(cpi.position, accounts[2]);
+        assert_eq!((cpi.fee_a_claimed, cpi.fee_b_claimed), (1_500, 42));
+    }
+
+    #[test]
+    fn test_parse_claim_protocol_fee() {
+        let accounts: Vec<Pubkey> = (0..14).map(|_| Pubkey::new_unique()).collect();
+        let mut data = 7u64.to_le_bytes().to_vec();
+        data.extend_from_slice(&u64::MAX.to_le_bytes());
+        let event = parse_meteora_damm_v2_instruction_data(
+            discriminators::CLAIM_PROTOCOL_FEE_IX,
+            &data,
+            &accounts,
+            EventMetadata::default(),
+        )
+        .expect("claim_protocol_fee instruction");
+        let DexEvent::MeteoraDammV2ClaimFeeEvent(e) = event else { panic!("unexpected event") };
+        assert_eq!(e.metadata.event_type, EventType::MeteoraDammV2ClaimProtocolFee);
+        assert_eq!(e.owner, accounts[9]);
+        assert_eq!((e.max_amount_a, e.max_amount_b), (7, u64::MAX));
+        assert_eq!(e.position, Pubkey::default());
+    }
+}
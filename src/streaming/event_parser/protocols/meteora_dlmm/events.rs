@@ -1,4 +1,5 @@
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+use crate::streaming::event_parser::core::capabilities::EventCapability;
 use crate::streaming::event_parser::protocols::meteora_dlmm::types::{
     BinArray, BinArrayBitmapExtension, LbPair,
 };
@@ -8,6 +9,7 @@ use solana_sdk::pubkey::Pubkey;
 
 /// Meteora DLMM Swap event
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MeteoraDlmmSwapEvent {
     pub metadata: EventMetadata,
 
@@ -47,28 +49,57 @@ pub struct MeteoraDlmmSwapEvent {
 }
 
 /// Meteora DLMM swap result from CPI log
+///
+/// 字段顺序与 Swap2 日志的 borsh 布局一致。由旧版 Swap 日志合并而来时只有 `amount_in`、
+/// `amount_out`、`total_fee`、`protocol_fee`、`host_fee` 有值，其余字段在旧日志中不存在，固定为 0
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct MeteoraDlmmSwapResult {
+    /// 实际消耗的输入数量（含手续费）
     pub amount_in: u64,
+    /// 流动性不足时未能成交、退还给用户的输入数量
     pub amount_left: u64,
+    /// 用户获得的输出数量
     pub amount_out: u64,
+    /// 输入侧收取的总手续费，包含 protocol / host fee
     pub total_fee: u64,
+    /// 归 bin 中做市流动性（LP）的手续费
     pub lp_mm_fee: u64,
+    /// 协议手续费
     pub protocol_fee: u64,
+    /// 交易带有 `host_fee_in` 账户时分给 host 的手续费
     pub host_fee: u64,
+    /// 归被成交的限价单的手续费
     pub lp_limit_order_fee: u64,
+    /// 本次 swap 中限价单被成交的数量
     pub limit_order_filled_amount: u64,
+    /// 限价单成交部分换得的数量
     pub limit_order_swapped_amount: u64,
 }
 
+impl MeteoraDlmmSwapResult {
+    /// 各手续费分项之和：`lp_mm_fee + lp_limit_order_fee + protocol_fee + host_fee`
+    ///
+    /// 由旧版 Swap 日志合并而来时 LP 分项未知（为 0），此时只有 `total_fee` 是完整的手续费
+    pub fn total_fees(&self) -> u64 {
+        self.lp_mm_fee
+            .saturating_add(self.lp_limit_order_fee)
+            .saturating_add(self.protocol_fee)
+            .saturating_add(self.host_fee)
+    }
+}
+
 /// Meteora DLMM Swap2 event
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MeteoraDlmmSwap2Event {
     pub metadata: EventMetadata,
 
     // Instruction params
     pub amount_in: u64,
     pub min_amount_out: u64,
+    /// 是否来自 swap2 (exact-in) 指令；swap_exact_out2 的参数语义不同，此时为 false
+    pub exact_in: bool,
 
     // CPI log details
     pub lb_pair: Pubkey,
@@ -98,8 +129,36 @@ pub struct MeteoraDlmmSwap2Event {
     pub remaining_accounts: Vec<Pubkey>,
 }
 
+/// Meteora DLMM InitializeLbPair event (对应 initialize_lb_pair 指令)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeteoraDlmmInitializeLbPairEvent {
+    pub metadata: EventMetadata,
+
+    // Instruction params
+    pub active_id: i32,
+    pub bin_step: u16,
+
+    // Instruction accounts
+    pub lb_pair: Pubkey,
+    pub bin_array_bitmap_extension: Option<Pubkey>,
+    pub token_x_mint: Pubkey,
+    pub token_y_mint: Pubkey,
+    pub reserve_x: Pubkey,
+    pub reserve_y: Pubkey,
+    pub oracle: Pubkey,
+    pub preset_parameter: Pubkey,
+    pub funder: Pubkey,
+    pub token_program: Pubkey,
+    pub system_program: Pubkey,
+    pub rent: Pubkey,
+    pub event_authority: Pubkey,
+    pub program: Pubkey,
+}
+
 /// Raw swap CPI event payload
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct MeteoraDlmmSwapCpiEventData {
     pub lb_pair: Pubkey,
     pub from: Pubkey,
@@ -116,6 +175,7 @@ pub struct MeteoraDlmmSwapCpiEventData {
 
 /// Raw swap2 CPI event payload
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct MeteoraDlmmSwap2CpiEventData {
     pub lb_pair: Pubkey,
     pub from: Pubkey,
@@ -128,6 +188,7 @@ pub struct MeteoraDlmmSwap2CpiEventData {
 
 /// LbPair 账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MeteoraDlmmLbPairAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -142,6 +203,7 @@ pub struct MeteoraDlmmLbPairAccountEvent {
 
 /// BinArray 账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MeteoraDlmmBinArrayAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -156,6 +218,7 @@ pub struct MeteoraDlmmBinArrayAccountEvent {
 
 /// BinArrayBitmapExtension 账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MeteoraDlmmBinArrayBitmapExtensionAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -168,12 +231,39 @@ pub struct MeteoraDlmmBinArrayBitmapExtensionAccountEvent {
     pub bin_array_bitmap_extension: BinArrayBitmapExtension,
 }
 
+/// Meteora DLMM 程序ID
+pub const METEORA_DLMM_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+
+/// Meteora DLMM 解析器支持的 discriminator 与事件类型
+pub const CAPABILITIES: &[EventCapability] = &[
+    EventCapability::instruction(discriminators::SWAP_IX, &[EventType::MeteoraDlmmSwap]),
+    EventCapability::instruction(discriminators::SWAP2_IX, &[EventType::MeteoraDlmmSwap2]),
+    EventCapability::instruction(
+        discriminators::SWAP_EXACT_OUT2_IX,
+        &[EventType::MeteoraDlmmSwap2],
+    ),
+    EventCapability::instruction(
+        discriminators::INITIALIZE_LB_PAIR_IX,
+        &[EventType::MeteoraDlmmInitializeLbPair],
+    ),
+    EventCapability::inner_instruction(discriminators::SWAP_EVENT, &[EventType::MeteoraDlmmSwap]),
+    EventCapability::inner_instruction(discriminators::SWAP2_EVENT, &[EventType::MeteoraDlmmSwap2]),
+    EventCapability::account(discriminators::LB_PAIR, &[EventType::AccountMeteoraDlmmLbPair]),
+    EventCapability::account(discriminators::BIN_ARRAY, &[EventType::AccountMeteoraDlmmBinArray]),
+    EventCapability::account(
+        discriminators::BIN_ARRAY_BITMAP_EXTENSION,
+        &[EventType::AccountMeteoraDlmmBinArrayBitmapExtension],
+    ),
+];
+
 /// 事件鉴别器常量
 pub mod discriminators {
     // Instruction discriminators
     pub const SWAP_IX: &[u8] = &[0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
     pub const SWAP2_IX: &[u8] = &[0x41, 0x4b, 0x3f, 0x4c, 0xeb, 0x5b, 0x5b, 0x88];
     pub const SWAP_EXACT_OUT2_IX: &[u8] = &[0x2b, 0xd7, 0xf7, 0x84, 0x89, 0x3c, 0xf3, 0x51];
+    pub const INITIALIZE_LB_PAIR_IX: &[u8] = &[45, 154, 237, 210, 221, 15, 166, 92];
 
     // CPI event discriminators
     // Prefix: e445a52e51cb9a1d
@@ -1,5 +1,7 @@
 pub mod events;
+#[cfg(feature = "protocol-meteora-dlmm")]
 pub mod parser;
+pub mod price;
 pub mod types;
 
 pub use events::*;
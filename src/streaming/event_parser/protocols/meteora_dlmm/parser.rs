@@ -1,16 +1,17 @@
 use crate::streaming::event_parser::{
-    common::{read_u64_le, EventMetadata, EventType},
+    common::{read_i32_le, read_u16_le, read_u64_le, EventMetadata, EventType},
+    core::dispatcher::EventDispatcher,
     protocols::meteora_dlmm::{
         discriminators, meteora_dlmm_swap2_event_decode, meteora_dlmm_swap_event_decode,
-        MeteoraDlmmSwap2Event, MeteoraDlmmSwapEvent,
+        MeteoraDlmmInitializeLbPairEvent, MeteoraDlmmSwap2Event, MeteoraDlmmSwapEvent,
     },
-    DexEvent,
+    DexEvent, Protocol,
 };
 use solana_sdk::pubkey::Pubkey;
 
-/// Meteora DLMM 程序ID
-pub const METEORA_DLMM_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+pub use crate::streaming::event_parser::protocols::meteora_dlmm::events::{
+    CAPABILITIES, METEORA_DLMM_PROGRAM_ID,
+};
 
 #[derive(Clone, Debug)]
 struct ParsedSwapAccounts {
@@ -42,9 +43,14 @@ pub fn parse_meteora_dlmm_instruction_data(
 ) -> Option<DexEvent> {
     match discriminator {
         discriminators::SWAP_IX => parse_swap_instruction(data, accounts, metadata),
-        discriminators::SWAP2_IX => parse_swap2_instruction(data, accounts, metadata),
+        discriminators::SWAP2_IX => parse_swap2_instruction(data, accounts, metadata, true),
         // SwapExactOut2 has the same account layout and emits the same Swap2 CPI event.
-        discriminators::SWAP_EXACT_OUT2_IX => parse_swap2_instruction(data, accounts, metadata),
+        discriminators::SWAP_EXACT_OUT2_IX => {
+            parse_swap2_instruction(data, accounts, metadata, false)
+        }
+        discriminators::INITIALIZE_LB_PAIR_IX => {
+            parse_initialize_lb_pair_instruction(data, accounts, metadata)
+        }
         _ => None,
     }
 }
@@ -74,7 +80,7 @@ pub fn parse_meteora_dlmm_inner_instruction_data(
 /// 根据判别器路由到具体的账户解析函数
 pub fn parse_meteora_dlmm_account_data(
     discriminator: &[u8],
-    account: crate::streaming::grpc::AccountPretty,
+    account: crate::streaming::event_parser::common::AccountPretty,
     metadata: crate::streaming::event_parser::common::EventMetadata,
 ) -> Option<crate::streaming::event_parser::DexEvent> {
     match discriminator {
@@ -142,6 +148,7 @@ fn parse_swap2_instruction(
     data: &[u8],
     accounts: &[Pubkey],
     mut metadata: EventMetadata,
+    exact_in: bool,
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::MeteoraDlmmSwap2;
     if data.len() < 16 {
@@ -156,6 +163,7 @@ fn parse_swap2_instruction(
         metadata,
         amount_in,
         min_amount_out,
+        exact_in,
         lb_pair: parsed_accounts.lb_pair,
         bin_array_bitmap_extension: parsed_accounts.bin_array_bitmap_extension,
         reserve_x: parsed_accounts.reserve_x,
@@ -177,6 +185,45 @@ fn parse_swap2_instruction(
     }))
 }
 
+/// 解析 initialize_lb_pair 指令
+///
+/// 参数为 active_id (i32) + bin_step (u16)；可选的 bin_array_bitmap_extension 缺省时传入的是程序 ID
+fn parse_initialize_lb_pair_instruction(
+    data: &[u8],
+    accounts: &[Pubkey],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    metadata.event_type = EventType::MeteoraDlmmInitializeLbPair;
+    if data.len() < 6 || accounts.len() < 14 {
+        return None;
+    }
+
+    let active_id = read_i32_le(data, 0)?;
+    let bin_step = read_u16_le(data, 4)?;
+    let bin_array_bitmap_extension =
+        (!is_meteora_dlmm_program(&accounts[1])).then_some(accounts[1]);
+
+    Some(DexEvent::MeteoraDlmmInitializeLbPairEvent(MeteoraDlmmInitializeLbPairEvent {
+        metadata,
+        active_id,
+        bin_step,
+        lb_pair: accounts[0],
+        bin_array_bitmap_extension,
+        token_x_mint: accounts[2],
+        token_y_mint: accounts[3],
+        reserve_x: accounts[4],
+        reserve_y: accounts[5],
+        oracle: accounts[6],
+        preset_parameter: accounts[7],
+        funder: accounts[8],
+        token_program: accounts[9],
+        system_program: accounts[10],
+        rent: accounts[11],
+        event_authority: accounts[12],
+        program: accounts[13],
+    }))
+}
+
 fn parse_swap_inner_instruction(data: &[u8], mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::MeteoraDlmmSwap;
     let cpi_event = meteora_dlmm_swap_event_decode(data)?;
@@ -213,16 +260,21 @@ fn parse_swap2_inner_instruction(data: &[u8], mut metadata: EventMetadata) -> Op
     }))
 }
 
+/// 内置或注册为 Meteora DLMM 的程序 ID
+fn is_meteora_dlmm_program(program_id: &Pubkey) -> bool {
+    EventDispatcher::match_protocol_by_program_id(program_id) == Some(Protocol::MeteoraDlmm)
+}
+
 fn parse_swap_accounts(accounts: &[Pubkey], has_memo_program: bool) -> Option<ParsedSwapAccounts> {
     if accounts.len() < 8 {
         return None;
     }
 
-    let event_authority =
-        Pubkey::find_program_address(&[b"__event_authority"], &METEORA_DLMM_PROGRAM_ID).0;
-    let event_authority_index = accounts
-        .windows(2)
-        .position(|window| window[0] == event_authority && window[1] == METEORA_DLMM_PROGRAM_ID)?;
+    // 程序 ID 可能是注册的 fork，event_authority 由实际的程序 ID 推导
+    let event_authority_index = accounts.windows(2).position(|window| {
+        is_meteora_dlmm_program(&window[1])
+            && window[0] == Pubkey::find_program_address(&[b"__event_authority"], &window[1]).0
+    })?;
 
     let (user_index, token_x_program_index, token_y_program_index, memo_program_index) =
         if has_memo_program {
@@ -348,3 +400,37 @@ fn parse_swap_prefix(
         host_fee_in,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swap_accounts(program_id: Pubkey) -> Vec<Pubkey> {
+        let mut accounts: Vec<Pubkey> = (0..14).map(|_| Pubkey::new_unique()).collect();
+        accounts[12] = Pubkey::find_program_address(&[b"__event_authority"], &program_id).0;
+        accounts[13] = program_id;
+        accounts
+    }
+
+    #[test]
+    fn test_swap_accounts_of_registered_fork() {
+        let fork = Pubkey::new_unique();
+        let accounts = swap_accounts(fork);
+        assert!(parse_swap_accounts(&accounts, false).is_none());
+
+        EventDispatcher::register_program_id(fork, Protocol::MeteoraDlmm).unwrap();
+        let parsed = parse_swap_accounts(&accounts, false).expect("fork swap accounts");
+        assert_eq!(parsed.lb_pair, accounts[0]);
+        assert_eq!(parsed.user, accounts[9]);
+        assert_eq!(parsed.event_authority, accounts[12]);
+        assert_eq!(parsed.program, fork);
+
+        // fork 的 event_authority 不对应内置程序 ID
+        let mut mismatched = accounts.clone();
+        mismatched[13] = METEORA_DLMM_PROGRAM_ID;
+        assert!(parse_swap_accounts(&mismatched, false).is_none());
+        assert!(parse_swap_accounts(&swap_accounts(METEORA_DLMM_PROGRAM_ID), false).is_some());
+
+        assert_eq!(EventDispatcher::unregister_program_id(&fork), Some(Protocol::MeteoraDlmm));
+    }
+}
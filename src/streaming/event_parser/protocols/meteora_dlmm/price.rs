@@ -0,0 +1,74 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::{
+    core::global_state::{get_lb_pair_bin_step, set_lb_pair_bin_step},
+    DexEvent,
+};
+
+/// bin 的价格：`(1 + bin_step / 10000) ^ bin_id`
+///
+/// 单位为每个 token_x 原始单位可换得的 token_y 原始单位，
+/// 换算为 UI 价格需乘以 `10 ^ (decimals_x - decimals_y)`
+#[inline]
+pub fn bin_id_to_price(bin_id: i32, bin_step: u16) -> f64 {
+    (1.0 + bin_step as f64 / 10_000.0).powi(bin_id)
+}
+
+/// 按已知的 bin_step 计算 lb_pair 中 bin 的价格，未见过该 lb_pair 的建池指令或 LbPair 账户时返回 None
+pub fn lb_pair_bin_price(lb_pair: &Pubkey, bin_id: i32) -> Option<f64> {
+    get_lb_pair_bin_step(lb_pair).map(|bin_step| bin_id_to_price(bin_id, bin_step))
+}
+
+/// 从建池事件和 LbPair 账户事件中学习 bin_step
+pub(crate) fn record_lb_pair_bin_step(event: &DexEvent) {
+    match event {
+        DexEvent::MeteoraDlmmInitializeLbPairEvent(e) => {
+            set_lb_pair_bin_step(e.lb_pair, e.bin_step)
+        }
+        DexEvent::MeteoraDlmmLbPairAccountEvent(e) => {
+            set_lb_pair_bin_step(e.pubkey, e.lb_pair.bin_step)
+        }
+        _ => {}
+    }
+}
+
+#[cfg(all(test, feature = "protocol-meteora-dlmm"))]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::{
+        common::EventMetadata,
+        protocols::meteora_dlmm::{
+            discriminators, parser::parse_meteora_dlmm_instruction_data,
+            parser::METEORA_DLMM_PROGRAM_ID,
+        },
+    };
+
+    #[test]
+    fn test_initialize_lb_pair_feeds_bin_price() {
+        let accounts: Vec<Pubkey> = (0..14)
+            .map(|i| if i == 1 { METEORA_DLMM_PROGRAM_ID } else { Pubkey::new_unique() })
+            .collect();
+        let mut data = (-100i32).to_le_bytes().to_vec();
+        data.extend_from_slice(&25u16.to_le_bytes());
+
+        let event = parse_meteora_dlmm_instruction_data(
+            discriminators::INITIALIZE_LB_PAIR_IX,
+            &data,
+            &accounts,
+            EventMetadata::default(),
+        )
+        .unwrap();
+        let DexEvent::MeteoraDlmmInitializeLbPairEvent(e) = &event else { panic!("{event:?}") };
+        assert_eq!((e.active_id, e.bin_step), (-100, 25));
+        assert_eq!(e.lb_pair, accounts[0]);
+        assert_eq!(e.bin_array_bitmap_extension, None);
+        assert_eq!((e.token_x_mint, e.token_y_mint), (accounts[2], accounts[3]));
+
+        assert_eq!(lb_pair_bin_price(&e.lb_pair, 0), None);
+        record_lb_pair_bin_step(&event);
+        assert_eq!(lb_pair_bin_price(&e.lb_pair, 0), Some(1.0));
+        let price = lb_pair_bin_price(&e.lb_pair, e.active_id).unwrap();
+        assert!((price - 1.0025f64.powi(-100)).abs() < 1e-12);
+        assert!((bin_id_to_price(2, 100) - 1.0201).abs() < 1e-12);
+    }
+}
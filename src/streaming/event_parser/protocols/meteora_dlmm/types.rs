@@ -3,20 +3,20 @@ use serde::{Deserialize, Serialize};
 use serde_big_array::BigArray;
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::{
-    event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::meteora_dlmm::{
-            MeteoraDlmmBinArrayAccountEvent, MeteoraDlmmBinArrayBitmapExtensionAccountEvent,
-            MeteoraDlmmLbPairAccountEvent,
-        },
-        DexEvent,
+use crate::streaming::event_parser::{
+    common::{
+        decode_field, note_account_data_len, AccountPretty, DecodeError, EventMetadata, EventType,
     },
-    grpc::AccountPretty,
+    protocols::meteora_dlmm::{
+        MeteoraDlmmBinArrayAccountEvent, MeteoraDlmmBinArrayBitmapExtensionAccountEvent,
+        MeteoraDlmmLbPairAccountEvent,
+    },
+    DexEvent,
 };
 
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct StaticParameters {
     pub base_factor: u16,
     pub filter_period: u16,
@@ -33,6 +33,7 @@ pub struct StaticParameters {
 
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct VariableParameters {
     pub volatility_accumulator: u32,
     pub volatility_reference: u32,
@@ -44,6 +45,7 @@ pub struct VariableParameters {
 
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct ProtocolFee {
     pub amount_x: u64,
     pub amount_y: u64,
@@ -51,6 +53,7 @@ pub struct ProtocolFee {
 
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RewardInfo {
     pub mint: Pubkey,
     pub vault: Pubkey,
@@ -64,6 +67,7 @@ pub struct RewardInfo {
 
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct LbPair {
     pub parameters: StaticParameters,
     pub v_parameters: VariableParameters,
@@ -102,6 +106,7 @@ pub struct LbPair {
 
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct BinArrayBitmapExtension {
     pub lb_pair: Pubkey,
     pub positive_bin_array_bitmap: [[u64; 8]; 12],
@@ -111,6 +116,7 @@ pub struct BinArrayBitmapExtension {
 /// Bin 结构体 - 表示一个价格区间内的流动性
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize, Copy)]
+#[serde(default)]
 pub struct Bin {
     /// Token X 的数量（已排除协议费用）
     pub amount_x: u64,
@@ -137,6 +143,7 @@ pub struct Bin {
 /// index: 2 包含 bin 600 <-> 1199, ...
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct BinArray {
     /// BinArray 的索引
     pub index: i64,
@@ -177,6 +184,7 @@ pub fn lb_pair_decode(data: &[u8]) -> Option<LbPair> {
 
 pub fn lb_pair_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountMeteoraDlmmLbPair;
+    note_account_data_len(&metadata.event_type, LB_PAIR_SIZE + 8, account.data.len());
 
     if account.data.len() < LB_PAIR_SIZE + 8 {
         return None;
@@ -197,11 +205,22 @@ pub fn lb_pair_parser(account: AccountPretty, mut metadata: EventMetadata) -> Op
     }
 }
 
+impl BinArrayBitmapExtension {
+    /// 解码 BinArrayBitmapExtension 账户数据（不含 8 字节 discriminator）
+    pub fn try_decode(data: &[u8]) -> Result<Self, DecodeError> {
+        DecodeError::ensure_len(data, BIN_ARRAY_BITMAP_EXTENSION_SIZE)?;
+        decode_field(&data[..BIN_ARRAY_BITMAP_EXTENSION_SIZE], 0, "bin_array_bitmap_extension")
+    }
+}
+
 pub fn bin_array_bitmap_extension_decode(data: &[u8]) -> Option<BinArrayBitmapExtension> {
-    if data.len() < BIN_ARRAY_BITMAP_EXTENSION_SIZE {
-        return None;
+    match BinArrayBitmapExtension::try_decode(data) {
+        Ok(extension) => Some(extension),
+        Err(e) => {
+            log::debug!("Failed to decode Meteora DLMM BinArrayBitmapExtension: {}", e);
+            None
+        }
     }
-    borsh::from_slice::<BinArrayBitmapExtension>(&data[..BIN_ARRAY_BITMAP_EXTENSION_SIZE]).ok()
 }
 
 pub fn bin_array_bitmap_extension_parser(
@@ -209,12 +228,15 @@ pub fn bin_array_bitmap_extension_parser(
     mut metadata: EventMetadata,
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountMeteoraDlmmBinArrayBitmapExtension;
+    note_account_data_len(
+        &metadata.event_type,
+        BIN_ARRAY_BITMAP_EXTENSION_SIZE + 8,
+        account.data.len(),
+    );
 
-    if account.data.len() < BIN_ARRAY_BITMAP_EXTENSION_SIZE + 8 {
-        return None;
-    }
+    // 跳过前 8 字节的 discriminator，长度不足时由解码函数记录原因
     if let Some(bin_array_bitmap_extension) =
-        bin_array_bitmap_extension_decode(&account.data[8..BIN_ARRAY_BITMAP_EXTENSION_SIZE + 8])
+        bin_array_bitmap_extension_decode(account.data.get(8..).unwrap_or_default())
     {
         Some(DexEvent::MeteoraDlmmBinArrayBitmapExtensionAccountEvent(
             MeteoraDlmmBinArrayBitmapExtensionAccountEvent {
@@ -233,80 +255,61 @@ pub fn bin_array_bitmap_extension_parser(
     }
 }
 
-pub fn bin_array_decode(data: &[u8]) -> Option<BinArray> {
-    if data.len() < BIN_ARRAY_SIZE {
-        return None;
-    }
+impl BinArray {
+    /// 解码 BinArray 账户数据（不含 8 字节 discriminator），失败时返回出错的字段和偏移
+    pub fn try_decode(data: &[u8]) -> Result<Self, DecodeError> {
+        DecodeError::ensure_len(data, BIN_ARRAY_SIZE)?;
+
+        // BinArray 使用 bytemuck 序列化，手动解析字节数组
+        // 由于 Pubkey 不满足 Pod 要求，我们需要手动解析
+        let mut offset = 0;
+
+        // 解析 index (i64, 8 bytes)
+        let index = decode_field::<i64>(data, offset, "index")?;
+        offset += 8;
+
+        // 解析 version (u8, 1 byte)
+        let version = data[offset];
+        offset += 1;
+
+        // 解析 _padding (7 bytes)
+        let mut _padding = [0u8; 7];
+        _padding.copy_from_slice(&data[offset..offset + 7]);
+        offset += 7;
 
-    // BinArray 使用 bytemuck 序列化，手动解析字节数组
-    // 由于 Pubkey 不满足 Pod 要求，我们需要手动解析
-    let mut offset = 0;
-
-    // 解析 index (i64, 8 bytes)
-    let index = i64::from_le_bytes([
-        data[offset],
-        data[offset + 1],
-        data[offset + 2],
-        data[offset + 3],
-        data[offset + 4],
-        data[offset + 5],
-        data[offset + 6],
-        data[offset + 7],
-    ]);
-    offset += 8;
-
-    // 解析 version (u8, 1 byte)
-    let version = data[offset];
-    offset += 1;
-
-    // 解析 _padding (7 bytes)
-    let mut _padding = [0u8; 7];
-    _padding.copy_from_slice(&data[offset..offset + 7]);
-    offset += 7;
-
-    // 解析 lb_pair (Pubkey, 32 bytes)
-    let lb_pair_bytes: [u8; 32] = data[offset..offset + 32].try_into().ok()?;
-    let lb_pair = Pubkey::new_from_array(lb_pair_bytes);
-    offset += 32;
-
-    // 解析 bins (70 * Bin size)
-    const BIN_SIZE: usize = std::mem::size_of::<Bin>();
-    let mut bins = [Bin::default(); 70];
-    for i in 0..70 {
-        let bin_start = offset + i * BIN_SIZE;
-        if bin_start + BIN_SIZE > data.len() {
-            return None;
+        // 解析 lb_pair (Pubkey, 32 bytes)
+        let lb_pair = decode_field::<Pubkey>(data, offset, "lb_pair")?;
+        offset += 32;
+
+        // 解析 bins (70 * Bin size)，每个 Bin 按 repr(C) 布局读取，字段之间没有填充
+        const BIN_SIZE: usize = std::mem::size_of::<Bin>();
+        let mut bins = [Bin::default(); 70];
+        for (i, bin) in bins.iter_mut().enumerate() {
+            let bin_start = offset + i * BIN_SIZE;
+            *bin = Bin::deserialize(&mut &data[bin_start..bin_start + BIN_SIZE])
+                .map_err(|e| DecodeError::invalid_field(format!("bins[{i}]"), bin_start, e))?;
         }
 
-        // 解析单个 Bin
-        let bin_data = &data[bin_start..bin_start + BIN_SIZE];
-        bins[i] = Bin {
-            amount_x: u64::from_le_bytes(bin_data[0..8].try_into().ok()?),
-            amount_y: u64::from_le_bytes(bin_data[8..16].try_into().ok()?),
-            price: u128::from_le_bytes(bin_data[16..32].try_into().ok()?),
-            liquidity_supply: u128::from_le_bytes(bin_data[32..48].try_into().ok()?),
-            reward_per_token_stored: [
-                u128::from_le_bytes(bin_data[48..64].try_into().ok()?),
-                u128::from_le_bytes(bin_data[64..80].try_into().ok()?),
-            ],
-            fee_amount_x_per_token_stored: u128::from_le_bytes(bin_data[80..96].try_into().ok()?),
-            fee_amount_y_per_token_stored: u128::from_le_bytes(bin_data[96..112].try_into().ok()?),
-            amount_x_in: u128::from_le_bytes(bin_data[112..128].try_into().ok()?),
-            amount_y_in: u128::from_le_bytes(bin_data[128..144].try_into().ok()?),
-        };
+        Ok(BinArray { index, version, _padding, lb_pair, bins })
     }
+}
 
-    Some(BinArray { index, version, _padding, lb_pair, bins })
+pub fn bin_array_decode(data: &[u8]) -> Option<BinArray> {
+    match BinArray::try_decode(data) {
+        Ok(bin_array) => Some(bin_array),
+        Err(e) => {
+            log::debug!("Failed to decode Meteora DLMM BinArray: {}", e);
+            None
+        }
+    }
 }
 
 pub fn bin_array_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountMeteoraDlmmBinArray;
+    note_account_data_len(&metadata.event_type, BIN_ARRAY_SIZE + 8, account.data.len());
 
-    // 跳过前 8 字节的 discriminator
-    if account.data.len() < BIN_ARRAY_SIZE + 8 {
-        return None;
-    }
-    if let Some(bin_array) = bin_array_decode(&account.data[8..BIN_ARRAY_SIZE + 8]) {
+    // 跳过前 8 字节的 discriminator，长度不足时由解码函数记录原因
+    if let Some(bin_array) = bin_array_decode(account.data.get(8..).unwrap_or_default()) {
         Some(DexEvent::MeteoraDlmmBinArrayAccountEvent(MeteoraDlmmBinArrayAccountEvent {
             metadata,
             pubkey: account.pubkey,
@@ -321,3 +324,40 @@ pub fn bin_array_parser(account: AccountPretty, mut metadata: EventMetadata) ->
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bin_array_try_decode_bounds() {
+        let lb_pair = Pubkey::new_unique();
+        let mut data = vec![0u8; BIN_ARRAY_SIZE];
+        data[..8].copy_from_slice(&(-3i64).to_le_bytes());
+        data[8] = 1;
+        data[16..48].copy_from_slice(lb_pair.as_ref());
+        let last_bin = 48 + 69 * std::mem::size_of::<Bin>();
+        data[last_bin..last_bin + 8].copy_from_slice(&7u64.to_le_bytes());
+        data[last_bin + 16..last_bin + 32].copy_from_slice(&42u128.to_le_bytes());
+
+        let bin_array = BinArray::try_decode(&data).expect("exact size");
+        assert_eq!(bin_array.index, -3);
+        assert_eq!(bin_array.version, 1);
+        assert_eq!(bin_array.lb_pair, lb_pair);
+        assert_eq!(bin_array.bins[69].amount_x, 7);
+        assert_eq!(bin_array.bins[69].price, 42);
+
+        assert_eq!(
+            BinArray::try_decode(&data[..BIN_ARRAY_SIZE - 1]),
+            Err(DecodeError::TooShort { expected: BIN_ARRAY_SIZE, actual: BIN_ARRAY_SIZE - 1 })
+        );
+        assert!(bin_array_decode(&[]).is_none());
+
+        let extension = vec![0u8; BIN_ARRAY_BITMAP_EXTENSION_SIZE];
+        assert!(BinArrayBitmapExtension::try_decode(&extension).is_ok());
+        assert!(matches!(
+            BinArrayBitmapExtension::try_decode(&extension[..40]),
+            Err(DecodeError::TooShort { actual: 40, .. })
+        ));
+    }
+}
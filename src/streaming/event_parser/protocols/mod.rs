@@ -11,4 +11,15 @@ pub mod raydium_cpmm;
 pub mod types;
 pub mod whirlpool;
 pub use block::block_meta_event::BlockMetaEvent;
-pub use types::Protocol;
+pub use block::slot_rolled_back_event::SlotRolledBackEvent;
+pub use bonk::{BONK_PROGRAM_ID, RAYDIUM_LAUNCHLAB_PROGRAM_ID};
+pub use meteora_damm_v2::METEORA_DAMM_V2_PROGRAM_ID;
+pub use meteora_dlmm::METEORA_DLMM_PROGRAM_ID;
+pub use pancakeswap::PANCAKESWAP_PROGRAM_ID;
+pub use pumpfun::PUMPFUN_PROGRAM_ID;
+pub use pumpswap::PUMPSWAP_PROGRAM_ID;
+pub use raydium_amm_v4::RAYDIUM_AMM_V4_PROGRAM_ID;
+pub use raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID;
+pub use raydium_cpmm::RAYDIUM_CPMM_PROGRAM_ID;
+pub use types::{Protocol, PROTOCOL_PROGRAM_IDS};
+pub use whirlpool::WHIRLPOOL_PROGRAM_ID;
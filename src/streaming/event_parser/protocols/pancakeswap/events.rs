@@ -1,12 +1,15 @@
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+use crate::streaming::event_parser::core::capabilities::EventCapability;
 use crate::streaming::event_parser::protocols::pancakeswap::types::{
     PoolState, TickArrayBitmapExtension, TickArrayState,
 };
+use crate::streaming::event_parser::protocols::raydium_clmm::events::discriminators as clmm_discriminators;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
 /// PancakeSwap V3 Swap 事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PancakeSwapSwapEvent {
     pub metadata: EventMetadata,
 
@@ -46,6 +49,7 @@ pub struct PancakeSwapSwapEvent {
 
 /// PancakeSwap V3 SwapV2 事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PancakeSwapSwapV2Event {
     pub metadata: EventMetadata,
 
@@ -88,6 +92,7 @@ pub struct PancakeSwapSwapV2Event {
 
 /// PancakeSwap PoolState 账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PancakeSwapPoolStateAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -102,6 +107,7 @@ pub struct PancakeSwapPoolStateAccountEvent {
 
 /// PancakeSwap TickArrayState 账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PancakeSwapTickArrayStateAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -116,6 +122,7 @@ pub struct PancakeSwapTickArrayStateAccountEvent {
 
 /// PancakeSwap TickArrayBitmapExtension 账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PancakeSwapTickArrayBitmapExtensionAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -128,6 +135,28 @@ pub struct PancakeSwapTickArrayBitmapExtensionAccountEvent {
     pub tick_array_bitmap_extension: TickArrayBitmapExtension,
 }
 
+/// PancakeSwap V3 程序ID（Solana）
+pub const PANCAKESWAP_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("HpNfyc2Saw7RKkQd8nEL4khUcuPhQ7WwY1B2qjx8jxFq");
+
+/// PancakeSwap 解析器支持的 discriminator 与事件类型
+pub const CAPABILITIES: &[EventCapability] = &[
+    EventCapability::instruction(discriminators::SWAP, &[EventType::PancakeSwapSwap]),
+    EventCapability::instruction(discriminators::SWAP_V2, &[EventType::PancakeSwapSwapV2]),
+    EventCapability::account(
+        clmm_discriminators::POOL_STATE,
+        &[EventType::AccountPancakeSwapPoolState],
+    ),
+    EventCapability::account(
+        clmm_discriminators::TICK_ARRAY_STATE,
+        &[EventType::AccountPancakeSwapTickArrayState],
+    ),
+    EventCapability::account(
+        clmm_discriminators::TICK_ARRAY_BITMAP_EXTENSION,
+        &[EventType::AccountPancakeSwapTickArrayBitmapExtension],
+    ),
+];
+
 /// 事件鉴别器常量
 pub mod discriminators {
     // 指令鉴别器
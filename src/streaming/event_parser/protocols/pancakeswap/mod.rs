@@ -1,4 +1,5 @@
 pub mod events;
+#[cfg(feature = "protocol-pancakeswap")]
 pub mod parser;
 pub mod types;
 
@@ -3,14 +3,18 @@ use crate::streaming::event_parser::{
         read_i32_le, read_u128_le, read_u64_le, read_u8_le, EventMetadata, EventType,
         ProgramDataItem,
     },
-    protocols::pancakeswap::{discriminators, PancakeSwapSwapEvent, PancakeSwapSwapV2Event},
-    DexEvent,
+    core::dispatcher::EventDispatcher,
+    protocols::{
+        pancakeswap::{discriminators, PancakeSwapSwapEvent, PancakeSwapSwapV2Event},
+        raydium_clmm::events::discriminators as clmm_discriminators,
+    },
+    DexEvent, Protocol,
 };
 use solana_sdk::pubkey::Pubkey;
 
-/// PancakeSwap V3 程序ID（Solana）
-pub const PANCAKESWAP_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("HpNfyc2Saw7RKkQd8nEL4khUcuPhQ7WwY1B2qjx8jxFq");
+pub use crate::streaming::event_parser::protocols::pancakeswap::events::{
+    CAPABILITIES, PANCAKESWAP_PROGRAM_ID,
+};
 
 /// 解析 PancakeSwap instruction data
 pub fn parse_pancakeswap_instruction_data(
@@ -51,12 +55,10 @@ pub fn parse_pancakeswap_inner_instruction_data(
 /// 解码层复用 Raydium CLMM 的结构体，但产出 PancakeSwap 自有账户事件类型。
 pub fn parse_pancakeswap_account_data(
     discriminator: &[u8],
-    account: crate::streaming::grpc::AccountPretty,
+    account: crate::streaming::event_parser::common::AccountPretty,
     metadata: crate::streaming::event_parser::common::EventMetadata,
 ) -> Option<crate::streaming::event_parser::DexEvent> {
-    use crate::streaming::event_parser::protocols::{
-        pancakeswap::types, raydium_clmm::events::discriminators as clmm_discriminators,
-    };
+    use crate::streaming::event_parser::protocols::pancakeswap::types;
 
     match discriminator {
         d if d == clmm_discriminators::POOL_STATE => types::pool_state_parser(account, metadata),
@@ -250,7 +252,9 @@ pub fn parse_swap_event_from_program_data(
     item: &ProgramDataItem,
     expected_pool_state: &Pubkey,
 ) -> Option<SwapEventLogData> {
-    if item.program_id != PANCAKESWAP_PROGRAM_ID {
+    if EventDispatcher::match_protocol_by_program_id(&item.program_id)
+        != Some(Protocol::PancakeSwap)
+    {
         return None;
     }
     let event_data = parse_swap_event_from_log(&item.base64)?;
@@ -1,17 +1,14 @@
-use crate::streaming::{
-    event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::{
-            pancakeswap::{
-                PancakeSwapPoolStateAccountEvent,
-                PancakeSwapTickArrayBitmapExtensionAccountEvent,
-                PancakeSwapTickArrayStateAccountEvent,
-            },
-            raydium_clmm::types as clmm_types,
+use crate::streaming::event_parser::{
+    common::{note_account_data_len, AccountPretty, EventMetadata, EventType},
+    protocols::{
+        pancakeswap::{
+            PancakeSwapPoolStateAccountEvent,
+            PancakeSwapTickArrayBitmapExtensionAccountEvent,
+            PancakeSwapTickArrayStateAccountEvent,
         },
-        DexEvent,
+        raydium_clmm::types as clmm_types,
     },
-    grpc::AccountPretty,
+    DexEvent,
 };
 
 pub type PoolState = clmm_types::PoolState;
@@ -36,6 +33,7 @@ pub fn tick_array_bitmap_extension_decode(data: &[u8]) -> Option<TickArrayBitmap
 
 pub fn pool_state_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPancakeSwapPoolState;
+    note_account_data_len(&metadata.event_type, POOL_STATE_SIZE + 8, account.data.len());
 
     if account.data.len() < POOL_STATE_SIZE + 8 {
         return None;
@@ -60,6 +58,7 @@ pub fn tick_array_state_parser(
     mut metadata: EventMetadata,
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPancakeSwapTickArrayState;
+    note_account_data_len(&metadata.event_type, TICK_ARRAY_STATE_SIZE + 8, account.data.len());
 
     if account.data.len() < TICK_ARRAY_STATE_SIZE + 8 {
         return None;
@@ -84,6 +83,11 @@ pub fn tick_array_bitmap_extension_parser(
     mut metadata: EventMetadata,
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPancakeSwapTickArrayBitmapExtension;
+    note_account_data_len(
+        &metadata.event_type,
+        TICK_ARRAY_BITMAP_EXTENSION_SIZE + 8,
+        account.data.len(),
+    );
 
     if account.data.len() < TICK_ARRAY_BITMAP_EXTENSION_SIZE + 8 {
         return None;
@@ -2,10 +2,12 @@ use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+use crate::streaming::event_parser::core::capabilities::EventCapability;
 use crate::streaming::event_parser::protocols::pumpfun::types::{BondingCurve, Global};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpFunCreateTokenEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -32,6 +34,7 @@ pub struct PumpFunCreateTokenEvent {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpFunCreateV2TokenEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -179,6 +182,7 @@ pub fn pumpfun_create_v2_token_event_log_decode(data: &[u8]) -> Option<PumpFunCr
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpFunTradeEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -259,6 +263,7 @@ pub fn pumpfun_trade_event_log_decode(data: &[u8]) -> Option<PumpFunTradeEvent>
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpFunMigrateEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -323,8 +328,47 @@ pub fn pumpfun_migrate_event_log_decode(data: &[u8]) -> Option<PumpFunMigrateEve
     borsh::from_slice::<PumpFunMigrateEvent>(&data[..PUMPFUN_MIGRATE_EVENT_LOG_SIZE]).ok()
 }
 
+/// 创建者领取 creator vault 中累积的手续费（collect_creator_fee）
+///
+/// creator vault 按创建者而不是按代币累积，一次领取包含该创建者所有代币的手续费，因此没有 mint
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
+pub struct PumpFunClaimCreatorFeeEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+
+    pub timestamp: i64,
+    pub creator: Pubkey,
+    /// 领取的 lamports
+    pub amount: u64,
+
+    #[borsh(skip)]
+    pub creator_vault: Pubkey,
+    #[borsh(skip)]
+    pub system_program: Pubkey,
+    #[borsh(skip)]
+    pub event_authority: Pubkey,
+    #[borsh(skip)]
+    pub program: Pubkey,
+}
+
+pub const PUMPFUN_CLAIM_CREATOR_FEE_EVENT_LOG_SIZE: usize = 48;
+
+pub fn pumpfun_claim_creator_fee_event_log_decode(
+    data: &[u8],
+) -> Option<PumpFunClaimCreatorFeeEvent> {
+    if data.len() < PUMPFUN_CLAIM_CREATOR_FEE_EVENT_LOG_SIZE {
+        return None;
+    }
+    borsh::from_slice::<PumpFunClaimCreatorFeeEvent>(
+        &data[..PUMPFUN_CLAIM_CREATOR_FEE_EVENT_LOG_SIZE],
+    )
+    .ok()
+}
+
 /// 铸币曲线
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpFunBondingCurveAccountEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -341,6 +385,7 @@ pub struct PumpFunBondingCurveAccountEvent {
 
 /// 全局配置
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpFunGlobalAccountEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -355,6 +400,47 @@ pub struct PumpFunGlobalAccountEvent {
     pub global: Global,
 }
 
+/// PumpFun程序ID
+pub const PUMPFUN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+
+/// PumpFun 解析器支持的 discriminator 与事件类型
+pub const CAPABILITIES: &[EventCapability] = &[
+    EventCapability::instruction(discriminators::CREATE_TOKEN_IX, &[EventType::PumpFunCreateToken]),
+    EventCapability::instruction(
+        discriminators::CREATE_V2_TOKEN_IX,
+        &[EventType::PumpFunCreateV2Token],
+    ),
+    EventCapability::instruction(discriminators::BUY_IX, &[EventType::PumpFunBuy]),
+    EventCapability::instruction(discriminators::SELL_IX, &[EventType::PumpFunSell]),
+    EventCapability::instruction(discriminators::MIGRATE_IX, &[EventType::PumpFunMigrate]),
+    EventCapability::instruction(
+        discriminators::COLLECT_CREATOR_FEE_IX,
+        &[EventType::PumpFunClaimCreatorFee],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::CREATE_TOKEN_EVENT,
+        &[EventType::PumpFunCreateToken, EventType::PumpFunCreateV2Token],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::TRADE_EVENT,
+        &[EventType::PumpFunBuy, EventType::PumpFunSell],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::COMPLETE_PUMP_AMM_MIGRATION_EVENT,
+        &[EventType::PumpFunMigrate],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::COLLECT_CREATOR_FEE_EVENT,
+        &[EventType::PumpFunClaimCreatorFee],
+    ),
+    EventCapability::account(
+        discriminators::BONDING_CURVE_ACCOUNT,
+        &[EventType::AccountPumpFunBondingCurve],
+    ),
+    EventCapability::account(discriminators::GLOBAL_ACCOUNT, &[EventType::AccountPumpFunGlobal]),
+];
+
 /// 事件鉴别器常量
 pub mod discriminators {
     // 事件鉴别器
@@ -367,6 +453,9 @@ pub mod discriminators {
     // pub const COMPLETE_PUMP_AMM_MIGRATION_EVENT: &str = "0xe445a52e51cb9a1dbde95db95c94ea94";
     pub const COMPLETE_PUMP_AMM_MIGRATION_EVENT: &[u8] =
         &[228, 69, 165, 46, 81, 203, 154, 29, 189, 233, 93, 185, 92, 148, 234, 148];
+    // pub const COLLECT_CREATOR_FEE_EVENT: &str = "0xe445a52e51cb9a1d7a027f010ebf0caf";
+    pub const COLLECT_CREATOR_FEE_EVENT: &[u8] =
+        &[228, 69, 165, 46, 81, 203, 154, 29, 122, 2, 127, 1, 14, 191, 12, 175];
 
     // 指令鉴别器
     pub const CREATE_TOKEN_IX: &[u8] = &[24, 30, 200, 40, 5, 28, 7, 119];
@@ -374,6 +463,7 @@ pub mod discriminators {
     pub const BUY_IX: &[u8] = &[102, 6, 61, 18, 1, 218, 235, 234];
     pub const SELL_IX: &[u8] = &[51, 230, 133, 164, 1, 127, 131, 173];
     pub const MIGRATE_IX: &[u8] = &[155, 234, 231, 146, 236, 158, 162, 30];
+    pub const COLLECT_CREATOR_FEE_IX: &[u8] = &[20, 22, 86, 123, 198, 28, 219, 132];
 
     // 账户鉴别器
     pub const BONDING_CURVE_ACCOUNT: &[u8] = &[23, 183, 248, 55, 96, 216, 172, 96];
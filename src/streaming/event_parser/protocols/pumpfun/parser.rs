@@ -1,17 +1,18 @@
 use crate::streaming::event_parser::{
     common::{EventMetadata, EventType},
     protocols::pumpfun::{
-        discriminators, pumpfun_create_v2_token_event_log_decode, pumpfun_migrate_event_log_decode,
-        pumpfun_trade_event_log_decode, PumpFunCreateTokenEvent, PumpFunCreateV2TokenEvent,
-        PumpFunMigrateEvent, PumpFunTradeEvent,
+        discriminators, pumpfun_claim_creator_fee_event_log_decode,
+        pumpfun_create_v2_token_event_log_decode, pumpfun_migrate_event_log_decode,
+        pumpfun_trade_event_log_decode, PumpFunClaimCreatorFeeEvent, PumpFunCreateTokenEvent,
+        PumpFunCreateV2TokenEvent, PumpFunMigrateEvent, PumpFunTradeEvent,
     },
     DexEvent,
 };
 use solana_sdk::pubkey::Pubkey;
 
-/// PumpFun程序ID
-pub const PUMPFUN_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+pub use crate::streaming::event_parser::protocols::pumpfun::events::{
+    CAPABILITIES, PUMPFUN_PROGRAM_ID,
+};
 
 /// 解析 PumpFun instruction data
 ///
@@ -30,6 +31,9 @@ pub fn parse_pumpfun_instruction_data(
         discriminators::BUY_IX => parse_buy_instruction(data, accounts, metadata),
         discriminators::SELL_IX => parse_sell_instruction(data, accounts, metadata),
         discriminators::MIGRATE_IX => parse_migrate_instruction(data, accounts, metadata),
+        discriminators::COLLECT_CREATOR_FEE_IX => {
+            parse_collect_creator_fee_instruction(data, accounts, metadata)
+        }
         _ => None,
     }
 }
@@ -48,6 +52,9 @@ pub fn parse_pumpfun_inner_instruction_data(
         discriminators::COMPLETE_PUMP_AMM_MIGRATION_EVENT => {
             parse_migrate_inner_instruction(data, metadata)
         }
+        discriminators::COLLECT_CREATOR_FEE_EVENT => {
+            parse_collect_creator_fee_inner_instruction(data, metadata)
+        }
         _ => None,
     }
 }
@@ -57,7 +64,7 @@ pub fn parse_pumpfun_inner_instruction_data(
 /// 根据判别器路由到具体的账户解析函数
 pub fn parse_pumpfun_account_data(
     discriminator: &[u8],
-    account: crate::streaming::grpc::AccountPretty,
+    account: crate::streaming::event_parser::common::AccountPretty,
     metadata: crate::streaming::event_parser::common::EventMetadata,
 ) -> Option<crate::streaming::event_parser::DexEvent> {
     match discriminator {
@@ -85,6 +92,16 @@ fn parse_migrate_inner_instruction(data: &[u8], mut metadata: EventMetadata) ->
     }
 }
 
+/// 解析创建者手续费领取事件
+fn parse_collect_creator_fee_inner_instruction(
+    data: &[u8],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    metadata.event_type = EventType::PumpFunClaimCreatorFee;
+    let event = pumpfun_claim_creator_fee_event_log_decode(data)?;
+    Some(DexEvent::PumpFunClaimCreatorFeeEvent(PumpFunClaimCreatorFeeEvent { metadata, ..event }))
+}
+
 /// 解析创建代币日志事件
 fn parse_create_token_inner_instruction(
     data: &[u8],
@@ -311,6 +328,28 @@ fn parse_sell_instruction(
     }))
 }
 
+/// 解析创建者手续费领取指令事件，领取数量来自 CPI 事件
+fn parse_collect_creator_fee_instruction(
+    _data: &[u8],
+    accounts: &[Pubkey],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    metadata.event_type = EventType::PumpFunClaimCreatorFee;
+
+    if accounts.len() < 5 {
+        return None;
+    }
+    Some(DexEvent::PumpFunClaimCreatorFeeEvent(PumpFunClaimCreatorFeeEvent {
+        metadata,
+        creator: accounts[0],
+        creator_vault: accounts[1],
+        system_program: accounts[2],
+        event_authority: accounts[3],
+        program: accounts[4],
+        ..Default::default()
+    }))
+}
+
 /// 解析迁移指令事件
 fn parse_migrate_instruction(
     _data: &[u8],
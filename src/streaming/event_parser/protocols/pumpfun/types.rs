@@ -2,16 +2,14 @@ use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::{
-    event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::pumpfun::{PumpFunBondingCurveAccountEvent, PumpFunGlobalAccountEvent},
-        DexEvent,
-    },
-    grpc::AccountPretty,
+use crate::streaming::event_parser::{
+    common::{note_account_data_len, AccountPretty, EventMetadata, EventType},
+    protocols::pumpfun::{PumpFunBondingCurveAccountEvent, PumpFunGlobalAccountEvent},
+    DexEvent,
 };
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct BondingCurve {
     pub virtual_token_reserves: u64,
     pub virtual_sol_reserves: u64,
@@ -37,6 +35,7 @@ pub fn bonding_curve_parser(
     mut metadata: EventMetadata,
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPumpFunBondingCurve;
+    note_account_data_len(&metadata.event_type, BONDING_CURVE_SIZE + 8, account.data.len());
 
     if account.data.len() < BONDING_CURVE_SIZE + 8 {
         return None;
@@ -58,6 +57,7 @@ pub fn bonding_curve_parser(
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct Global {
     pub initialized: bool,
     pub authority: Pubkey,
@@ -83,6 +83,48 @@ pub struct Global {
 pub const GLOBAL_SIZE: usize =
     1 + 32 * 2 + 8 * 5 + 32 + 1 + 8 * 2 + 32 * 7 + 32 * 2 + 1 + 32 * 2 + 1;
 
+/// 迁移（bonding curve 完成）手续费与新建 bonding curve 的初始储备
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompletionFeeAndReserves {
+    pub pool_migration_fee: u64,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub token_total_supply: u64,
+}
+
+impl Global {
+    /// 协议费 + 创作者费（basis points）
+    #[inline]
+    pub fn total_fee_basis_points(&self) -> u64 {
+        self.fee_basis_points.saturating_add(self.creator_fee_basis_points)
+    }
+
+    /// 所有协议费接收地址（fee_recipient、fee_recipients、reserved_fee_recipient），跳过未设置的地址
+    pub fn all_fee_recipients(&self) -> impl Iterator<Item = &Pubkey> {
+        std::iter::once(&self.fee_recipient)
+            .chain(self.fee_recipients.iter())
+            .chain(std::iter::once(&self.reserved_fee_recipient))
+            .filter(|recipient| **recipient != Pubkey::default())
+    }
+
+    /// 是否为协议费接收地址
+    pub fn is_fee_recipient(&self, address: &Pubkey) -> bool {
+        self.all_fee_recipients().any(|recipient| recipient == address)
+    }
+
+    pub fn completion_fee_and_reserves(&self) -> CompletionFeeAndReserves {
+        CompletionFeeAndReserves {
+            pool_migration_fee: self.pool_migration_fee,
+            initial_virtual_token_reserves: self.initial_virtual_token_reserves,
+            initial_virtual_sol_reserves: self.initial_virtual_sol_reserves,
+            initial_real_token_reserves: self.initial_real_token_reserves,
+            token_total_supply: self.token_total_supply,
+        }
+    }
+}
+
 pub fn global_decode(data: &[u8]) -> Option<Global> {
     if data.len() < GLOBAL_SIZE {
         return None;
@@ -92,6 +134,7 @@ pub fn global_decode(data: &[u8]) -> Option<Global> {
 
 pub fn global_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPumpFunGlobal;
+    note_account_data_len(&metadata.event_type, GLOBAL_SIZE + 8, account.data.len());
 
     if account.data.len() < GLOBAL_SIZE + 8 {
         return None;
@@ -111,3 +154,69 @@ pub fn global_parser(account: AccountPretty, mut metadata: EventMetadata) -> Opt
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_decode_fee_fields() {
+        let authority = Pubkey::new_unique();
+        let fee_recipient = Pubkey::new_unique();
+        let extra_recipient = Pubkey::new_unique();
+
+        let mut data = vec![0u8; GLOBAL_SIZE];
+        data[0] = 1;
+        data[1..33].copy_from_slice(authority.as_ref());
+        data[33..65].copy_from_slice(fee_recipient.as_ref());
+        data[65..73].copy_from_slice(&1_073_000_000_000_000u64.to_le_bytes());
+        data[73..81].copy_from_slice(&30_000_000_000u64.to_le_bytes());
+        data[97..105].copy_from_slice(&95u64.to_le_bytes());
+        data[138..146].copy_from_slice(&15_000_000u64.to_le_bytes());
+        data[146..154].copy_from_slice(&5u64.to_le_bytes());
+        // fee_recipients[2]
+        data[154 + 64..154 + 96].copy_from_slice(extra_recipient.as_ref());
+
+        let global = global_decode(&data).unwrap();
+        assert!(global.initialized);
+        assert_eq!(global.authority, authority);
+        assert_eq!(global.fee_basis_points, 95);
+        assert_eq!(global.creator_fee_basis_points, 5);
+        assert_eq!(global.total_fee_basis_points(), 100);
+        assert_eq!(global.fee_recipients[2], extra_recipient);
+        assert_eq!(
+            global.all_fee_recipients().copied().collect::<Vec<_>>(),
+            vec![fee_recipient, extra_recipient]
+        );
+        assert!(global.is_fee_recipient(&extra_recipient));
+        assert!(!global.is_fee_recipient(&authority));
+
+        let completion = global.completion_fee_and_reserves();
+        assert_eq!(completion.pool_migration_fee, 15_000_000);
+        assert_eq!(completion.initial_virtual_token_reserves, 1_073_000_000_000_000);
+        assert_eq!(completion.initial_virtual_sol_reserves, 30_000_000_000);
+    }
+
+    #[test]
+    fn test_account_event_deserializes_archive_missing_nested_fields() {
+        let event = PumpFunBondingCurveAccountEvent {
+            pubkey: Pubkey::new_unique(),
+            bonding_curve: BondingCurve {
+                virtual_sol_reserves: 30_000_000_000,
+                creator: Pubkey::new_unique(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // 模拟旧版本写入的 JSON：嵌套的 BondingCurve 中缺少之后新增的字段
+        let mut json = serde_json::to_value(&event).unwrap();
+        json["bonding_curve"].as_object_mut().unwrap().remove("is_mayhem_mode");
+        json.as_object_mut().unwrap().remove("raw_account_data");
+
+        let decoded: PumpFunBondingCurveAccountEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.pubkey, event.pubkey);
+        assert_eq!(decoded.bonding_curve, event.bonding_curve);
+        assert!(!decoded.bonding_curve.is_mayhem_mode);
+    }
+}
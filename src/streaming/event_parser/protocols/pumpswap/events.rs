@@ -2,11 +2,13 @@ use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+use crate::streaming::event_parser::core::capabilities::EventCapability;
 use crate::streaming::event_parser::protocols::pumpswap::types::{GlobalConfig, Pool};
 
 /// 买入事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpSwapBuyEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -97,7 +99,11 @@ pub fn pump_swap_buy_event_log_decode(data: &[u8]) -> Option<PumpSwapBuyEvent> {
 /// - quote_amount_in: 精确输入的 quote 数量（指令参数）
 /// - min_base_amount_out: 最小可接受的 base 输出（指令参数）
 /// - actual_base_amount_out: 真实成交 base 输出（由 inner BUY_EVENT 回填）
+///
+/// 读取成交数量请使用 `resolved_quote_in()` / `resolved_base_out()`，
+/// 并通过 `amount_source()` 区分真实成交与指令参数估算
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PumpSwapBuyExactQuoteInEvent {
     pub metadata: EventMetadata,
     pub timestamp: i64,
@@ -144,10 +150,55 @@ pub struct PumpSwapBuyExactQuoteInEvent {
     pub coin_creator_vault_authority: Pubkey,
     pub base_token_program: Pubkey,
     pub quote_token_program: Pubkey,
+    /// 是否已合并 inner BUY_EVENT（CPI 日志）
+    pub has_cpi_log: bool,
+}
+
+/// 成交数量来源
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AmountSource {
+    /// 来自 CPI 日志的真实成交数量
+    Actual,
+    /// 来自指令参数（输入上限 / 最小输出），仅为估算
+    Estimate,
+}
+
+impl PumpSwapBuyExactQuoteInEvent {
+    /// `resolved_quote_in()` / `resolved_base_out()` 的数据来源
+    pub fn amount_source(&self) -> AmountSource {
+        if self.has_cpi_log {
+            AmountSource::Actual
+        } else {
+            AmountSource::Estimate
+        }
+    }
+
+    /// 用户实际支付的 quote 数量（含手续费）
+    ///
+    /// 优先级：CPI 日志 `user_quote_amount_in` > 指令参数 `quote_amount_in`
+    pub fn resolved_quote_in(&self) -> u64 {
+        if self.has_cpi_log {
+            self.user_quote_amount_in
+        } else {
+            self.quote_amount_in
+        }
+    }
+
+    /// 用户获得的 base 数量
+    ///
+    /// 优先级：CPI 日志 `actual_base_amount_out` > 指令参数 `min_base_amount_out`
+    pub fn resolved_base_out(&self) -> u64 {
+        if self.has_cpi_log {
+            self.actual_base_amount_out
+        } else {
+            self.min_base_amount_out
+        }
+    }
 }
 
 /// 卖出事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpSwapSellEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -262,6 +313,7 @@ fn decode_swap_event_tail(
 
 /// 创建池子事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpSwapCreatePoolEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -305,6 +357,7 @@ pub fn pump_swap_create_pool_event_log_decode(data: &[u8]) -> Option<PumpSwapCre
 
 /// 存款事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpSwapDepositEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -334,6 +387,18 @@ pub struct PumpSwapDepositEvent {
     pub pool_quote_token_account: Pubkey,
 }
 
+impl PumpSwapDepositEvent {
+    /// 本次铸造的 LP 数量占 `lp_mint_supply` 的比例
+    ///
+    /// `lp_mint_supply` 只来自 CPI 事件，仅有指令数据时为 0，此时返回 None
+    pub fn lp_share_fraction(&self) -> Option<f64> {
+        if self.lp_mint_supply == 0 {
+            return None;
+        }
+        Some(self.lp_token_amount_out as f64 / self.lp_mint_supply as f64)
+    }
+}
+
 pub const PUMP_SWAP_DEPOSIT_EVENT_LOG_SIZE: usize = 248;
 
 pub fn pump_swap_deposit_event_log_decode(data: &[u8]) -> Option<PumpSwapDepositEvent> {
@@ -345,6 +410,7 @@ pub fn pump_swap_deposit_event_log_decode(data: &[u8]) -> Option<PumpSwapDeposit
 
 /// 提款事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpSwapWithdrawEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -374,6 +440,18 @@ pub struct PumpSwapWithdrawEvent {
     pub pool_quote_token_account: Pubkey,
 }
 
+impl PumpSwapWithdrawEvent {
+    /// 本次销毁的 LP 数量占 `lp_mint_supply` 的比例
+    ///
+    /// `lp_mint_supply` 只来自 CPI 事件，仅有指令数据时为 0，此时返回 None
+    pub fn lp_share_fraction(&self) -> Option<f64> {
+        if self.lp_mint_supply == 0 {
+            return None;
+        }
+        Some(self.lp_token_amount_in as f64 / self.lp_mint_supply as f64)
+    }
+}
+
 pub const PUMP_SWAP_WITHDRAW_EVENT_LOG_SIZE: usize = 248;
 
 pub fn pump_swap_withdraw_event_log_decode(data: &[u8]) -> Option<PumpSwapWithdrawEvent> {
@@ -385,6 +463,7 @@ pub fn pump_swap_withdraw_event_log_decode(data: &[u8]) -> Option<PumpSwapWithdr
 
 /// 全局配置
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpSwapGlobalConfigAccountEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -401,6 +480,7 @@ pub struct PumpSwapGlobalConfigAccountEvent {
 
 /// 池
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PumpSwapPoolAccountEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -415,6 +495,47 @@ pub struct PumpSwapPoolAccountEvent {
     pub pool: Pool,
 }
 
+/// PumpSwap程序ID
+///
+/// 即 pump.fun 的 AMM（pump AMM），PumpFun bonding curve 迁移后的代币在此交易
+pub const PUMPSWAP_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+
+/// PumpSwap 解析器支持的 discriminator 与事件类型
+pub const CAPABILITIES: &[EventCapability] = &[
+    EventCapability::instruction(discriminators::BUY_IX, &[EventType::PumpSwapBuy]),
+    EventCapability::instruction(
+        discriminators::BUY_EXACT_QUOTE_IN_IX,
+        &[EventType::PumpSwapBuyExactQuoteIn],
+    ),
+    EventCapability::instruction(discriminators::SELL_IX, &[EventType::PumpSwapSell]),
+    EventCapability::instruction(discriminators::CREATE_POOL_IX, &[EventType::PumpSwapCreatePool]),
+    EventCapability::instruction(discriminators::DEPOSIT_IX, &[EventType::PumpSwapDeposit]),
+    EventCapability::instruction(discriminators::WITHDRAW_IX, &[EventType::PumpSwapWithdraw]),
+    EventCapability::inner_instruction(
+        discriminators::BUY_EVENT,
+        &[EventType::PumpSwapBuy, EventType::PumpSwapBuyExactQuoteIn],
+    ),
+    EventCapability::inner_instruction(discriminators::SELL_EVENT, &[EventType::PumpSwapSell]),
+    EventCapability::inner_instruction(
+        discriminators::CREATE_POOL_EVENT,
+        &[EventType::PumpSwapCreatePool],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::DEPOSIT_EVENT,
+        &[EventType::PumpSwapDeposit],
+    ),
+    EventCapability::inner_instruction(
+        discriminators::WITHDRAW_EVENT,
+        &[EventType::PumpSwapWithdraw],
+    ),
+    EventCapability::account(
+        discriminators::GLOBAL_CONFIG_ACCOUNT,
+        &[EventType::AccountPumpSwapGlobalConfig],
+    ),
+    EventCapability::account(discriminators::POOL_ACCOUNT, &[EventType::AccountPumpSwapPool]),
+];
+
 /// 事件鉴别器常量
 pub mod discriminators {
     // 事件鉴别器
@@ -1,4 +1,5 @@
 pub mod events;
+#[cfg(feature = "protocol-pumpswap")]
 pub mod parser;
 pub mod types;
 
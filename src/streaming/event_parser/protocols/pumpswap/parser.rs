@@ -10,9 +10,9 @@ use crate::streaming::event_parser::{
 };
 use solana_sdk::pubkey::Pubkey;
 
-/// PumpSwap程序ID
-pub const PUMPSWAP_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+pub use crate::streaming::event_parser::protocols::pumpswap::events::{
+    CAPABILITIES, PUMPSWAP_PROGRAM_ID,
+};
 
 /// 解析 PumpSwap instruction data
 ///
@@ -59,7 +59,7 @@ pub fn parse_pumpswap_inner_instruction_data(
 /// 根据判别器路由到具体的账户解析函数
 pub fn parse_pumpswap_account_data(
     discriminator: &[u8],
-    account: crate::streaming::grpc::AccountPretty,
+    account: crate::streaming::event_parser::common::AccountPretty,
     metadata: crate::streaming::event_parser::common::EventMetadata,
 ) -> Option<crate::streaming::event_parser::DexEvent> {
     match discriminator {
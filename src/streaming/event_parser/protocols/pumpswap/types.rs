@@ -2,16 +2,14 @@ use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::{
-    event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::pumpswap::{PumpSwapGlobalConfigAccountEvent, PumpSwapPoolAccountEvent},
-        DexEvent,
-    },
-    grpc::AccountPretty,
+use crate::streaming::event_parser::{
+    common::{note_account_data_len, AccountPretty, EventMetadata, EventType},
+    protocols::pumpswap::{PumpSwapGlobalConfigAccountEvent, PumpSwapPoolAccountEvent},
+    DexEvent,
 };
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct GlobalConfig {
     pub admin: Pubkey,
     pub lp_fee_basis_points: u64,
@@ -27,6 +25,31 @@ pub struct GlobalConfig {
     pub is_cashback_enabled: bool,
 }
 
+impl GlobalConfig {
+    /// LP 费 + 协议费 + 创作者费（basis points）
+    #[inline]
+    pub fn total_fee_basis_points(&self) -> u64 {
+        self.lp_fee_basis_points
+            .saturating_add(self.protocol_fee_basis_points)
+            .saturating_add(self.coin_creator_fee_basis_points)
+    }
+
+    /// 所有协议费接收地址（protocol_fee_recipients、reserved_fee_recipient、reserved_fee_recipients），
+    /// 跳过未设置的地址
+    pub fn all_fee_recipients(&self) -> impl Iterator<Item = &Pubkey> {
+        self.protocol_fee_recipients
+            .iter()
+            .chain(std::iter::once(&self.reserved_fee_recipient))
+            .chain(self.reserved_fee_recipients.iter())
+            .filter(|recipient| **recipient != Pubkey::default())
+    }
+
+    /// 是否为协议费接收地址
+    pub fn is_fee_recipient(&self, address: &Pubkey) -> bool {
+        self.all_fee_recipients().any(|recipient| recipient == address)
+    }
+}
+
 pub const GLOBAL_CONFIG_SIZE: usize =
     32 + 8 + 8 + 1 + 32 * 8 + 8 + 32 + 32 + 32 + 1 + 32 * 7 + 1;
 
@@ -42,6 +65,7 @@ pub fn global_config_parser(
     mut metadata: EventMetadata,
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPumpSwapGlobalConfig;
+    note_account_data_len(&metadata.event_type, GLOBAL_CONFIG_SIZE + 8, account.data.len());
 
     if account.data.len() < GLOBAL_CONFIG_SIZE + 8 {
         return None;
@@ -63,6 +87,7 @@ pub fn global_config_parser(
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct Pool {
     pub pool_bump: u8,
     pub index: u16,
@@ -96,6 +121,7 @@ pub fn pool_decode(data: &[u8]) -> Option<Pool> {
 
 pub fn pool_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPumpSwapPool;
+    note_account_data_len(&metadata.event_type, POOL_SIZE + 8, account.data.len());
 
     if account.data.len() < POOL_SIZE + 8 {
         return None;
@@ -115,3 +141,55 @@ pub fn pool_parser(account: AccountPretty, mut metadata: EventMetadata) -> Optio
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_config_parser_fee_fields() {
+        let admin = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let reserved = Pubkey::new_unique();
+        let reserved_extra = Pubkey::new_unique();
+
+        // 8 字节 discriminator + GlobalConfig
+        let mut data = vec![0u8; 8 + GLOBAL_CONFIG_SIZE];
+        let config = &mut data[8..];
+        config[0..32].copy_from_slice(admin.as_ref());
+        config[32..40].copy_from_slice(&20u64.to_le_bytes());
+        config[40..48].copy_from_slice(&5u64.to_le_bytes());
+        config[48] = 1;
+        // protocol_fee_recipients[1]
+        config[49 + 32..49 + 64].copy_from_slice(recipient.as_ref());
+        config[305..313].copy_from_slice(&5u64.to_le_bytes());
+        config[377..409].copy_from_slice(reserved.as_ref());
+        config[409] = 1;
+        // reserved_fee_recipients[6]
+        config[410 + 192..410 + 224].copy_from_slice(reserved_extra.as_ref());
+        config[634] = 1;
+
+        let account = AccountPretty { data, ..Default::default() };
+        let Some(DexEvent::PumpSwapGlobalConfigAccountEvent(event)) =
+            global_config_parser(account, EventMetadata::default())
+        else {
+            panic!("global config not parsed");
+        };
+        let config = event.global_config;
+        assert_eq!(event.metadata.event_type, EventType::AccountPumpSwapGlobalConfig);
+        assert_eq!(config.admin, admin);
+        assert_eq!(config.lp_fee_basis_points, 20);
+        assert_eq!(config.protocol_fee_basis_points, 5);
+        assert_eq!(config.coin_creator_fee_basis_points, 5);
+        assert_eq!(config.total_fee_basis_points(), 30);
+        assert_eq!(config.disable_flags, 1);
+        assert!(config.mayhem_mode_enabled);
+        assert!(config.is_cashback_enabled);
+        assert_eq!(
+            config.all_fee_recipients().copied().collect::<Vec<_>>(),
+            vec![recipient, reserved, reserved_extra]
+        );
+        assert!(config.is_fee_recipient(&reserved_extra));
+        assert!(!config.is_fee_recipient(&admin));
+    }
+}
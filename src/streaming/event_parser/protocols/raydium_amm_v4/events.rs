@@ -1,4 +1,5 @@
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+use crate::streaming::event_parser::core::capabilities::EventCapability;
 use crate::streaming::event_parser::protocols::raydium_amm_v4::types::AmmInfo;
 use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
@@ -6,6 +7,7 @@ use solana_sdk::pubkey::Pubkey;
 
 /// 交易
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumAmmV4SwapEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -38,6 +40,7 @@ pub struct RaydiumAmmV4SwapEvent {
 
 /// 添加流动性
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumAmmV4DepositEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -63,6 +66,7 @@ pub struct RaydiumAmmV4DepositEvent {
 
 /// 初始化
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumAmmV4Initialize2Event {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -96,6 +100,7 @@ pub struct RaydiumAmmV4Initialize2Event {
 
 /// 移除流动性
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumAmmV4WithdrawEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -127,6 +132,7 @@ pub struct RaydiumAmmV4WithdrawEvent {
 
 /// 提现
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumAmmV4WithdrawPnlEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -152,6 +158,7 @@ pub struct RaydiumAmmV4WithdrawPnlEvent {
 
 /// 池信息
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumAmmV4AmmInfoAccountEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -166,6 +173,33 @@ pub struct RaydiumAmmV4AmmInfoAccountEvent {
     pub amm_info: AmmInfo,
 }
 
+/// Raydium AMM V4程序ID
+pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+
+/// Raydium AMM V4 解析器支持的 discriminator 与事件类型
+pub const CAPABILITIES: &[EventCapability] = &[
+    EventCapability::instruction(
+        discriminators::SWAP_BASE_IN,
+        &[EventType::RaydiumAmmV4SwapBaseIn],
+    ),
+    EventCapability::instruction(
+        discriminators::SWAP_BASE_OUT,
+        &[EventType::RaydiumAmmV4SwapBaseOut],
+    ),
+    EventCapability::instruction(discriminators::DEPOSIT, &[EventType::RaydiumAmmV4Deposit]),
+    EventCapability::instruction(
+        discriminators::INITIALIZE2,
+        &[EventType::RaydiumAmmV4Initialize2],
+    ),
+    EventCapability::instruction(discriminators::WITHDRAW, &[EventType::RaydiumAmmV4Withdraw]),
+    EventCapability::instruction(
+        discriminators::WITHDRAW_PNL,
+        &[EventType::RaydiumAmmV4WithdrawPnl],
+    ),
+    EventCapability::account(discriminators::AMM_INFO, &[EventType::AccountRaydiumAmmV4AmmInfo]),
+];
+
 /// 事件鉴别器常量
 pub mod discriminators {
     // 指令鉴别器
@@ -176,6 +210,7 @@ pub mod discriminators {
     pub const WITHDRAW: &[u8] = &[04];
     pub const WITHDRAW_PNL: &[u8] = &[07];
 
-    /// 池信息鉴别器
-    pub const AMM_INFO: &[u8] = &[6];
+    /// 池信息账户没有鉴别器：首字段是 `status`，按 owner 与数据长度 `AMM_INFO_SIZE` 识别。
+    /// 空前缀在账户 discriminator 过滤中匹配该程序的所有账户
+    pub const AMM_INFO: &[u8] = &[];
 }
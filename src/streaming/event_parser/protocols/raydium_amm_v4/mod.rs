@@ -1,4 +1,5 @@
 pub mod events;
+#[cfg(feature = "protocol-raydium-amm-v4")]
 pub mod parser;
 pub mod types;
 
@@ -1,16 +1,18 @@
 use crate::streaming::event_parser::{
     common::{read_u64_le, EventMetadata, EventType},
     protocols::raydium_amm_v4::{
-        discriminators, RaydiumAmmV4DepositEvent, RaydiumAmmV4Initialize2Event,
-        RaydiumAmmV4SwapEvent, RaydiumAmmV4WithdrawEvent, RaydiumAmmV4WithdrawPnlEvent,
+        discriminators,
+        types::{amm_info_parser, AMM_INFO_SIZE},
+        RaydiumAmmV4DepositEvent, RaydiumAmmV4Initialize2Event, RaydiumAmmV4SwapEvent,
+        RaydiumAmmV4WithdrawEvent, RaydiumAmmV4WithdrawPnlEvent,
     },
     DexEvent,
 };
 use solana_sdk::pubkey::Pubkey;
 
-/// Raydium AMM V4程序ID
-pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+pub use crate::streaming::event_parser::protocols::raydium_amm_v4::events::{
+    CAPABILITIES, RAYDIUM_AMM_V4_PROGRAM_ID,
+};
 
 /// 解析 Raydium AMM V4 instruction data
 ///
@@ -47,19 +49,17 @@ pub fn parse_raydium_amm_v4_inner_instruction_data(
 
 /// 解析 Raydium AMM V4 账户数据
 ///
-/// 根据判别器路由到具体的账户解析函数
+/// AMM V4 账户没有鉴别器，owner 已由 dispatcher 确认；数据长度为 `AMM_INFO_SIZE` 的账户
+/// 解析为 AmmInfo，不区分 `status`。其他长度的账户（如 target orders）不产出事件
 pub fn parse_raydium_amm_v4_account_data(
-    discriminator: &[u8],
-    account: crate::streaming::grpc::AccountPretty,
+    _discriminator: &[u8],
+    account: crate::streaming::event_parser::common::AccountPretty,
     metadata: crate::streaming::event_parser::common::EventMetadata,
 ) -> Option<crate::streaming::event_parser::DexEvent> {
-    match discriminator {
-        discriminators::AMM_INFO => {
-            crate::streaming::event_parser::protocols::raydium_amm_v4::types::amm_info_parser(
-                account, metadata,
-            )
-        }
-        _ => None,
+    if account.data.len() == AMM_INFO_SIZE {
+        amm_info_parser(account, metadata)
+    } else {
+        None
     }
 }
 
@@ -321,3 +321,59 @@ fn parse_swap_base_input_instruction(
         ..Default::default()
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::common::AccountPretty;
+
+    fn amm_account(status: u64, data_len: usize) -> AccountPretty {
+        let mut data = vec![0u8; data_len];
+        data[0..8].copy_from_slice(&status.to_le_bytes());
+        AccountPretty {
+            pubkey: Pubkey::new_unique(),
+            owner: RAYDIUM_AMM_V4_PROGRAM_ID,
+            data,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_amm_info_for_every_status() {
+        // 1 = Initialized，6 = SwapOnly，7 = WaitingTrade
+        for status in [1u64, 6, 7] {
+            let account = amm_account(status, AMM_INFO_SIZE);
+            let discriminator = account.data[..8].to_vec();
+            let event = parse_raydium_amm_v4_account_data(
+                &discriminator,
+                account.clone(),
+                EventMetadata::default(),
+            )
+            .unwrap_or_else(|| panic!("status {status} should parse"));
+
+            match event {
+                DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => {
+                    assert_eq!(e.metadata.event_type, EventType::AccountRaydiumAmmV4AmmInfo);
+                    assert_eq!(e.pubkey, account.pubkey);
+                    assert_eq!(e.amm_info.status, status);
+                }
+                other => panic!("unexpected event: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn skips_accounts_of_other_sizes() {
+        // target orders 等账户同属 AMM V4 程序，长度不同
+        for data_len in [AMM_INFO_SIZE - 8, AMM_INFO_SIZE + 8, 2208] {
+            let account = amm_account(6, data_len);
+            let discriminator = account.data[..8].to_vec();
+            assert!(parse_raydium_amm_v4_account_data(
+                &discriminator,
+                account,
+                EventMetadata::default()
+            )
+            .is_none());
+        }
+    }
+}
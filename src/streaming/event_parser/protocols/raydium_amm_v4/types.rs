@@ -2,16 +2,14 @@ use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::{
-    event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::raydium_amm_v4::RaydiumAmmV4AmmInfoAccountEvent,
-        DexEvent,
-    },
-    grpc::AccountPretty,
+use crate::streaming::event_parser::{
+    common::{note_account_data_len, AccountPretty, EventMetadata, EventType},
+    protocols::raydium_amm_v4::RaydiumAmmV4AmmInfoAccountEvent,
+    DexEvent,
 };
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct Fees {
     pub min_separate_numerator: u64,
     pub min_separate_denominator: u64,
@@ -24,6 +22,7 @@ pub struct Fees {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct OutPutData {
     pub need_take_pnl_coin: u64,
     pub need_take_pnl_pc: u64,
@@ -42,6 +41,7 @@ pub struct OutPutData {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct AmmInfo {
     pub status: u64,
     pub nonce: u64,
@@ -89,6 +89,7 @@ pub fn amm_info_decode(data: &[u8]) -> Option<AmmInfo> {
 
 pub fn amm_info_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumAmmV4AmmInfo;
+    note_account_data_len(&metadata.event_type, AMM_INFO_SIZE, account.data.len());
 
     if account.data.len() < AMM_INFO_SIZE {
         return None;
@@ -110,6 +111,7 @@ pub fn amm_info_parser(account: AccountPretty, mut metadata: EventMetadata) -> O
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct MarketState {
     pub padding: [u8; 5],
     pub account_flags: u64,
@@ -1,4 +1,5 @@
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+use crate::streaming::event_parser::core::capabilities::EventCapability;
 use crate::streaming::event_parser::protocols::raydium_clmm::types::AmmConfig;
 use crate::streaming::event_parser::protocols::raydium_clmm::types::{
     PoolState, TickArrayBitmapExtension, TickArrayState,
@@ -8,6 +9,7 @@ use solana_sdk::pubkey::Pubkey;
 
 /// 交易
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmSwapEvent {
     pub metadata: EventMetadata,
     pub amount: u64,
@@ -41,6 +43,7 @@ pub struct RaydiumClmmSwapEvent {
 
 /// 交易v2
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmSwapV2Event {
     pub metadata: EventMetadata,
     pub amount: u64,
@@ -75,8 +78,66 @@ pub struct RaydiumClmmSwapV2Event {
     pub remaining_accounts: Vec<Pubkey>,
 }
 
+/// 多跳路由交易中的单跳，数据来自该跳的 SwapEvent 日志
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RaydiumClmmRouteHop {
+    /// 在路由中的顺序，从 0 开始
+    pub route_index: u32,
+    pub pool_state: Pubkey,
+    pub sender: Pubkey,
+    pub token_account_0: Pubkey,
+    pub token_account_1: Pubkey,
+    pub amount_0: u64,
+    pub transfer_fee_0: u64,
+    pub amount_1: u64,
+    pub transfer_fee_1: u64,
+    pub zero_for_one: bool,
+    pub sqrt_price_x64: u128,
+    pub liquidity: u128,
+    pub tick: i32,
+}
+
+impl RaydiumClmmRouteHop {
+    /// (输入数量, 输出数量)
+    pub fn amounts(&self) -> (u64, u64) {
+        if self.zero_for_one {
+            (self.amount_0, self.amount_1)
+        } else {
+            (self.amount_1, self.amount_0)
+        }
+    }
+}
+
+/// 多跳路由交易（SwapRouterBaseIn）
+///
+/// 每跳的池子账户数量不固定，hops 按 SwapEvent 日志顺序填充；未解析到日志时为空
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RaydiumClmmSwapRouterBaseInEvent {
+    pub metadata: EventMetadata,
+    pub amount_in: u64,
+    pub amount_out_minimum: u64,
+    pub payer: Pubkey,
+    pub input_token_account: Pubkey,
+    pub input_token_mint: Pubkey,
+    pub token_program: Pubkey,
+    pub token_program2022: Pubkey,
+    pub memo_program: Pubkey,
+    pub hops: Vec<RaydiumClmmRouteHop>,
+    pub remaining_accounts: Vec<Pubkey>,
+}
+
+impl RaydiumClmmSwapRouterBaseInEvent {
+    /// 最后一跳的实际输出数量
+    pub fn amount_out(&self) -> Option<u64> {
+        self.hops.last().map(|hop| hop.amounts().1)
+    }
+}
+
 /// 关闭仓位
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmClosePositionEvent {
     pub metadata: EventMetadata,
     pub nft_owner: Pubkey,
@@ -89,6 +150,7 @@ pub struct RaydiumClmmClosePositionEvent {
 
 /// 减少流动性v2
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmDecreaseLiquidityV2Event {
     pub metadata: EventMetadata,
     pub liquidity: u128,
@@ -115,6 +177,7 @@ pub struct RaydiumClmmDecreaseLiquidityV2Event {
 
 /// 创建池
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmCreatePoolEvent {
     pub metadata: EventMetadata,
     pub sqrt_price_x64: u128,
@@ -136,6 +199,7 @@ pub struct RaydiumClmmCreatePoolEvent {
 
 /// 增加流动性v2
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmIncreaseLiquidityV2Event {
     pub metadata: EventMetadata,
     pub liquidity: u128,
@@ -161,6 +225,7 @@ pub struct RaydiumClmmIncreaseLiquidityV2Event {
 
 /// 打开仓位v2
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmOpenPositionWithToken22NftEvent {
     pub metadata: EventMetadata,
     pub tick_lower_index: i32,
@@ -197,6 +262,7 @@ pub struct RaydiumClmmOpenPositionWithToken22NftEvent {
 
 /// 打开仓位V2
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmOpenPositionV2Event {
     pub metadata: EventMetadata,
     pub tick_lower_index: i32,
@@ -236,6 +302,7 @@ pub struct RaydiumClmmOpenPositionV2Event {
 
 /// 池配置
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmAmmConfigAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -250,6 +317,7 @@ pub struct RaydiumClmmAmmConfigAccountEvent {
 
 /// 池状态
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmPoolStateAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -264,6 +332,7 @@ pub struct RaydiumClmmPoolStateAccountEvent {
 
 /// 池状态
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmTickArrayStateAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -278,6 +347,7 @@ pub struct RaydiumClmmTickArrayStateAccountEvent {
 
 /// TickArrayBitmapExtension 账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RaydiumClmmTickArrayBitmapExtensionAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -290,11 +360,57 @@ pub struct RaydiumClmmTickArrayBitmapExtensionAccountEvent {
     pub tick_array_bitmap_extension: TickArrayBitmapExtension,
 }
 
+/// Raydium CLMM程序ID
+pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+
+/// Raydium CLMM 解析器支持的 discriminator 与事件类型
+pub const CAPABILITIES: &[EventCapability] = &[
+    EventCapability::instruction(discriminators::SWAP, &[EventType::RaydiumClmmSwap]),
+    EventCapability::instruction(discriminators::SWAP_V2, &[EventType::RaydiumClmmSwapV2]),
+    EventCapability::instruction(
+        discriminators::SWAP_ROUTER_BASE_IN,
+        &[EventType::RaydiumClmmSwapRouterBaseIn],
+    ),
+    EventCapability::instruction(
+        discriminators::CLOSE_POSITION,
+        &[EventType::RaydiumClmmClosePosition],
+    ),
+    EventCapability::instruction(
+        discriminators::DECREASE_LIQUIDITY_V2,
+        &[EventType::RaydiumClmmDecreaseLiquidityV2],
+    ),
+    EventCapability::instruction(discriminators::CREATE_POOL, &[EventType::RaydiumClmmCreatePool]),
+    EventCapability::instruction(
+        discriminators::INCREASE_LIQUIDITY_V2,
+        &[EventType::RaydiumClmmIncreaseLiquidityV2],
+    ),
+    EventCapability::instruction(
+        discriminators::OPEN_POSITION_WITH_TOKEN_22_NFT,
+        &[EventType::RaydiumClmmOpenPositionWithToken22Nft],
+    ),
+    EventCapability::instruction(
+        discriminators::OPEN_POSITION_V2,
+        &[EventType::RaydiumClmmOpenPositionV2],
+    ),
+    EventCapability::account(discriminators::AMM_CONFIG, &[EventType::AccountRaydiumClmmAmmConfig]),
+    EventCapability::account(discriminators::POOL_STATE, &[EventType::AccountRaydiumClmmPoolState]),
+    EventCapability::account(
+        discriminators::TICK_ARRAY_STATE,
+        &[EventType::AccountRaydiumClmmTickArrayState],
+    ),
+    EventCapability::account(
+        discriminators::TICK_ARRAY_BITMAP_EXTENSION,
+        &[EventType::AccountRaydiumClmmTickArrayBitmapExtension],
+    ),
+];
+
 /// 事件鉴别器常量
 pub mod discriminators {
     // 指令鉴别器
     pub const SWAP: &[u8] = &[248, 198, 158, 145, 225, 117, 135, 200];
     pub const SWAP_V2: &[u8] = &[43, 4, 237, 11, 26, 201, 30, 98];
+    pub const SWAP_ROUTER_BASE_IN: &[u8] = &[69, 125, 115, 218, 245, 186, 242, 196];
     pub const CLOSE_POSITION: &[u8] = &[123, 134, 81, 0, 49, 68, 98, 98];
     pub const INCREASE_LIQUIDITY_V2: &[u8] = &[133, 29, 89, 223, 69, 238, 176, 10];
     pub const DECREASE_LIQUIDITY_V2: &[u8] = &[58, 127, 188, 62, 79, 82, 196, 96];
@@ -1,4 +1,5 @@
 pub mod events;
+#[cfg(feature = "protocol-raydium-clmm")]
 pub mod parser;
 pub mod types;
 
@@ -3,19 +3,21 @@ use crate::streaming::event_parser::{
         read_i32_le, read_option_bool, read_u128_le, read_u64_le, read_u8_le, EventMetadata,
         EventType, ProgramDataItem,
     },
+    core::dispatcher::EventDispatcher,
     protocols::raydium_clmm::{
         discriminators, RaydiumClmmClosePositionEvent, RaydiumClmmCreatePoolEvent,
         RaydiumClmmDecreaseLiquidityV2Event, RaydiumClmmIncreaseLiquidityV2Event,
         RaydiumClmmOpenPositionV2Event, RaydiumClmmOpenPositionWithToken22NftEvent,
-        RaydiumClmmSwapEvent, RaydiumClmmSwapV2Event,
+        RaydiumClmmRouteHop, RaydiumClmmSwapEvent, RaydiumClmmSwapRouterBaseInEvent,
+        RaydiumClmmSwapV2Event,
     },
-    DexEvent,
+    DexEvent, Protocol,
 };
 use solana_sdk::pubkey::Pubkey;
 
-/// Raydium CLMM程序ID
-pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+pub use crate::streaming::event_parser::protocols::raydium_clmm::events::{
+    CAPABILITIES, RAYDIUM_CLMM_PROGRAM_ID,
+};
 
 /// SwapEvent 从 Anchor 事件日志解析出来的数据
 #[derive(Debug, Clone, Default)]
@@ -46,6 +48,9 @@ pub fn parse_raydium_clmm_instruction_data(
     match discriminator {
         discriminators::SWAP => parse_swap_instruction(data, accounts, metadata),
         discriminators::SWAP_V2 => parse_swap_v2_instruction(data, accounts, metadata),
+        discriminators::SWAP_ROUTER_BASE_IN => {
+            parse_swap_router_base_in_instruction(data, accounts, metadata)
+        }
         discriminators::CLOSE_POSITION => {
             parse_close_position_instruction(data, accounts, metadata)
         }
@@ -67,7 +72,10 @@ pub fn parse_raydium_clmm_instruction_data(
 }
 
 pub fn is_raydium_clmm_swap_instruction(discriminator: &[u8]) -> bool {
-    matches!(discriminator, discriminators::SWAP | discriminators::SWAP_V2)
+    matches!(
+        discriminator,
+        discriminators::SWAP | discriminators::SWAP_V2 | discriminators::SWAP_ROUTER_BASE_IN
+    )
 }
 
 /// 解析 Raydium CLMM inner instruction data
@@ -86,7 +94,7 @@ pub fn parse_raydium_clmm_inner_instruction_data(
 /// 根据判别器路由到具体的账户解析函数
 pub fn parse_raydium_clmm_account_data(
     discriminator: &[u8],
-    account: crate::streaming::grpc::AccountPretty,
+    account: crate::streaming::event_parser::common::AccountPretty,
     metadata: crate::streaming::event_parser::common::EventMetadata,
 ) -> Option<crate::streaming::event_parser::DexEvent> {
     match discriminator {
@@ -403,6 +411,35 @@ fn parse_swap_v2_instruction(
     }))
 }
 
+/// 解析多跳路由交易指令事件
+///
+/// 参数布局: amount_in(u64), amount_out_minimum(u64)
+fn parse_swap_router_base_in_instruction(
+    data: &[u8],
+    accounts: &[Pubkey],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    metadata.event_type = EventType::RaydiumClmmSwapRouterBaseIn;
+
+    if data.len() < 16 || accounts.len() < 6 {
+        return None;
+    }
+
+    Some(DexEvent::RaydiumClmmSwapRouterBaseInEvent(RaydiumClmmSwapRouterBaseInEvent {
+        metadata,
+        amount_in: read_u64_le(data, 0)?,
+        amount_out_minimum: read_u64_le(data, 8)?,
+        payer: accounts[0],
+        input_token_account: accounts[1],
+        input_token_mint: accounts[2],
+        token_program: accounts[3],
+        token_program2022: accounts[4],
+        memo_program: accounts[5],
+        remaining_accounts: accounts[6..].to_vec(),
+        ..Default::default()
+    }))
+}
+
 /// 从 Anchor 事件日志中解析 SwapEvent 数据
 ///
 /// Anchor 事件日志格式: "Program data: <base64_encoded_event>"
@@ -462,12 +499,45 @@ pub fn parse_swap_event_from_log(log_data_base64: &str) -> Option<SwapEventLogDa
     })
 }
 
+/// 按日志顺序解析路由交易中每一跳的 SwapEvent，`logs` 为构建 `item` 所用的交易日志
+pub fn parse_route_hops_from_program_data(
+    item: &ProgramDataItem,
+    logs: &[String],
+) -> Vec<RaydiumClmmRouteHop> {
+    if EventDispatcher::match_protocol_by_program_id(&item.program_id)
+        != Some(Protocol::RaydiumClmm)
+    {
+        return Vec::new();
+    }
+    item.all_program_data(logs)
+        .filter_map(parse_swap_event_from_log)
+        .enumerate()
+        .map(|(route_index, log_data)| RaydiumClmmRouteHop {
+            route_index: route_index as u32,
+            pool_state: log_data.pool_state,
+            sender: log_data.sender,
+            token_account_0: log_data.token_account_0,
+            token_account_1: log_data.token_account_1,
+            amount_0: log_data.amount_0,
+            transfer_fee_0: log_data.transfer_fee_0,
+            amount_1: log_data.amount_1,
+            transfer_fee_1: log_data.transfer_fee_1,
+            zero_for_one: log_data.zero_for_one,
+            sqrt_price_x64: log_data.sqrt_price_x64,
+            liquidity: log_data.liquidity,
+            tick: log_data.tick,
+        })
+        .collect()
+}
+
 /// 从 ProgramDataItem 解析 SwapEvent 数据
 pub fn parse_swap_event_from_program_data(
     item: &ProgramDataItem,
     expected_pool_state: &Pubkey,
 ) -> Option<SwapEventLogData> {
-    if item.program_id != RAYDIUM_CLMM_PROGRAM_ID {
+    if EventDispatcher::match_protocol_by_program_id(&item.program_id)
+        != Some(Protocol::RaydiumClmm)
+    {
         return None;
     }
     let event_data = parse_swap_event_from_log(&item.base64)?;
@@ -476,3 +546,76 @@ pub fn parse_swap_event_from_program_data(
     }
     Some(event_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    fn swap_event_log(
+        pool_state: Pubkey,
+        amount_0: u64,
+        amount_1: u64,
+        zero_for_one: bool,
+    ) -> String {
+        let mut data = discriminators::SWAP_EVENT.to_vec();
+        data.extend_from_slice(pool_state.as_ref());
+        for _ in 0..3 {
+            data.extend_from_slice(Pubkey::new_unique().as_ref());
+        }
+        data.extend_from_slice(&amount_0.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&amount_1.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.push(zero_for_one as u8);
+        data.extend_from_slice(&1u128.to_le_bytes());
+        data.extend_from_slice(&1u128.to_le_bytes());
+        data.extend_from_slice(&(-5i32).to_le_bytes());
+        STANDARD.encode(data)
+    }
+
+    #[test]
+    fn parses_swap_router_base_in_hops_in_log_order() {
+        let accounts: Vec<Pubkey> = (0..20).map(|_| Pubkey::new_unique()).collect();
+        let mut data = 1_000u64.to_le_bytes().to_vec();
+        data.extend_from_slice(&450u64.to_le_bytes());
+        let event = parse_raydium_clmm_instruction_data(
+            discriminators::SWAP_ROUTER_BASE_IN,
+            &data,
+            &accounts,
+            EventMetadata::default(),
+        )
+        .expect("swap_router_base_in should parse");
+        let DexEvent::RaydiumClmmSwapRouterBaseInEvent(mut route) = event else {
+            panic!("unexpected event type");
+        };
+        assert_eq!(route.amount_in, 1_000);
+        assert_eq!(route.amount_out_minimum, 450);
+        assert_eq!(route.input_token_mint, accounts[2]);
+        assert_eq!(route.remaining_accounts.len(), 14);
+
+        let (pool_a, pool_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let item = ProgramDataItem {
+            base64: swap_event_log(pool_a, 1_000, 700, true),
+            program_id: RAYDIUM_CLMM_PROGRAM_ID,
+            depth: 1,
+            log_index: 0,
+            additional: vec![2],
+        };
+        let logs = vec![
+            format!("Program data: {}", item.base64),
+            "Program log: hop".to_string(),
+            format!("Program data: {}", swap_event_log(pool_b, 500, 700, false)),
+        ];
+        route.hops = parse_route_hops_from_program_data(&item, &logs);
+
+        assert_eq!(route.hops.len(), 2);
+        assert_eq!(route.hops[0].route_index, 0);
+        assert_eq!(route.hops[0].pool_state, pool_a);
+        assert_eq!(route.hops[0].amounts(), (1_000, 700));
+        assert_eq!(route.hops[1].route_index, 1);
+        assert_eq!(route.hops[1].pool_state, pool_b);
+        assert_eq!(route.hops[1].amounts(), (700, 500));
+        assert_eq!(route.amount_out(), Some(500));
+    }
+}
@@ -2,19 +2,17 @@ use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::{
-    event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::raydium_clmm::{
-            RaydiumClmmAmmConfigAccountEvent, RaydiumClmmPoolStateAccountEvent,
-            RaydiumClmmTickArrayBitmapExtensionAccountEvent, RaydiumClmmTickArrayStateAccountEvent,
-        },
-        DexEvent,
+use crate::streaming::event_parser::{
+    common::{note_account_data_len, AccountPretty, EventMetadata, EventType},
+    protocols::raydium_clmm::{
+        RaydiumClmmAmmConfigAccountEvent, RaydiumClmmPoolStateAccountEvent,
+        RaydiumClmmTickArrayBitmapExtensionAccountEvent, RaydiumClmmTickArrayStateAccountEvent,
     },
-    grpc::AccountPretty,
+    DexEvent,
 };
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct AmmConfig {
     pub bump: u8,
     pub index: u16,
@@ -39,6 +37,7 @@ pub fn amm_config_decode(data: &[u8]) -> Option<AmmConfig> {
 
 pub fn amm_config_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumClmmAmmConfig;
+    note_account_data_len(&metadata.event_type, AMM_CONFIG_SIZE + 8, account.data.len());
 
     if account.data.len() < AMM_CONFIG_SIZE + 8 {
         return None;
@@ -60,6 +59,7 @@ pub fn amm_config_parser(account: AccountPretty, mut metadata: EventMetadata) ->
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RewardInfo {
     pub reward_state: u8,
     pub open_time: u64,
@@ -75,6 +75,7 @@ pub struct RewardInfo {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PoolState {
     pub bump: [u8; 1],
     pub amm_config: Pubkey,
@@ -127,6 +128,7 @@ pub fn pool_state_decode(data: &[u8]) -> Option<PoolState> {
 
 pub fn pool_state_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumClmmPoolState;
+    note_account_data_len(&metadata.event_type, POOL_STATE_SIZE + 8, account.data.len());
 
     if account.data.len() < POOL_STATE_SIZE + 8 {
         return None;
@@ -148,6 +150,7 @@ pub fn pool_state_parser(account: AccountPretty, mut metadata: EventMetadata) ->
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct TickState {
     pub tick: i32,
     pub liquidity_net: i128,
@@ -173,6 +176,7 @@ impl Default for TickState {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct TickArrayState {
     pub pool_id: Pubkey,
     pub start_tick_index: i32,
@@ -211,6 +215,7 @@ pub fn tick_array_state_parser(
     mut metadata: EventMetadata,
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumClmmTickArrayState;
+    note_account_data_len(&metadata.event_type, TICK_ARRAY_STATE_SIZE + 8, account.data.len());
 
     if account.data.len() < TICK_ARRAY_STATE_SIZE + 8 {
         return None;
@@ -240,6 +245,7 @@ pub const EXTENSION_TICKARRAY_BITMAP_SIZE: usize = 14;
 
 #[repr(C, packed)]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct TickArrayBitmapExtension {
     pub pool_id: Pubkey,
     /// Packed initialized tick array state for start_tick_index is positive
@@ -314,6 +320,11 @@ pub fn tick_array_bitmap_extension_parser(
     mut metadata: EventMetadata,
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumClmmTickArrayBitmapExtension;
+    note_account_data_len(
+        &metadata.event_type,
+        TICK_ARRAY_BITMAP_EXTENSION_SIZE + 8,
+        account.data.len(),
+    );
 
     if account.data.len() < TICK_ARRAY_BITMAP_EXTENSION_SIZE + 8 {
         return None;
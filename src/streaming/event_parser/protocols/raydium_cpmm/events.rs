@@ -1,4 +1,5 @@
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+use crate::streaming::event_parser::core::capabilities::EventCapability;
 use crate::streaming::event_parser::protocols::raydium_cpmm::types::AmmConfig;
 use crate::streaming::event_parser::protocols::raydium_cpmm::types::PoolState;
 use borsh::BorshDeserialize;
@@ -7,6 +8,7 @@ use solana_sdk::pubkey::Pubkey;
 
 /// 交易
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumCpmmSwapEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -28,6 +30,11 @@ pub struct RaydiumCpmmSwapEvent {
     pub creator_fee: u64,
     pub creator_fee_on_input: bool,
 
+    /// 权威的 swap 方向：以指令类型 (SwapBaseInput / SwapBaseOutput) 为准，
+    /// 与日志中的 base_input 不一致时会记录 warning
+    #[serde(default)]
+    pub swap_base_input: bool,
+
     // 账户信息
     pub payer: Pubkey,
     pub authority: Pubkey,
@@ -44,8 +51,51 @@ pub struct RaydiumCpmmSwapEvent {
     pub observation_state: Pubkey,
 }
 
+impl RaydiumCpmmSwapEvent {
+    /// 是否已合并日志中的 SwapEvent，手续费和实际成交量只有合并后才有值
+    #[inline]
+    pub fn has_swap_log(&self) -> bool {
+        self.input_amount != 0 || self.output_amount != 0
+    }
+
+    /// 进入曲线计算的输入数量：`input_amount` 扣除 `trade_fee`，
+    /// `creator_fee_on_input` 时再扣除 `creator_fee`
+    ///
+    /// `input_amount` 已不含输入 mint 的 Token-2022 转账手续费。没有日志时返回 None
+    pub fn net_input_amount(&self) -> Option<u64> {
+        if !self.has_swap_log() {
+            return None;
+        }
+        let creator_fee = if self.creator_fee_on_input { self.creator_fee } else { 0 };
+        Some(self.input_amount.saturating_sub(self.trade_fee).saturating_sub(creator_fee))
+    }
+
+    /// 曲线计算得到的输出数量
+    ///
+    /// creator_fee 在输出侧收取时从曲线输出中扣除后才记入 `output_amount`，
+    /// 此时为 `output_amount + creator_fee`，否则等于 `output_amount`。没有日志时返回 None
+    pub fn gross_output_amount(&self) -> Option<u64> {
+        if !self.has_swap_log() {
+            return None;
+        }
+        let creator_fee = if self.creator_fee_on_input { 0 } else { self.creator_fee };
+        Some(self.output_amount.saturating_add(creator_fee))
+    }
+
+    /// 用户实际到账的输出数量：`output_amount`（已扣除输出侧 creator_fee）再扣除输出 mint 的转账手续费
+    ///
+    /// 与用户支付的 `input_amount + input_transfer_fee` 之比即含全部费用的成交价。没有日志时返回 None
+    pub fn net_output_amount(&self) -> Option<u64> {
+        if !self.has_swap_log() {
+            return None;
+        }
+        Some(self.output_amount.saturating_sub(self.output_transfer_fee))
+    }
+}
+
 /// 存款
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumCpmmDepositEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -70,6 +120,7 @@ pub struct RaydiumCpmmDepositEvent {
 
 /// 初始化
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumCpmmInitializeEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -101,6 +152,7 @@ pub struct RaydiumCpmmInitializeEvent {
 
 /// 提款
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumCpmmWithdrawEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -126,6 +178,7 @@ pub struct RaydiumCpmmWithdrawEvent {
 
 /// 池配置
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumCpmmAmmConfigAccountEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -142,6 +195,7 @@ pub struct RaydiumCpmmAmmConfigAccountEvent {
 
 /// 池状态
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct RaydiumCpmmPoolStateAccountEvent {
     #[borsh(skip)]
     pub metadata: EventMetadata,
@@ -156,6 +210,27 @@ pub struct RaydiumCpmmPoolStateAccountEvent {
     pub pool_state: PoolState,
 }
 
+/// Raydium CPMM程序ID
+pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+
+/// Raydium CPMM 解析器支持的 discriminator 与事件类型
+pub const CAPABILITIES: &[EventCapability] = &[
+    EventCapability::instruction(
+        discriminators::SWAP_BASE_IN,
+        &[EventType::RaydiumCpmmSwapBaseInput],
+    ),
+    EventCapability::instruction(
+        discriminators::SWAP_BASE_OUT,
+        &[EventType::RaydiumCpmmSwapBaseOutput],
+    ),
+    EventCapability::instruction(discriminators::DEPOSIT, &[EventType::RaydiumCpmmDeposit]),
+    EventCapability::instruction(discriminators::INITIALIZE, &[EventType::RaydiumCpmmInitialize]),
+    EventCapability::instruction(discriminators::WITHDRAW, &[EventType::RaydiumCpmmWithdraw]),
+    EventCapability::account(discriminators::AMM_CONFIG, &[EventType::AccountRaydiumCpmmAmmConfig]),
+    EventCapability::account(discriminators::POOL_STATE, &[EventType::AccountRaydiumCpmmPoolState]),
+];
+
 /// 事件鉴别器常量
 pub mod discriminators {
     // 指令鉴别器
@@ -176,3 +251,33 @@ pub mod discriminators {
     pub const SWAP_EVENT: &[u8] = &[0x40, 0xc6, 0xcd, 0xe8, 0x26, 0x08, 0x71, 0xe2];
     pub const LP_CHANGE_EVENT: &[u8] = &[0x9a, 0x0b, 0x0a, 0x7c, 0x7e, 0x5f, 0x7f, 0x3c];
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_adjusted_amounts_follow_creator_fee_side() {
+        assert_eq!(RaydiumCpmmSwapEvent::default().net_input_amount(), None);
+
+        let mut event = RaydiumCpmmSwapEvent {
+            input_amount: 1_000,
+            output_amount: 490,
+            output_transfer_fee: 5,
+            trade_fee: 3,
+            creator_fee: 10,
+            creator_fee_on_input: false,
+            ..Default::default()
+        };
+        // creator_fee 在输出侧：只影响曲线输出
+        assert_eq!(event.net_input_amount(), Some(997));
+        assert_eq!(event.gross_output_amount(), Some(500));
+        assert_eq!(event.net_output_amount(), Some(485));
+
+        // creator_fee 在输入侧：从进入曲线的数量中扣除
+        event.creator_fee_on_input = true;
+        assert_eq!(event.net_input_amount(), Some(987));
+        assert_eq!(event.gross_output_amount(), Some(490));
+        assert_eq!(event.net_output_amount(), Some(485));
+    }
+}
@@ -2,16 +2,17 @@ use solana_sdk::pubkey::Pubkey;
 
 use crate::streaming::event_parser::{
     common::{read_u64_le, read_u8, EventMetadata, EventType, ProgramDataItem},
+    core::dispatcher::EventDispatcher,
     protocols::raydium_cpmm::{
         discriminators, RaydiumCpmmDepositEvent, RaydiumCpmmInitializeEvent, RaydiumCpmmSwapEvent,
         RaydiumCpmmWithdrawEvent,
     },
-    DexEvent,
+    DexEvent, Protocol,
 };
 
-/// Raydium CPMM程序ID
-pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+pub use crate::streaming::event_parser::protocols::raydium_cpmm::events::{
+    CAPABILITIES, RAYDIUM_CPMM_PROGRAM_ID,
+};
 
 /// SwapEvent 从 Anchor 事件日志解析出来的数据
 #[derive(Debug, Clone, Default)]
@@ -70,7 +71,7 @@ pub fn parse_raydium_cpmm_inner_instruction_data(
 /// 根据判别器路由到具体的账户解析函数
 pub fn parse_raydium_cpmm_account_data(
     discriminator: &[u8],
-    account: crate::streaming::grpc::AccountPretty,
+    account: crate::streaming::event_parser::common::AccountPretty,
     metadata: crate::streaming::event_parser::common::EventMetadata,
 ) -> Option<crate::streaming::event_parser::DexEvent> {
     match discriminator {
@@ -211,6 +212,7 @@ fn parse_swap_base_input_instruction(
         metadata,
         amount_in,
         minimum_amount_out,
+        swap_base_input: true,
         payer: accounts[0],
         authority: accounts[1],
         amm_config: accounts[2],
@@ -246,6 +248,7 @@ fn parse_swap_base_output_instruction(
         metadata,
         max_amount_in,
         amount_out,
+        swap_base_input: false,
         payer: accounts[0],
         authority: accounts[1],
         amm_config: accounts[2],
@@ -353,7 +356,9 @@ pub fn parse_swap_event_from_program_data(
     item: &ProgramDataItem,
     expected_pool_id: &Pubkey,
 ) -> Option<SwapEventLogData> {
-    if item.program_id != RAYDIUM_CPMM_PROGRAM_ID {
+    if EventDispatcher::match_protocol_by_program_id(&item.program_id)
+        != Some(Protocol::RaydiumCpmm)
+    {
         return None;
     }
     let event_data = parse_swap_event_from_log(&item.base64)?;
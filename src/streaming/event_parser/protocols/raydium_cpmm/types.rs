@@ -2,18 +2,14 @@ use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::{
-    event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::raydium_cpmm::{
-            RaydiumCpmmAmmConfigAccountEvent, RaydiumCpmmPoolStateAccountEvent,
-        },
-        DexEvent,
-    },
-    grpc::AccountPretty,
+use crate::streaming::event_parser::{
+    common::{note_account_data_len, AccountPretty, EventMetadata, EventType},
+    protocols::raydium_cpmm::{RaydiumCpmmAmmConfigAccountEvent, RaydiumCpmmPoolStateAccountEvent},
+    DexEvent,
 };
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct AmmConfig {
     pub bump: u8,
     pub disable_create_pool: bool,
@@ -41,6 +37,7 @@ pub fn amm_config_decode(data: &[u8]) -> Option<AmmConfig> {
 
 pub fn amm_config_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumCpmmAmmConfig;
+    note_account_data_len(&metadata.event_type, AMM_CONFIG_SIZE + 8, account.data.len());
 
     if account.data.len() < AMM_CONFIG_SIZE + 8 {
         return None;
@@ -62,6 +59,7 @@ pub fn amm_config_parser(account: AccountPretty, mut metadata: EventMetadata) ->
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct PoolState {
     pub amm_config: Pubkey,
     pub pool_creator: Pubkey,
@@ -110,6 +108,7 @@ pub fn pool_state_decode(data: &[u8]) -> Option<PoolState> {
 
 pub fn pool_state_parser(account: AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumCpmmPoolState;
+    note_account_data_len(&metadata.event_type, POOL_STATE_SIZE + 8, account.data.len());
 
     if account.data.len() < POOL_STATE_SIZE + 8 {
         return None;
@@ -1,10 +1,10 @@
+use crate::streaming::event_parser::common::ProtocolType;
 use crate::streaming::event_parser::protocols::{
-    bonk::parser::BONK_PROGRAM_ID, meteora_damm_v2::parser::METEORA_DAMM_V2_PROGRAM_ID,
-    meteora_dlmm::parser::METEORA_DLMM_PROGRAM_ID, pancakeswap::parser::PANCAKESWAP_PROGRAM_ID,
-    pumpfun::parser::PUMPFUN_PROGRAM_ID, pumpswap::parser::PUMPSWAP_PROGRAM_ID,
-    raydium_amm_v4::parser::RAYDIUM_AMM_V4_PROGRAM_ID,
-    raydium_clmm::parser::RAYDIUM_CLMM_PROGRAM_ID, raydium_cpmm::parser::RAYDIUM_CPMM_PROGRAM_ID,
-    whirlpool::parser::WHIRLPOOL_PROGRAM_ID,
+    bonk::BONK_PROGRAM_ID, meteora_damm_v2::METEORA_DAMM_V2_PROGRAM_ID,
+    meteora_dlmm::METEORA_DLMM_PROGRAM_ID, pancakeswap::PANCAKESWAP_PROGRAM_ID,
+    pumpfun::PUMPFUN_PROGRAM_ID, pumpswap::PUMPSWAP_PROGRAM_ID,
+    raydium_amm_v4::RAYDIUM_AMM_V4_PROGRAM_ID, raydium_clmm::RAYDIUM_CLMM_PROGRAM_ID,
+    raydium_cpmm::RAYDIUM_CPMM_PROGRAM_ID, whirlpool::WHIRLPOOL_PROGRAM_ID,
 };
 use anyhow::{anyhow, Result};
 use solana_sdk::pubkey::Pubkey;
@@ -13,8 +13,12 @@ use solana_sdk::pubkey::Pubkey;
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Protocol {
     PancakeSwap,
+    /// pump.fun 的 AMM（pump AMM），PumpFun 迁移后的交易
     PumpSwap,
+    /// pump.fun bonding curve
     PumpFun,
+    /// Raydium LaunchLab bonding curve，letsbonk.fun 等 launchpad 平台共用同一程序，
+    /// 没有单独的 RaydiumLaunchlab 协议；平台可通过 `BonkPoolCreateEvent.platform_config` 区分
     Bonk,
     RaydiumCpmm,
     RaydiumClmm,
@@ -24,19 +28,114 @@ pub enum Protocol {
     Whirlpool,
 }
 
+impl From<&Protocol> for ProtocolType {
+    fn from(protocol: &Protocol) -> Self {
+        match protocol {
+            Protocol::PancakeSwap => ProtocolType::PancakeSwap,
+            Protocol::PumpFun => ProtocolType::PumpFun,
+            Protocol::PumpSwap => ProtocolType::PumpSwap,
+            Protocol::Bonk => ProtocolType::Bonk,
+            Protocol::RaydiumCpmm => ProtocolType::RaydiumCpmm,
+            Protocol::RaydiumClmm => ProtocolType::RaydiumClmm,
+            Protocol::RaydiumAmmV4 => ProtocolType::RaydiumAmmV4,
+            Protocol::MeteoraDammV2 => ProtocolType::MeteoraDammV2,
+            Protocol::MeteoraDlmm => ProtocolType::MeteoraDlmm,
+            Protocol::Whirlpool => ProtocolType::Whirlpool,
+        }
+    }
+}
+
+/// 所有支持的协议
+pub const ALL_PROTOCOLS: &[Protocol] = &[
+    Protocol::PancakeSwap,
+    Protocol::PumpSwap,
+    Protocol::PumpFun,
+    Protocol::Bonk,
+    Protocol::RaydiumCpmm,
+    Protocol::RaydiumClmm,
+    Protocol::RaydiumAmmV4,
+    Protocol::MeteoraDammV2,
+    Protocol::MeteoraDlmm,
+    Protocol::Whirlpool,
+];
+
+/// 每个协议及其程序 ID，可用于构建 `TransactionFilter` / `AccountFilter`
+pub const PROTOCOL_PROGRAM_IDS: &[(Protocol, Pubkey)] = &[
+    (Protocol::PancakeSwap, PANCAKESWAP_PROGRAM_ID),
+    (Protocol::PumpSwap, PUMPSWAP_PROGRAM_ID),
+    (Protocol::PumpFun, PUMPFUN_PROGRAM_ID),
+    (Protocol::Bonk, BONK_PROGRAM_ID),
+    (Protocol::RaydiumCpmm, RAYDIUM_CPMM_PROGRAM_ID),
+    (Protocol::RaydiumClmm, RAYDIUM_CLMM_PROGRAM_ID),
+    (Protocol::RaydiumAmmV4, RAYDIUM_AMM_V4_PROGRAM_ID),
+    (Protocol::MeteoraDammV2, METEORA_DAMM_V2_PROGRAM_ID),
+    (Protocol::MeteoraDlmm, METEORA_DLMM_PROGRAM_ID),
+    (Protocol::Whirlpool, WHIRLPOOL_PROGRAM_ID),
+];
+
 impl Protocol {
+    /// 协议的程序 ID
+    pub const fn program_id(&self) -> Pubkey {
+        match self {
+            Protocol::PancakeSwap => PANCAKESWAP_PROGRAM_ID,
+            Protocol::PumpSwap => PUMPSWAP_PROGRAM_ID,
+            Protocol::PumpFun => PUMPFUN_PROGRAM_ID,
+            Protocol::Bonk => BONK_PROGRAM_ID,
+            Protocol::RaydiumCpmm => RAYDIUM_CPMM_PROGRAM_ID,
+            Protocol::RaydiumClmm => RAYDIUM_CLMM_PROGRAM_ID,
+            Protocol::RaydiumAmmV4 => RAYDIUM_AMM_V4_PROGRAM_ID,
+            Protocol::MeteoraDammV2 => METEORA_DAMM_V2_PROGRAM_ID,
+            Protocol::MeteoraDlmm => METEORA_DLMM_PROGRAM_ID,
+            Protocol::Whirlpool => WHIRLPOOL_PROGRAM_ID,
+        }
+    }
+
     pub fn get_program_id(&self) -> Vec<Pubkey> {
+        vec![self.program_id()]
+    }
+
+    /// 该协议的解析器是否在编译时启用（对应 `protocol-*` cargo feature）
+    ///
+    /// 未启用的协议仍可订阅，但其指令、日志和账户数据不会被解析
+    pub const fn is_enabled(&self) -> bool {
+        match self {
+            Protocol::PancakeSwap => cfg!(feature = "protocol-pancakeswap"),
+            Protocol::PumpSwap => cfg!(feature = "protocol-pumpswap"),
+            Protocol::PumpFun => cfg!(feature = "protocol-pumpfun"),
+            Protocol::Bonk => cfg!(feature = "protocol-bonk"),
+            Protocol::RaydiumCpmm => cfg!(feature = "protocol-raydium-cpmm"),
+            Protocol::RaydiumClmm => cfg!(feature = "protocol-raydium-clmm"),
+            Protocol::RaydiumAmmV4 => cfg!(feature = "protocol-raydium-amm-v4"),
+            Protocol::MeteoraDammV2 => cfg!(feature = "protocol-meteora-damm-v2"),
+            Protocol::MeteoraDlmm => cfg!(feature = "protocol-meteora-dlmm"),
+            Protocol::Whirlpool => cfg!(feature = "protocol-whirlpool"),
+        }
+    }
+
+    /// 外层指令 discriminator 长度（Raydium AMM V4 为 1 字节 opcode，其余 Anchor 程序为 8 字节）
+    pub const fn discriminator_len(&self) -> usize {
         match self {
-            Protocol::PancakeSwap => vec![PANCAKESWAP_PROGRAM_ID],
-            Protocol::PumpSwap => vec![PUMPSWAP_PROGRAM_ID],
-            Protocol::PumpFun => vec![PUMPFUN_PROGRAM_ID],
-            Protocol::Bonk => vec![BONK_PROGRAM_ID],
-            Protocol::RaydiumCpmm => vec![RAYDIUM_CPMM_PROGRAM_ID],
-            Protocol::RaydiumClmm => vec![RAYDIUM_CLMM_PROGRAM_ID],
-            Protocol::RaydiumAmmV4 => vec![RAYDIUM_AMM_V4_PROGRAM_ID],
-            Protocol::MeteoraDammV2 => vec![METEORA_DAMM_V2_PROGRAM_ID],
-            Protocol::MeteoraDlmm => vec![METEORA_DLMM_PROGRAM_ID],
-            Protocol::Whirlpool => vec![WHIRLPOOL_PROGRAM_ID],
+            Protocol::RaydiumAmmV4 => 1,
+            _ => 8,
+        }
+    }
+
+    /// inner instruction（emit_cpi 事件）discriminator 长度
+    ///
+    /// emit_cpi 事件由 8 字节 event ix tag + 8 字节事件 discriminator 组成；
+    /// 返回 None 表示该协议不从 inner instruction 解析事件
+    pub const fn inner_discriminator_len(&self) -> Option<usize> {
+        match self {
+            Protocol::PumpFun
+            | Protocol::PumpSwap
+            | Protocol::Bonk
+            | Protocol::MeteoraDammV2
+            | Protocol::MeteoraDlmm => Some(16),
+            Protocol::PancakeSwap
+            | Protocol::RaydiumCpmm
+            | Protocol::RaydiumClmm
+            | Protocol::RaydiumAmmV4
+            | Protocol::Whirlpool => None,
         }
     }
 }
@@ -58,22 +157,55 @@ impl std::fmt::Display for Protocol {
     }
 }
 
+/// 忽略大小写以及 `-` / `_`，`Display` 输出和 `raydium-cpmm` 这类写法都可以解析
 impl std::str::FromStr for Protocol {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        let normalized: String =
+            s.trim().chars().filter(|c| *c != '-' && *c != '_').collect::<String>().to_lowercase();
+        match normalized.as_str() {
             "pancakeswap" => Ok(Protocol::PancakeSwap),
             "pumpswap" => Ok(Protocol::PumpSwap),
             "pumpfun" => Ok(Protocol::PumpFun),
-            "bonk" => Ok(Protocol::Bonk),
+            "bonk" | "raydiumlaunchlab" | "launchlab" => Ok(Protocol::Bonk),
             "raydiumcpmm" => Ok(Protocol::RaydiumCpmm),
             "raydiumclmm" => Ok(Protocol::RaydiumClmm),
             "raydiumammv4" => Ok(Protocol::RaydiumAmmV4),
-            "meteoradamm_v2" => Ok(Protocol::MeteoraDammV2),
+            "meteoradammv2" => Ok(Protocol::MeteoraDammV2),
             "meteoradlmm" => Ok(Protocol::MeteoraDlmm),
             "whirlpool" => Ok(Protocol::Whirlpool),
             _ => Err(anyhow!("Unsupported protocol: {}", s)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_protocol_display_from_str_round_trip() {
+        for protocol in ALL_PROTOCOLS {
+            assert_eq!(&Protocol::from_str(&protocol.to_string()).unwrap(), protocol);
+        }
+        assert_eq!(Protocol::from_str("raydium-cpmm").unwrap(), Protocol::RaydiumCpmm);
+        assert_eq!(Protocol::from_str("meteoradamm_v2").unwrap(), Protocol::MeteoraDammV2);
+        assert_eq!(Protocol::from_str("raydium-launchlab").unwrap(), Protocol::Bonk);
+        assert!(Protocol::from_str("uniswap").is_err());
+    }
+
+    #[test]
+    fn test_program_id_map_covers_all_protocols() {
+        assert_eq!(PROTOCOL_PROGRAM_IDS.len(), ALL_PROTOCOLS.len());
+        for (protocol, (mapped, program_id)) in ALL_PROTOCOLS.iter().zip(PROTOCOL_PROGRAM_IDS) {
+            assert_eq!(protocol, mapped);
+            assert_eq!(protocol.program_id(), *program_id);
+        }
+        assert_eq!(
+            Protocol::PumpFun.program_id().to_string(),
+            "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"
+        );
+    }
+}
@@ -1,10 +1,12 @@
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+use crate::streaming::event_parser::core::capabilities::EventCapability;
 use crate::streaming::event_parser::protocols::whirlpool::types::{Whirlpool, WhirlpoolTickArray};
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
 /// Whirlpool Swap 事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WhirlpoolSwapEvent {
     pub metadata: EventMetadata,
 
@@ -25,6 +27,11 @@ pub struct WhirlpoolSwapEvent {
     pub lp_fee: u64,
     pub protocol_fee: u64,
 
+    // 池子费率，来自已见过的 Whirlpool 账户或 PoolFeeRegistry 预置，未知时为 None
+    /// 单位为百分之一个基点（3000 = 0.3%）
+    pub fee_rate: Option<u16>,
+    pub tick_spacing: Option<u16>,
+
     // 指令账户
     pub token_program: Pubkey,
     pub token_authority: Pubkey,
@@ -42,6 +49,7 @@ pub struct WhirlpoolSwapEvent {
 
 /// Whirlpool SwapV2 事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WhirlpoolSwapV2Event {
     pub metadata: EventMetadata,
 
@@ -62,6 +70,11 @@ pub struct WhirlpoolSwapV2Event {
     pub lp_fee: u64,
     pub protocol_fee: u64,
 
+    // 池子费率，来自已见过的 Whirlpool 账户或 PoolFeeRegistry 预置，未知时为 None
+    /// 单位为百分之一个基点（3000 = 0.3%）
+    pub fee_rate: Option<u16>,
+    pub tick_spacing: Option<u16>,
+
     // 指令账户
     pub token_program_a: Pubkey,
     pub token_program_b: Pubkey,
@@ -81,8 +94,23 @@ pub struct WhirlpoolSwapV2Event {
     pub remaining_accounts: Vec<Pubkey>,
 }
 
+impl WhirlpoolSwapEvent {
+    /// 池子费率（基点），未知时为 None
+    pub fn fee_rate_bps(&self) -> Option<f64> {
+        self.fee_rate.map(|fee_rate| fee_rate as f64 / 100.0)
+    }
+}
+
+impl WhirlpoolSwapV2Event {
+    /// 池子费率（基点），未知时为 None
+    pub fn fee_rate_bps(&self) -> Option<f64> {
+        self.fee_rate.map(|fee_rate| fee_rate as f64 / 100.0)
+    }
+}
+
 /// Whirlpool 账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WhirlpoolAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -97,6 +125,7 @@ pub struct WhirlpoolAccountEvent {
 
 /// Whirlpool TickArray 账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WhirlpoolTickArrayAccountEvent {
     pub metadata: EventMetadata,
     pub pubkey: Pubkey,
@@ -109,6 +138,18 @@ pub struct WhirlpoolTickArrayAccountEvent {
     pub tick_array: WhirlpoolTickArray,
 }
 
+/// Whirlpool 程序ID
+pub const WHIRLPOOL_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+/// Whirlpool 解析器支持的 discriminator 与事件类型
+pub const CAPABILITIES: &[EventCapability] = &[
+    EventCapability::instruction(discriminators::SWAP, &[EventType::WhirlpoolSwap]),
+    EventCapability::instruction(discriminators::SWAP_V2, &[EventType::WhirlpoolSwapV2]),
+    EventCapability::account(discriminators::WHIRLPOOL, &[EventType::AccountWhirlpool]),
+    EventCapability::account(discriminators::TICK_ARRAY, &[EventType::AccountWhirlpoolTickArray]),
+];
+
 /// 事件鉴别器常量
 pub mod discriminators {
     // 指令鉴别器
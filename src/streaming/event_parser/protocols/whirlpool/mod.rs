@@ -1,4 +1,5 @@
 pub mod events;
+#[cfg(feature = "protocol-whirlpool")]
 pub mod parser;
 pub mod types;
 
@@ -1,13 +1,14 @@
 use crate::streaming::event_parser::{
     common::{read_u128_le, read_u64_le, read_u8_le, EventMetadata, EventType, ProgramDataItem},
+    core::dispatcher::EventDispatcher,
     protocols::whirlpool::{discriminators, WhirlpoolSwapEvent, WhirlpoolSwapV2Event},
-    DexEvent,
+    DexEvent, Protocol,
 };
 use solana_sdk::pubkey::Pubkey;
 
-/// Whirlpool 程序ID
-pub const WHIRLPOOL_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+pub use crate::streaming::event_parser::protocols::whirlpool::events::{
+    CAPABILITIES, WHIRLPOOL_PROGRAM_ID,
+};
 
 /// Traded 事件日志解析后的数据
 #[derive(Clone, Debug, Default)]
@@ -58,7 +59,7 @@ pub fn parse_whirlpool_inner_instruction_data(
 /// 根据判别器路由到具体的账户解析函数
 pub fn parse_whirlpool_account_data(
     discriminator: &[u8],
-    account: crate::streaming::grpc::AccountPretty,
+    account: crate::streaming::event_parser::common::AccountPretty,
     metadata: crate::streaming::event_parser::common::EventMetadata,
 ) -> Option<crate::streaming::event_parser::DexEvent> {
     match discriminator {
@@ -203,7 +204,8 @@ pub fn parse_traded_event_from_program_data(
     item: &ProgramDataItem,
     expected_whirlpool: &Pubkey,
 ) -> Option<TradedEventLogData> {
-    if item.program_id != WHIRLPOOL_PROGRAM_ID {
+    if EventDispatcher::match_protocol_by_program_id(&item.program_id) != Some(Protocol::Whirlpool)
+    {
         return None;
     }
     let event_data = parse_traded_event_from_log(&item.base64)?;
@@ -2,13 +2,12 @@ use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::streaming::{
-    event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::whirlpool::{WhirlpoolAccountEvent, WhirlpoolTickArrayAccountEvent},
-        DexEvent,
+use crate::streaming::event_parser::{
+    common::{
+        decode_field, note_account_data_len, AccountPretty, DecodeError, EventMetadata, EventType,
     },
-    grpc::AccountPretty,
+    protocols::whirlpool::{WhirlpoolAccountEvent, WhirlpoolTickArrayAccountEvent},
+    DexEvent,
 };
 
 // Number of rewards supported by Whirlpools
@@ -20,6 +19,7 @@ pub const WHIRLPOOL_TICK_ARRAY_SIZE: usize =
     32 + 4 + (WHIRLPOOL_TICK_ARRAY_LEN * WHIRLPOOL_TICK_SIZE);
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct WhirlpoolRewardInfo {
     /// Reward token mint.
     pub mint: Pubkey,
@@ -35,6 +35,7 @@ pub struct WhirlpoolRewardInfo {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Whirlpool {
     pub whirlpools_config: Pubkey,
     pub whirlpool_bump: [u8; 1],
@@ -58,6 +59,7 @@ pub struct Whirlpool {
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct WhirlpoolTick {
     pub initialized: bool,
     pub liquidity_net: i128,
@@ -68,6 +70,7 @@ pub struct WhirlpoolTick {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[serde(default)]
 pub struct WhirlpoolTickArray {
     pub start_tick_index: i32,
     #[serde(with = "serde_big_array::BigArray")]
@@ -281,6 +284,7 @@ pub fn whirlpool_parser(account: AccountPretty, mut metadata: EventMetadata) ->
 
     // 账户总大小应该是 8 (discriminator) + 645 (数据) = 653 字节
     let expected_size = 8 + WHIRLPOOL_SIZE;
+    note_account_data_len(&metadata.event_type, expected_size, account.data.len());
     if account.data.len() < expected_size {
         log::warn!(
             "Whirlpool 账户数据长度不足: 需要至少 {} 字节，实际 {} 字节",
@@ -319,11 +323,37 @@ pub fn whirlpool_parser(account: AccountPretty, mut metadata: EventMetadata) ->
     }
 }
 
+impl WhirlpoolTickArray {
+    /// 解码 TickArray 账户数据（不含 8 字节 discriminator），失败时返回出错的字段和偏移
+    pub fn try_decode(data: &[u8]) -> Result<Self, DecodeError> {
+        DecodeError::ensure_len(data, WHIRLPOOL_TICK_ARRAY_SIZE)?;
+
+        let start_tick_index = decode_field::<i32>(data, 0, "start_tick_index")?;
+        let mut ticks: [WhirlpoolTick; WHIRLPOOL_TICK_ARRAY_LEN] =
+            core::array::from_fn(|_| WhirlpoolTick::default());
+        for (i, tick) in ticks.iter_mut().enumerate() {
+            let offset = 4 + i * WHIRLPOOL_TICK_SIZE;
+            *tick = WhirlpoolTick::deserialize(&mut &data[offset..])
+                .map_err(|e| DecodeError::invalid_field(format!("ticks[{i}]"), offset, e))?;
+        }
+        let whirlpool = decode_field::<Pubkey>(
+            data,
+            4 + WHIRLPOOL_TICK_ARRAY_LEN * WHIRLPOOL_TICK_SIZE,
+            "whirlpool",
+        )?;
+
+        Ok(Self { start_tick_index, ticks, whirlpool })
+    }
+}
+
 pub fn whirlpool_tick_array_decode(data: &[u8]) -> Option<WhirlpoolTickArray> {
-    if data.len() < WHIRLPOOL_TICK_ARRAY_SIZE {
-        return None;
+    match WhirlpoolTickArray::try_decode(data) {
+        Ok(tick_array) => Some(tick_array),
+        Err(e) => {
+            log::debug!("Failed to decode Whirlpool TickArray: {}", e);
+            None
+        }
     }
-    borsh::from_slice::<WhirlpoolTickArray>(&data[..WHIRLPOOL_TICK_ARRAY_SIZE]).ok()
 }
 
 pub fn whirlpool_tick_array_parser(
@@ -333,9 +363,10 @@ pub fn whirlpool_tick_array_parser(
     metadata.event_type = EventType::AccountWhirlpoolTickArray;
 
     let expected_size = 8 + WHIRLPOOL_TICK_ARRAY_SIZE;
+    note_account_data_len(&metadata.event_type, expected_size, account.data.len());
     if account.data.len() < expected_size {
         log::warn!(
-            "Whirlpool TickArray 账户数据长度不足: 需要至少 {} 字节，实际 {} 字节",
+            "Whirlpool TickArray account data too short: expected at least {} bytes, got {}",
             expected_size,
             account.data.len()
         );
@@ -357,10 +388,67 @@ pub fn whirlpool_tick_array_parser(
         }))
     } else {
         log::warn!(
-            "Whirlpool TickArray 账户数据解析失败: pubkey={}, 数据长度={}",
+            "Failed to parse Whirlpool TickArray account: pubkey={}, data_len={}",
             account.pubkey,
             account.data.len()
         );
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_tick_array(start_tick_index: i32, whirlpool: &Pubkey) -> Vec<u8> {
+        let mut data = Vec::with_capacity(WHIRLPOOL_TICK_ARRAY_SIZE);
+        data.extend_from_slice(&start_tick_index.to_le_bytes());
+        for i in 0..WHIRLPOOL_TICK_ARRAY_LEN {
+            let mut tick = vec![0u8; WHIRLPOOL_TICK_SIZE];
+            tick[0] = (i % 2) as u8;
+            tick[1..17].copy_from_slice(&(i as i128 - 44).to_le_bytes());
+            data.extend_from_slice(&tick);
+        }
+        data.extend_from_slice(whirlpool.as_ref());
+        data
+    }
+
+    #[test]
+    fn test_tick_array_try_decode_bounds() {
+        let whirlpool = Pubkey::new_unique();
+        let data = encode_tick_array(-5632, &whirlpool);
+        assert_eq!(data.len(), WHIRLPOOL_TICK_ARRAY_SIZE);
+
+        let tick_array = WhirlpoolTickArray::try_decode(&data).expect("exact size");
+        assert_eq!(tick_array.start_tick_index, -5632);
+        assert_eq!(tick_array.whirlpool, whirlpool);
+        assert!(tick_array.ticks[87].initialized);
+        assert_eq!(tick_array.ticks[87].liquidity_net, 43);
+
+        // 尾部多余数据被忽略
+        let mut padded = data.clone();
+        padded.extend_from_slice(&[0u8; 8]);
+        assert_eq!(WhirlpoolTickArray::try_decode(&padded), Ok(tick_array));
+
+        assert_eq!(
+            WhirlpoolTickArray::try_decode(&data[..WHIRLPOOL_TICK_ARRAY_SIZE - 1]),
+            Err(DecodeError::TooShort {
+                expected: WHIRLPOOL_TICK_ARRAY_SIZE,
+                actual: WHIRLPOOL_TICK_ARRAY_SIZE - 1
+            })
+        );
+        assert!(whirlpool_tick_array_decode(&[]).is_none());
+
+        // initialized 不是合法的 bool
+        let mut corrupted = data;
+        let offset = 4 + 3 * WHIRLPOOL_TICK_SIZE;
+        corrupted[offset] = 2;
+        match WhirlpoolTickArray::try_decode(&corrupted) {
+            Err(DecodeError::InvalidField { field, offset: at, .. }) => {
+                assert_eq!(field, "ticks[3]");
+                assert_eq!(at, offset);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use super::types::{AccountPretty, BlockMetaPretty, TransactionPretty};
+use crate::streaming::common::PoolConfig;
 use crate::streaming::event_parser::common::high_performance_clock::get_high_perf_clock;
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
 use std::collections::VecDeque;
 use std::ops::DerefMut;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use yellowstone_grpc_proto::{
     geyser::{SubscribeUpdateAccount, SubscribeUpdateBlockMeta, SubscribeUpdateTransaction},
     prost_types::Timestamp,
@@ -32,7 +35,7 @@ impl<T> PooledObject<T> {
 impl<T> Drop for PooledObject<T> {
     fn drop(&mut self) {
         if let Some(obj) = self.object.take() {
-            let mut pool = self.pool.lock().unwrap();
+            let mut pool = self.pool.lock();
             if pool.len() < self.max_size {
                 pool.push_back(obj);
             }
@@ -74,7 +77,7 @@ impl AccountPrettyPool {
     }
 
     pub fn acquire(&self) -> PooledAccountPretty {
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.pool.lock();
         let account = match pool.pop_front() {
             Some(reused) => reused,
             None => Box::new(AccountPretty::default()),
@@ -121,7 +124,7 @@ impl PooledAccountPretty {
 
 impl Drop for PooledAccountPretty {
     fn drop(&mut self) {
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.pool.lock();
         if pool.len() < self.max_size {
             // 清理敏感数据
             self.account.data.clear();
@@ -166,7 +169,7 @@ impl BlockMetaPrettyPool {
     }
 
     pub fn acquire(&self) -> PooledBlockMetaPretty {
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.pool.lock();
         let block_meta = match pool.pop_front() {
             Some(reused) => reused,
             None => Box::new(BlockMetaPretty::default()),
@@ -193,17 +196,24 @@ impl PooledBlockMetaPretty {
         self.block_meta.slot = block_update.slot;
         self.block_meta.block_hash = block_update.blockhash;
         self.block_meta.block_time = block_time;
+        self.block_meta.parent_slot = block_update.parent_slot;
+        self.block_meta.parent_block_hash = block_update.parent_blockhash;
+        self.block_meta.block_height = block_update.block_height.map(|h| h.block_height);
+        self.block_meta.executed_transaction_count = block_update.executed_transaction_count;
+        self.block_meta.entries_count = block_update.entries_count;
         self.block_meta.recv_us = get_high_perf_clock();
     }
 }
 
 impl Drop for PooledBlockMetaPretty {
     fn drop(&mut self) {
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.pool.lock();
         if pool.len() < self.max_size {
             // 清理数据
             self.block_meta.block_hash.clear();
+            self.block_meta.parent_block_hash.clear();
             self.block_meta.block_time = None;
+            self.block_meta.block_height = None;
             pool.push_back(std::mem::take(&mut self.block_meta));
         }
     }
@@ -242,7 +252,7 @@ impl TransactionPrettyPool {
     }
 
     pub fn acquire(&self) -> PooledTransactionPretty {
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.pool.lock();
         let transaction = match pool.pop_front() {
             Some(reused) => reused,
             None => Box::new(TransactionPretty::default()),
@@ -274,6 +284,8 @@ impl PooledTransactionPretty {
 
         self.transaction.slot = tx_update.slot;
         self.transaction.transaction_index = Some(tx.index);
+        self.transaction.grpc_created_at_us =
+            block_time.as_ref().map(|ts| ts.seconds * 1_000_000 + ts.nanos as i64 / 1_000);
         self.transaction.block_time = block_time;
         self.transaction.block_hash.clear(); // 重置 block_hash
         self.transaction.signature =
@@ -286,11 +298,12 @@ impl PooledTransactionPretty {
 
 impl Drop for PooledTransactionPretty {
     fn drop(&mut self) {
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.pool.lock();
         if pool.len() < self.max_size {
             // 清理数据
             self.transaction.block_hash.clear();
             self.transaction.block_time = None;
+            self.transaction.grpc_created_at_us = None;
             self.transaction.signature = Signature::default();
             pool.push_back(std::mem::take(&mut self.transaction));
         }
@@ -320,10 +333,24 @@ pub struct EventPrettyPool {
 
 impl EventPrettyPool {
     pub fn new() -> Self {
+        Self::with_config(&PoolConfig::default())
+    }
+
+    /// 按配置创建对象池
+    pub fn with_config(config: &PoolConfig) -> Self {
         Self {
-            account_pool: AccountPrettyPool::new(10000, 20000),
-            block_pool: BlockMetaPrettyPool::new(500, 1000),
-            transaction_pool: TransactionPrettyPool::new(10000, 20000),
+            account_pool: AccountPrettyPool::new(
+                config.account_initial_size,
+                config.account_max_size,
+            ),
+            block_pool: BlockMetaPrettyPool::new(
+                config.block_meta_initial_size,
+                config.block_meta_max_size,
+            ),
+            transaction_pool: TransactionPrettyPool::new(
+                config.transaction_initial_size,
+                config.transaction_max_size,
+            ),
         }
     }
 
@@ -349,8 +376,14 @@ pub struct PoolManager {
 }
 
 impl PoolManager {
+    /// 使用 `init_pool_config` 设置的配置创建，未设置时使用默认大小
     pub fn new() -> Self {
-        Self { event_pool: EventPrettyPool::new() }
+        let config = POOL_CONFIG.get().cloned().unwrap_or_default();
+        Self::with_config(&config)
+    }
+
+    pub fn with_config(config: &PoolConfig) -> Self {
+        Self { event_pool: EventPrettyPool::with_config(config) }
     }
 
     pub fn get_event_pool(&self) -> &EventPrettyPool {
@@ -402,6 +435,17 @@ impl EventPrettyPool {
     }
 }
 
+// 全局池配置，需要在首次访问 GLOBAL_POOL_MANAGER 之前设置
+static POOL_CONFIG: OnceCell<PoolConfig> = OnceCell::new();
+
+/// 设置全局对象池大小
+///
+/// 仅第一次调用生效；返回 false 表示配置已被设置过，本次调用被忽略。
+/// 全局池在首次使用时按此配置创建，之后再修改不会生效。
+pub fn init_pool_config(config: PoolConfig) -> bool {
+    POOL_CONFIG.set(config).is_ok()
+}
+
 // 全局池管理器实例
 lazy_static::lazy_static! {
     pub static ref GLOBAL_POOL_MANAGER: PoolManager = PoolManager::new();
@@ -432,3 +476,28 @@ pub mod factory {
         GLOBAL_POOL_MANAGER.get_event_pool().create_transaction_event_optimized(update, block_time)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_while_holding_pool_lock_does_not_break_later_acquires() {
+        let pool = AccountPrettyPool::new(0, 4);
+
+        let shared = Arc::clone(&pool.pool);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            let _guard = shared.lock();
+            panic!("simulated panic while holding the pool lock");
+        }));
+        assert!(result.is_err());
+
+        let mut account = pool.acquire();
+        account.slot = 7;
+        drop(account);
+
+        let reused = pool.acquire();
+        assert_eq!(reused.slot, 7);
+        assert!(pool.pool.lock().is_empty());
+    }
+}
@@ -5,10 +5,12 @@ use tonic::{transport::channel::ClientTlsConfig, Status};
 use yellowstone_grpc_client::{GeyserGrpcClient, Interceptor};
 use yellowstone_grpc_proto::geyser::{
     CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts,
-    SubscribeRequestFilterBlocksMeta, SubscribeRequestFilterTransactions, SubscribeUpdate,
+    SubscribeRequestFilterBlocksMeta, SubscribeRequestFilterSlots,
+    SubscribeRequestFilterTransactions, SubscribeUpdate,
 };
 
 use super::types::AccountsFilterMap;
+use super::types::SlotsFilterMap;
 use super::types::TransactionsFilterMap;
 use crate::common::AnyResult;
 use crate::streaming::common::StreamClientConfig as ClientConfig;
@@ -16,6 +18,129 @@ use crate::streaming::event_parser::common::filter::EventTypeFilter;
 use crate::streaming::yellowstone_grpc::AccountFilter;
 use crate::streaming::yellowstone_grpc::TransactionFilter;
 
+/// 增量交易过滤器条目名
+pub const DYNAMIC_TRANSACTION_FILTER: &str = "transaction_dynamic";
+/// 增量账户过滤器条目名（按账户地址）
+pub const DYNAMIC_ACCOUNT_FILTER: &str = "account_dynamic";
+/// 增量账户过滤器条目名（按 owner 程序）
+pub const DYNAMIC_OWNER_FILTER: &str = "account_dynamic_owner";
+
+/// 通过 `add_accounts` / `add_programs` 增量维护的订阅项
+///
+/// 以独立的过滤器条目下发，不修改 `update_subscription` 设置的过滤器：
+/// Yellowstone 同一账户过滤器内 account 与 owner 为 AND 关系，合并进已有条目会改变其语义。
+/// 空列表的条目会匹配全部数据，因此列表为空时移除对应条目。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DynamicSubscription {
+    /// 当前订阅是否包含交易，决定增量项是否下发到交易过滤器
+    pub include_transactions: bool,
+    /// 当前订阅是否包含账户更新，决定增量项是否下发到账户过滤器
+    pub include_accounts: bool,
+    /// 交易 account_include，同时订阅这些账户的更新
+    pub accounts: Vec<String>,
+    /// 交易 account_include，同时订阅这些程序拥有的账户的更新
+    pub programs: Vec<String>,
+}
+
+impl DynamicSubscription {
+    pub fn new(include_transactions: bool, include_accounts: bool) -> Self {
+        Self { include_transactions, include_accounts, ..Default::default() }
+    }
+
+    /// 添加账户，返回是否有变化
+    pub fn add_accounts(&mut self, accounts: Vec<String>) -> bool {
+        Self::add_unique(&mut self.accounts, accounts)
+    }
+
+    /// 移除账户，返回是否有变化
+    pub fn remove_accounts(&mut self, accounts: &[String]) -> bool {
+        Self::remove_all(&mut self.accounts, accounts)
+    }
+
+    /// 添加程序，返回是否有变化
+    pub fn add_programs(&mut self, programs: Vec<String>) -> bool {
+        Self::add_unique(&mut self.programs, programs)
+    }
+
+    /// 移除程序，返回是否有变化
+    pub fn remove_programs(&mut self, programs: &[String]) -> bool {
+        Self::remove_all(&mut self.programs, programs)
+    }
+
+    /// 已有交易过滤器的 `failed` 设置：没有条目时只订阅成功的交易，各条目不一致时两者都订阅
+    fn existing_failed(request: &SubscribeRequest) -> Option<bool> {
+        let mut failed = request.transactions.values().map(|filter| filter.failed);
+        let Some(first) = failed.next() else {
+            return Some(false);
+        };
+        if failed.all(|f| f == first) {
+            first
+        } else {
+            None
+        }
+    }
+
+    /// 用当前的增量项替换请求中的增量过滤器条目，其余条目保持不变
+    pub fn apply(&self, request: &mut SubscribeRequest) {
+        request.transactions.remove(DYNAMIC_TRANSACTION_FILTER);
+        request.accounts.remove(DYNAMIC_ACCOUNT_FILTER);
+        request.accounts.remove(DYNAMIC_OWNER_FILTER);
+
+        if self.include_transactions && !(self.accounts.is_empty() && self.programs.is_empty()) {
+            let failed = Self::existing_failed(request);
+            request.transactions.insert(
+                DYNAMIC_TRANSACTION_FILTER.to_string(),
+                SubscribeRequestFilterTransactions {
+                    vote: Some(false),
+                    failed,
+                    signature: None,
+                    account_include: self.accounts.iter().chain(&self.programs).cloned().collect(),
+                    account_exclude: vec![],
+                    account_required: vec![],
+                },
+            );
+        }
+        if self.include_accounts && !self.accounts.is_empty() {
+            request.accounts.insert(
+                DYNAMIC_ACCOUNT_FILTER.to_string(),
+                SubscribeRequestFilterAccounts {
+                    account: self.accounts.clone(),
+                    owner: vec![],
+                    filters: vec![],
+                    nonempty_txn_signature: None,
+                },
+            );
+        }
+        if self.include_accounts && !self.programs.is_empty() {
+            request.accounts.insert(
+                DYNAMIC_OWNER_FILTER.to_string(),
+                SubscribeRequestFilterAccounts {
+                    account: vec![],
+                    owner: self.programs.clone(),
+                    filters: vec![],
+                    nonempty_txn_signature: None,
+                },
+            );
+        }
+    }
+
+    fn add_unique(list: &mut Vec<String>, items: Vec<String>) -> bool {
+        let len = list.len();
+        for item in items {
+            if !list.contains(&item) {
+                list.push(item);
+            }
+        }
+        list.len() != len
+    }
+
+    fn remove_all(list: &mut Vec<String>, items: &[String]) -> bool {
+        let len = list.len();
+        list.retain(|item| !items.contains(item));
+        list.len() != len
+    }
+}
+
 /// Subscription manager
 #[derive(Clone)]
 pub struct SubscriptionManager {
@@ -42,10 +167,14 @@ impl SubscriptionManager {
     }
 
     /// Create subscription request and return stream
+    ///
+    /// `slots` 为 `get_slot_filter` 生成的 slot 状态订阅，供 `SlotReorderBuffer` 根据 rooted / dead
+    /// 判定分叉；不需要时传 None。
     pub async fn subscribe_with_request(
         &self,
         transactions: Option<TransactionsFilterMap>,
         accounts: Option<AccountsFilterMap>,
+        slots: Option<SlotsFilterMap>,
         commitment: Option<CommitmentLevel>,
         event_type_filter: Option<&EventTypeFilter>,
     ) -> AnyResult<(
@@ -65,6 +194,7 @@ impl SubscriptionManager {
             accounts: accounts.unwrap_or_default(),
             transactions: transactions.unwrap_or_default(),
             blocks_meta,
+            slots: slots.unwrap_or_default(),
             commitment: if let Some(commitment) = commitment {
                 Some(commitment as i32)
             } else {
@@ -77,6 +207,26 @@ impl SubscriptionManager {
         Ok((sink, stream, subscribe_request))
     }
 
+    /// Create an account-only subscription (no transactions or block meta) and return stream
+    pub async fn subscribe_accounts_with_request(
+        &self,
+        accounts: AccountsFilterMap,
+        commitment: Option<CommitmentLevel>,
+    ) -> AnyResult<(
+        impl Sink<SubscribeRequest, Error = mpsc::SendError>,
+        impl Stream<Item = Result<SubscribeUpdate, Status>>,
+        SubscribeRequest,
+    )> {
+        let subscribe_request = SubscribeRequest {
+            accounts,
+            commitment: Some(commitment.unwrap_or(CommitmentLevel::Processed) as i32),
+            ..Default::default()
+        };
+        let mut client = self.connect().await?;
+        let (sink, stream) = client.subscribe_with_request(Some(subscribe_request.clone())).await?;
+        Ok((sink, stream, subscribe_request))
+    }
+
     /// Create account subscription request and return stream
     pub fn subscribe_with_account_request(
         &self,
@@ -119,7 +269,7 @@ impl SubscriptionManager {
                 format!("transaction_{}", index),
                 SubscribeRequestFilterTransactions {
                     vote: Some(false),
-                    failed: Some(false),
+                    failed: tf.failed,
                     signature: None,
                     account_include: tf.account_include.clone(),
                     account_exclude: tf.account_exclude.clone(),
@@ -130,8 +280,218 @@ impl SubscriptionManager {
         Some(transactions)
     }
 
+    /// 订阅全部 slot 状态更新（不按 commitment 过滤）的过滤器
+    ///
+    /// rooted 状态总会推送；dead 等 slot 之间的状态只在开启 `slot_interslot_updates` 时推送
+    pub fn get_slot_filter(&self) -> SlotsFilterMap {
+        hashmap! {
+            "".to_owned() => SubscribeRequestFilterSlots {
+                filter_by_commitment: Some(false),
+                interslot_updates: Some(self.config.slot_interslot_updates),
+            }
+        }
+    }
+
     /// Get configuration
     pub fn get_config(&self) -> &ClientConfig {
         &self.config
     }
 }
+
+/// 把包含交易与账户过滤器的订阅请求拆成 (交易请求, 账户请求)
+///
+/// Yellowstone 的 commitment 作用于整个 `SubscribeRequest`，账户需要与交易不同的 commitment 时
+/// 只能建立一条独立的账户流；账户请求只包含账户过滤器并使用 `account_commitment`。
+pub fn split_account_request(
+    mut request: SubscribeRequest,
+    account_commitment: CommitmentLevel,
+) -> (SubscribeRequest, SubscribeRequest) {
+    let account_request = SubscribeRequest {
+        accounts: std::mem::take(&mut request.accounts),
+        commitment: Some(account_commitment as i32),
+        ..Default::default()
+    };
+    (request, account_request)
+}
+
+/// 订阅请求中生效的过滤条件汇总，用于确认动态更新后服务端实际过滤的内容
+///
+/// 各过滤器条目合并后去重排序；条目之间是 OR 关系，需要区分条目时直接查看 `SubscribeRequest`。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubscriptionFilterSummary {
+    /// 交易（及未单独设置时账户）的 commitment
+    pub commitment: Option<CommitmentLevel>,
+    /// 账户更新的 commitment，未订阅账户时为 None
+    pub account_commitment: Option<CommitmentLevel>,
+    pub transaction_account_include: Vec<String>,
+    pub transaction_account_exclude: Vec<String>,
+    pub transaction_account_required: Vec<String>,
+    /// 各交易过滤器条目的 `failed` 设置（None 表示失败交易与成功交易都推送）
+    pub transaction_failed: Vec<Option<bool>>,
+    /// 各交易过滤器条目的 `vote` 设置（None 表示投票交易与普通交易都推送）
+    pub transaction_vote: Vec<Option<bool>>,
+    /// 按地址订阅的账户
+    pub accounts: Vec<String>,
+    /// 按 owner 订阅的程序
+    pub account_owners: Vec<String>,
+    pub blocks_meta: bool,
+}
+
+impl SubscriptionFilterSummary {
+    pub fn from_request(request: &SubscribeRequest) -> Self {
+        fn collect<'a, T: 'a>(
+            filters: impl Iterator<Item = &'a T>,
+            field: impl Fn(&'a T) -> &'a Vec<String>,
+        ) -> Vec<String> {
+            let mut values: Vec<String> = filters.flat_map(|f| field(f).iter().cloned()).collect();
+            values.sort_unstable();
+            values.dedup();
+            values
+        }
+
+        fn collect_flag(filters: impl Iterator<Item = Option<bool>>) -> Vec<Option<bool>> {
+            let mut values: Vec<Option<bool>> = filters.collect();
+            values.sort_unstable();
+            values.dedup();
+            values
+        }
+
+        let commitment = request.commitment.and_then(|level| CommitmentLevel::try_from(level).ok());
+        Self {
+            commitment,
+            account_commitment: (!request.accounts.is_empty()).then_some(commitment).flatten(),
+            transaction_account_include: collect(request.transactions.values(), |f| {
+                &f.account_include
+            }),
+            transaction_account_exclude: collect(request.transactions.values(), |f| {
+                &f.account_exclude
+            }),
+            transaction_account_required: collect(request.transactions.values(), |f| {
+                &f.account_required
+            }),
+            transaction_failed: collect_flag(request.transactions.values().map(|f| f.failed)),
+            transaction_vote: collect_flag(request.transactions.values().map(|f| f.vote)),
+            accounts: collect(request.accounts.values(), |f| &f.account),
+            account_owners: collect(request.accounts.values(), |f| &f.owner),
+            blocks_meta: !request.blocks_meta.is_empty(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_subscription_keeps_existing_filters() {
+        let mut request = SubscribeRequest::default();
+        request
+            .transactions
+            .insert("transaction_0".to_string(), SubscribeRequestFilterTransactions::default());
+
+        let mut dynamic = DynamicSubscription::new(true, false);
+        assert!(dynamic.add_accounts(vec!["pool".to_string(), "pool".to_string()]));
+        assert!(!dynamic.add_accounts(vec!["pool".to_string()]));
+        assert!(dynamic.add_programs(vec!["program".to_string()]));
+        dynamic.apply(&mut request);
+        assert_eq!(request.transactions.len(), 2);
+        assert_eq!(
+            request.transactions[DYNAMIC_TRANSACTION_FILTER].account_include,
+            vec!["pool".to_string(), "program".to_string()]
+        );
+        // 沿用已有过滤器的 failed 设置
+        assert_eq!(request.transactions[DYNAMIC_TRANSACTION_FILTER].failed, None);
+        request.transactions.get_mut("transaction_0").unwrap().failed = Some(true);
+        dynamic.apply(&mut request);
+        assert_eq!(request.transactions[DYNAMIC_TRANSACTION_FILTER].failed, Some(true));
+        request.transactions.insert(
+            "transaction_1".to_string(),
+            SubscribeRequestFilterTransactions { failed: Some(false), ..Default::default() },
+        );
+        dynamic.apply(&mut request);
+        assert_eq!(request.transactions[DYNAMIC_TRANSACTION_FILTER].failed, None);
+        request.transactions.remove("transaction_1");
+        // 未订阅账户更新时不下发账户过滤器
+        assert!(request.accounts.is_empty());
+
+        assert!(dynamic.remove_accounts(&["pool".to_string()]));
+        assert!(dynamic.remove_programs(&["program".to_string()]));
+        dynamic.apply(&mut request);
+        assert_eq!(request.transactions.keys().collect::<Vec<_>>(), vec!["transaction_0"]);
+    }
+
+    #[test]
+    fn test_split_account_request() {
+        let mut request = SubscribeRequest {
+            commitment: Some(CommitmentLevel::Processed as i32),
+            ..Default::default()
+        };
+        request
+            .transactions
+            .insert("transaction_0".to_string(), SubscribeRequestFilterTransactions::default());
+        request.accounts.insert("account_0".to_string(), SubscribeRequestFilterAccounts::default());
+
+        let (tx_request, account_request) =
+            split_account_request(request, CommitmentLevel::Confirmed);
+        assert_eq!(tx_request.transactions.len(), 1);
+        assert!(tx_request.accounts.is_empty());
+        assert_eq!(tx_request.commitment, Some(CommitmentLevel::Processed as i32));
+        assert!(account_request.transactions.is_empty());
+        assert_eq!(account_request.accounts.len(), 1);
+        assert_eq!(account_request.commitment, Some(CommitmentLevel::Confirmed as i32));
+    }
+
+    #[test]
+    fn test_filter_summary_includes_dynamic_entries() {
+        let mut request = SubscribeRequest {
+            commitment: Some(CommitmentLevel::Confirmed as i32),
+            ..Default::default()
+        };
+        request.transactions.insert(
+            "transaction_0".to_string(),
+            SubscribeRequestFilterTransactions {
+                account_include: vec!["program".to_string(), "amm".to_string()],
+                account_exclude: vec!["spam".to_string()],
+                vote: Some(false),
+                ..Default::default()
+            },
+        );
+        let summary = SubscriptionFilterSummary::from_request(&request);
+        assert_eq!(summary.transaction_failed, [None]);
+        assert_eq!(summary.transaction_vote, [Some(false)]);
+        assert_eq!(summary.commitment, Some(CommitmentLevel::Confirmed));
+        assert_eq!(summary.account_commitment, None);
+        assert_eq!(summary.transaction_account_include, ["amm", "program"]);
+        assert_eq!(summary.transaction_account_exclude, ["spam"]);
+        assert!(!summary.blocks_meta);
+
+        let mut dynamic = DynamicSubscription::new(true, true);
+        dynamic.add_accounts(vec!["pool".to_string(), "amm".to_string()]);
+        dynamic.apply(&mut request);
+        let summary = SubscriptionFilterSummary::from_request(&request);
+        assert_eq!(summary.transaction_account_include, ["amm", "pool", "program"]);
+        // 动态条目沿用已有过滤器的 failed 设置，并排除投票交易
+        assert_eq!(summary.transaction_failed, [None]);
+        assert_eq!(summary.transaction_vote, [Some(false)]);
+        assert_eq!(summary.accounts, ["amm", "pool"]);
+        assert!(summary.account_owners.is_empty());
+        assert_eq!(summary.account_commitment, Some(CommitmentLevel::Confirmed));
+    }
+
+    #[test]
+    fn test_transaction_filter_failed_is_forwarded() {
+        let manager = SubscriptionManager::new(String::new(), None, ClientConfig::default());
+        let filter = |failed| TransactionFilter {
+            account_include: vec!["program".to_string()],
+            account_exclude: vec![],
+            account_required: vec![],
+            failed,
+        };
+        let transactions = manager
+            .get_subscribe_request_filter(vec![filter(Some(false)), filter(None)], None)
+            .unwrap();
+        assert_eq!(transactions["transaction_0"].failed, Some(false));
+        assert_eq!(transactions["transaction_1"].failed, None);
+        assert_eq!(transactions["transaction_1"].vote, Some(false));
+    }
+}
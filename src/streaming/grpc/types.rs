@@ -1,15 +1,19 @@
-use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solana_sdk::signature::Signature;
 use std::{collections::HashMap, fmt};
 use yellowstone_grpc_proto::{
     geyser::{
-        SubscribeRequestFilterAccounts, SubscribeRequestFilterTransactions,
-        SubscribeUpdateTransactionInfo,
+        SubscribeRequestFilterAccounts, SubscribeRequestFilterSlots,
+        SubscribeRequestFilterTransactions, SubscribeUpdateTransactionInfo,
     },
     prost_types::Timestamp,
 };
 
+/// 账户更新类型定义在 event_parser 中，账户解析器不依赖 gRPC 模块
+pub use crate::streaming::event_parser::common::AccountPretty;
+
 pub type TransactionsFilterMap = HashMap<String, SubscribeRequestFilterTransactions>;
 pub type AccountsFilterMap = HashMap<String, SubscribeRequestFilterAccounts>;
+pub type SlotsFilterMap = HashMap<String, SubscribeRequestFilterSlots>;
 
 #[derive(Clone, Debug)]
 pub enum EventPretty {
@@ -18,43 +22,16 @@ pub enum EventPretty {
     Account(AccountPretty),
 }
 
-#[derive(Clone, Default)]
-pub struct AccountPretty {
-    pub slot: u64,
-    pub write_version: u64,
-    pub is_startup: bool,
-    pub signature: Signature,
-    pub pubkey: Pubkey,
-    pub executable: bool,
-    pub lamports: u64,
-    pub owner: Pubkey,
-    pub rent_epoch: u64,
-    pub data: Vec<u8>,
-    pub recv_us: i64,
-}
-
-impl fmt::Debug for AccountPretty {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("AccountPretty")
-            .field("slot", &self.slot)
-            .field("write_version", &self.write_version)
-            .field("is_startup", &self.is_startup)
-            .field("signature", &self.signature)
-            .field("pubkey", &self.pubkey)
-            .field("executable", &self.executable)
-            .field("lamports", &self.lamports)
-            .field("owner", &self.owner)
-            .field("rent_epoch", &self.rent_epoch)
-            .field("data", &self.data)
-            .finish()
-    }
-}
-
 #[derive(Clone, Default)]
 pub struct BlockMetaPretty {
     pub slot: u64,
     pub block_hash: String,
     pub block_time: Option<Timestamp>,
+    pub parent_slot: u64,
+    pub parent_block_hash: String,
+    pub block_height: Option<u64>,
+    pub executed_transaction_count: u64,
+    pub entries_count: u64,
     pub recv_us: i64,
 }
 
@@ -64,6 +41,11 @@ impl fmt::Debug for BlockMetaPretty {
             .field("slot", &self.slot)
             .field("block_hash", &self.block_hash)
             .field("block_time", &self.block_time)
+            .field("parent_slot", &self.parent_slot)
+            .field("parent_block_hash", &self.parent_block_hash)
+            .field("block_height", &self.block_height)
+            .field("executed_transaction_count", &self.executed_transaction_count)
+            .field("entries_count", &self.entries_count)
             .field("recv_us", &self.recv_us)
             .finish()
     }
@@ -78,6 +60,8 @@ pub struct TransactionPretty {
     pub signature: Signature,
     pub is_vote: bool,
     pub recv_us: i64,
+    /// gRPC 服务端推送时间（`SubscribeUpdate.created_at`，微秒）
+    pub grpc_created_at_us: Option<i64>,
     pub grpc_tx: SubscribeUpdateTransactionInfo,
 }
 
@@ -104,6 +88,7 @@ impl Default for TransactionPretty {
             is_vote: false,
             grpc_tx: SubscribeUpdateTransactionInfo::default(),
             recv_us: 0,
+            grpc_created_at_us: None,
         }
     }
 }
@@ -1,11 +1,24 @@
 pub mod common;
 pub mod event_parser;
+#[cfg(feature = "grpc")]
 pub mod grpc;
+#[cfg(feature = "grpc")]
+pub mod multi_endpoint;
+pub mod pnl;
+#[cfg(feature = "grpc")]
 pub mod shred;
+#[cfg(feature = "grpc")]
 pub mod shred_stream;
+#[cfg(feature = "grpc")]
 pub mod yellowstone_grpc;
+#[cfg(feature = "grpc")]
 pub mod yellowstone_sub_system;
 
+#[cfg(feature = "grpc")]
+pub use multi_endpoint::MultiEndpointStream;
+#[cfg(feature = "grpc")]
 pub use shred::ShredStreamGrpc;
+#[cfg(feature = "grpc")]
 pub use yellowstone_grpc::YellowstoneGrpc;
+#[cfg(feature = "grpc")]
 pub use yellowstone_sub_system::{SystemEvent, TransferInfo};
@@ -0,0 +1,200 @@
+//! 同时订阅多个 Yellowstone 端点并合并去重
+//!
+//! 同一笔交易会从每个端点各收到一次，`MultiEndpointStream` 按 `dedup_key` 只转发最先到达的一份，
+//! 某个端点延迟或断线时仍能从其他端点收到事件。
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use solana_sdk::pubkey::Pubkey;
+use yellowstone_grpc_proto::geyser::CommitmentLevel;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::{common::filter::EventTypeFilter, DexEvent, Protocol};
+use crate::streaming::yellowstone_grpc::{AccountFilter, TransactionFilter};
+use crate::streaming::YellowstoneGrpc;
+
+/// 默认去重窗口，覆盖端点之间通常的延迟差
+pub const DEFAULT_DEDUP_WINDOW: Duration = Duration::from_secs(30);
+
+/// 跨端点去重使用的键
+///
+/// 在 `event_id` 的基础上加入 slot 和账户地址：区块事件没有签名、账户事件没有指令位置，
+/// 只用 `event_id` 会把不同 slot 的 BlockMeta 或同一交易写入的不同账户当成重复。
+/// 不包含 `account_write_version`，它由各端点的验证者分别编号。
+pub fn dedup_key(event: &DexEvent) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(event.event_id());
+    hasher.update(event.metadata().slot.to_le_bytes());
+    hasher.update(event.account_pubkey().unwrap_or_default().as_ref());
+    hasher.finalize().into()
+}
+
+#[derive(Default)]
+struct DedupState {
+    seen: HashSet<[u8; 32]>,
+    /// 按首次出现时间排序，用于过期
+    order: VecDeque<(Instant, [u8; 32])>,
+}
+
+/// 按时间窗口去重：同一个键在首次出现后的 `window` 内再次出现视为重复
+pub struct EventDeduplicator {
+    window: Duration,
+    state: Mutex<DedupState>,
+}
+
+impl EventDeduplicator {
+    pub fn new(window: Duration) -> Self {
+        Self { window, state: Mutex::new(DedupState::default()) }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// 键在窗口内第一次出现时返回 true，并记录该键
+    pub fn first_seen(&self, key: [u8; 32], now: Instant) -> bool {
+        let mut state = self.state.lock();
+        while let Some(&(seen_at, expired)) = state.order.front() {
+            if now.saturating_duration_since(seen_at) < self.window {
+                break;
+            }
+            state.order.pop_front();
+            state.seen.remove(&expired);
+        }
+        if !state.seen.insert(key) {
+            return false;
+        }
+        state.order.push_back((now, key));
+        true
+    }
+
+    /// 窗口内记录的键数量
+    pub fn len(&self) -> usize {
+        self.state.lock().seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 多端点冗余订阅
+///
+/// 对每个客户端发起相同的订阅，事件按 `dedup_key` 在 `dedup_window` 内去重后交给同一个 callback。
+/// 窗口应大于端点之间的最大延迟差，否则落后端点的事件会被再次转发；窗口越大占用的内存越多。
+/// callback 会在各端点的流处理任务中并发调用。
+pub struct MultiEndpointStream {
+    clients: Vec<YellowstoneGrpc>,
+    dedup: Arc<EventDeduplicator>,
+}
+
+impl MultiEndpointStream {
+    pub fn new(clients: Vec<YellowstoneGrpc>, dedup_window: Duration) -> Self {
+        Self { clients, dedup: Arc::new(EventDeduplicator::new(dedup_window)) }
+    }
+
+    pub fn clients(&self) -> &[YellowstoneGrpc] {
+        &self.clients
+    }
+
+    pub fn dedup_window(&self) -> Duration {
+        self.dedup.window()
+    }
+
+    /// 在所有端点上调用 `YellowstoneGrpc::subscribe_events_immediate`，参数含义相同
+    ///
+    /// 部分端点订阅失败时记录警告并继续，只有全部失败时返回错误
+    #[allow(clippy::too_many_arguments)]
+    pub async fn subscribe_events_immediate<F>(
+        &self,
+        protocols: Vec<Protocol>,
+        bot_wallet: Option<Pubkey>,
+        transaction_filter: Vec<TransactionFilter>,
+        account_filter: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+        commitment: Option<CommitmentLevel>,
+        callback: F,
+    ) -> AnyResult<()>
+    where
+        F: Fn(DexEvent) + Send + Sync + 'static,
+    {
+        if self.clients.is_empty() {
+            return Err(anyhow!("MultiEndpointStream has no clients"));
+        }
+        let callback = Arc::new(callback);
+        let mut subscribed = 0;
+        let mut last_error = None;
+        for (index, client) in self.clients.iter().enumerate() {
+            let dedup = self.dedup.clone();
+            let callback = callback.clone();
+            let result = client
+                .subscribe_events_immediate(
+                    protocols.clone(),
+                    bot_wallet,
+                    transaction_filter.clone(),
+                    account_filter.clone(),
+                    event_type_filter.clone(),
+                    commitment,
+                    move |event: DexEvent| {
+                        if dedup.first_seen(dedup_key(&event), Instant::now()) {
+                            callback(event);
+                        }
+                    },
+                )
+                .await;
+            match result {
+                Ok(()) => subscribed += 1,
+                Err(e) => {
+                    log::warn!(
+                        "MultiEndpointStream: endpoint {} failed to subscribe: {}",
+                        index,
+                        e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        match last_error {
+            Some(e) if subscribed == 0 => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// 停止所有端点的订阅
+    pub async fn stop(&self) {
+        for client in &self.clients {
+            client.stop().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::protocols::block::block_meta_event::BlockMetaEvent;
+
+    #[test]
+    fn test_deduplicator_window_and_keys() {
+        let dedup = EventDeduplicator::new(Duration::from_secs(10));
+        let start = Instant::now();
+        let (a, b) = ([1u8; 32], [2u8; 32]);
+        assert!(dedup.first_seen(a, start));
+        assert!(!dedup.first_seen(a, start + Duration::from_secs(9)));
+        assert!(dedup.first_seen(b, start + Duration::from_secs(5)));
+        // 窗口从首次出现算起，过期后重新视为新事件
+        assert!(dedup.first_seen(a, start + Duration::from_secs(10)));
+        assert_eq!(dedup.len(), 2);
+        assert!(!dedup.first_seen(b, start + Duration::from_secs(14)));
+        assert!(dedup.first_seen(b, start + Duration::from_secs(15)));
+
+        // 没有签名的区块事件按 slot 区分
+        let block = |slot| DexEvent::BlockMetaEvent(BlockMetaEvent::new(slot, String::new(), 0, 0));
+        assert_eq!(block(1).event_id(), block(2).event_id());
+        assert_ne!(dedup_key(&block(1)), dedup_key(&block(2)));
+        assert_eq!(dedup_key(&block(1)), dedup_key(&block(1)));
+    }
+}
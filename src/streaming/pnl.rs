@@ -0,0 +1,193 @@
+//! 按钱包统计已实现盈亏
+//!
+//! `PnlTracker` 接收交易事件，按 (钱包, mint) 以平均成本法维护持仓成本和已实现盈亏。
+//! 只统计开始跟踪之后的成交：卖出数量超过已跟踪持仓时，超出部分没有成本，不计入盈亏。
+use dashmap::{DashMap, DashSet};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::{
+    core::{NormalizedTrade, QuoteMints},
+    DexEvent,
+};
+
+/// 单个 (钱包, mint) 的持仓与盈亏，数量均为原始数量（未按精度换算）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PnlPosition {
+    /// 计价币，由该 mint 的第一笔成交确定
+    pub quote_mint: Pubkey,
+    /// 当前持有的 base 数量（只计跟踪期间买入的部分）
+    pub base_amount: u128,
+    /// 当前持仓的成本（quote）
+    pub cost_basis: u128,
+    /// 已实现盈亏（quote）
+    pub realized_pnl: i128,
+    /// 卖出时超出已跟踪持仓的 base 数量
+    pub untracked_sold: u128,
+    /// 计入的成交笔数
+    pub trades: u64,
+}
+
+impl PnlPosition {
+    /// 平均持仓成本（quote / base），没有持仓时返回 None
+    pub fn average_cost(&self) -> Option<f64> {
+        (self.base_amount > 0).then(|| self.cost_basis as f64 / self.base_amount as f64)
+    }
+
+    fn apply(&mut self, trade: &NormalizedTrade) {
+        let base = trade.base_amount as u128;
+        let quote = trade.quote_amount as u128;
+        self.trades += 1;
+        if trade.is_buy {
+            self.base_amount += base;
+            self.cost_basis += quote;
+            return;
+        }
+
+        let matched = base.min(self.base_amount);
+        self.untracked_sold += base - matched;
+        if matched == 0 {
+            return;
+        }
+        let cost = self.cost_basis * matched / self.base_amount;
+        let proceeds = quote * matched / base;
+        self.realized_pnl += proceeds as i128 - cost as i128;
+        self.base_amount -= matched;
+        self.cost_basis -= cost;
+    }
+}
+
+/// 已实现盈亏跟踪器
+///
+/// 只处理被关注钱包发起、且一侧属于 quote 集合的成交；手续费不计入成本。
+/// 同一 mint 先后以不同 quote 成交时，只统计与第一笔成交相同 quote 的部分。
+pub struct PnlTracker {
+    wallets: DashSet<Pubkey>,
+    quote_mints: QuoteMints,
+    positions: DashMap<(Pubkey, Pubkey), PnlPosition>,
+}
+
+impl PnlTracker {
+    /// 跟踪 `wallets`，按 `quote_mints` 区分 base / quote
+    pub fn new<I: IntoIterator<Item = Pubkey>>(wallets: I, quote_mints: QuoteMints) -> Self {
+        Self { wallets: wallets.into_iter().collect(), quote_mints, positions: DashMap::new() }
+    }
+
+    /// 关注钱包
+    pub fn watch(&self, wallet: Pubkey) {
+        self.wallets.insert(wallet);
+    }
+
+    /// 取消关注钱包，已有的持仓和盈亏保留
+    pub fn unwatch(&self, wallet: &Pubkey) {
+        self.wallets.remove(wallet);
+    }
+
+    /// 是否关注该钱包
+    pub fn is_watched(&self, wallet: &Pubkey) -> bool {
+        self.wallets.contains(wallet)
+    }
+
+    /// 从事件中提取成交并计入，返回是否被计入
+    pub fn record_event(&self, event: &DexEvent) -> bool {
+        match event.to_normalized_trade(&self.quote_mints) {
+            Some(trade) => self.record_trade(&trade),
+            None => false,
+        }
+    }
+
+    /// 计入一笔成交，返回是否被计入
+    pub fn record_trade(&self, trade: &NormalizedTrade) -> bool {
+        if trade.base_amount == 0 || !self.is_watched(&trade.trader) {
+            return false;
+        }
+        let mut position = self
+            .positions
+            .entry((trade.trader, trade.base_mint))
+            .or_insert_with(|| PnlPosition { quote_mint: trade.quote_mint, ..Default::default() });
+        if position.quote_mint != trade.quote_mint {
+            return false;
+        }
+        position.apply(trade);
+        true
+    }
+
+    /// 查询 (钱包, mint) 的持仓与盈亏
+    pub fn get_pnl(&self, wallet: &Pubkey, mint: &Pubkey) -> Option<PnlPosition> {
+        self.positions.get(&(*wallet, *mint)).map(|position| *position)
+    }
+
+    /// 钱包的所有持仓，返回 (mint, 持仓)
+    pub fn positions(&self, wallet: &Pubkey) -> Vec<(Pubkey, PnlPosition)> {
+        self.positions
+            .iter()
+            .filter(|entry| entry.key().0 == *wallet)
+            .map(|entry| (entry.key().1, *entry.value()))
+            .collect()
+    }
+
+    /// 清空所有持仓
+    pub fn clear(&self) {
+        self.positions.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::{
+        common::types::{USDC_MINT, WSOL_MINT},
+        protocols::pumpfun::PumpFunTradeEvent,
+    };
+
+    fn pumpfun_trade(user: Pubkey, mint: Pubkey, is_buy: bool, sol: u64, token: u64) -> DexEvent {
+        DexEvent::PumpFunTradeEvent(PumpFunTradeEvent {
+            user,
+            mint,
+            is_buy,
+            sol_amount: sol,
+            token_amount: token,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_average_cost_realized_pnl() {
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let tracker = PnlTracker::new([wallet], QuoteMints::default());
+
+        assert!(tracker.record_event(&pumpfun_trade(wallet, mint, true, 1_000, 100)));
+        assert!(tracker.record_event(&pumpfun_trade(wallet, mint, true, 3_000, 100)));
+        assert_eq!(tracker.get_pnl(&wallet, &mint).unwrap().average_cost(), Some(20.0));
+
+        // 卖出 50，成本 1000，收入 1500
+        assert!(tracker.record_event(&pumpfun_trade(wallet, mint, false, 1_500, 50)));
+        let position = tracker.get_pnl(&wallet, &mint).unwrap();
+        assert_eq!(position.realized_pnl, 500);
+        assert_eq!((position.base_amount, position.cost_basis), (150, 3_000));
+
+        // 卖出 200，只有 150 有成本：收入 4500 - 成本 3000
+        assert!(tracker.record_event(&pumpfun_trade(wallet, mint, false, 6_000, 200)));
+        let position = tracker.get_pnl(&wallet, &mint).unwrap();
+        assert_eq!(position.realized_pnl, 2_000);
+        assert_eq!(position.untracked_sold, 50);
+        assert_eq!(position.average_cost(), None);
+        assert_eq!(position.quote_mint, WSOL_MINT);
+        assert_eq!(position.trades, 4);
+
+        // 未关注的钱包和不同 quote 的成交不计入
+        assert!(!tracker.record_event(&pumpfun_trade(Pubkey::new_unique(), mint, true, 1, 1)));
+        let trade = NormalizedTrade {
+            signature: Default::default(),
+            slot: 0,
+            trader: wallet,
+            base_mint: mint,
+            base_amount: 10,
+            quote_mint: USDC_MINT,
+            quote_amount: 10,
+            is_buy: true,
+        };
+        assert!(!tracker.record_trade(&trade));
+        assert_eq!(tracker.positions(&wallet).len(), 1);
+    }
+}
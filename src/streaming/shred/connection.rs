@@ -7,6 +7,10 @@ use crate::protos::shredstream::shredstream_proxy_client::ShredstreamProxyClient
 use crate::streaming::common::{
     MetricsManager, PerformanceMetrics, StreamClientConfig, SubscriptionHandle,
 };
+use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
+use crate::streaming::event_parser::core::{
+    MintRegistry, ParseOptions, QuoteMints, SolTransferWatchlist,
+};
 
 /// ShredStream gRPC 客户端
 #[derive(Clone)]
@@ -14,6 +18,9 @@ pub struct ShredStreamGrpc {
     pub shredstream_client: Arc<ShredstreamProxyClient<Channel>>,
     pub config: StreamClientConfig,
     pub subscription_handle: Arc<Mutex<Option<SubscriptionHandle>>>,
+    /// 本客户端的 SOL 转账关注列表，由 `StreamClientConfig::sol_transfer_wallets` 初始化，
+    /// 运行时可增减钱包
+    pub sol_transfer_watchlist: Arc<SolTransferWatchlist>,
 }
 
 impl ShredStreamGrpc {
@@ -26,10 +33,16 @@ impl ShredStreamGrpc {
     pub async fn new_with_config(endpoint: String, config: StreamClientConfig) -> AnyResult<Self> {
         let shredstream_client = ShredstreamProxyClient::connect(endpoint.clone()).await?;
         MetricsManager::init(config.enable_metrics);
+        MintRegistry::global().set_attach_ui_amounts(config.attach_ui_amounts);
+        QuoteMints::set_global(config.quote_mints.clone());
+        AccountEventParser::set_retain_raw_account_data(config.retain_raw_account_data);
+        let sol_transfer_watchlist = Arc::new(SolTransferWatchlist::new());
+        sol_transfer_watchlist.configure(config.sol_transfer_wallets.as_deref());
         Ok(Self {
             shredstream_client: Arc::new(shredstream_client),
             config,
             subscription_handle: Arc::new(Mutex::new(None)),
+            sol_transfer_watchlist,
         })
     }
 
@@ -40,9 +53,23 @@ impl ShredStreamGrpc {
 
     /// 更新配置
     pub fn update_config(&mut self, config: StreamClientConfig) {
+        self.sol_transfer_watchlist.configure(config.sol_transfer_wallets.as_deref());
         self.config = config;
     }
 
+    /// 本客户端的 SOL 转账关注列表
+    pub fn sol_transfer_watchlist(&self) -> &Arc<SolTransferWatchlist> {
+        &self.sol_transfer_watchlist
+    }
+
+    /// 按当前配置构建解析选项，附带本客户端的 SOL 转账关注列表
+    pub(crate) fn parse_options(&self) -> ParseOptions {
+        ParseOptions {
+            sol_transfer_watchlist: Some(self.sol_transfer_watchlist.clone()),
+            ..self.config.parse_options()
+        }
+    }
+
     /// 获取性能指标
     pub fn get_metrics(&self) -> PerformanceMetrics {
         MetricsManager::global().get_metrics()
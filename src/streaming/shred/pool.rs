@@ -1,7 +1,8 @@
+use parking_lot::Mutex;
 use solana_sdk::transaction::VersionedTransaction;
 use std::collections::VecDeque;
 use std::ops::DerefMut;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use super::TransactionWithSlot;
 
@@ -24,7 +25,7 @@ impl TransactionWithSlotPool {
     }
 
     pub fn acquire(&self) -> PooledTransactionWithSlot {
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.pool.lock();
         let transaction = match pool.pop_front() {
             Some(reused) => reused,
             None => Box::new(TransactionWithSlot::default()),
@@ -62,7 +63,7 @@ impl PooledTransactionWithSlot {
 
 impl Drop for PooledTransactionWithSlot {
     fn drop(&mut self) {
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.pool.lock();
         if pool.len() < self.max_size {
             // 清理敏感数据
             self.transaction.slot = 0;
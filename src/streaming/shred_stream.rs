@@ -6,7 +6,8 @@ use solana_sdk::pubkey::Pubkey;
 use crate::common::AnyResult;
 use crate::protos::shredstream::SubscribeEntriesRequest;
 use crate::streaming::common::{
-    process_shred_transaction, process_shred_tx_events, SubscriptionHandle,
+    process_shred_transaction_with_options, process_shred_tx_events_with_options, CallbackWatchdog,
+    FirstEventPerTransaction, SubscriptionHandle, TpsThrottle,
 };
 use crate::streaming::event_parser::common::filter::EventTypeFilter;
 use crate::streaming::event_parser::common::high_performance_clock::get_high_perf_clock;
@@ -45,8 +46,18 @@ impl ShredStreamGrpc {
         let mut stream = client.subscribe_entries(request).await?.into_inner();
 
         // Wrap callback once before the async block
-        let callback = Arc::new(callback);
+        let callback = CallbackWatchdog::wrap_if_enabled(
+            self.config.callback_watchdog.as_ref(),
+            Arc::new(callback) as Arc<dyn Fn(DexEvent) + Send + Sync>,
+        );
+        let callback = if self.config.first_event_per_transaction {
+            FirstEventPerTransaction::wrap(callback)
+        } else {
+            callback
+        };
         let swap_cu_parse_config = self.config.swap_cu_parse_config.clone();
+        let parse_options = self.parse_options();
+        let mut throttle = TpsThrottle::from_config(self.config.throttle_tps);
 
         let stream_task = tokio::spawn(async move {
             while let Some(message) = stream.next().await {
@@ -61,14 +72,18 @@ impl ShredStreamGrpc {
                                             msg.slot,
                                             get_high_perf_clock(),
                                         );
+                                    if let Some(throttle) = throttle.as_mut() {
+                                        throttle.acquire().await;
+                                    }
                                     // Process transaction - clone Arc and Vec for each call
-                                    if let Err(e) = process_shred_transaction(
+                                    if let Err(e) = process_shred_transaction_with_options(
                                         transaction_with_slot,
                                         &protocols,
                                         event_type_filter.as_ref(),
                                         swap_cu_parse_config.as_ref(),
                                         callback.clone(),
                                         bot_wallet,
+                                        &parse_options,
                                     )
                                     .await
                                     {
@@ -117,8 +132,18 @@ impl ShredStreamGrpc {
         let request = tonic::Request::new(SubscribeEntriesRequest {});
         let mut stream = client.subscribe_entries(request).await?.into_inner();
 
-        let callback = Arc::new(callback);
+        let callback = CallbackWatchdog::wrap_if_enabled(
+            self.config.callback_watchdog.as_ref(),
+            Arc::new(callback) as Arc<dyn Fn(TxDexEvents) + Send + Sync>,
+        );
+        let callback = if self.config.first_event_per_transaction {
+            FirstEventPerTransaction::wrap_tx_events(callback)
+        } else {
+            callback
+        };
         let swap_cu_parse_config = self.config.swap_cu_parse_config.clone();
+        let parse_options = self.parse_options();
+        let mut throttle = TpsThrottle::from_config(self.config.throttle_tps);
 
         let stream_task = tokio::spawn(async move {
             while let Some(message) = stream.next().await {
@@ -135,7 +160,10 @@ impl ShredStreamGrpc {
                                             msg.slot,
                                             get_high_perf_clock(),
                                         );
-                                    if let Err(e) = process_shred_tx_events(
+                                    if let Some(throttle) = throttle.as_mut() {
+                                        throttle.acquire().await;
+                                    }
+                                    if let Err(e) = process_shred_tx_events_with_options(
                                         transaction_with_slot,
                                         &protocols,
                                         event_type_filter.as_ref(),
@@ -144,6 +172,7 @@ impl ShredStreamGrpc {
                                         bot_wallet,
                                         Some(entry_index as u64),
                                         Some(tx_index as u64),
+                                        &parse_options,
                                     )
                                     .await
                                     {
@@ -1,24 +1,42 @@
 use crate::common::AnyResult;
 use crate::streaming::common::{
-    process_grpc_transaction, process_grpc_tx_events, MetricsManager, PerformanceMetrics,
-    StreamClientConfig, SubscriptionHandle,
+    process_grpc_account, process_grpc_transaction_with_options,
+    process_grpc_tx_events_with_options, AccountCoalescer, CallbackWatchdog,
+    FirstEventPerTransaction, MetricsEventType, MetricsManager, PerformanceMetrics, ProcessOptions,
+    SlotGapTracker, SlotReorderBuffer, StreamClientConfig, SubscriptionHandle, TpsThrottle,
+};
+use crate::streaming::event_parser::common::filter::{AccountEventTypeIndex, EventTypeFilter};
+use crate::streaming::event_parser::common::high_performance_clock::{
+    elapsed_micros_since, get_high_perf_clock,
+};
+use crate::streaming::event_parser::common::EventType;
+use crate::streaming::event_parser::core::account_event_parser::{
+    AccountEventParser, AccountParseOptions,
+};
+use crate::streaming::event_parser::core::{
+    MintRegistry, ParseOptions, QuoteMints, SolTransferWatchlist,
 };
-use crate::streaming::event_parser::common::filter::EventTypeFilter;
 use crate::streaming::event_parser::{DexEvent, Protocol, TxDexEvents};
-use crate::streaming::grpc::pool::factory;
-use crate::streaming::grpc::{EventPretty, SubscriptionManager};
+use crate::streaming::grpc::pool::{factory, init_pool_config};
+use crate::streaming::grpc::{
+    split_account_request, DynamicSubscription, EventPretty, SubscriptionFilterSummary,
+    SubscriptionManager,
+};
 use anyhow::anyhow;
 use chrono::Local;
 use futures::channel::mpsc;
-use futures::{SinkExt, StreamExt};
+use futures::{future, Sink, SinkExt, Stream, StreamExt};
 use log::error;
 use solana_sdk::pubkey::Pubkey;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
+use tokio::time::{Instant, MissedTickBehavior};
 use yellowstone_grpc_proto::geyser::subscribe_update::UpdateOneof;
 use yellowstone_grpc_proto::geyser::{
-    CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccountsFilter, SubscribeRequestPing,
+    CommitmentLevel, SlotStatus, SubscribeRequest, SubscribeRequestFilterAccountsFilter,
+    SubscribeRequestPing,
 };
 
 /// 交易过滤器
@@ -27,6 +45,9 @@ pub struct TransactionFilter {
     pub account_include: Vec<String>,
     pub account_exclude: Vec<String>,
     pub account_required: Vec<String>,
+    /// 按执行结果过滤，与 Yellowstone 的 `failed` 字段一致：`Some(false)` 只订阅成功的交易，
+    /// `Some(true)` 只订阅失败的交易，`None` 两者都订阅
+    pub failed: Option<bool>,
 }
 
 /// 账户过滤器
@@ -35,6 +56,75 @@ pub struct AccountFilter {
     pub account: Vec<String>,
     pub owner: Vec<String>,
     pub filters: Vec<SubscribeRequestFilterAccountsFilter>,
+    /// 只解码这些账户事件类型，例如只要 `AccountWhirlpool` 而不要 `AccountWhirlpoolTickArray`；
+    /// 为空时解码 owner 的所有账户类型。跳过的账户在解码之前按 discriminator 过滤，
+    /// 服务端仍会推送，需要减少流量时配合 `filters` 中的 memcmp / datasize 使用
+    pub event_types: Vec<EventType>,
+}
+
+impl AccountFilter {
+    /// 由各过滤器的 `event_types` 生成按 owner 的解码索引，没有过滤器限制事件类型时返回 None
+    pub fn event_type_index(filters: &[AccountFilter]) -> Option<AccountEventTypeIndex> {
+        let mut index = AccountEventTypeIndex::default();
+        for filter in filters {
+            let owners: Vec<Pubkey> =
+                filter.owner.iter().filter_map(|owner| owner.parse().ok()).collect();
+            index.add(&owners, &filter.event_types);
+        }
+        index.is_restricted().then_some(index)
+    }
+}
+
+/// 订阅连接状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionState {
+    /// 未订阅
+    Idle,
+    /// 订阅中且数据流正常
+    Connected,
+    /// 订阅仍处于激活状态，但数据流已因错误或服务端关闭而结束
+    Disconnected,
+}
+
+/// 订阅数据流结束的原因，见 `YellowstoneGrpc::on_disconnect`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// 数据流返回错误
+    StreamError,
+    /// 服务端关闭了数据流
+    StreamEnded,
+    /// 超过 `idle_timeout` 没有收到任何消息（含 ping / pong）
+    IdleTimeout,
+    /// 发送 keepalive ping 或订阅更新失败
+    SendFailed,
+}
+
+/// 数据流结束时的回调
+pub type DisconnectCallback = Arc<dyn Fn(DisconnectReason) + Send + Sync>;
+
+/// 客户端 keepalive 定时器的一次触发
+enum Keepalive {
+    /// 到了发送客户端 ping 的时间
+    Ping,
+    /// 超过 `idle_timeout` 没有收到任何消息
+    IdleTimeout,
+}
+
+/// 订阅健康状态，用于监控面板
+#[derive(Debug, Clone)]
+pub struct SubscriptionStatus {
+    pub state: SubscriptionState,
+    /// 最近收到的账户 / 交易 / 区块更新的 slot
+    pub current_slot: Option<u64>,
+    /// 自上一个缺口以来的 slot 区间（闭区间），缺口由 BlockMeta 判断，见 `on_slot_gap`
+    pub contiguous_slots: Option<(u64, u64)>,
+    /// 距最近一次收到账户 / 交易 / 区块更新的时间，ping 不计入
+    pub last_event_age: Option<Duration>,
+    /// 距最近一次收到任意消息（含 ping / pong）的时间
+    pub last_message_age: Option<Duration>,
+    /// 最近几秒的滑动平均交易事件速率，未启用 metrics 时为 0
+    pub events_per_second: f64,
+    pub dropped_events: u64,
 }
 
 pub struct YellowstoneGrpc {
@@ -47,8 +137,21 @@ pub struct YellowstoneGrpc {
     pub active_subscription: Arc<AtomicBool>,
     pub control_tx: Arc<tokio::sync::Mutex<Option<mpsc::Sender<SubscribeRequest>>>>,
     pub current_request: Arc<tokio::sync::RwLock<Option<SubscribeRequest>>>,
+    pub dynamic_subscription: Arc<tokio::sync::Mutex<DynamicSubscription>>,
+    // Health tracking fields
+    pub stream_connected: Arc<AtomicBool>,
+    pub last_processed_slot: Arc<AtomicU64>,
+    pub last_event_us: Arc<AtomicI64>,
+    pub last_message_us: Arc<AtomicI64>,
+    pub slot_tracker: Arc<SlotGapTracker>,
+    pub disconnect_callback: Arc<parking_lot::RwLock<Option<DisconnectCallback>>>,
 
     pub event_type_filter: Arc<tokio::sync::RwLock<Option<EventTypeFilter>>>,
+    /// 当前账户过滤器选择的账户事件类型，随 `update_subscription` 更新
+    pub account_event_types: Arc<parking_lot::RwLock<Option<Arc<AccountEventTypeIndex>>>>,
+    /// 本客户端的 SOL 转账关注列表，由 `StreamClientConfig::sol_transfer_wallets` 初始化，
+    /// 运行时可增减钱包
+    pub sol_transfer_watchlist: Arc<SolTransferWatchlist>,
 }
 
 impl YellowstoneGrpc {
@@ -67,6 +170,12 @@ impl YellowstoneGrpc {
         let subscription_manager =
             SubscriptionManager::new(endpoint.clone(), x_token.clone(), config.clone());
         MetricsManager::init(config.enable_metrics);
+        init_pool_config(config.pool.clone());
+        MintRegistry::global().set_attach_ui_amounts(config.attach_ui_amounts);
+        QuoteMints::set_global(config.quote_mints.clone());
+        AccountEventParser::set_retain_raw_account_data(config.retain_raw_account_data);
+        let sol_transfer_watchlist = Arc::new(SolTransferWatchlist::new());
+        sol_transfer_watchlist.configure(config.sol_transfer_wallets.as_deref());
 
         Ok(Self {
             endpoint,
@@ -77,7 +186,16 @@ impl YellowstoneGrpc {
             active_subscription: Arc::new(AtomicBool::new(false)),
             control_tx: Arc::new(tokio::sync::Mutex::new(None)),
             current_request: Arc::new(tokio::sync::RwLock::new(None)),
+            dynamic_subscription: Arc::new(tokio::sync::Mutex::new(DynamicSubscription::default())),
+            stream_connected: Arc::new(AtomicBool::new(false)),
+            last_processed_slot: Arc::new(AtomicU64::new(0)),
+            last_event_us: Arc::new(AtomicI64::new(0)),
+            last_message_us: Arc::new(AtomicI64::new(0)),
+            slot_tracker: Arc::new(SlotGapTracker::new()),
+            disconnect_callback: Arc::new(parking_lot::RwLock::new(None)),
             event_type_filter: Arc::new(tokio::sync::RwLock::new(None)),
+            account_event_types: Arc::new(parking_lot::RwLock::new(None)),
+            sol_transfer_watchlist,
         })
     }
 
@@ -88,9 +206,23 @@ impl YellowstoneGrpc {
 
     /// 更新配置
     pub fn update_config(&mut self, config: StreamClientConfig) {
+        self.sol_transfer_watchlist.configure(config.sol_transfer_wallets.as_deref());
         self.config = config;
     }
 
+    /// 本客户端的 SOL 转账关注列表
+    pub fn sol_transfer_watchlist(&self) -> &Arc<SolTransferWatchlist> {
+        &self.sol_transfer_watchlist
+    }
+
+    /// 按当前配置构建解析选项，附带本客户端的 SOL 转账关注列表
+    fn parse_options(&self) -> ParseOptions {
+        ParseOptions {
+            sol_transfer_watchlist: Some(self.sol_transfer_watchlist.clone()),
+            ..self.config.parse_options()
+        }
+    }
+
     /// 获取性能指标
     pub fn get_metrics(&self) -> PerformanceMetrics {
         MetricsManager::global().get_metrics()
@@ -106,6 +238,223 @@ impl YellowstoneGrpc {
         self.config.enable_metrics = enabled;
     }
 
+    /// 获取订阅健康状态
+    pub fn status(&self) -> SubscriptionStatus {
+        let state = if !self.active_subscription.load(Ordering::Acquire) {
+            SubscriptionState::Idle
+        } else if self.stream_connected.load(Ordering::Acquire) {
+            SubscriptionState::Connected
+        } else {
+            SubscriptionState::Disconnected
+        };
+        let current_slot = match self.last_processed_slot.load(Ordering::Relaxed) {
+            0 => None,
+            slot => Some(slot),
+        };
+        let last_event_age = Self::age_since(self.last_event_us.load(Ordering::Relaxed));
+        let last_message_age = Self::age_since(self.last_message_us.load(Ordering::Relaxed));
+        let metrics = MetricsManager::global();
+        SubscriptionStatus {
+            state,
+            current_slot,
+            contiguous_slots: self.slot_tracker.contiguous_range(),
+            last_event_age,
+            last_message_age,
+            events_per_second: metrics.events_per_second(MetricsEventType::Transaction),
+            dropped_events: metrics.get_dropped_events_count(),
+        }
+    }
+
+    /// 当前下发给服务端的订阅请求（包含 `update_subscription` 和增量订阅的修改），未订阅时返回 None
+    ///
+    /// 账户单独使用 `account_commitment` 时，请求中的账户过滤器实际通过另一条流下发
+    pub async fn current_filters(&self) -> Option<SubscribeRequest> {
+        self.current_request.read().await.clone()
+    }
+
+    /// 当前订阅过滤条件的汇总，未订阅时返回 None
+    pub async fn current_filter_summary(&self) -> Option<SubscriptionFilterSummary> {
+        let request = self.current_filters().await?;
+        let mut summary = SubscriptionFilterSummary::from_request(&request);
+        if summary.account_commitment.is_some() {
+            summary.account_commitment =
+                self.config.account_commitment.or(summary.account_commitment);
+        }
+        Some(summary)
+    }
+
+    /// 设置跳过 slot 的回调，参数为缺失的闭区间 `(from, to)`
+    ///
+    /// 只根据 BlockMeta 判断：`parent_slot` 大于上一个收到的区块 slot 时触发，区间为
+    /// `(上一个区块 + 1, parent_slot)`；leader 跳过的 slot 不算缺口。交易 / 账户更新不参与判断，
+    /// 需要缺口检测时必须订阅 BlockMeta。
+    pub fn on_slot_gap<F>(&self, callback: F)
+    where
+        F: Fn(u64, u64) + Send + Sync + 'static,
+    {
+        self.slot_tracker.set_callback(Arc::new(callback));
+    }
+
+    /// 当前连续收到的 slot 区间 `(start, last)`（闭区间），尚未收到交易 / 区块更新时为 None
+    pub fn contiguous_slot_range(&self) -> Option<(u64, u64)> {
+        self.slot_tracker.contiguous_range()
+    }
+
+    /// 设置数据流结束时的回调，用于重连
+    ///
+    /// 数据流出错、被服务端关闭、超过 `idle_timeout` 没有消息或 keepalive ping 发送失败时，
+    /// 订阅任务结束，`status().state` 变为 `Disconnected`，并以原因调用回调。`stop()` 不触发回调。
+    /// 回调在订阅任务中执行，不应阻塞；需要重连时在回调中 spawn 任务，调用 `stop()` 后重新订阅。
+    pub fn on_disconnect<F>(&self, callback: F)
+    where
+        F: Fn(DisconnectReason) + Send + Sync + 'static,
+    {
+        *self.disconnect_callback.write() = Some(Arc::new(callback));
+    }
+
+    /// 清空上一次订阅留下的健康状态，避免重新订阅后报告旧的 slot 或误报缺口
+    fn reset_status(&self) {
+        self.last_processed_slot.store(0, Ordering::Relaxed);
+        self.last_event_us.store(0, Ordering::Relaxed);
+        self.last_message_us.store(0, Ordering::Relaxed);
+        self.slot_tracker.reset();
+    }
+
+    /// 记录收到的数据更新，供 `status()` 使用
+    #[inline]
+    fn record_update(last_processed_slot: &AtomicU64, last_event_us: &AtomicI64, slot: u64) {
+        last_processed_slot.fetch_max(slot, Ordering::Relaxed);
+        last_event_us.store(get_high_perf_clock(), Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn age_since(timestamp_us: i64) -> Option<Duration> {
+        match timestamp_us {
+            0 => None,
+            timestamp_us => {
+                Some(Duration::from_micros(elapsed_micros_since(timestamp_us).max(0) as u64))
+            }
+        }
+    }
+
+    /// 按 ping_interval 创建客户端 ping 定时器，0 表示不主动 ping
+    fn keepalive_timer(ping_interval: u64) -> Option<tokio::time::Interval> {
+        (ping_interval > 0).then(|| {
+            let period = Duration::from_secs(ping_interval);
+            let mut timer = tokio::time::interval_at(Instant::now() + period, period);
+            timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            timer
+        })
+    }
+
+    /// 等待下一次客户端 ping，未启用时永不返回
+    async fn tick_keepalive(timer: &mut Option<tokio::time::Interval>) {
+        match timer {
+            Some(timer) => {
+                timer.tick().await;
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    /// 等待空闲超时（自最近一条消息起），未启用时永不返回
+    async fn wait_idle(last_message_at: Instant, idle_timeout: Option<Duration>) {
+        match idle_timeout {
+            Some(timeout) => tokio::time::sleep_until(last_message_at + timeout).await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// 等待限速暂停结束，未暂停时永不返回
+    async fn wait_throttle(resume_at: Option<Instant>) {
+        match resume_at {
+            Some(at) => tokio::time::sleep_until(at).await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// 等待下一次客户端 ping 或空闲超时，两者都未启用时永不返回
+    async fn next_keepalive(
+        timer: &mut Option<tokio::time::Interval>,
+        last_message_at: Instant,
+        idle_timeout: Option<Duration>,
+    ) -> Keepalive {
+        tokio::select! {
+            _ = Self::tick_keepalive(timer) => Keepalive::Ping,
+            _ = Self::wait_idle(last_message_at, idle_timeout) => Keepalive::IdleTimeout,
+        }
+    }
+
+    /// 处理一次 keepalive 触发：向每条订阅流发送客户端 ping，或判定连接空闲超时
+    ///
+    /// 返回 Err 时连接已失效，订阅循环以该原因结束并通过 `signal_disconnect` 通知重连
+    async fn handle_keepalive<S, A>(
+        keepalive: Keepalive,
+        subscribe_tx: &Mutex<S>,
+        account_tx: Option<&Mutex<A>>,
+        idle_timeout: Option<Duration>,
+    ) -> Result<(), DisconnectReason>
+    where
+        S: Sink<SubscribeRequest, Error = mpsc::SendError> + Unpin,
+        A: Sink<SubscribeRequest, Error = mpsc::SendError> + Unpin,
+    {
+        match keepalive {
+            Keepalive::IdleTimeout => {
+                error!(
+                    "No message received for {:?}, treating the connection as dead",
+                    idle_timeout.unwrap_or_default()
+                );
+                Err(DisconnectReason::IdleTimeout)
+            }
+            Keepalive::Ping => {
+                let ping = SubscribeRequest {
+                    ping: Some(SubscribeRequestPing { id: 1 }),
+                    ..Default::default()
+                };
+                if let Some(account_tx) = account_tx {
+                    if let Err(e) = account_tx.lock().await.send(ping.clone()).await {
+                        error!("Failed to send keepalive ping: {}", e);
+                        return Err(DisconnectReason::SendFailed);
+                    }
+                }
+                if let Err(e) = subscribe_tx.lock().await.send(ping).await {
+                    error!("Failed to send keepalive ping: {}", e);
+                    return Err(DisconnectReason::SendFailed);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 订阅循环结束：标记数据流断开，并以原因调用 `on_disconnect` 回调
+    fn signal_disconnect(
+        stream_connected: &AtomicBool,
+        disconnect_callback: &parking_lot::RwLock<Option<DisconnectCallback>>,
+        reason: DisconnectReason,
+    ) {
+        stream_connected.store(false, Ordering::Release);
+        log::warn!("Subscription stream ended: {:?}", reason);
+        if let Some(callback) = disconnect_callback.read().clone() {
+            callback(reason);
+        }
+    }
+
+    /// 合并交易流与账户流，任一条流结束时合并后的流随即结束
+    ///
+    /// `futures::stream::select` 要等两条流都结束，一条连接断开后处理循环不会退出，
+    /// `stream_connected` 也不会变为 false。
+    fn merge_until_either_ends<T, A, B>(first: A, second: B) -> impl Stream<Item = T>
+    where
+        A: Stream<Item = T>,
+        B: Stream<Item = T>,
+    {
+        let first = first.map(Some).chain(futures::stream::once(future::ready(None)));
+        let second = second.map(Some).chain(futures::stream::once(future::ready(None)));
+        futures::stream::select(first, second)
+            .take_while(|item| future::ready(item.is_some()))
+            .filter_map(future::ready)
+    }
+
     /// 停止当前订阅
     pub async fn stop(&self) {
         let mut handle_guard = self.subscription_handle.lock().await;
@@ -114,6 +463,8 @@ impl YellowstoneGrpc {
         }
         *self.control_tx.lock().await = None;
         *self.current_request.write().await = None;
+        *self.dynamic_subscription.lock().await = DynamicSubscription::default();
+        self.stream_connected.store(false, Ordering::Release);
         self.active_subscription.store(false, Ordering::Release);
     }
 
@@ -125,7 +476,8 @@ impl YellowstoneGrpc {
     /// * `transaction_filter` - Transaction filter specifying accounts to include/exclude
     /// * `account_filter` - Account filter specifying accounts and owners to monitor
     /// * `event_filter` - Optional event filter for further event filtering, no filtering if None
-    /// * `commitment` - Optional commitment level, defaults to Confirmed
+    /// * `commitment` - Optional commitment level, defaults to Processed. Applies to account
+    ///   updates too unless `StreamClientConfig::account_commitment` sets a different level
     /// * `callback` - Event callback function that receives parsed unified events
     ///
     /// # Returns
@@ -143,6 +495,13 @@ impl YellowstoneGrpc {
     where
         F: Fn(DexEvent) + Send + Sync + 'static,
     {
+        if self.config.reorder_buffer_depth.is_some()
+            && event_type_filter.as_ref().is_some_and(|filter| !filter.include_block_event())
+        {
+            return Err(anyhow!(
+                "reorder_buffer_depth requires EventType::BlockMeta in the filter"
+            ));
+        }
         *self.event_type_filter.write().await = event_type_filter.clone();
         if self
             .active_subscription
@@ -151,6 +510,7 @@ impl YellowstoneGrpc {
         {
             return Err(anyhow!("Already subscribed. Use update_subscription() to modify filters"));
         }
+        self.reset_status();
 
         let mut metrics_handle = None;
         // 启动自动性能监控（如果启用）
@@ -161,60 +521,184 @@ impl YellowstoneGrpc {
         let transactions = self
             .subscription_manager
             .get_subscribe_request_filter(transaction_filter, event_type_filter.as_ref());
+        *self.account_event_types.write() =
+            AccountFilter::event_type_index(&account_filter).map(Arc::new);
         let accounts = self
             .subscription_manager
             .subscribe_with_account_request(account_filter, event_type_filter.as_ref());
 
+        // commitment 作用于整个请求，账户需要不同的 commitment 时单独建立一条账户流
+        let tx_commitment = commitment.unwrap_or(CommitmentLevel::Processed);
+        let account_commitment = self
+            .config
+            .account_commitment
+            .filter(|level| *level != tx_commitment && accounts.is_some());
+        let (accounts, split_accounts) = match account_commitment {
+            Some(_) => (None, accounts),
+            None => (accounts, None),
+        };
+
         // 订阅事件
-        let (subscribe_tx, mut stream, subscribe_request) = self
+        // 重排缓冲区依赖 slot 的 rooted / dead 更新判定分叉
+        let reorder_buffer =
+            self.config.reorder_buffer_depth.map(|depth| Arc::new(SlotReorderBuffer::new(depth)));
+        let (subscribe_tx, stream, mut subscribe_request) = self
             .subscription_manager
-            .subscribe_with_request(transactions, accounts, commitment, event_type_filter.as_ref())
+            .subscribe_with_request(
+                transactions,
+                accounts,
+                reorder_buffer.as_ref().map(|_| self.subscription_manager.get_slot_filter()),
+                commitment,
+                event_type_filter.as_ref(),
+            )
             .await?;
+        let (account_tx, mut stream) = match (split_accounts, account_commitment) {
+            (Some(accounts), Some(level)) => {
+                let (account_tx, account_stream, account_request) = self
+                    .subscription_manager
+                    .subscribe_accounts_with_request(accounts, Some(level))
+                    .await?;
+                // current_request 保存合并后的请求，下发更新时再按流拆分
+                subscribe_request.accounts = account_request.accounts;
+                (
+                    Some(Arc::new(Mutex::new(account_tx))),
+                    Self::merge_until_either_ends(stream, account_stream).boxed(),
+                )
+            }
+            _ => (None, stream.boxed()),
+        };
 
         // 用 Arc<Mutex<>> 包装 subscribe_tx 以支持多线程共享
         let subscribe_tx = Arc::new(Mutex::new(subscribe_tx));
         *self.current_request.write().await = Some(subscribe_request);
+        *self.dynamic_subscription.lock().await = DynamicSubscription::new(
+            event_type_filter.as_ref().map_or(true, |filter| filter.include_transaction_event()),
+            event_type_filter.as_ref().map_or(true, |filter| filter.include_account_event()),
+        );
         let (control_tx, mut control_rx) = mpsc::channel(100);
         *self.control_tx.lock().await = Some(control_tx);
 
         // Wrap callback once before the async block
-        let callback = Arc::new(callback);
+        let callback = CallbackWatchdog::wrap_if_enabled(
+            self.config.callback_watchdog.as_ref(),
+            Arc::new(callback) as Arc<dyn Fn(DexEvent) + Send + Sync>,
+        );
+        // slot 状态更新判定出的事件直接转发给内层 callback
+        let ordered_callback = callback.clone();
+        let callback = match &reorder_buffer {
+            Some(buffer) => SlotReorderBuffer::wrap(buffer.clone(), callback),
+            None => callback,
+        };
+        let (callback, coalescer) = if self.config.coalesce_account_updates {
+            let (callback, coalescer) = AccountCoalescer::wrap(callback);
+            (callback, Some(coalescer))
+        } else {
+            (callback, None)
+        };
+        let callback = if self.config.first_event_per_transaction {
+            FirstEventPerTransaction::wrap(callback)
+        } else {
+            callback
+        };
         let swap_cu_parse_config = self.config.swap_cu_parse_config.clone();
+        let account_discriminator_filter = self.config.account_discriminator_filter.clone();
+        let account_event_types = self.account_event_types.clone();
+        let parse_options = self.parse_options();
+        let skip_votes = self.config.skip_votes;
+        let latency_monitor = self.config.latency_monitor();
+        let emit_transaction_summary = self.config.emit_transaction_summary;
+        let stream_connected = self.stream_connected.clone();
+        let disconnect_callback = self.disconnect_callback.clone();
+        let last_processed_slot = self.last_processed_slot.clone();
+        let last_event_us = self.last_event_us.clone();
+        let last_message_us = self.last_message_us.clone();
+        let slot_tracker = self.slot_tracker.clone();
+        let mut throttle = TpsThrottle::from_config(self.config.throttle_tps);
+        let mut keepalive_timer = Self::keepalive_timer(self.config.connection.ping_interval);
+        let idle_timeout = (self.config.connection.idle_timeout > 0)
+            .then(|| Duration::from_secs(self.config.connection.idle_timeout));
+        stream_connected.store(true, Ordering::Release);
 
         let stream_handle = tokio::spawn(async move {
-            loop {
+            let process_options = ProcessOptions {
+                account: AccountParseOptions {
+                    discriminator_filter: account_discriminator_filter.as_ref(),
+                    event_types: None,
+                },
+                parse: &parse_options,
+                skip_votes,
+                emit_transaction_summary,
+                latency_monitor: latency_monitor.as_ref(),
+            };
+            let mut last_message_at = Instant::now();
+            let reason = loop {
+                let resume_at =
+                    throttle.as_ref().and_then(|throttle| throttle.resume_at(Instant::now()));
                 tokio::select! {
-                    message = stream.next() => {
+                    message = stream.next(), if resume_at.is_none() => {
                         match message {
                             Some(Ok(msg)) => {
+                                last_message_at = Instant::now();
+                                last_message_us.store(get_high_perf_clock(), Ordering::Relaxed);
                                 let created_at = msg.created_at;
                                 match msg.update_oneof {
                                     Some(UpdateOneof::Account(account)) => {
+                                        Self::record_update(&last_processed_slot, &last_event_us, account.slot);
                                         let account_pretty = factory::create_account_pretty_pooled(account);
                                         log::debug!("Received account: {:?}", account_pretty);
-                                        if let Err(e) = process_grpc_transaction(
+                                        let selected_types = account_event_types.read().clone();
+                                        if let Err(e) = process_grpc_transaction_with_options(
                                             EventPretty::Account(account_pretty),
                                             &protocols,
                                             event_type_filter.as_ref(),
                                             swap_cu_parse_config.as_ref(),
                                             callback.clone(),
                                             bot_wallet,
+                                            ProcessOptions {
+                                                account: AccountParseOptions {
+                                                    event_types: selected_types.as_deref(),
+                                                    ..process_options.account
+                                                },
+                                                ..process_options
+                                            },
                                         )
                                         .await
                                         {
                                             error!("Error processing account event: {e:?}");
                                         }
                                     }
+                                    Some(UpdateOneof::Slot(slot_update)) => {
+                                        if let Some(coalescer) = coalescer.as_ref() {
+                                            coalescer.flush_until(slot_update.slot);
+                                        }
+                                        if let Some(buffer) = reorder_buffer.as_ref() {
+                                            let resolved = match SlotStatus::try_from(slot_update.status) {
+                                                Ok(SlotStatus::SlotFinalized) => buffer.slot_rooted(slot_update.slot),
+                                                Ok(SlotStatus::SlotDead) => buffer.slot_dead(slot_update.slot),
+                                                _ => Vec::new(),
+                                            };
+                                            for event in resolved {
+                                                ordered_callback(event);
+                                            }
+                                        }
+                                    }
                                     Some(UpdateOneof::BlockMeta(sut)) => {
+                                        Self::record_update(&last_processed_slot, &last_event_us, sut.slot);
+                                        slot_tracker.observe(sut.slot, Some(sut.parent_slot));
+                                        // 该 slot 的账户更新已结束，先于 BlockMeta 事件发出合并的账户事件
+                                        if let Some(coalescer) = coalescer.as_ref() {
+                                            coalescer.flush_until(sut.slot + 1);
+                                        }
                                         let block_meta_pretty = factory::create_block_meta_pretty_pooled(sut, created_at);
                                         log::debug!("Received block meta: {:?}", block_meta_pretty);
-                                        if let Err(e) = process_grpc_transaction(
+                                        if let Err(e) = process_grpc_transaction_with_options(
                                             EventPretty::BlockMeta(block_meta_pretty),
                                             &protocols,
                                             event_type_filter.as_ref(),
                                             swap_cu_parse_config.as_ref(),
                                             callback.clone(),
                                             bot_wallet,
+                                            process_options,
                                         )
                                         .await
                                         {
@@ -222,19 +706,25 @@ impl YellowstoneGrpc {
                                         }
                                     }
                                     Some(UpdateOneof::Transaction(sut)) => {
+                                        Self::record_update(&last_processed_slot, &last_event_us, sut.slot);
+                                        slot_tracker.observe(sut.slot, None);
                                         let transaction_pretty = factory::create_transaction_pretty_pooled(sut, created_at);
                                         log::debug!(
                                             "Received transaction: {} at slot {}",
                                             transaction_pretty.signature,
                                             transaction_pretty.slot
                                         );
-                                        if let Err(e) = process_grpc_transaction(
+                                        if let Some(throttle) = throttle.as_mut() {
+                                            throttle.reserve(Instant::now());
+                                        }
+                                        if let Err(e) = process_grpc_transaction_with_options(
                                             EventPretty::Transaction(transaction_pretty),
                                             &protocols,
                                             event_type_filter.as_ref(),
                                             swap_cu_parse_config.as_ref(),
                                             callback.clone(),
                                             bot_wallet,
+                                            process_options,
                                         )
                                         .await
                                         {
@@ -251,6 +741,14 @@ impl YellowstoneGrpc {
                                                 })
                                                 .await;
                                         }
+                                        if let Some(Ok(mut tx_guard)) = account_tx.as_ref().map(|tx| tx.try_lock()) {
+                                            let _ = tx_guard
+                                                .send(SubscribeRequest {
+                                                    ping: Some(SubscribeRequestPing { id: 1 }),
+                                                    ..Default::default()
+                                                })
+                                                .await;
+                                        }
                                         log::debug!("service is ping: {}", Local::now());
                                     }
                                     Some(UpdateOneof::Pong(_)) => {
@@ -263,19 +761,47 @@ impl YellowstoneGrpc {
                             }
                             Some(Err(error)) => {
                                 error!("Stream error: {error:?}");
-                                break;
+                                break DisconnectReason::StreamError;
                             }
-                            None => break,
+                            None => break DisconnectReason::StreamEnded,
                         }
                     }
                     Some(update) = control_rx.next() => {
+                        let update = match (&account_tx, account_commitment) {
+                            (Some(account_tx), Some(level)) => {
+                                let (update, account_update) = split_account_request(update, level);
+                                if let Err(e) = account_tx.lock().await.send(account_update).await {
+                                    error!("Failed to send account subscription update: {}", e);
+                                    break DisconnectReason::SendFailed;
+                                }
+                                update
+                            }
+                            _ => update,
+                        };
                         if let Err(e) = subscribe_tx.lock().await.send(update).await {
                             error!("Failed to send subscription update: {}", e);
-                            break;
+                            break DisconnectReason::SendFailed;
+                        }
+                    }
+                    _ = Self::wait_throttle(resume_at) => {}
+                    keepalive = Self::next_keepalive(&mut keepalive_timer, last_message_at, idle_timeout) => {
+                        if let Err(reason) = Self::handle_keepalive(
+                            keepalive,
+                            &subscribe_tx,
+                            account_tx.as_deref(),
+                            idle_timeout,
+                        )
+                        .await
+                        {
+                            break reason;
                         }
                     }
                 }
+            };
+            if let Some(coalescer) = coalescer.as_ref() {
+                coalescer.flush();
             }
+            Self::signal_disconnect(&stream_connected, &disconnect_callback, reason);
         });
 
         // 保存订阅句柄
@@ -310,6 +836,7 @@ impl YellowstoneGrpc {
         {
             return Err(anyhow!("Already subscribed. Use update_subscription() to modify filters"));
         }
+        self.reset_status();
 
         let mut metrics_handle = None;
         if self.config.enable_metrics {
@@ -323,39 +850,87 @@ impl YellowstoneGrpc {
 
         let (subscribe_tx, mut stream, subscribe_request) = self
             .subscription_manager
-            .subscribe_with_request(transactions, accounts, commitment, event_type_filter.as_ref())
+            .subscribe_with_request(
+                transactions,
+                accounts,
+                None,
+                commitment,
+                event_type_filter.as_ref(),
+            )
             .await?;
 
         let subscribe_tx = Arc::new(Mutex::new(subscribe_tx));
         *self.current_request.write().await = Some(subscribe_request);
+        *self.dynamic_subscription.lock().await = DynamicSubscription::new(
+            event_type_filter.as_ref().map_or(true, |filter| filter.include_transaction_event()),
+            false,
+        );
         let (control_tx, mut control_rx) = mpsc::channel(100);
         *self.control_tx.lock().await = Some(control_tx);
 
-        let callback = Arc::new(callback);
+        let callback = CallbackWatchdog::wrap_if_enabled(
+            self.config.callback_watchdog.as_ref(),
+            Arc::new(callback) as Arc<dyn Fn(TxDexEvents) + Send + Sync>,
+        );
+        let callback = if self.config.first_event_per_transaction {
+            FirstEventPerTransaction::wrap_tx_events(callback)
+        } else {
+            callback
+        };
         let swap_cu_parse_config = self.config.swap_cu_parse_config.clone();
+        let parse_options = self.parse_options();
+        let skip_votes = self.config.skip_votes;
+        let latency_monitor = self.config.latency_monitor();
+        let stream_connected = self.stream_connected.clone();
+        let disconnect_callback = self.disconnect_callback.clone();
+        let last_processed_slot = self.last_processed_slot.clone();
+        let last_event_us = self.last_event_us.clone();
+        let last_message_us = self.last_message_us.clone();
+        let slot_tracker = self.slot_tracker.clone();
+        let mut throttle = TpsThrottle::from_config(self.config.throttle_tps);
+        let mut keepalive_timer = Self::keepalive_timer(self.config.connection.ping_interval);
+        let idle_timeout = (self.config.connection.idle_timeout > 0)
+            .then(|| Duration::from_secs(self.config.connection.idle_timeout));
+        stream_connected.store(true, Ordering::Release);
 
         let stream_handle = tokio::spawn(async move {
-            loop {
+            let mut last_message_at = Instant::now();
+            let reason = loop {
+                let resume_at =
+                    throttle.as_ref().and_then(|throttle| throttle.resume_at(Instant::now()));
                 tokio::select! {
-                    message = stream.next() => {
+                    message = stream.next(), if resume_at.is_none() => {
                         match message {
                             Some(Ok(msg)) => {
+                                last_message_at = Instant::now();
+                                last_message_us.store(get_high_perf_clock(), Ordering::Relaxed);
                                 let created_at = msg.created_at;
                                 match msg.update_oneof {
                                     Some(UpdateOneof::Transaction(sut)) => {
+                                        Self::record_update(&last_processed_slot, &last_event_us, sut.slot);
+                                        slot_tracker.observe(sut.slot, None);
                                         let transaction_pretty = factory::create_transaction_pretty_pooled(sut, created_at);
                                         log::debug!(
                                             "Received tx events transaction: {} at slot {}",
                                             transaction_pretty.signature,
                                             transaction_pretty.slot
                                         );
-                                        if let Err(e) = process_grpc_tx_events(
+                                        if let Some(throttle) = throttle.as_mut() {
+                                            throttle.reserve(Instant::now());
+                                        }
+                                        if let Err(e) = process_grpc_tx_events_with_options(
                                             EventPretty::Transaction(transaction_pretty),
                                             &protocols,
                                             event_type_filter.as_ref(),
                                             swap_cu_parse_config.as_ref(),
                                             callback.clone(),
                                             bot_wallet,
+                                            ProcessOptions {
+                                                parse: &parse_options,
+                                                skip_votes,
+                                                latency_monitor: latency_monitor.as_ref(),
+                                                ..Default::default()
+                                            },
                                         )
                                         .await
                                         {
@@ -383,19 +958,198 @@ impl YellowstoneGrpc {
                             }
                             Some(Err(error)) => {
                                 error!("Stream error: {error:?}");
-                                break;
+                                break DisconnectReason::StreamError;
+                            }
+                            None => break DisconnectReason::StreamEnded,
+                        }
+                    }
+                    Some(update) = control_rx.next() => {
+                        if let Err(e) = subscribe_tx.lock().await.send(update).await {
+                            error!("Failed to send subscription update: {}", e);
+                            break DisconnectReason::SendFailed;
+                        }
+                    }
+                    _ = Self::wait_throttle(resume_at) => {}
+                    keepalive = Self::next_keepalive(&mut keepalive_timer, last_message_at, idle_timeout) => {
+                        if let Err(reason) = Self::handle_keepalive(
+                            keepalive,
+                            &subscribe_tx,
+                            None::<&Mutex<mpsc::Sender<SubscribeRequest>>>,
+                            idle_timeout,
+                        )
+                        .await
+                        {
+                            break reason;
+                        }
+                    }
+                }
+            };
+            Self::signal_disconnect(&stream_connected, &disconnect_callback, reason);
+        });
+
+        let subscription_handle = SubscriptionHandle::new(stream_handle, None, metrics_handle);
+        let mut handle_guard = self.subscription_handle.lock().await;
+        *handle_guard = Some(subscription_handle);
+
+        Ok(())
+    }
+
+    /// Account-only event subscription.
+    ///
+    /// Requests account updates only (no transactions or block meta) and routes every update
+    /// through the account-event dispatch path, skipping transaction processing entirely.
+    /// `account_filter` must not be empty.
+    pub async fn subscribe_accounts_immediate<F>(
+        &self,
+        protocols: Vec<Protocol>,
+        account_filter: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+        commitment: Option<CommitmentLevel>,
+        callback: F,
+    ) -> AnyResult<()>
+    where
+        F: Fn(DexEvent) + Send + Sync + 'static,
+    {
+        let account_event_types = AccountFilter::event_type_index(&account_filter).map(Arc::new);
+        let accounts = self
+            .subscription_manager
+            .subscribe_with_account_request(account_filter, event_type_filter.as_ref())
+            .ok_or_else(|| {
+                anyhow!("Account-only subscription requires a non-empty account filter")
+            })?;
+
+        *self.event_type_filter.write().await = event_type_filter.clone();
+        if self
+            .active_subscription
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(anyhow!("Already subscribed. Use update_subscription() to modify filters"));
+        }
+        self.reset_status();
+        *self.account_event_types.write() = account_event_types;
+
+        let mut metrics_handle = None;
+        if self.config.enable_metrics {
+            metrics_handle = MetricsManager::global().start_auto_monitoring().await;
+        }
+
+        let (subscribe_tx, mut stream, subscribe_request) = match self
+            .subscription_manager
+            .subscribe_accounts_with_request(accounts, commitment)
+            .await
+        {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                self.active_subscription.store(false, Ordering::Release);
+                return Err(e);
+            }
+        };
+
+        let subscribe_tx = Arc::new(Mutex::new(subscribe_tx));
+        *self.current_request.write().await = Some(subscribe_request);
+        *self.dynamic_subscription.lock().await = DynamicSubscription::new(false, true);
+        let (control_tx, mut control_rx) = mpsc::channel(100);
+        *self.control_tx.lock().await = Some(control_tx);
+
+        let callback = CallbackWatchdog::wrap_if_enabled(
+            self.config.callback_watchdog.as_ref(),
+            Arc::new(callback) as Arc<dyn Fn(DexEvent) + Send + Sync>,
+        );
+        let (callback, coalescer) = if self.config.coalesce_account_updates {
+            let (callback, coalescer) = AccountCoalescer::wrap(callback);
+            (callback, Some(coalescer))
+        } else {
+            (callback, None)
+        };
+        let account_discriminator_filter = self.config.account_discriminator_filter.clone();
+        let account_event_types = self.account_event_types.clone();
+        let stream_connected = self.stream_connected.clone();
+        let disconnect_callback = self.disconnect_callback.clone();
+        let last_processed_slot = self.last_processed_slot.clone();
+        let last_event_us = self.last_event_us.clone();
+        let last_message_us = self.last_message_us.clone();
+        let mut keepalive_timer = Self::keepalive_timer(self.config.connection.ping_interval);
+        let idle_timeout = (self.config.connection.idle_timeout > 0)
+            .then(|| Duration::from_secs(self.config.connection.idle_timeout));
+        stream_connected.store(true, Ordering::Release);
+
+        let stream_handle = tokio::spawn(async move {
+            let mut last_message_at = Instant::now();
+            let reason = loop {
+                tokio::select! {
+                    message = stream.next() => {
+                        match message {
+                            Some(Ok(msg)) => {
+                                last_message_at = Instant::now();
+                                last_message_us.store(get_high_perf_clock(), Ordering::Relaxed);
+                                match msg.update_oneof {
+                                    Some(UpdateOneof::Account(account)) => {
+                                        Self::record_update(&last_processed_slot, &last_event_us, account.slot);
+                                        let account_pretty = factory::create_account_pretty_pooled(account);
+                                        log::debug!("Received account: {:?}", account_pretty);
+                                        let selected_types = account_event_types.read().clone();
+                                        process_grpc_account(
+                                            account_pretty,
+                                            &protocols,
+                                            event_type_filter.as_ref(),
+                                            AccountParseOptions {
+                                                discriminator_filter: account_discriminator_filter.as_ref(),
+                                                event_types: selected_types.as_deref(),
+                                            },
+                                            callback.as_ref(),
+                                        );
+                                    }
+                                    Some(UpdateOneof::Ping(_)) => {
+                                        if let Ok(mut tx_guard) = subscribe_tx.try_lock() {
+                                            let _ = tx_guard
+                                                .send(SubscribeRequest {
+                                                    ping: Some(SubscribeRequestPing { id: 1 }),
+                                                    ..Default::default()
+                                                })
+                                                .await;
+                                        }
+                                        log::debug!("service is ping: {}", Local::now());
+                                    }
+                                    Some(UpdateOneof::Pong(_)) => {
+                                        log::debug!("service is pong: {}", Local::now());
+                                    }
+                                    _ => {
+                                        log::debug!("Received non-account message in account subscription");
+                                    }
+                                }
+                            }
+                            Some(Err(error)) => {
+                                error!("Stream error: {error:?}");
+                                break DisconnectReason::StreamError;
                             }
-                            None => break,
+                            None => break DisconnectReason::StreamEnded,
                         }
                     }
                     Some(update) = control_rx.next() => {
                         if let Err(e) = subscribe_tx.lock().await.send(update).await {
                             error!("Failed to send subscription update: {}", e);
-                            break;
+                            break DisconnectReason::SendFailed;
+                        }
+                    }
+                    keepalive = Self::next_keepalive(&mut keepalive_timer, last_message_at, idle_timeout) => {
+                        if let Err(reason) = Self::handle_keepalive(
+                            keepalive,
+                            &subscribe_tx,
+                            None::<&Mutex<mpsc::Sender<SubscribeRequest>>>,
+                            idle_timeout,
+                        )
+                        .await
+                        {
+                            break reason;
                         }
                     }
                 }
+            };
+            if let Some(coalescer) = coalescer.as_ref() {
+                coalescer.flush();
             }
+            Self::signal_disconnect(&stream_connected, &disconnect_callback, reason);
         });
 
         let subscription_handle = SubscriptionHandle::new(stream_handle, None, metrics_handle);
@@ -407,6 +1161,8 @@ impl YellowstoneGrpc {
 
     /// Update subscription filters at runtime without reconnection
     ///
+    /// 通过 `add_accounts` / `add_programs` 增量添加的订阅项会保留。
+    ///
     /// # Parameters
     /// * `transaction_filter` - New transaction filter to apply
     /// * `account_filter` - New account filter to apply
@@ -418,18 +1174,8 @@ impl YellowstoneGrpc {
         transaction_filter: Vec<TransactionFilter>,
         account_filter: Vec<AccountFilter>,
     ) -> AnyResult<()> {
-        let mut control_sender = {
-            let control_guard = self.control_tx.lock().await;
-
-            if !self.active_subscription.load(Ordering::Acquire) {
-                return Err(anyhow!("No active subscription to update"));
-            }
-
-            control_guard
-                .as_ref()
-                .ok_or_else(|| anyhow!("No active subscription to update"))?
-                .clone()
-        };
+        let mut control_sender = self.active_control_sender().await?;
+        let dynamic_subscription = self.dynamic_subscription.lock().await;
 
         let mut request = self
             .current_request
@@ -447,6 +1193,7 @@ impl YellowstoneGrpc {
             )
             .unwrap_or_default();
 
+        let account_event_types = AccountFilter::event_type_index(&account_filter).map(Arc::new);
         request.accounts = self
             .subscription_manager
             .subscribe_with_account_request(
@@ -454,6 +1201,7 @@ impl YellowstoneGrpc {
                 self.event_type_filter.read().await.as_ref(),
             )
             .unwrap_or_default();
+        dynamic_subscription.apply(&mut request);
 
         control_sender
             .send(request.clone())
@@ -461,9 +1209,77 @@ impl YellowstoneGrpc {
             .map_err(|e| anyhow!("Failed to send update: {}", e))?;
 
         *self.current_request.write().await = Some(request);
+        *self.account_event_types.write() = account_event_types;
 
         Ok(())
     }
+
+    /// 增量订阅账户，不影响已有过滤器
+    ///
+    /// 交易订阅中这些账户会加入 account_include，账户订阅中会推送这些账户的更新。
+    /// 已订阅的账户会被忽略，没有变化时不会发送更新。
+    pub async fn add_accounts(&self, accounts: Vec<String>) -> AnyResult<()> {
+        self.update_dynamic_subscription(|dynamic| dynamic.add_accounts(accounts)).await
+    }
+
+    /// 取消通过 `add_accounts` 增量订阅的账户
+    pub async fn remove_accounts(&self, accounts: Vec<String>) -> AnyResult<()> {
+        self.update_dynamic_subscription(|dynamic| dynamic.remove_accounts(&accounts)).await
+    }
+
+    /// 增量订阅程序，不影响已有过滤器
+    ///
+    /// 交易订阅中这些程序会加入 account_include，账户订阅中会推送这些程序拥有的账户的更新。
+    pub async fn add_programs(&self, programs: Vec<String>) -> AnyResult<()> {
+        self.update_dynamic_subscription(|dynamic| dynamic.add_programs(programs)).await
+    }
+
+    /// 取消通过 `add_programs` 增量订阅的程序
+    pub async fn remove_programs(&self, programs: Vec<String>) -> AnyResult<()> {
+        self.update_dynamic_subscription(|dynamic| dynamic.remove_programs(&programs)).await
+    }
+
+    /// 修改增量订阅项并下发，更新期间持有锁以保证并发调用按顺序生效
+    async fn update_dynamic_subscription<F>(&self, update: F) -> AnyResult<()>
+    where
+        F: FnOnce(&mut DynamicSubscription) -> bool,
+    {
+        let mut control_sender = self.active_control_sender().await?;
+        let mut dynamic_subscription = self.dynamic_subscription.lock().await;
+        let mut updated = dynamic_subscription.clone();
+        if !update(&mut updated) {
+            return Ok(());
+        }
+
+        let mut request = self
+            .current_request
+            .read()
+            .await
+            .as_ref()
+            .ok_or_else(|| anyhow!("No active subscription"))?
+            .clone();
+        updated.apply(&mut request);
+
+        control_sender
+            .send(request.clone())
+            .await
+            .map_err(|e| anyhow!("Failed to send update: {}", e))?;
+
+        *self.current_request.write().await = Some(request);
+        *dynamic_subscription = updated;
+
+        Ok(())
+    }
+
+    async fn active_control_sender(&self) -> AnyResult<mpsc::Sender<SubscribeRequest>> {
+        let control_guard = self.control_tx.lock().await;
+
+        if !self.active_subscription.load(Ordering::Acquire) {
+            return Err(anyhow!("No active subscription to update"));
+        }
+
+        control_guard.as_ref().ok_or_else(|| anyhow!("No active subscription to update")).cloned()
+    }
 }
 
 // 实现 Clone trait 以支持模块间共享
@@ -479,6 +1295,89 @@ impl Clone for YellowstoneGrpc {
             control_tx: self.control_tx.clone(),
             event_type_filter: self.event_type_filter.clone(),
             current_request: self.current_request.clone(),
+            dynamic_subscription: self.dynamic_subscription.clone(),
+            stream_connected: self.stream_connected.clone(),
+            last_processed_slot: self.last_processed_slot.clone(),
+            last_event_us: self.last_event_us.clone(),
+            last_message_us: self.last_message_us.clone(),
+            slot_tracker: self.slot_tracker.clone(),
+            disconnect_callback: self.disconnect_callback.clone(),
+            account_event_types: self.account_event_types.clone(),
+            sol_transfer_watchlist: self.sol_transfer_watchlist.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merged_stream_ends_with_either_stream() {
+        // 账户流结束时，即使交易流仍在等待也立即结束
+        let merged = YellowstoneGrpc::merge_until_either_ends(
+            futures::stream::pending::<u64>(),
+            futures::stream::iter([1, 2]),
+        );
+        assert_eq!(futures::executor::block_on(merged.collect::<Vec<_>>()), vec![1, 2]);
+
+        let merged = YellowstoneGrpc::merge_until_either_ends(
+            futures::stream::iter([3]),
+            futures::stream::pending::<u64>(),
+        );
+        assert_eq!(futures::executor::block_on(merged.collect::<Vec<_>>()), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn test_keepalive_failures_end_the_stream_with_a_reason() {
+        let (subscribe_tx, mut subscribe_rx) = mpsc::channel::<SubscribeRequest>(4);
+        let (account_tx, account_rx) = mpsc::channel::<SubscribeRequest>(4);
+        let subscribe_tx = Mutex::new(subscribe_tx);
+        let account_tx = Mutex::new(account_tx);
+
+        // ping 同时发给交易流和账户流
+        let sent = YellowstoneGrpc::handle_keepalive(
+            Keepalive::Ping,
+            &subscribe_tx,
+            Some(&account_tx),
+            None,
+        )
+        .await;
+        assert_eq!(sent, Ok(()));
+        assert!(subscribe_rx.try_next().unwrap().unwrap().ping.is_some());
+
+        // 账户流已关闭，ping 发送失败
+        drop(account_rx);
+        let failed = YellowstoneGrpc::handle_keepalive(
+            Keepalive::Ping,
+            &subscribe_tx,
+            Some(&account_tx),
+            None,
+        )
+        .await;
+        assert_eq!(failed, Err(DisconnectReason::SendFailed));
+
+        let idle = YellowstoneGrpc::handle_keepalive(
+            Keepalive::IdleTimeout,
+            &subscribe_tx,
+            None::<&Mutex<mpsc::Sender<SubscribeRequest>>>,
+            Some(Duration::from_secs(60)),
+        )
+        .await;
+        assert_eq!(idle, Err(DisconnectReason::IdleTimeout));
+
+        // 数据流结束时标记断开并通知回调
+        let client_connected = AtomicBool::new(true);
+        let reasons = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let recorded = reasons.clone();
+        let callback: DisconnectCallback = Arc::new(move |reason| recorded.lock().push(reason));
+        let disconnect_callback = parking_lot::RwLock::new(Some(callback));
+        YellowstoneGrpc::signal_disconnect(
+            &client_connected,
+            &disconnect_callback,
+            DisconnectReason::IdleTimeout,
+        );
+        assert!(!client_connected.load(Ordering::Acquire));
+        assert_eq!(*reasons.lock(), vec![DisconnectReason::IdleTimeout]);
+    }
+}
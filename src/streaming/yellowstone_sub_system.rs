@@ -42,12 +42,16 @@ impl YellowstoneGrpc {
         let addrs = vec![SYSTEM_PROGRAM_ID.to_string()];
         let account_include = account_include.unwrap_or_default();
         let account_exclude = account_exclude.unwrap_or_default();
-        let tx_filter =
-            vec![TransactionFilter { account_include, account_exclude, account_required: addrs }];
+        let tx_filter = vec![TransactionFilter {
+            account_include,
+            account_exclude,
+            account_required: addrs,
+            failed: Some(false),
+        }];
         let transactions = self.subscription_manager.get_subscribe_request_filter(tx_filter, None);
         let (mut subscribe_tx, mut stream, _) = self
             .subscription_manager
-            .subscribe_with_request(transactions, None, None, None)
+            .subscribe_with_request(transactions, None, None, None, None)
             .await?;
 
         let callback = Box::new(callback);